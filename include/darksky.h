@@ -0,0 +1,394 @@
+#ifndef DARKSKY_H
+#define DARKSKY_H
+
+/* This file is generated by cbindgen from src/ffi.rs. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * The severity of an alert.
+ */
+typedef enum DarkskySeverity {
+  /**
+   * An individual should be aware of potentially severe weather.
+   */
+  DarkskySeverity_Advisory,
+  /**
+   * An individual should prepare for potentially severe weather.
+   */
+  DarkskySeverity_Watch,
+  /**
+   * An individual should take immediate action.
+   */
+  DarkskySeverity_Warning,
+} DarkskySeverity;
+
+typedef struct DarkskyAlert DarkskyAlert;
+
+typedef struct DarkskyDatapoint DarkskyDatapoint;
+
+typedef struct DarkskyForecast DarkskyForecast;
+
+/**
+ * The callback of `darksky_fetch_forecast`.
+ *
+ * On success, `forecast` is a new forecast owned by the callback, which must
+ * be freed with `darksky_forecast_free`, and `error` is null. On failure,
+ * `forecast` is null and `error` is a message that is only valid for the
+ * duration of the callback.
+ */
+typedef void (*DarkskyFetchCallback)(void *user_data, DarkskyForecast *forecast, const char *error);
+
+/**
+ * Parses a forecast from `len` bytes of JSON, returning null if it is not a
+ * valid forecast.
+ *
+ * # Safety
+ *
+ * `json` must point to at least `len` readable bytes.
+ */
+DarkskyForecast *darksky_forecast_from_json(const char *json, size_t len);
+
+/**
+ * Retrieves the forecast for a location on a background thread, calling
+ * `callback` with `user_data` once it completes.
+ *
+ * Returns whether the request was started, which is false if `token` is
+ * null or not valid UTF-8.
+ *
+ * # Safety
+ *
+ * `token` must be null or a NUL-terminated string, and `user_data` must be
+ * safe to use from another thread.
+ */
+bool darksky_fetch_forecast(const char *token, double latitude, double longitude, DarkskyFetchCallback callback, void *user_data);
+
+/**
+ * Frees a forecast, along with any datapoints or alerts borrowed from it.
+ *
+ * # Safety
+ *
+ * `forecast` must be null or a forecast that has not yet been freed.
+ */
+void darksky_forecast_free(DarkskyForecast *forecast);
+
+/**
+ * Returns the latitude of a forecast.
+ *
+ * # Safety
+ *
+ * `forecast` must be null or a valid forecast.
+ */
+double darksky_forecast_latitude(const DarkskyForecast *forecast);
+
+/**
+ * Returns the longitude of a forecast.
+ *
+ * # Safety
+ *
+ * `forecast` must be null or a valid forecast.
+ */
+double darksky_forecast_longitude(const DarkskyForecast *forecast);
+
+/**
+ * Copies the name of a forecast's timezone into `buffer`, returning its full
+ * length.
+ *
+ * # Safety
+ *
+ * `forecast` must be null or a valid forecast, and `buffer` must be null or
+ * point to at least `len` writable bytes.
+ */
+size_t darksky_forecast_timezone(const DarkskyForecast *forecast, char *buffer, size_t len);
+
+/**
+ * Returns the current conditions of a forecast, or null if there are none.
+ *
+ * # Safety
+ *
+ * `forecast` must be null or a valid forecast.
+ */
+const DarkskyDatapoint *darksky_forecast_currently(const DarkskyForecast *forecast);
+
+/**
+ * Returns the number of hourly datapoints in a forecast.
+ *
+ * # Safety
+ *
+ * `forecast` must be null or a valid forecast.
+ */
+size_t darksky_forecast_hourly_len(const DarkskyForecast *forecast);
+
+/**
+ * Returns the hourly datapoint at `index`, or null if it is out of bounds.
+ *
+ * # Safety
+ *
+ * `forecast` must be null or a valid forecast.
+ */
+const DarkskyDatapoint *darksky_forecast_hourly(const DarkskyForecast *forecast, size_t index);
+
+/**
+ * Returns the number of daily datapoints in a forecast.
+ *
+ * # Safety
+ *
+ * `forecast` must be null or a valid forecast.
+ */
+size_t darksky_forecast_daily_len(const DarkskyForecast *forecast);
+
+/**
+ * Returns the daily datapoint at `index`, or null if it is out of bounds.
+ *
+ * # Safety
+ *
+ * `forecast` must be null or a valid forecast.
+ */
+const DarkskyDatapoint *darksky_forecast_daily(const DarkskyForecast *forecast, size_t index);
+
+/**
+ * Returns the number of alerts in a forecast.
+ *
+ * # Safety
+ *
+ * `forecast` must be null or a valid forecast.
+ */
+size_t darksky_forecast_alerts_len(const DarkskyForecast *forecast);
+
+/**
+ * Returns the alert at `index`, or null if it is out of bounds.
+ *
+ * # Safety
+ *
+ * `forecast` must be null or a valid forecast.
+ */
+const DarkskyAlert *darksky_forecast_alert(const DarkskyForecast *forecast, size_t index);
+
+/**
+ * Returns the unix timestamp at which a datapoint begins, or `0` if
+ * `datapoint` is null.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+uint64_t darksky_datapoint_time(const DarkskyDatapoint *datapoint);
+
+/**
+ * Copies the summary of a datapoint into `buffer`, returning its full
+ * length.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast, and `buffer`
+ * must be null or point to at least `len` writable bytes.
+ */
+size_t darksky_datapoint_summary(const DarkskyDatapoint *datapoint, char *buffer, size_t len);
+
+/**
+ * Returns the apparent (or "feels like") temperature of a datapoint, or `NAN`
+ * if it is not present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_apparent_temperature(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the percentage of sky occluded by clouds at a datapoint, or `NAN` if
+ * it is not present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_cloud_cover(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the dew point of a datapoint, or `NAN` if it is not present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_dew_point(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the relative humidity of a datapoint, or `NAN` if it is not present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_humidity(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the intensity of precipitation of a datapoint, or `NAN` if it is not
+ * present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_precip_intensity(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the probability of precipitation of a datapoint, or `NAN` if it is
+ * not present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_precip_probability(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the sea-level air pressure of a datapoint, or `NAN` if it is not
+ * present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_pressure(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the air temperature of a datapoint, or `NAN` if it is not present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_temperature(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the maximum temperature of a daily datapoint, or `NAN` if it is not
+ * present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_temperature_max(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the minimum temperature of a daily datapoint, or `NAN` if it is not
+ * present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_temperature_min(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the UV index of a datapoint, or `NAN` if it is not present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_uv_index(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the average visibility of a datapoint, or `NAN` if it is not
+ * present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_visibility(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the direction the wind is coming from at a datapoint, or `NAN` if it
+ * is not present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_wind_bearing(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the wind gust speed of a datapoint, or `NAN` if it is not present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_wind_gust(const DarkskyDatapoint *datapoint);
+
+/**
+ * Returns the wind speed of a datapoint, or `NAN` if it is not present.
+ *
+ * # Safety
+ *
+ * `datapoint` must be null or borrowed from a valid forecast.
+ */
+double darksky_datapoint_wind_speed(const DarkskyDatapoint *datapoint);
+
+/**
+ * Copies the title of an alert into `buffer`, returning its full length.
+ *
+ * # Safety
+ *
+ * `alert` must be null or borrowed from a valid forecast, and `buffer` must
+ * be null or point to at least `len` writable bytes.
+ */
+size_t darksky_alert_title(const DarkskyAlert *alert, char *buffer, size_t len);
+
+/**
+ * Copies the description of an alert into `buffer`, returning its full
+ * length.
+ *
+ * # Safety
+ *
+ * `alert` must be null or borrowed from a valid forecast, and `buffer` must
+ * be null or point to at least `len` writable bytes.
+ */
+size_t darksky_alert_description(const DarkskyAlert *alert, char *buffer, size_t len);
+
+/**
+ * Copies the URI of an alert into `buffer`, returning its full length.
+ *
+ * # Safety
+ *
+ * `alert` must be null or borrowed from a valid forecast, and `buffer` must
+ * be null or point to at least `len` writable bytes.
+ */
+size_t darksky_alert_uri(const DarkskyAlert *alert, char *buffer, size_t len);
+
+/**
+ * Returns the unix timestamp at which an alert was issued, or `0` if `alert`
+ * is null.
+ *
+ * # Safety
+ *
+ * `alert` must be null or borrowed from a valid forecast.
+ */
+uint64_t darksky_alert_time(const DarkskyAlert *alert);
+
+/**
+ * Returns the unix timestamp at which an alert expires, or `0` if `alert` is
+ * null.
+ *
+ * # Safety
+ *
+ * `alert` must be null or borrowed from a valid forecast.
+ */
+uint64_t darksky_alert_expires(const DarkskyAlert *alert);
+
+/**
+ * Returns the severity of an alert, or an advisory if `alert` is null.
+ *
+ * # Safety
+ *
+ * `alert` must be null or borrowed from a valid forecast.
+ */
+DarkskySeverity darksky_alert_severity(const DarkskyAlert *alert);
+
+#endif /* DARKSKY_H */
@@ -0,0 +1,502 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Requesting forecasts from several providers at once.
+//!
+//! An [`Ensemble`] sends a request to each of its providers concurrently, and
+//! combines their forecasts according to its [`Strategy`]: resolving to the
+//! first one to succeed, to every provider's result, or to a forecast
+//! blending all of them. This keeps forecasts available while one upstream
+//! is down.
+//!
+//! A provider is a closure returning a future of a [`Forecast`], such as one
+//! of the [adapters] or a DarkSky request.
+//!
+//! # Examples
+//!
+//! Fall back between DarkSky and Open-Meteo, whichever answers first:
+//!
+#![cfg_attr(feature = "hyper", doc = "```rust,no_run")]
+#![cfg_attr(not(feature = "hyper"), doc = "```rust,ignore")]
+//! extern crate darksky;
+//! extern crate hyper;
+//! extern crate tokio;
+//!
+//! # use std::error::Error;
+//! #
+//! # fn try_main() -> Result<(), Box<dyn Error>> {
+//! use darksky::adapters::ensemble::{Ensemble, Strategy};
+//! use darksky::adapters::open_meteo;
+//! use darksky::bridge::DarkskyRequester;
+//! use hyper::Client;
+//! use tokio::runtime::Runtime;
+//!
+//! let client = Client::new();
+//! let client = &client;
+//!
+//! let ensemble = Ensemble::new(Strategy::FirstSuccess)
+//!     .provider("darksky", move |location| client.get_forecast("token", location))
+//!     .provider("open-meteo", move |location| open_meteo::get_forecast(client, location));
+//!
+//! let outcome = Runtime::new()?.block_on(ensemble.get_forecast((49.9, -97.1)))?;
+//!
+//! println!("Forecast: {:?}", outcome.into_forecast());
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     try_main().unwrap();
+//! # }
+//! ```
+//!
+//! [`Ensemble`]: struct.Ensemble.html
+//! [`Forecast`]: ../../models/struct.Forecast.html
+//! [`Strategy`]: enum.Strategy.html
+//! [adapters]: ../index.html
+
+use models::Forecast;
+use serde_json::{Map, Value};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use {serde_json, utils, Coordinate, Error, IntoLocation, Result};
+
+/// A boxed future resolving to a provider's forecast.
+pub type ProviderFuture<'a> = Pin<Box<dyn Future<Output = Result<Forecast>> + 'a>>;
+
+type Provider<'a> = Box<dyn Fn(Coordinate) -> ProviderFuture<'a> + 'a>;
+
+/// How an [`Ensemble`] combines the forecasts of its providers.
+///
+/// [`Ensemble`]: struct.Ensemble.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Strategy {
+    /// Resolve to the first forecast to be retrieved, without waiting for
+    /// the other providers.
+    FirstSuccess,
+    /// Wait for every provider, resolving to each of their results.
+    All,
+    /// Wait for every provider, resolving to a forecast blending the
+    /// successful ones.
+    ///
+    /// Numeric values of the current conditions, and of datapoints at the
+    /// same time, are averaged. Other values, such as icons and summaries,
+    /// are taken from the first provider with a forecast, falling back to
+    /// later providers for values it does not have. Alerts are combined, and
+    /// the sources of every provider are listed in the flags.
+    Blend,
+}
+
+/// The forecasts of an [`Ensemble`]'s providers, combined by its
+/// [`Strategy`].
+///
+/// [`Ensemble`]: struct.Ensemble.html
+/// [`Strategy`]: enum.Strategy.html
+#[derive(Debug)]
+pub enum Outcome {
+    /// The first forecast to be retrieved, with the name of its provider.
+    First {
+        /// The name of the provider.
+        provider: String,
+        /// The provider's forecast.
+        forecast: Forecast,
+    },
+    /// The result of every provider, with their names, in the order the
+    /// providers were added.
+    All(Vec<(String, Result<Forecast>)>),
+    /// A forecast blended from those of the named providers.
+    Blended {
+        /// The names of the providers with a forecast.
+        providers: Vec<String>,
+        /// The blended forecast.
+        forecast: Forecast,
+    },
+}
+
+impl Outcome {
+    /// Returns the forecast of the outcome, or the first successful one of
+    /// [`Outcome::All`].
+    ///
+    /// [`Outcome::All`]: #variant.All
+    pub fn forecast(&self) -> Option<&Forecast> {
+        match *self {
+            Outcome::First { ref forecast, .. } | Outcome::Blended { ref forecast, .. } => {
+                Some(forecast)
+            }
+            Outcome::All(ref results) => {
+                results.iter().find_map(|(_, result)| result.as_ref().ok())
+            }
+        }
+    }
+
+    /// Converts the outcome into its forecast, or the first successful one
+    /// of [`Outcome::All`].
+    ///
+    /// [`Outcome::All`]: #variant.All
+    pub fn into_forecast(self) -> Option<Forecast> {
+        match self {
+            Outcome::First { forecast, .. } | Outcome::Blended { forecast, .. } => Some(forecast),
+            Outcome::All(results) => results.into_iter().find_map(|(_, result)| result.ok()),
+        }
+    }
+}
+
+/// A client requesting forecasts from several providers concurrently.
+///
+/// Refer to the [module-level documentation] for more information.
+///
+/// [module-level documentation]: index.html
+pub struct Ensemble<'a> {
+    providers: Vec<(String, Provider<'a>)>,
+    strategy: Strategy,
+}
+
+impl<'a> Ensemble<'a> {
+    /// Creates an ensemble without any providers, combining their forecasts
+    /// with the given strategy.
+    pub fn new(strategy: Strategy) -> Self {
+        Ensemble {
+            providers: Vec::new(),
+            strategy,
+        }
+    }
+
+    /// Adds a provider with the given name, as a closure returning a future
+    /// of its forecast for a location.
+    ///
+    /// Providers are kept in the order they are added, which is the order of
+    /// preference when combining their forecasts.
+    pub fn provider<N, F, Fut>(mut self, name: N, provider: F) -> Self
+    where
+        N: Into<String>,
+        F: Fn(Coordinate) -> Fut + 'a,
+        Fut: Future<Output = Result<Forecast>> + 'a,
+    {
+        let provider: Provider<'a> = Box::new(move |location| Box::pin(provider(location)));
+        self.providers.push((name.into(), provider));
+
+        self
+    }
+
+    /// Returns the strategy combining the providers' forecasts.
+    pub fn get_strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Returns the names of the providers, in order.
+    pub fn get_providers(&self) -> Vec<&str> {
+        self.providers.iter().map(|(name, _)| &name[..]).collect()
+    }
+
+    /// Requests a forecast for the given location from every provider.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::InvalidCoordinate`] is returned if the location is out of
+    /// range, without requesting it from any provider.
+    ///
+    /// With [`Strategy::FirstSuccess`] or [`Strategy::Blend`], if no
+    /// provider succeeds, the error of the first provider is returned.
+    /// [`Error::NoProviders`] is returned if there are no providers.
+    ///
+    /// With [`Strategy::All`], errors are returned for each provider in
+    /// [`Outcome::All`].
+    ///
+    /// [`Error::InvalidCoordinate`]: ../../enum.Error.html#variant.InvalidCoordinate
+    /// [`Error::NoProviders`]: ../../enum.Error.html#variant.NoProviders
+    /// [`Outcome::All`]: enum.Outcome.html#variant.All
+    /// [`Strategy::Blend`]: enum.Strategy.html#variant.Blend
+    /// [`Strategy::FirstSuccess`]: enum.Strategy.html#variant.FirstSuccess
+    /// [`Strategy::All`]: enum.Strategy.html#variant.All
+    pub fn get_forecast<L: IntoLocation>(&self, location: L) -> EnsembleFuture<'a> {
+        let location = match utils::checked_location(location) {
+            Ok(location) => location,
+            Err(why) => {
+                return EnsembleFuture {
+                    error: Some(why),
+                    requests: Vec::new(),
+                    strategy: self.strategy,
+                };
+            }
+        };
+        let requests = self
+            .providers
+            .iter()
+            .map(|(name, provider)| Request {
+                name: name.clone(),
+                future: Some(provider(location)),
+                result: None,
+            })
+            .collect();
+
+        EnsembleFuture {
+            error: None,
+            requests,
+            strategy: self.strategy,
+        }
+    }
+}
+
+impl<'a> Debug for Ensemble<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Ensemble")
+            .field("providers", &self.get_providers())
+            .field("strategy", &self.strategy)
+            .finish()
+    }
+}
+
+/// A future resolving to the [`Outcome`] of an [`Ensemble`]'s requests,
+/// returned by [`Ensemble::get_forecast`].
+///
+/// [`Ensemble`]: struct.Ensemble.html
+/// [`Ensemble::get_forecast`]: struct.Ensemble.html#method.get_forecast
+/// [`Outcome`]: enum.Outcome.html
+#[must_use = "futures do nothing unless polled"]
+pub struct EnsembleFuture<'a> {
+    error: Option<Error>,
+    requests: Vec<Request<'a>>,
+    strategy: Strategy,
+}
+
+impl<'a> Debug for EnsembleFuture<'a> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("EnsembleFuture")
+            .field("strategy", &self.strategy)
+            .finish()
+    }
+}
+
+impl<'a> Future for EnsembleFuture<'a> {
+    type Output = Result<Outcome>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Outcome>> {
+        let this = &mut *self;
+        let mut pending = false;
+
+        if let Some(why) = this.error.take() {
+            return Poll::Ready(Err(why));
+        }
+
+        for request in &mut this.requests {
+            if let Some(ref mut future) = request.future {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(result) => request.result = Some(result),
+                    Poll::Pending => pending = true,
+                }
+            }
+
+            if request.result.is_some() {
+                request.future = None;
+            }
+
+            if this.strategy == Strategy::FirstSuccess {
+                if let Some(Ok(_)) = request.result {
+                    if let Some(Ok(forecast)) = request.result.take() {
+                        return Poll::Ready(Ok(Outcome::First {
+                            provider: request.name.clone(),
+                            forecast,
+                        }));
+                    }
+                }
+            }
+        }
+
+        if pending {
+            return Poll::Pending;
+        }
+
+        let results = this
+            .requests
+            .drain(..)
+            .filter_map(|Request { name, result, .. }| result.map(|result| (name, result)))
+            .collect::<Vec<_>>();
+
+        Poll::Ready(match this.strategy {
+            Strategy::All => Ok(Outcome::All(results)),
+            Strategy::FirstSuccess => Err(first_error(results)),
+            Strategy::Blend => {
+                let mut providers = Vec::new();
+                let mut forecasts = Vec::new();
+                let mut errors = Vec::new();
+
+                for (name, result) in results {
+                    match result {
+                        Ok(forecast) => {
+                            providers.push(name);
+                            forecasts.push(forecast);
+                        }
+                        Err(why) => errors.push((name, Err(why))),
+                    }
+                }
+
+                if forecasts.is_empty() {
+                    Err(first_error(errors))
+                } else {
+                    blend(&forecasts).map(|forecast| Outcome::Blended {
+                        providers,
+                        forecast,
+                    })
+                }
+            }
+        })
+    }
+}
+
+/// A provider's request, which is dropped once it has resolved.
+struct Request<'a> {
+    name: String,
+    future: Option<ProviderFuture<'a>>,
+    result: Option<Result<Forecast>>,
+}
+
+/// Returns the error of the first provider, or [`Error::NoProviders`] if
+/// there are none.
+fn first_error(results: Vec<(String, Result<Forecast>)>) -> Error {
+    results
+        .into_iter()
+        .find_map(|(_, result)| result.err())
+        .unwrap_or(Error::NoProviders)
+}
+
+/// Blends forecasts by averaging their numeric values, going through their
+/// serialized form so that every representation of the models is supported.
+fn blend(forecasts: &[Forecast]) -> Result<Forecast> {
+    let mut values = forecasts
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<::std::result::Result<Vec<_>, _>>()?;
+    let mut blended = values.remove(0);
+
+    {
+        let others = values
+            .iter()
+            .map(|value| &value["currently"])
+            .collect::<Vec<_>>();
+        blend_point(&mut blended["currently"], &others);
+    }
+
+    for block in &["minutely", "hourly", "daily"] {
+        if let Some(data) = blended[*block]["data"].as_array_mut() {
+            for point in data {
+                let time = point["time"].clone();
+                let others = values
+                    .iter()
+                    .filter_map(|value| value[*block]["data"].as_array())
+                    .filter_map(|data| data.iter().find(|other| other["time"] == time))
+                    .collect::<Vec<_>>();
+
+                blend_point(point, &others);
+            }
+        }
+    }
+
+    let mut alerts = blended["alerts"].as_array().cloned().unwrap_or_default();
+    let mut sources = blended["flags"]["sources"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+
+    for value in &values {
+        for alert in value["alerts"].as_array().into_iter().flatten() {
+            if !alerts.iter().any(|other| other["uri"] == alert["uri"]) {
+                alerts.push(alert.clone());
+            }
+        }
+
+        for source in value["flags"]["sources"].as_array().into_iter().flatten() {
+            if !sources.contains(source) {
+                sources.push(source.clone());
+            }
+        }
+    }
+
+    if !alerts.is_empty() {
+        blended["alerts"] = Value::Array(alerts);
+    }
+
+    if let Some(flags) = blended["flags"].as_object_mut() {
+        flags.insert("sources".to_owned(), Value::Array(sources));
+    }
+
+    serde_json::from_value(blended).map_err(From::from)
+}
+
+/// Averages the numeric values of a datapoint with those of the same
+/// datapoint from other providers, filling in the values it is missing.
+fn blend_point(point: &mut Value, others: &[&Value]) {
+    if point.is_null() {
+        if let Some(other) = others.iter().find(|other| other.is_object()) {
+            *point = (*other).clone();
+        }
+    }
+
+    let point = match point.as_object_mut() {
+        Some(point) => point,
+        None => return,
+    };
+
+    for other in others.iter().filter_map(|other| other.as_object()) {
+        for (key, value) in other {
+            if point.get(key).is_none_or(Value::is_null) {
+                point.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    let keys = point.keys().cloned().collect::<Vec<_>>();
+
+    for key in keys {
+        // Timestamps are kept from the first provider.
+        if key == "time" || key.ends_with("Time") {
+            continue;
+        }
+
+        if let Some(average) = average(point, &key, others) {
+            point.insert(key, average);
+        }
+    }
+}
+
+/// Averages the value of a key across datapoints, if it is numeric.
+///
+/// Whole numbers, such as a UV index, are averaged into a rounded whole
+/// number.
+fn average(point: &Map<String, Value>, key: &str, others: &[&Value]) -> Option<Value> {
+    let values = Some(&point[key])
+        .into_iter()
+        .chain(others.iter().map(|other| &other[key]))
+        .filter(|value| value.is_number())
+        .collect::<Vec<_>>();
+
+    if values.len() < 2 {
+        return None;
+    }
+
+    let sum = values
+        .iter()
+        .filter_map(|value| value.as_f64())
+        .sum::<f64>();
+    let mean = sum / values.len() as f64;
+
+    if values.iter().all(|value| value.is_u64()) {
+        Some(Value::from(mean.round() as u64))
+    } else if values.iter().all(|value| value.is_i64()) {
+        Some(Value::from(mean.round() as i64))
+    } else {
+        serde_json::Number::from_f64(mean).map(Value::Number)
+    }
+}
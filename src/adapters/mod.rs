@@ -0,0 +1,204 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Adapters fetching forecasts from providers other than DarkSky, normalized
+//! to the same [`Forecast`] model.
+//!
+//! Each adapter formats the URI of a provider's API, and converts its
+//! response into a `Forecast`, so that downstream code can use another
+//! provider as a fallback without changes. Requests are sent over any
+//! [`HttpSend`] backend.
+//!
+//! Providers return less than DarkSky does, so the fields they have no
+//! equivalent for are left as `None`.
+//!
+//! [`Forecast`]: ../models/struct.Forecast.html
+//! [`HttpSend`]: ../bridge/send/trait.HttpSend.html
+
+pub mod ensemble;
+pub mod nws;
+pub mod open_meteo;
+
+use models::{Alert, Forecast, Icon, PrecipitationType};
+use serde_json;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use {Error, Result};
+
+/// A future resolving to a forecast converted from a provider's response,
+/// returned by the adapters' `get_forecast` functions.
+#[must_use = "futures do nothing unless polled"]
+pub struct AdapterFuture<F> {
+    body: Option<F>,
+    convert: fn(&[u8]) -> Result<Forecast>,
+    error: Option<Error>,
+}
+
+impl<F> AdapterFuture<F> {
+    pub(crate) fn new(body: Result<F>, convert: fn(&[u8]) -> Result<Forecast>) -> Self {
+        match body {
+            Ok(body) => AdapterFuture {
+                body: Some(body),
+                convert,
+                error: None,
+            },
+            Err(why) => AdapterFuture {
+                body: None,
+                convert,
+                error: Some(why),
+            },
+        }
+    }
+}
+
+impl<F> Debug for AdapterFuture<F> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("AdapterFuture").finish()
+    }
+}
+
+impl<F> Future for AdapterFuture<F>
+where
+    F: Future<Output = Result<Vec<u8>>> + Unpin,
+{
+    type Output = Result<Forecast>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Forecast>> {
+        let this = &mut *self;
+
+        match this.body {
+            Some(ref mut body) => Pin::new(body)
+                .poll(cx)
+                .map(|body| body.and_then(|body| (this.convert)(&body))),
+            None => {
+                let why = this
+                    .error
+                    .take()
+                    .expect("AdapterFuture polled after completion");
+
+                Poll::Ready(Err(why))
+            }
+        }
+    }
+}
+
+/// A forecast in the shape of a DarkSky response, which adapters build
+/// before deserializing it into a [`Forecast`].
+///
+/// Going through the response's shape, rather than building a `Forecast`
+/// directly, means that adapters work with every representation of the
+/// models, such as the compact one.
+///
+/// [`Forecast`]: ../models/struct.Forecast.html
+#[derive(Default, Serialize)]
+struct Shape {
+    latitude: f64,
+    longitude: f64,
+    timezone: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    offset: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    currently: Option<Point>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hourly: Option<Block>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    daily: Option<Block>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    alerts: Vec<Alert>,
+    flags: Flags,
+}
+
+impl Shape {
+    /// Deserializes the shape into a forecast.
+    fn into_forecast(self) -> Result<Forecast> {
+        let value = serde_json::to_value(self)?;
+
+        serde_json::from_value(value).map_err(From::from)
+    }
+}
+
+/// A datablock in the shape of a DarkSky response.
+#[derive(Default, Serialize)]
+struct Block {
+    data: Vec<Point>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<Icon>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+}
+
+/// The flags of a response, naming the provider it was converted from.
+#[derive(Default, Serialize)]
+struct Flags {
+    sources: Vec<String>,
+    units: &'static str,
+}
+
+/// A datapoint in the shape of a DarkSky response.
+#[derive(Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Point {
+    time: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    apparent_temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    apparent_temperature_max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    apparent_temperature_min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cloud_cover: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dew_point: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    humidity: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    icon: Option<Icon>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    precip_intensity: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    precip_probability: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    precip_type: Option<PrecipitationType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pressure: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sunrise_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sunset_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature_high: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature_low: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature_max: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature_min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uv_index: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    visibility: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wind_bearing: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wind_gust: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wind_speed: Option<f64>,
+}
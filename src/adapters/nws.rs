@@ -0,0 +1,778 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! An adapter for the US [National Weather Service API], which needs no key
+//! and covers only the United States and its territories.
+//!
+//! A forecast takes several requests: the location is first looked up to
+//! find its forecast office and grid, and the hourly and 12-hour forecasts
+//! of the grid are then requested along with the active alerts for the
+//! location. The hourly forecast is converted into the `currently` and
+//! `hourly` blocks, the 12-hour periods are paired into days for the `daily`
+//! block, and the alerts into `alerts`.
+//!
+//! Forecasts are requested in SI units, and converted to DarkSky's `si`
+//! units. Their flags have a `units` of `si` and a `sources` of `nws`.
+//!
+//! The API rejects requests without a `User-Agent` header, so
+//! [`get_forecast`] sends [`USER_AGENT`] unless one is configured.
+//!
+//! # Examples
+//!
+//! Retrieve a forecast from the NWS with a hyper client:
+//!
+#![cfg_attr(feature = "hyper", doc = "```rust,no_run")]
+#![cfg_attr(not(feature = "hyper"), doc = "```rust,ignore")]
+//! extern crate darksky;
+//! extern crate hyper;
+//! extern crate hyper_tls;
+//! extern crate tokio;
+//!
+//! # use std::error::Error;
+//! #
+//! # fn try_main() -> Result<(), Box<dyn Error>> {
+//! use darksky::adapters::nws;
+//! use hyper::Client;
+//! use hyper_tls::HttpsConnector;
+//! use tokio::runtime::Runtime;
+//!
+//! let client = Client::builder().build::<_, hyper::Body>(HttpsConnector::new());
+//! let forecast = Runtime::new()?.block_on(nws::get_forecast(&client, (38.8894, -77.0352)))?;
+//!
+//! for alert in forecast.alerts {
+//!     println!("{}: {}", alert.title, alert.description);
+//! }
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     try_main().unwrap();
+//! # }
+//! ```
+//!
+//! [National Weather Service API]: https://www.weather.gov/documentation/services-web-api
+//! [`USER_AGENT`]: constant.USER_AGENT.html
+//! [`get_forecast`]: fn.get_forecast.html
+
+use super::{Block, Flags, Point, Shape};
+use bridge::send::HttpSend;
+use constants::Config;
+use models::{Alert, Forecast, Icon, PrecipitationType, Severity};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use {serde_json, utils, Coordinate, Error, IntoLocation, Result};
+
+/// The base URI of the NWS API.
+pub const API_URL: &str = "https://api.weather.gov";
+
+/// The name of the provider, as used in the `sources` flag of its forecasts.
+pub const SOURCE: &str = "nws";
+
+/// The `User-Agent` header sent when none is configured, as the API requires
+/// one identifying the application.
+pub const USER_AGENT: &str = concat!(
+    "darksky.rs/",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/zeyla/darksky.rs)",
+);
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Formats the URI for looking up the forecast office and grid of the given
+/// location.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::adapters::nws;
+/// use darksky::Coordinate;
+///
+/// assert_eq!(
+///     nws::points_uri(Coordinate::new(38.8894, -77.0352)),
+///     "https://api.weather.gov/points/38.8894,-77.0352",
+/// );
+/// ```
+pub fn points_uri(location: Coordinate) -> String {
+    format!("{}/points/{},{}", API_URL, location.lat, location.lon)
+}
+
+/// Formats the URI for retrieving the active alerts for the given location.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::adapters::nws;
+/// use darksky::Coordinate;
+///
+/// assert_eq!(
+///     nws::alerts_uri(Coordinate::new(38.8894, -77.0352)),
+///     "https://api.weather.gov/alerts/active?point=38.8894,-77.0352",
+/// );
+/// ```
+pub fn alerts_uri(location: Coordinate) -> String {
+    format!(
+        "{}/alerts/active?point={},{}",
+        API_URL, location.lat, location.lon
+    )
+}
+
+/// Retrieves a forecast for the given location from the NWS, over any
+/// [`HttpSend`] backend, sending [`USER_AGENT`].
+///
+/// # Errors
+///
+/// The future resolves to [`Error::InvalidCoordinate`] if the location is
+/// out of range, without sending any request.
+///
+/// [`Error::InvalidCoordinate`]: ../../enum.Error.html#variant.InvalidCoordinate
+/// [`HttpSend`]: ../../bridge/send/trait.HttpSend.html
+/// [`USER_AGENT`]: constant.USER_AGENT.html
+pub fn get_forecast<S, L>(sender: &S, location: L) -> NwsFuture<'_, S>
+where
+    S: HttpSend + ?Sized,
+    L: IntoLocation,
+{
+    get_forecast_with_config(sender, Config::default(), location)
+}
+
+/// Retrieves a forecast for the given location from the NWS, with the
+/// timeout and user agent of a [`Config`].
+///
+/// The API URL of the configuration is not used. If it has no user agent,
+/// [`USER_AGENT`] is sent.
+///
+/// [`Config`]: ../../constants/struct.Config.html
+/// [`USER_AGENT`]: constant.USER_AGENT.html
+pub fn get_forecast_with_config<S, L>(
+    sender: &S,
+    mut config: Config,
+    location: L,
+) -> NwsFuture<'_, S>
+where
+    S: HttpSend + ?Sized,
+    L: IntoLocation,
+{
+    if config.get_user_agent().is_none() {
+        config = config.user_agent(USER_AGENT);
+    }
+
+    let state = match utils::checked_location(location) {
+        Ok(location) => State::Points {
+            points: sender.send_with(points_uri(location), &config),
+            alerts: Body::Pending(sender.send_with(alerts_uri(location), &config)),
+        },
+        Err(why) => State::Failed(why),
+    };
+
+    NwsFuture {
+        config,
+        sender,
+        state,
+    }
+}
+
+/// Converts the bodies of the responses from the NWS into a [`Forecast`].
+///
+/// These are the responses of the [points] lookup, the hourly and 12-hour
+/// forecasts linked to by it, and the [active alerts].
+///
+/// # Errors
+///
+/// Returns [`Error::Json`] if a body is not a valid response.
+///
+/// [`Error::Json`]: ../../enum.Error.html#variant.Json
+/// [`Forecast`]: ../../models/struct.Forecast.html
+/// [active alerts]: fn.alerts_uri.html
+/// [points]: fn.points_uri.html
+pub fn to_forecast(points: &[u8], hourly: &[u8], daily: &[u8], alerts: &[u8]) -> Result<Forecast> {
+    let points: Points = serde_json::from_slice(points)?;
+    let hourly: Gridpoint = serde_json::from_slice(hourly)?;
+    let daily: Gridpoint = serde_json::from_slice(daily)?;
+    let alerts = to_alerts(alerts)?;
+
+    let offset = hourly
+        .properties
+        .periods
+        .first()
+        .and_then(|period| timestamp(&period.start_time))
+        .map(|(_, offset)| offset);
+    let hourly = hourly
+        .properties
+        .periods
+        .iter()
+        .filter_map(Period::to_point)
+        .collect::<Vec<_>>();
+    let currently = hourly.first().cloned();
+
+    let shape = Shape {
+        latitude: points.geometry.coordinates[1],
+        longitude: points.geometry.coordinates[0],
+        timezone: points.properties.time_zone,
+        offset: offset.map(|offset| offset as f64 / 3600.0),
+        currently,
+        hourly: Some(Block {
+            data: hourly,
+            ..Block::default()
+        }),
+        daily: Some(Block {
+            data: days(&daily.properties.periods),
+            ..Block::default()
+        }),
+        alerts,
+        flags: Flags {
+            sources: vec![SOURCE.to_owned()],
+            units: "si",
+        },
+    };
+
+    shape.into_forecast()
+}
+
+/// Converts the body of a response of [active alerts] into [`Alert`]s.
+///
+/// NWS events named as a warning or a watch are given those severities, and
+/// all others are advisories.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::adapters::nws;
+/// use darksky::models::Severity;
+///
+/// let body = br#"{"features": [{"properties": {
+///     "@id": "https://api.weather.gov/alerts/urn:oid:1",
+///     "areaDesc": "Fairfax; Arlington",
+///     "sent": "2023-11-14T12:00:00-05:00",
+///     "expires": "2023-11-15T06:00:00-05:00",
+///     "event": "Winter Storm Warning",
+///     "headline": "Winter Storm Warning issued November 14",
+///     "description": "Heavy snow expected."
+/// }}]}"#;
+/// let alerts = nws::to_alerts(body).unwrap();
+///
+/// assert_eq!(alerts[0].severity, Severity::Warning);
+/// assert_eq!(alerts[0].regions, ["Fairfax", "Arlington"]);
+/// assert_eq!(alerts[0].time, 1699981200);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::Json`] if the body is not a valid response.
+///
+/// [`Alert`]: ../../models/struct.Alert.html
+/// [`Error::Json`]: ../../enum.Error.html#variant.Json
+/// [active alerts]: fn.alerts_uri.html
+pub fn to_alerts(body: &[u8]) -> Result<Vec<Alert>> {
+    let collection: AlertCollection = serde_json::from_slice(body)?;
+
+    Ok(collection
+        .features
+        .into_iter()
+        .map(|feature| feature.properties.into_alert())
+        .collect())
+}
+
+/// A future resolving to a forecast from the NWS, returned by
+/// [`get_forecast`].
+///
+/// [`get_forecast`]: fn.get_forecast.html
+#[must_use = "futures do nothing unless polled"]
+pub struct NwsFuture<'a, S: HttpSend + ?Sized + 'a> {
+    config: Config,
+    sender: &'a S,
+    state: State<S::Future>,
+}
+
+impl<'a, S: HttpSend + ?Sized> Debug for NwsFuture<'a, S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("NwsFuture")
+            .field("config", &self.config)
+            .finish()
+    }
+}
+
+impl<'a, S: HttpSend + ?Sized> NwsFuture<'a, S> {
+    fn poll_state(&mut self, cx: &mut Context) -> Poll<Result<Forecast>> {
+        loop {
+            match self.state {
+                State::Points {
+                    ref mut points,
+                    ref mut alerts,
+                } => {
+                    if let Poll::Ready(Err(why)) = alerts.poll(cx) {
+                        return Poll::Ready(Err(why));
+                    }
+
+                    let body = match Pin::new(points).poll(cx)? {
+                        Poll::Ready(body) => body,
+                        Poll::Pending => return Poll::Pending,
+                    };
+                    let links: Points = serde_json::from_slice(&body)?;
+                    let alerts = match mem::replace(&mut self.state, State::Done) {
+                        State::Points { alerts, .. } => alerts,
+                        _ => unreachable!(),
+                    };
+                    let hourly = with_units(&links.properties.forecast_hourly);
+                    let daily = with_units(&links.properties.forecast);
+
+                    self.state = State::Forecasts {
+                        points: body,
+                        hourly: Body::Pending(self.sender.send_with(hourly, &self.config)),
+                        daily: Body::Pending(self.sender.send_with(daily, &self.config)),
+                        alerts,
+                    };
+                }
+                State::Forecasts {
+                    ref points,
+                    ref mut hourly,
+                    ref mut daily,
+                    ref mut alerts,
+                } => {
+                    let hourly = hourly.poll(cx)?;
+                    let daily = daily.poll(cx)?;
+                    let alerts = alerts.poll(cx)?;
+
+                    return match (hourly, daily, alerts) {
+                        (Poll::Ready(hourly), Poll::Ready(daily), Poll::Ready(alerts)) => {
+                            Poll::Ready(to_forecast(points, hourly, daily, alerts))
+                        }
+                        _ => Poll::Pending,
+                    };
+                }
+                State::Failed(_) => {
+                    return match mem::replace(&mut self.state, State::Done) {
+                        State::Failed(why) => Poll::Ready(Err(why)),
+                        _ => unreachable!(),
+                    };
+                }
+                State::Done => panic!("NwsFuture polled after completion"),
+            }
+        }
+    }
+}
+
+impl<'a, S: HttpSend + ?Sized> Future for NwsFuture<'a, S> {
+    type Output = Result<Forecast>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Forecast>> {
+        let this = &mut *self;
+        let poll = this.poll_state(cx);
+
+        if poll.is_ready() {
+            this.state = State::Done;
+        }
+
+        poll
+    }
+}
+
+enum State<F> {
+    Points {
+        points: F,
+        alerts: Body<F>,
+    },
+    Forecasts {
+        points: Vec<u8>,
+        hourly: Body<F>,
+        daily: Body<F>,
+        alerts: Body<F>,
+    },
+    Failed(Error),
+    Done,
+}
+
+/// The body of one of several concurrent requests.
+enum Body<F> {
+    Pending(F),
+    Ready(Vec<u8>),
+}
+
+impl<F: Future<Output = Result<Vec<u8>>> + Unpin> Body<F> {
+    /// Polls the request if it is still pending, returning its body once it
+    /// has resolved.
+    fn poll(&mut self, cx: &mut Context) -> Poll<Result<&[u8]>> {
+        if let Body::Pending(ref mut future) = *self {
+            match Pin::new(future).poll(cx) {
+                Poll::Ready(Ok(body)) => *self = Body::Ready(body),
+                Poll::Ready(Err(why)) => return Poll::Ready(Err(why)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        match *self {
+            Body::Ready(ref body) => Poll::Ready(Ok(body)),
+            Body::Pending(_) => unreachable!(),
+        }
+    }
+}
+
+/// Requests a forecast of the grid in SI units.
+fn with_units(uri: &str) -> String {
+    format!("{}?units=si", uri)
+}
+
+#[derive(Deserialize)]
+struct Points {
+    geometry: Geometry,
+    properties: PointsProperties,
+}
+
+#[derive(Deserialize)]
+struct Geometry {
+    /// The longitude and latitude of the point, in that order.
+    coordinates: [f64; 2],
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PointsProperties {
+    forecast: String,
+    forecast_hourly: String,
+    #[serde(default)]
+    time_zone: String,
+}
+
+#[derive(Deserialize)]
+struct Gridpoint {
+    properties: GridpointProperties,
+}
+
+#[derive(Deserialize)]
+struct GridpointProperties {
+    periods: Vec<Period>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Period {
+    start_time: String,
+    #[serde(default = "default_true")]
+    is_daytime: bool,
+    temperature: Option<f64>,
+    temperature_unit: Option<String>,
+    #[serde(default)]
+    probability_of_precipitation: Quantity,
+    #[serde(default)]
+    dewpoint: Quantity,
+    #[serde(default)]
+    relative_humidity: Quantity,
+    wind_speed: Option<String>,
+    wind_direction: Option<String>,
+    icon: Option<String>,
+    short_forecast: Option<String>,
+}
+
+impl Period {
+    fn to_point(&self) -> Option<Point> {
+        let (time, _) = timestamp(&self.start_time)?;
+        let weather = self.icon.as_ref().and_then(|icon| Weather::from_icon(icon));
+
+        Some(Point {
+            time,
+            dew_point: self.dewpoint.value,
+            humidity: self.relative_humidity.value.map(percent),
+            icon: weather.map(|weather| weather.icon),
+            precip_probability: self.probability_of_precipitation.value.map(percent),
+            precip_type: weather.and_then(|weather| weather.precip_type),
+            summary: self.short_forecast.clone(),
+            temperature: self.celsius(),
+            wind_bearing: self.wind_direction.as_ref().and_then(|dir| bearing(dir)),
+            wind_speed: self.wind_speed.as_ref().and_then(|speed| wind_speed(speed)),
+            ..Point::default()
+        })
+    }
+
+    /// Returns the temperature in degrees Celsius, converting it if the
+    /// forecast was not in SI units.
+    fn celsius(&self) -> Option<f64> {
+        let temperature = self.temperature?;
+
+        match self.temperature_unit.as_ref().map(|unit| &unit[..]) {
+            Some("F") => Some((temperature - 32.0) * 5.0 / 9.0),
+            _ => Some(temperature),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A measurement with a unit, such as `{"unitCode": "wmoUnit:percent", "value": 20}`.
+#[derive(Default, Deserialize)]
+struct Quantity {
+    value: Option<f64>,
+}
+
+/// Pairs 12-hour periods into days, with the high of the daytime period and
+/// the low of the night following it.
+///
+/// A forecast made in the evening starts with a night, which becomes a day of
+/// its own with only a low.
+fn days(periods: &[Period]) -> Vec<Point> {
+    let mut days: Vec<Point> = Vec::new();
+
+    for period in periods {
+        let (time, offset) = match timestamp(&period.start_time) {
+            Some(time) => time,
+            None => continue,
+        };
+        let temperature = period.celsius();
+
+        if !period.is_daytime {
+            if let Some(day) = days.last_mut() {
+                if day.temperature_low.is_none() && time > day.time {
+                    day.temperature_low = temperature;
+                    day.temperature_min = temperature;
+
+                    continue;
+                }
+            }
+        }
+
+        let point = period.to_point().unwrap_or_default();
+        let midnight = time as i64 - (time as i64 + offset).rem_euclid(SECONDS_PER_DAY);
+        let (high, low) = if period.is_daytime {
+            (temperature, None)
+        } else {
+            (None, temperature)
+        };
+
+        days.push(Point {
+            time: midnight as u64,
+            icon: point.icon,
+            precip_probability: point.precip_probability,
+            precip_type: point.precip_type,
+            summary: point.summary,
+            temperature_high: high,
+            temperature_low: low,
+            temperature_max: high,
+            temperature_min: low,
+            wind_bearing: point.wind_bearing,
+            wind_speed: point.wind_speed,
+            ..Point::default()
+        });
+    }
+
+    days
+}
+
+#[derive(Deserialize)]
+struct AlertCollection {
+    features: Vec<AlertFeature>,
+}
+
+#[derive(Deserialize)]
+struct AlertFeature {
+    properties: AlertProperties,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AlertProperties {
+    #[serde(rename = "@id", default)]
+    id: String,
+    #[serde(default)]
+    area_desc: String,
+    sent: Option<String>,
+    effective: Option<String>,
+    expires: Option<String>,
+    ends: Option<String>,
+    #[serde(default)]
+    event: String,
+    headline: Option<String>,
+    #[serde(default)]
+    description: String,
+}
+
+impl AlertProperties {
+    fn into_alert(self) -> Alert {
+        let time = |value: &Option<String>| {
+            value
+                .as_ref()
+                .and_then(|value| timestamp(value))
+                .map(|(time, _)| time)
+        };
+        let expires = time(&self.ends)
+            .or_else(|| time(&self.expires))
+            .unwrap_or(0);
+        let issued = time(&self.sent)
+            .or_else(|| time(&self.effective))
+            .unwrap_or(0);
+        let severity = if self.event.ends_with("Warning") {
+            Severity::Warning
+        } else if self.event.ends_with("Watch") {
+            Severity::Watch
+        } else {
+            Severity::Advisory
+        };
+
+        Alert {
+            expires,
+            description: self.description,
+            title: self.headline.unwrap_or(self.event),
+            uri: self.id,
+            regions: self
+                .area_desc
+                .split(';')
+                .map(str::trim)
+                .filter(|region| !region.is_empty())
+                .map(str::to_owned)
+                .collect(),
+            time: issued,
+            severity,
+        }
+    }
+}
+
+/// The conditions of an NWS icon URI, such as
+/// `https://api.weather.gov/icons/land/night/rain_showers,40?size=small`.
+#[derive(Clone, Copy)]
+struct Weather {
+    icon: Icon,
+    precip_type: Option<PrecipitationType>,
+}
+
+impl Weather {
+    fn from_icon(uri: &str) -> Option<Self> {
+        let path = uri.split('?').next()?;
+        let mut segments = path.rsplit('/');
+        // Icons with two conditions, such as `rain/tsra`, are named by the
+        // first one.
+        let (time, condition) = loop {
+            let segment = segments.next()?;
+
+            if let Some(time) = segments.clone().next() {
+                if time == "day" || time == "night" {
+                    break (time, segment);
+                }
+            }
+        };
+        let condition = condition.split(',').next()?;
+        let is_day = time == "day";
+
+        let (icon, precip_type) = match condition {
+            "skc" | "few" if is_day => (Icon::ClearDay, None),
+            "skc" | "few" => (Icon::ClearNight, None),
+            "sct" | "bkn" if is_day => (Icon::PartlyCloudyDay, None),
+            "sct" | "bkn" => (Icon::PartlyCloudyNight, None),
+            "ovc" => (Icon::Cloudy, None),
+            "wind_skc" | "wind_few" | "wind_sct" | "wind_bkn" | "wind_ovc" => (Icon::Wind, None),
+            "snow" | "blizzard" | "cold" => (Icon::Snow, Some(PrecipitationType::Snow)),
+            "rain_snow" | "rain_sleet" | "snow_sleet" | "fzra" | "rain_fzra" | "snow_fzra"
+            | "sleet" => (Icon::Sleet, Some(PrecipitationType::Sleet)),
+            "rain" | "rain_showers" | "rain_showers_hi" => {
+                (Icon::Rain, Some(PrecipitationType::Rain))
+            }
+            "tsra" | "tsra_sct" | "tsra_hi" => (Icon::Thunderstorm, Some(PrecipitationType::Rain)),
+            "tornado" | "hurricane" | "tropical_storm" => (Icon::Tornado, None),
+            "fog" | "haze" | "smoke" | "dust" => (Icon::Fog, None),
+            _ => return None,
+        };
+
+        Some(Weather { icon, precip_type })
+    }
+}
+
+/// Parses an ISO 8601 timestamp with an offset, such as
+/// `2023-11-14T18:00:00-06:00`, into a UNIX timestamp and the offset in
+/// seconds.
+fn timestamp(value: &str) -> Option<(u64, i64)> {
+    let number = |range: ::std::ops::Range<usize>| -> Option<i64> {
+        value.get(range).and_then(|digits| digits.parse().ok())
+    };
+
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    let offset = match value.get(19..)? {
+        "Z" | "+00:00" => 0,
+        zone => {
+            let sign = match zone.get(..1)? {
+                "+" => 1,
+                "-" => -1,
+                _ => return None,
+            };
+            let hours: i64 = zone.get(1..3)?.parse().ok()?;
+            let minutes: i64 = zone.get(4..6)?.parse().ok()?;
+
+            sign * (hours * 3600 + minutes * 60)
+        }
+    };
+
+    let time =
+        days_from_civil(year, month, day) * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second
+            - offset;
+
+    if time < 0 {
+        return None;
+    }
+
+    Some((time as u64, offset))
+}
+
+// Converts a proleptic Gregorian date into a number of days since the UNIX
+// epoch, using the algorithm from Howard Hinnant's `days_from_civil`.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+    era * 146_097 + doe - 719_468
+}
+
+/// Returns the bearing in degrees of a compass direction, such as `NW`.
+fn bearing(direction: &str) -> Option<f64> {
+    const POINTS: [&str; 16] = [
+        "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW",
+        "NW", "NNW",
+    ];
+
+    POINTS
+        .iter()
+        .position(|point| *point == direction)
+        .map(|index| index as f64 * 22.5)
+}
+
+/// Parses a wind speed, such as `10 km/h` or `5 to 15 mph`, into meters per
+/// second, averaging ranges.
+fn wind_speed(value: &str) -> Option<f64> {
+    let numbers = value
+        .split_whitespace()
+        .filter_map(|word| word.parse::<f64>().ok())
+        .collect::<Vec<_>>();
+
+    if numbers.is_empty() {
+        return None;
+    }
+
+    let speed = numbers.iter().sum::<f64>() / numbers.len() as f64;
+
+    if value.ends_with("mph") {
+        Some(speed * 0.447_04)
+    } else {
+        Some(speed / 3.6)
+    }
+}
+
+/// Converts a percentage into a fraction.
+fn percent(value: f64) -> f64 {
+    value / 100.0
+}
@@ -0,0 +1,411 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! An adapter for [Open-Meteo], a free weather API which needs no key.
+//!
+//! Forecasts are requested in SI units, and converted to the same units as
+//! DarkSky's `si` units: temperatures in degrees Celsius, speeds in meters
+//! per second, precipitation in millimeters per hour, pressure in
+//! hectopascals, and visibility in kilometers. Their flags have a `units` of
+//! `si` and a `sources` of `open-meteo`.
+//!
+//! # Examples
+//!
+//! Retrieve a forecast from Open-Meteo with a hyper client:
+//!
+#![cfg_attr(feature = "hyper", doc = "```rust,no_run")]
+#![cfg_attr(not(feature = "hyper"), doc = "```rust,ignore")]
+//! extern crate darksky;
+//! extern crate hyper;
+//! extern crate tokio;
+//!
+//! # use std::error::Error;
+//! #
+//! # fn try_main() -> Result<(), Box<dyn Error>> {
+//! use darksky::adapters::open_meteo;
+//! use hyper::Client;
+//! use tokio::runtime::Runtime;
+//!
+//! let client = Client::new();
+//! let forecast = Runtime::new()?.block_on(open_meteo::get_forecast(&client, (49.9, -97.1)))?;
+//!
+//! println!("Forecast: {:?}", forecast.currently);
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     try_main().unwrap();
+//! # }
+//! ```
+//!
+//! [Open-Meteo]: https://open-meteo.com
+
+use super::{AdapterFuture, Block, Flags, Point, Shape};
+use bridge::send::HttpSend;
+use models::{Forecast, Icon, PrecipitationType};
+use {serde_json, utils, Coordinate, IntoLocation, Result};
+
+/// The URI of Open-Meteo's forecast API.
+pub const API_URL: &str = "https://api.open-meteo.com/v1/forecast";
+
+/// The name of the provider, as used in the `sources` flag of its forecasts.
+pub const SOURCE: &str = "open-meteo";
+
+const CURRENT: &str = "temperature_2m,relative_humidity_2m,apparent_temperature,is_day,\
+                       precipitation,weather_code,cloud_cover,pressure_msl,wind_speed_10m,\
+                       wind_direction_10m,wind_gusts_10m";
+const HOURLY: &str = "temperature_2m,relative_humidity_2m,dew_point_2m,apparent_temperature,\
+                      precipitation_probability,precipitation,weather_code,pressure_msl,\
+                      cloud_cover,visibility,wind_speed_10m,wind_direction_10m,wind_gusts_10m,\
+                      uv_index,is_day";
+const DAILY: &str = "weather_code,temperature_2m_max,temperature_2m_min,\
+                     apparent_temperature_max,apparent_temperature_min,sunrise,sunset,\
+                     uv_index_max,precipitation_sum,precipitation_probability_max,\
+                     wind_speed_10m_max,wind_gusts_10m_max,wind_direction_10m_dominant";
+
+/// Formats the URI for retrieving a forecast for the given location, with
+/// all of the fields that are converted.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::adapters::open_meteo;
+/// use darksky::Coordinate;
+///
+/// let uri = open_meteo::uri(Coordinate::new(49.9, -97.1));
+///
+/// assert!(uri.starts_with("https://api.open-meteo.com/v1/forecast?latitude=49.9&longitude=-97.1&"));
+/// ```
+pub fn uri(location: Coordinate) -> String {
+    format!(
+        "{}?latitude={}&longitude={}&current={}&hourly={}&daily={}\
+         &timeformat=unixtime&timezone=auto&wind_speed_unit=ms",
+        API_URL, location.lat, location.lon, CURRENT, HOURLY, DAILY,
+    )
+}
+
+/// Retrieves a forecast for the given location from Open-Meteo, over any
+/// [`HttpSend`] backend.
+///
+/// # Errors
+///
+/// The future resolves to [`Error::InvalidCoordinate`] if the location is
+/// out of range, without sending any request.
+///
+/// [`Error::InvalidCoordinate`]: ../../enum.Error.html#variant.InvalidCoordinate
+/// [`HttpSend`]: ../../bridge/send/trait.HttpSend.html
+pub fn get_forecast<S, L>(sender: &S, location: L) -> AdapterFuture<S::Future>
+where
+    S: HttpSend + ?Sized,
+    L: IntoLocation,
+{
+    let body = utils::checked_location(location).map(|location| sender.send(uri(location)));
+
+    AdapterFuture::new(body, to_forecast)
+}
+
+/// Converts the body of a response from Open-Meteo into a [`Forecast`].
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::adapters::open_meteo;
+///
+/// let body = br#"{
+///     "latitude": 49.9,
+///     "longitude": -97.1,
+///     "timezone": "America/Winnipeg",
+///     "utc_offset_seconds": -18000,
+///     "current": {"time": 1700000000, "temperature_2m": -3.5, "weather_code": 3, "is_day": 1}
+/// }"#;
+/// let forecast = open_meteo::to_forecast(body).unwrap();
+///
+/// assert_eq!(forecast.offset, Some(-5.0));
+/// assert_eq!(forecast.currently.unwrap().temperature, Some(-3.5));
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::Json`] if the body is not a valid response.
+///
+/// [`Error::Json`]: ../../enum.Error.html#variant.Json
+/// [`Forecast`]: ../../models/struct.Forecast.html
+pub fn to_forecast(body: &[u8]) -> Result<Forecast> {
+    let res: Response = serde_json::from_slice(body)?;
+
+    let shape = Shape {
+        latitude: res.latitude,
+        longitude: res.longitude,
+        timezone: res.timezone,
+        offset: res
+            .utc_offset_seconds
+            .map(|seconds| seconds as f64 / 3600.0),
+        currently: res.current.map(Current::into_point),
+        hourly: res.hourly.map(Hourly::into_block),
+        daily: res.daily.map(Daily::into_block),
+        alerts: Vec::new(),
+        flags: Flags {
+            sources: vec![SOURCE.to_owned()],
+            units: "si",
+        },
+    };
+
+    shape.into_forecast()
+}
+
+#[derive(Deserialize)]
+struct Response {
+    latitude: f64,
+    longitude: f64,
+    #[serde(default)]
+    timezone: String,
+    utc_offset_seconds: Option<i64>,
+    current: Option<Current>,
+    hourly: Option<Hourly>,
+    daily: Option<Daily>,
+}
+
+#[derive(Deserialize)]
+struct Current {
+    time: u64,
+    temperature_2m: Option<f64>,
+    relative_humidity_2m: Option<f64>,
+    apparent_temperature: Option<f64>,
+    is_day: Option<u8>,
+    precipitation: Option<f64>,
+    weather_code: Option<u8>,
+    cloud_cover: Option<f64>,
+    pressure_msl: Option<f64>,
+    wind_speed_10m: Option<f64>,
+    wind_direction_10m: Option<f64>,
+    wind_gusts_10m: Option<f64>,
+}
+
+impl Current {
+    fn into_point(self) -> Point {
+        let weather = self
+            .weather_code
+            .map(|code| Weather::new(code, self.is_day != Some(0)));
+
+        Point {
+            time: self.time,
+            apparent_temperature: self.apparent_temperature,
+            cloud_cover: self.cloud_cover.map(percent),
+            humidity: self.relative_humidity_2m.map(percent),
+            icon: weather.map(|weather| weather.icon),
+            precip_intensity: self.precipitation,
+            precip_type: weather.and_then(|weather| weather.precip_type),
+            pressure: self.pressure_msl,
+            summary: weather.map(|weather| weather.summary.to_owned()),
+            temperature: self.temperature_2m,
+            wind_bearing: self.wind_direction_10m,
+            wind_gust: self.wind_gusts_10m,
+            wind_speed: self.wind_speed_10m,
+            ..Point::default()
+        }
+    }
+}
+
+/// Hourly data, as parallel arrays indexed by the hour.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Hourly {
+    time: Vec<u64>,
+    temperature_2m: Vec<Option<f64>>,
+    relative_humidity_2m: Vec<Option<f64>>,
+    dew_point_2m: Vec<Option<f64>>,
+    apparent_temperature: Vec<Option<f64>>,
+    precipitation_probability: Vec<Option<f64>>,
+    precipitation: Vec<Option<f64>>,
+    weather_code: Vec<Option<u8>>,
+    pressure_msl: Vec<Option<f64>>,
+    cloud_cover: Vec<Option<f64>>,
+    visibility: Vec<Option<f64>>,
+    wind_speed_10m: Vec<Option<f64>>,
+    wind_direction_10m: Vec<Option<f64>>,
+    wind_gusts_10m: Vec<Option<f64>>,
+    uv_index: Vec<Option<f64>>,
+    is_day: Vec<Option<u8>>,
+}
+
+impl Hourly {
+    fn into_block(self) -> Block {
+        let data = self
+            .time
+            .iter()
+            .enumerate()
+            .map(|(i, &time)| {
+                let is_day = at(&self.is_day, i) != Some(0);
+                let weather = at(&self.weather_code, i).map(|code| Weather::new(code, is_day));
+
+                Point {
+                    time,
+                    apparent_temperature: at(&self.apparent_temperature, i),
+                    cloud_cover: at(&self.cloud_cover, i).map(percent),
+                    dew_point: at(&self.dew_point_2m, i),
+                    humidity: at(&self.relative_humidity_2m, i).map(percent),
+                    icon: weather.map(|weather| weather.icon),
+                    precip_intensity: at(&self.precipitation, i),
+                    precip_probability: at(&self.precipitation_probability, i).map(percent),
+                    precip_type: weather.and_then(|weather| weather.precip_type),
+                    pressure: at(&self.pressure_msl, i),
+                    summary: weather.map(|weather| weather.summary.to_owned()),
+                    temperature: at(&self.temperature_2m, i),
+                    uv_index: at(&self.uv_index, i).map(uv_index),
+                    visibility: at(&self.visibility, i).map(|meters| meters / 1000.0),
+                    wind_bearing: at(&self.wind_direction_10m, i),
+                    wind_gust: at(&self.wind_gusts_10m, i),
+                    wind_speed: at(&self.wind_speed_10m, i),
+                    ..Point::default()
+                }
+            })
+            .collect();
+
+        Block {
+            data,
+            ..Block::default()
+        }
+    }
+}
+
+/// Daily data, as parallel arrays indexed by the day.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct Daily {
+    time: Vec<u64>,
+    weather_code: Vec<Option<u8>>,
+    temperature_2m_max: Vec<Option<f64>>,
+    temperature_2m_min: Vec<Option<f64>>,
+    apparent_temperature_max: Vec<Option<f64>>,
+    apparent_temperature_min: Vec<Option<f64>>,
+    sunrise: Vec<Option<u64>>,
+    sunset: Vec<Option<u64>>,
+    uv_index_max: Vec<Option<f64>>,
+    precipitation_sum: Vec<Option<f64>>,
+    precipitation_probability_max: Vec<Option<f64>>,
+    wind_speed_10m_max: Vec<Option<f64>>,
+    wind_gusts_10m_max: Vec<Option<f64>>,
+    wind_direction_10m_dominant: Vec<Option<f64>>,
+}
+
+impl Daily {
+    fn into_block(self) -> Block {
+        let data = self
+            .time
+            .iter()
+            .enumerate()
+            .map(|(i, &time)| {
+                let weather = at(&self.weather_code, i).map(|code| Weather::new(code, true));
+                let high = at(&self.temperature_2m_max, i);
+                let low = at(&self.temperature_2m_min, i);
+
+                Point {
+                    time,
+                    apparent_temperature_max: at(&self.apparent_temperature_max, i),
+                    apparent_temperature_min: at(&self.apparent_temperature_min, i),
+                    icon: weather.map(|weather| weather.icon),
+                    // DarkSky gives the average intensity over the day.
+                    precip_intensity: at(&self.precipitation_sum, i).map(|sum| sum / 24.0),
+                    precip_probability: at(&self.precipitation_probability_max, i).map(percent),
+                    precip_type: weather.and_then(|weather| weather.precip_type),
+                    summary: weather.map(|weather| weather.summary.to_owned()),
+                    sunrise_time: at(&self.sunrise, i),
+                    sunset_time: at(&self.sunset, i),
+                    temperature_high: high,
+                    temperature_low: low,
+                    temperature_max: high,
+                    temperature_min: low,
+                    uv_index: at(&self.uv_index_max, i).map(uv_index),
+                    wind_bearing: at(&self.wind_direction_10m_dominant, i),
+                    wind_gust: at(&self.wind_gusts_10m_max, i),
+                    wind_speed: at(&self.wind_speed_10m_max, i),
+                    ..Point::default()
+                }
+            })
+            .collect();
+
+        Block {
+            data,
+            ..Block::default()
+        }
+    }
+}
+
+/// The conditions described by a WMO weather interpretation code, as used by
+/// Open-Meteo.
+#[derive(Clone, Copy)]
+struct Weather {
+    icon: Icon,
+    precip_type: Option<PrecipitationType>,
+    summary: &'static str,
+}
+
+impl Weather {
+    fn new(code: u8, is_day: bool) -> Self {
+        let (icon, precip_type, summary) = match code {
+            0 if is_day => (Icon::ClearDay, None, "Clear"),
+            0 => (Icon::ClearNight, None, "Clear"),
+            1 | 2 if is_day => (Icon::PartlyCloudyDay, None, "Partly Cloudy"),
+            1 | 2 => (Icon::PartlyCloudyNight, None, "Partly Cloudy"),
+            3 => (Icon::Cloudy, None, "Overcast"),
+            45 | 48 => (Icon::Fog, None, "Foggy"),
+            51..=55 => (Icon::Rain, Some(PrecipitationType::Rain), "Drizzle"),
+            56 | 57 => (
+                Icon::Sleet,
+                Some(PrecipitationType::Sleet),
+                "Freezing Drizzle",
+            ),
+            61..=65 => (Icon::Rain, Some(PrecipitationType::Rain), "Rain"),
+            66 | 67 => (Icon::Sleet, Some(PrecipitationType::Sleet), "Freezing Rain"),
+            71..=77 => (Icon::Snow, Some(PrecipitationType::Snow), "Snow"),
+            80..=82 => (Icon::Rain, Some(PrecipitationType::Rain), "Rain Showers"),
+            85 | 86 => (Icon::Snow, Some(PrecipitationType::Snow), "Snow Showers"),
+            95 => (
+                Icon::Thunderstorm,
+                Some(PrecipitationType::Rain),
+                "Thunderstorm",
+            ),
+            96 | 99 => (
+                Icon::Hail,
+                Some(PrecipitationType::Rain),
+                "Thunderstorm with Hail",
+            ),
+            _ => (Icon::Cloudy, None, "Unknown"),
+        };
+
+        Weather {
+            icon,
+            precip_type,
+            summary,
+        }
+    }
+}
+
+/// Returns the value of an hourly or daily array at an index, if the array
+/// has one.
+fn at<T: Copy>(values: &[Option<T>], index: usize) -> Option<T> {
+    values.get(index).cloned().and_then(|value| value)
+}
+
+/// Converts a percentage into a fraction, as DarkSky gives humidity, cloud
+/// cover, and precipitation probability.
+fn percent(value: f64) -> f64 {
+    value / 100.0
+}
+
+/// Rounds a UV index, which DarkSky gives as a whole number.
+fn uv_index(value: f64) -> u64 {
+    value.max(0.0).round() as u64
+}
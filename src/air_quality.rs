@@ -0,0 +1,351 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Enrichment of forecasts with air quality readings, which DarkSky does not
+//! provide.
+//!
+//! Readings come from an [`AirQualityProvider`], such as [`OpenAq`], and are
+//! attached to a forecast as an [`EnrichedForecast`], which serializes as the
+//! forecast with an additional `airQuality` field.
+//!
+//! # Examples
+//!
+//! Attach the latest readings from the nearest OpenAQ station:
+//!
+//! ```rust,no_run
+//! extern crate darksky;
+//! extern crate reqwest;
+//!
+//! # use std::error::Error;
+//! #
+//! # fn try_main() -> Result<(), Box<dyn Error>> {
+//! use darksky::air_quality::{self, OpenAq};
+//! use darksky::DarkskyReqwestRequester;
+//! use reqwest::blocking::Client;
+//! use std::env;
+//!
+//! let token = env::var("FORECAST_TOKEN")?;
+//! let provider = OpenAq::new(env::var("OPENAQ_API_KEY")?).radius(5_000);
+//!
+//! let forecast = Client::new().get_forecast(&token, (37.8267, -122.423))?;
+//! let enriched = air_quality::enrich(forecast, &provider)?;
+//!
+//! if let Some(aqi) = enriched.air_quality.as_ref().and_then(|aq| aq.us_aqi()) {
+//!     println!("AQI: {}", aqi);
+//! }
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     try_main().unwrap();
+//! # }
+//! ```
+//!
+//! [`AirQualityProvider`]: trait.AirQualityProvider.html
+//! [`EnrichedForecast`]: struct.EnrichedForecast.html
+//! [`OpenAq`]: struct.OpenAq.html
+
+use models::Forecast;
+use reqwest::blocking::Client;
+use serde::de::DeserializeOwned;
+use std::borrow::Cow;
+use {internal, Result};
+
+/// The base URI to the OpenAQ API.
+pub const OPENAQ_API_URL: &str = "https://api.openaq.org";
+
+/// The US EPA's breakpoints for PM2.5 concentrations, in µg/m³, and the AQI
+/// ranges that they map to.
+const PM25_BREAKPOINTS: [(f64, f64, u32, u32); 6] = [
+    (0.0, 9.0, 0, 50),
+    (9.1, 35.4, 51, 100),
+    (35.5, 55.4, 101, 150),
+    (55.5, 125.4, 151, 200),
+    (125.5, 225.4, 201, 300),
+    (225.5, 325.4, 301, 500),
+];
+
+/// A single air quality measurement.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reading {
+    /// The name of the measured parameter, such as `pm25`, `pm10`, or `o3`.
+    pub parameter: String,
+    /// The measured value, in [`units`].
+    ///
+    /// [`units`]: #structfield.units
+    pub value: f64,
+    /// The units of the value, such as `µg/m³` or `ppm`.
+    pub units: String,
+    /// When the measurement was taken, as an ISO 8601 timestamp in UTC, if
+    /// known.
+    pub time: Option<String>,
+}
+
+/// The air quality readings near a location.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirQuality {
+    /// The name of the station or location the readings are from, if known.
+    pub location: Option<String>,
+    /// The latest reading of each parameter measured at the location.
+    pub readings: Vec<Reading>,
+}
+
+impl AirQuality {
+    /// Returns the reading of the given parameter, such as `pm25`, if it was
+    /// measured.
+    pub fn reading(&self, parameter: &str) -> Option<&Reading> {
+        self.readings
+            .iter()
+            .find(|reading| reading.parameter == parameter)
+    }
+
+    /// Returns the US EPA Air Quality Index computed from the PM2.5 reading,
+    /// if there is one in µg/m³.
+    ///
+    /// Concentrations above the highest breakpoint are reported as `500`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use darksky::air_quality::{AirQuality, Reading};
+    ///
+    /// let air_quality = AirQuality {
+    ///     location: None,
+    ///     readings: vec![Reading {
+    ///         parameter: "pm25".to_owned(),
+    ///         value: 12.0,
+    ///         units: "µg/m³".to_owned(),
+    ///         time: None,
+    ///     }],
+    /// };
+    ///
+    /// assert_eq!(air_quality.us_aqi(), Some(56));
+    /// ```
+    pub fn us_aqi(&self) -> Option<u32> {
+        let reading = self.reading("pm25")?;
+
+        if reading.units != "µg/m³" || reading.value < 0.0 {
+            return None;
+        }
+
+        // Concentrations are truncated to a tenth, as the breakpoints are.
+        let concentration = (reading.value * 10.0).floor() / 10.0;
+
+        let aqi = PM25_BREAKPOINTS
+            .iter()
+            .find(|&&(_, high, _, _)| concentration <= high)
+            .map_or(500, |&(c_low, c_high, i_low, i_high)| {
+                let ratio = (concentration - c_low) / (c_high - c_low);
+
+                i_low + (f64::from(i_high - i_low) * ratio).round() as u32
+            });
+
+        Some(aqi)
+    }
+}
+
+/// A source of air quality readings for a location.
+///
+/// Implement this to enrich forecasts from a source other than [`OpenAq`].
+///
+/// [`OpenAq`]: struct.OpenAq.html
+pub trait AirQualityProvider {
+    /// Retrieves the latest air quality readings near a location.
+    ///
+    /// Returns `None` if the provider has no readings near the location.
+    fn air_quality(&self, latitude: f64, longitude: f64) -> Result<Option<AirQuality>>;
+}
+
+/// A forecast with the air quality readings near its location attached.
+///
+/// This serializes as the forecast's fields, plus an `airQuality` field.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnrichedForecast {
+    /// The forecast that was enriched.
+    #[serde(flatten)]
+    pub forecast: Forecast,
+    /// The air quality readings near the forecast's location, if there are
+    /// any.
+    pub air_quality: Option<AirQuality>,
+}
+
+/// Attaches the air quality readings near a forecast's location, retrieved
+/// from the given provider.
+///
+/// # Errors
+///
+/// Returns any error from the provider. The forecast is not returned in this
+/// case; use [`AirQualityProvider::air_quality`] directly to fall back to the
+/// bare forecast.
+///
+/// [`AirQualityProvider::air_quality`]: trait.AirQualityProvider.html#tymethod.air_quality
+pub fn enrich<P>(forecast: Forecast, provider: &P) -> Result<EnrichedForecast>
+where
+    P: AirQualityProvider + ?Sized,
+{
+    let air_quality = provider.air_quality(forecast.latitude, forecast.longitude)?;
+
+    Ok(EnrichedForecast {
+        forecast,
+        air_quality,
+    })
+}
+
+/// An [`AirQualityProvider`] retrieving the latest readings from the nearest
+/// [OpenAQ] location, using version 3 of its API.
+///
+/// [`AirQualityProvider`]: trait.AirQualityProvider.html
+/// [OpenAQ]: https://openaq.org
+#[derive(Debug)]
+pub struct OpenAq {
+    api_key: String,
+    api_url: Cow<'static, str>,
+    client: Client,
+    radius: u32,
+}
+
+impl OpenAq {
+    /// Creates a provider authenticating with the given API key, searching
+    /// for locations within 10 kilometers.
+    pub fn new<S: Into<String>>(api_key: S) -> Self {
+        OpenAq {
+            api_key: api_key.into(),
+            api_url: Cow::Borrowed(OPENAQ_API_URL),
+            client: Client::new(),
+            radius: 10_000,
+        }
+    }
+
+    /// Sets the base URI of the API, without a trailing slash.
+    ///
+    /// Defaults to [`OPENAQ_API_URL`].
+    ///
+    /// [`OPENAQ_API_URL`]: constant.OPENAQ_API_URL.html
+    pub fn api_url<T: Into<Cow<'static, str>>>(mut self, api_url: T) -> Self {
+        self.api_url = api_url.into();
+
+        self
+    }
+
+    /// Sets the client used to make requests.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+
+        self
+    }
+
+    /// Sets the radius to search for a location within, in meters.
+    ///
+    /// OpenAQ accepts a radius of up to 25 kilometers.
+    pub fn radius(mut self, radius: u32) -> Self {
+        self.radius = radius;
+
+        self
+    }
+
+    fn get<T: DeserializeOwned>(&self, uri: &str) -> Result<T> {
+        let res = self
+            .client
+            .get(uri)
+            .header("X-API-Key", &self.api_key[..])
+            .send()?
+            .error_for_status()?;
+
+        internal::from_reader(res)
+    }
+}
+
+impl AirQualityProvider for OpenAq {
+    fn air_quality(&self, latitude: f64, longitude: f64) -> Result<Option<AirQuality>> {
+        let uri = format!(
+            "{}/v3/locations?coordinates={},{}&radius={}&limit=1",
+            self.api_url, latitude, longitude, self.radius,
+        );
+        let location = match self
+            .get::<OpenAqResults<OpenAqLocation>>(&uri)?
+            .results
+            .pop()
+        {
+            Some(location) => location,
+            None => return Ok(None),
+        };
+
+        let uri = format!("{}/v3/locations/{}/latest", self.api_url, location.id);
+        let latest = self.get::<OpenAqResults<OpenAqLatest>>(&uri)?.results;
+
+        let readings = latest
+            .into_iter()
+            .filter_map(|latest| {
+                let sensor = location
+                    .sensors
+                    .iter()
+                    .find(|sensor| sensor.id == latest.sensors_id)?;
+
+                Some(Reading {
+                    parameter: sensor.parameter.name.clone(),
+                    value: latest.value,
+                    units: sensor.parameter.units.clone(),
+                    time: latest.datetime.map(|datetime| datetime.utc),
+                })
+            })
+            .collect();
+
+        Ok(Some(AirQuality {
+            location: location.name,
+            readings,
+        }))
+    }
+}
+
+#[derive(Deserialize)]
+struct OpenAqResults<T> {
+    results: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct OpenAqLocation {
+    id: u64,
+    name: Option<String>,
+    #[serde(default)]
+    sensors: Vec<OpenAqSensor>,
+}
+
+#[derive(Deserialize)]
+struct OpenAqSensor {
+    id: u64,
+    parameter: OpenAqParameter,
+}
+
+#[derive(Deserialize)]
+struct OpenAqParameter {
+    name: String,
+    units: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OpenAqLatest {
+    datetime: Option<OpenAqDatetime>,
+    sensors_id: u64,
+    value: f64,
+}
+
+#[derive(Deserialize)]
+struct OpenAqDatetime {
+    utc: String,
+}
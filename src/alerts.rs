@@ -0,0 +1,289 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Querying of [`Alert`]s, and tracking of them across successive forecasts
+//! for a location.
+//!
+//! [`Alert`]: ../models/struct.Alert.html
+
+use models::{Alert, Forecast, Severity};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// The order in which alerts matched by an [`AlertQuery`] are returned.
+///
+/// [`AlertQuery`]: struct.AlertQuery.html
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum AlertOrder {
+    /// The order in which the alerts were given by the API.
+    Api,
+    /// The soonest expiring alerts first.
+    Expiry,
+    /// The most severe alerts first, with ties broken by the soonest expiry.
+    Severity,
+}
+
+/// A query for the [`Alert`]s of a forecast, used with
+/// [`Forecast::alerts_matching`].
+///
+/// By default, every alert is matched in the order given by the API.
+///
+/// [`Alert`]: ../models/struct.Alert.html
+/// [`Forecast::alerts_matching`]: ../models/struct.Forecast.html#method.alerts_matching
+#[derive(Clone, Debug)]
+pub struct AlertQuery {
+    active_at: Option<u64>,
+    min_severity: Option<Severity>,
+    order: AlertOrder,
+    regions: Vec<String>,
+}
+
+impl AlertQuery {
+    /// Only match alerts that have been issued and have not expired at the
+    /// given Unix timestamp.
+    pub fn active_at(mut self, time: u64) -> Self {
+        self.active_at = Some(time);
+
+        self
+    }
+
+    /// Only match alerts that are at least as severe as the given severity.
+    pub fn min_severity(mut self, severity: Severity) -> Self {
+        self.min_severity = Some(severity);
+
+        self
+    }
+
+    /// Sets the order in which matched alerts are returned.
+    pub fn order(mut self, order: AlertOrder) -> Self {
+        self.order = order;
+
+        self
+    }
+
+    /// Only match alerts with a region containing the given text, ignoring
+    /// case.
+    ///
+    /// This may be called multiple times, in which case alerts with a region
+    /// containing any of the given texts are matched.
+    pub fn region<S: AsRef<str>>(mut self, region: S) -> Self {
+        self.regions.push(region.as_ref().to_lowercase());
+
+        self
+    }
+
+    /// Returns whether an alert matches the query.
+    pub fn matches(&self, alert: &Alert) -> bool {
+        if let Some(time) = self.active_at {
+            if alert.time > time || alert.expires <= time {
+                return false;
+            }
+        }
+
+        if let Some(severity) = self.min_severity {
+            if alert.severity < severity {
+                return false;
+            }
+        }
+
+        self.regions.is_empty()
+            || alert.regions.iter().any(|region| {
+                let region = region.to_lowercase();
+
+                self.regions.iter().any(|query| region.contains(&query[..]))
+            })
+    }
+
+    /// Returns the alerts matching the query, in the query's order.
+    pub fn filter<'a>(&self, alerts: &'a [Alert]) -> Vec<&'a Alert> {
+        let mut matched = alerts
+            .iter()
+            .filter(|alert| self.matches(alert))
+            .collect::<Vec<_>>();
+
+        match self.order {
+            AlertOrder::Api => {}
+            AlertOrder::Expiry => matched.sort_by_key(|alert| alert.expires),
+            AlertOrder::Severity => matched.sort_by(|a, b| match b.severity.cmp(&a.severity) {
+                Ordering::Equal => a.expires.cmp(&b.expires),
+                other => other,
+            }),
+        }
+
+        matched
+    }
+}
+
+impl Default for AlertQuery {
+    fn default() -> Self {
+        AlertQuery {
+            active_at: None,
+            min_severity: None,
+            order: AlertOrder::Api,
+            regions: Vec::new(),
+        }
+    }
+}
+
+/// A change in the alerts for a location, as detected by an [`AlertWatcher`].
+///
+/// [`AlertWatcher`]: struct.AlertWatcher.html
+#[derive(Clone, Debug)]
+pub enum AlertEvent {
+    /// An alert that was not present in the previous forecast.
+    Issued(Alert),
+    /// An alert that was present in the previous forecast, but with a lower
+    /// severity.
+    Escalated {
+        /// The alert with its new severity.
+        alert: Alert,
+        /// The severity of the alert in the previous forecast.
+        previous: Severity,
+    },
+}
+
+impl AlertEvent {
+    /// The alert that the event is for.
+    pub fn alert(&self) -> &Alert {
+        match *self {
+            AlertEvent::Issued(ref alert) => alert,
+            AlertEvent::Escalated { ref alert, .. } => alert,
+        }
+    }
+}
+
+/// Watches the alerts of successive forecasts for a single location, reporting
+/// alerts that are newly issued or have escalated in severity.
+///
+/// Alerts are identified by their [`uri`] and [`title`]. An alert that is
+/// absent from a forecast is forgotten, so if it is later reissued then it is
+/// reported again.
+///
+/// Only alerts matching a [filter][`filter`] can be watched for, such as
+/// warnings in specific regions.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate serde_json;
+///
+/// use darksky::alerts::{AlertEvent, AlertWatcher};
+/// use darksky::models::{Forecast, Severity};
+///
+/// # fn main() {
+/// let forecast = |severity: &str| -> Forecast {
+///     serde_json::from_str(&format!(r#"{{
+///         "latitude": 49.9,
+///         "longitude": -97.1,
+///         "timezone": "America/Winnipeg",
+///         "alerts": [{{
+///             "title": "Winter Storm",
+///             "regions": ["Winnipeg"],
+///             "severity": "{}",
+///             "time": 1509993277,
+///             "expires": 1510036680,
+///             "description": "Heavy snow expected.",
+///             "uri": "https://alerts.weather.gov/1"
+///         }}]
+///     }}"#, severity)).unwrap()
+/// };
+///
+/// let mut watcher = AlertWatcher::new();
+///
+/// let events = watcher.check(&forecast("watch"));
+/// assert_eq!(events.len(), 1);
+/// assert!(match events[0] { AlertEvent::Issued(_) => true, _ => false });
+///
+/// // Nothing has changed, so nothing is reported.
+/// assert!(watcher.check(&forecast("watch")).is_empty());
+///
+/// let events = watcher.check(&forecast("warning"));
+/// assert!(match events[0] {
+///     AlertEvent::Escalated { previous, .. } => previous == Severity::Watch,
+///     _ => false,
+/// });
+/// # }
+/// ```
+///
+/// [`filter`]: #method.filter
+/// [`title`]: ../models/struct.Alert.html#structfield.title
+/// [`uri`]: ../models/struct.Alert.html#structfield.uri
+#[derive(Clone, Debug, Default)]
+pub struct AlertWatcher {
+    filter: AlertQuery,
+    seen: HashMap<(String, String), Severity>,
+}
+
+impl AlertWatcher {
+    /// Creates a new watcher that has not yet seen any alerts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a filter for the alerts that are watched, such as a minimum
+    /// severity or a set of regions. The ordering of the query is ignored.
+    ///
+    /// Alerts not matching the filter are ignored entirely, so an alert that
+    /// escalates to match a minimum severity is reported as newly issued.
+    ///
+    /// # Examples
+    ///
+    /// Only watch for warnings affecting Wake County:
+    ///
+    /// ```rust
+    /// use darksky::alerts::AlertWatcher;
+    /// use darksky::models::Severity;
+    ///
+    /// let watcher = AlertWatcher::new()
+    ///     .filter(|query| query.min_severity(Severity::Warning).region("Wake"));
+    /// ```
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: FnOnce(AlertQuery) -> AlertQuery,
+    {
+        self.filter = filter(AlertQuery::default());
+
+        self
+    }
+
+    /// Compares the alerts of a forecast against those of the previously
+    /// checked forecast, returning any that were issued or escalated.
+    pub fn check(&mut self, forecast: &Forecast) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+        let mut seen = HashMap::with_capacity(forecast.alerts.len());
+
+        for alert in forecast.alerts.iter().filter(|a| self.filter.matches(a)) {
+            let key = (alert.uri.clone(), alert.title.clone());
+
+            match self.seen.get(&key) {
+                None => events.push(AlertEvent::Issued(alert.clone())),
+                Some(&previous) if previous < alert.severity => {
+                    events.push(AlertEvent::Escalated {
+                        alert: alert.clone(),
+                        previous,
+                    });
+                }
+                Some(_) => {}
+            }
+
+            seen.insert(key, alert.severity);
+        }
+
+        self.seen = seen;
+
+        events
+    }
+}
@@ -0,0 +1,589 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Quota-aware backfilling of historical forecasts with Time Machine
+//! requests, for building local climate datasets.
+//!
+//! A [`Backfill`] requests each day of a date range for a single location,
+//! while a [`Scheduler`] works through a queue of [`Job`]s, each a location
+//! and time, paced by a [`RateLimiter`].
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`Backfill`] and [`Scheduler`].
+//!
+//! [`Backfill`]: struct.Backfill.html
+//! [`Job`]: struct.Job.html
+//! [`RateLimiter`]: ../bridge/limit/struct.RateLimiter.html
+//! [`Scheduler`]: struct.Scheduler.html
+
+use bridge::limit::RateLimiter;
+use constants::DEFAULT_DAILY_LIMIT;
+use models::Forecast;
+use serde_json;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use {Coordinate, Result};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// The progress of a [`Backfill`], as persisted between runs.
+///
+/// [`Backfill`]: struct.Backfill.html
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BackfillProgress {
+    /// The Unix timestamp of the next day to request.
+    pub next: u64,
+    /// The UTC day, as a number of days since the Unix epoch, that
+    /// [`calls`][`BackfillProgress::calls`] were made on.
+    ///
+    /// [`BackfillProgress::calls`]: #structfield.calls
+    pub quota_day: u64,
+    /// The number of requests made on the current UTC day.
+    pub calls: u64,
+}
+
+/// The outcome of a call to [`Backfill::run`] or [`Scheduler::run`].
+///
+/// [`Backfill::run`]: struct.Backfill.html#method.run
+/// [`Scheduler::run`]: struct.Scheduler.html#method.run
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BackfillStatus {
+    /// Every day of the range, or every job of the queue, has been
+    /// requested.
+    Complete,
+    /// The daily quota has been used up, and requests can resume at the given
+    /// time, being the next midnight UTC, when DarkSky resets its quotas.
+    QuotaExhausted {
+        /// When requests can resume.
+        resume_at: SystemTime,
+    },
+}
+
+/// Requests a historical forecast for each day of a date range, spread over
+/// as many days as needed to stay under the daily API quota.
+///
+/// Progress is kept in a [`BackfillProgress`], which is saved as JSON after
+/// each request if a [progress file][`progress_file`] is set, so that a
+/// backfill spanning months or years resumes where it left off after a
+/// restart.
+///
+/// The backfill is independent of the HTTP client: a closure is given the
+/// Unix timestamp of each day to request, and another receives each
+/// forecast, such as to write it to a database.
+///
+/// # Examples
+///
+/// Backfill two years of history, 900 requests a day:
+///
+#[cfg_attr(feature = "reqwest-blocking", doc = "```rust,no_run")]
+#[cfg_attr(not(feature = "reqwest-blocking"), doc = "```rust,ignore")]
+/// extern crate darksky;
+/// extern crate reqwest;
+///
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<dyn Error>> {
+/// use darksky::backfill::Backfill;
+/// use darksky::DarkskyReqwestRequester;
+/// use reqwest::blocking::Client;
+/// use std::env;
+///
+/// let token = env::var("FORECAST_TOKEN")?;
+/// let client = Client::new();
+///
+/// let mut backfill = Backfill::new(1_451_606_400, 1_514_764_800)
+///     .daily_limit(900)
+///     .progress_file("winnipeg.progress.json")?;
+///
+/// backfill.run_to_completion(
+///     |time| client.get_forecast_time_machine(&token, (49.9, -97.1), time, |o| o),
+///     |time, forecast| {
+///         println!("{}: {:?}", time, forecast.daily);
+///
+///         Ok(())
+///     },
+/// )?;
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// [`BackfillProgress`]: struct.BackfillProgress.html
+/// [`progress_file`]: #method.progress_file
+#[derive(Clone, Debug)]
+pub struct Backfill {
+    daily_limit: u64,
+    end: u64,
+    path: Option<PathBuf>,
+    progress: BackfillProgress,
+}
+
+impl Backfill {
+    /// Creates a backfill of each day from the `start` Unix timestamp up to,
+    /// but not including, the `end` timestamp, allowing
+    /// [`DEFAULT_DAILY_LIMIT`] requests per day.
+    ///
+    /// [`DEFAULT_DAILY_LIMIT`]: ../constants/constant.DEFAULT_DAILY_LIMIT.html
+    pub fn new(start: u64, end: u64) -> Self {
+        Backfill {
+            daily_limit: DEFAULT_DAILY_LIMIT,
+            end,
+            path: None,
+            progress: BackfillProgress {
+                next: start,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Sets the number of requests made per UTC day.
+    ///
+    /// Set this below the plan's limit to leave room for other requests made
+    /// with the same token.
+    pub fn daily_limit(mut self, daily_limit: u64) -> Self {
+        self.daily_limit = daily_limit;
+
+        self
+    }
+
+    /// Sets the file that progress is saved to after each request.
+    ///
+    /// If the file exists, the backfill resumes from the progress saved in
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file exists but cannot be read, or
+    /// [`Error::Json`] if it does not contain saved progress.
+    ///
+    /// [`Error::Io`]: ../enum.Error.html#variant.Io
+    /// [`Error::Json`]: ../enum.Error.html#variant.Json
+    pub fn progress_file<P: Into<PathBuf>>(mut self, path: P) -> Result<Self> {
+        let path = path.into();
+
+        if path.exists() {
+            self.progress = serde_json::from_slice(&fs::read(&path)?)?;
+        }
+
+        self.path = Some(path);
+
+        Ok(self)
+    }
+
+    /// Returns the current progress.
+    pub fn progress(&self) -> &BackfillProgress {
+        &self.progress
+    }
+
+    /// Returns the number of days that are left to request.
+    pub fn remaining(&self) -> u64 {
+        self.end
+            .saturating_sub(self.progress.next)
+            .div_ceil(SECONDS_PER_DAY)
+    }
+
+    /// Requests days until the range is complete or the day's quota is used
+    /// up.
+    ///
+    /// `fetch` is called with the Unix timestamp of each day, and `store` with
+    /// the timestamp and the resulting forecast. Progress only advances past a
+    /// day once it has been stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from `fetch` or `store`, or from saving progress. The
+    /// failed day is retried on the next run.
+    pub fn run<F, S>(&mut self, mut fetch: F, mut store: S) -> Result<BackfillStatus>
+    where
+        F: FnMut(u64) -> Result<Forecast>,
+        S: FnMut(u64, Forecast) -> Result<()>,
+    {
+        while self.progress.next < self.end {
+            let progress = &mut self.progress;
+
+            if let Some(resume_at) = take_call(
+                &mut progress.quota_day,
+                &mut progress.calls,
+                self.daily_limit,
+            ) {
+                return Ok(BackfillStatus::QuotaExhausted { resume_at });
+            }
+
+            let time = self.progress.next;
+            let result = fetch(time).and_then(|forecast| store(time, forecast));
+
+            if result.is_ok() {
+                self.progress.next += SECONDS_PER_DAY;
+            }
+
+            self.save()?;
+            result?;
+        }
+
+        Ok(BackfillStatus::Complete)
+    }
+
+    /// Runs the backfill to completion, sleeping until the quota resets
+    /// whenever it is used up.
+    ///
+    /// Refer to [`run`] for details.
+    ///
+    /// [`run`]: #method.run
+    pub fn run_to_completion<F, S>(&mut self, mut fetch: F, mut store: S) -> Result<()>
+    where
+        F: FnMut(u64) -> Result<Forecast>,
+        S: FnMut(u64, Forecast) -> Result<()>,
+    {
+        while let BackfillStatus::QuotaExhausted { resume_at } = self.run(&mut fetch, &mut store)? {
+            sleep_until(resume_at);
+        }
+
+        Ok(())
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(ref path) = self.path {
+            fs::write(path, serde_json::to_vec(&self.progress)?)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A request for the forecast of a location at a time, queued in a
+/// [`Scheduler`].
+///
+/// [`Scheduler`]: struct.Scheduler.html
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(from = "JobRecord", into = "JobRecord")]
+pub struct Job {
+    /// The location to request the forecast of.
+    pub location: Coordinate,
+    /// The Unix timestamp to request the forecast at.
+    pub time: u64,
+}
+
+impl Job {
+    /// Creates a job for the forecast of a location at a time.
+    ///
+    /// The location is a [`Coordinate`], or a `(latitude, longitude)`
+    /// tuple.
+    ///
+    /// [`Coordinate`]: ../location/struct.Coordinate.html
+    pub fn new<L: Into<Coordinate>>(location: L, time: u64) -> Self {
+        Job {
+            location: location.into(),
+            time,
+        }
+    }
+}
+
+/// A [`Job`] as persisted in a progress file, keeping the latitude and
+/// longitude as separate fields.
+///
+/// [`Job`]: struct.Job.html
+#[derive(Deserialize, Serialize)]
+struct JobRecord {
+    latitude: f64,
+    longitude: f64,
+    time: u64,
+}
+
+impl From<JobRecord> for Job {
+    fn from(record: JobRecord) -> Self {
+        Job::new((record.latitude, record.longitude), record.time)
+    }
+}
+
+impl From<Job> for JobRecord {
+    fn from(job: Job) -> Self {
+        JobRecord {
+            latitude: job.location.lat,
+            longitude: job.location.lon,
+            time: job.time,
+        }
+    }
+}
+
+/// The progress of a [`Scheduler`], as persisted between runs.
+///
+/// [`Scheduler`]: struct.Scheduler.html
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SchedulerProgress {
+    /// The jobs left to request, in order.
+    pub pending: VecDeque<Job>,
+    /// The UTC day, as a number of days since the Unix epoch, that
+    /// [`calls`][`SchedulerProgress::calls`] were made on.
+    ///
+    /// [`SchedulerProgress::calls`]: #structfield.calls
+    pub quota_day: u64,
+    /// The number of requests made on the current UTC day.
+    pub calls: u64,
+}
+
+/// Requests the forecast of each of a queue of [`Job`]s, in order, pacing
+/// requests with a [`RateLimiter`] and spreading them over as many days as
+/// needed to stay under the daily API quota.
+///
+/// Like a [`Backfill`], progress is kept in a [`SchedulerProgress`], which
+/// is saved as JSON after each request if a [progress file][`progress_file`]
+/// is set, so that a run interrupted part way through resumes where it left
+/// off, and a closure is given each job to request.
+///
+/// # Examples
+///
+/// Backfill a month of history for two locations, at most 2 requests a
+/// second and 900 requests a day:
+///
+#[cfg_attr(feature = "reqwest-blocking", doc = "```rust,no_run")]
+#[cfg_attr(not(feature = "reqwest-blocking"), doc = "```rust,ignore")]
+/// extern crate darksky;
+/// extern crate reqwest;
+///
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<dyn Error>> {
+/// use darksky::backfill::{Job, Scheduler};
+/// use darksky::bridge::limit::RateLimiter;
+/// use darksky::DarkskyReqwestRequester;
+/// use reqwest::blocking::Client;
+/// use std::env;
+///
+/// let token = env::var("FORECAST_TOKEN")?;
+/// let client = Client::new();
+///
+/// let jobs = (0..30).flat_map(|day| {
+///     let time = 1_514_764_800 + day * 86_400;
+///
+///     vec![Job::new((49.9, -97.1), time), Job::new((45.4, -75.7), time)]
+/// });
+///
+/// let mut scheduler = Scheduler::new(jobs)
+///     .daily_limit(900)
+///     .rate_limiter(RateLimiter::new().per_second(2))
+///     .progress_file("january.progress.json")?;
+///
+/// scheduler.run_to_completion(
+///     |job| client.get_forecast_time_machine(&token, job.location, job.time, |o| o),
+///     |job, forecast| {
+///         println!("{:?}: {:?}", job, forecast.daily);
+///
+///         Ok(())
+///     },
+/// )?;
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// [`Backfill`]: struct.Backfill.html
+/// [`Job`]: struct.Job.html
+/// [`RateLimiter`]: ../bridge/limit/struct.RateLimiter.html
+/// [`SchedulerProgress`]: struct.SchedulerProgress.html
+/// [`progress_file`]: #method.progress_file
+#[derive(Clone, Debug)]
+pub struct Scheduler {
+    daily_limit: u64,
+    limiter: RateLimiter,
+    path: Option<PathBuf>,
+    progress: SchedulerProgress,
+}
+
+impl Scheduler {
+    /// Creates a scheduler of the given jobs, allowing
+    /// [`DEFAULT_DAILY_LIMIT`] requests per day, with no limit per second.
+    ///
+    /// [`DEFAULT_DAILY_LIMIT`]: ../constants/constant.DEFAULT_DAILY_LIMIT.html
+    pub fn new<I: IntoIterator<Item = Job>>(jobs: I) -> Self {
+        Scheduler {
+            daily_limit: DEFAULT_DAILY_LIMIT,
+            limiter: RateLimiter::new(),
+            path: None,
+            progress: SchedulerProgress {
+                pending: jobs.into_iter().collect(),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Sets the number of requests made per UTC day.
+    ///
+    /// Set this below the plan's limit to leave room for other requests made
+    /// with the same token.
+    pub fn daily_limit(mut self, daily_limit: u64) -> Self {
+        self.daily_limit = daily_limit;
+
+        self
+    }
+
+    /// Sets the limiter pacing requests, such as to a number per second.
+    ///
+    /// The limiter may be shared with other clients using the same token.
+    /// Its own daily limit is not persisted, so the scheduler's
+    /// [`daily_limit`] should be used for the day's quota instead.
+    ///
+    /// [`daily_limit`]: #method.daily_limit
+    pub fn rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = limiter;
+
+        self
+    }
+
+    /// Sets the file that progress is saved to after each request.
+    ///
+    /// If the file exists, the scheduler resumes from the progress saved in
+    /// it, replacing the jobs it was created with.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file exists but cannot be read, or
+    /// [`Error::Json`] if it does not contain saved progress.
+    ///
+    /// [`Error::Io`]: ../enum.Error.html#variant.Io
+    /// [`Error::Json`]: ../enum.Error.html#variant.Json
+    pub fn progress_file<P: Into<PathBuf>>(mut self, path: P) -> Result<Self> {
+        let path = path.into();
+
+        if path.exists() {
+            self.progress = serde_json::from_slice(&fs::read(&path)?)?;
+        }
+
+        self.path = Some(path);
+
+        Ok(self)
+    }
+
+    /// Returns the current progress.
+    pub fn progress(&self) -> &SchedulerProgress {
+        &self.progress
+    }
+
+    /// Returns the number of jobs that are left to request.
+    pub fn remaining(&self) -> usize {
+        self.progress.pending.len()
+    }
+
+    /// Requests jobs until the queue is empty or the day's quota is used up,
+    /// waiting between requests as the rate limiter requires.
+    ///
+    /// `fetch` is called with each job, and `store` with the job and the
+    /// resulting forecast. A job is only removed from the queue once it has
+    /// been stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from `fetch` or `store`, or from saving progress. The
+    /// failed job is retried on the next run.
+    pub fn run<F, S>(&mut self, mut fetch: F, mut store: S) -> Result<BackfillStatus>
+    where
+        F: FnMut(&Job) -> Result<Forecast>,
+        S: FnMut(&Job, Forecast) -> Result<()>,
+    {
+        while let Some(&job) = self.progress.pending.front() {
+            let progress = &mut self.progress;
+
+            if let Some(resume_at) = take_call(
+                &mut progress.quota_day,
+                &mut progress.calls,
+                self.daily_limit,
+            ) {
+                return Ok(BackfillStatus::QuotaExhausted { resume_at });
+            }
+
+            thread::sleep(self.limiter.reserve());
+            let result = fetch(&job).and_then(|forecast| store(&job, forecast));
+
+            if result.is_ok() {
+                self.progress.pending.pop_front();
+            }
+
+            self.save()?;
+            result?;
+        }
+
+        Ok(BackfillStatus::Complete)
+    }
+
+    /// Runs the scheduler until the queue is empty, sleeping until the quota
+    /// resets whenever it is used up.
+    ///
+    /// Refer to [`run`] for details.
+    ///
+    /// [`run`]: #method.run
+    pub fn run_to_completion<F, S>(&mut self, mut fetch: F, mut store: S) -> Result<()>
+    where
+        F: FnMut(&Job) -> Result<Forecast>,
+        S: FnMut(&Job, Forecast) -> Result<()>,
+    {
+        while let BackfillStatus::QuotaExhausted { resume_at } = self.run(&mut fetch, &mut store)? {
+            sleep_until(resume_at);
+        }
+
+        Ok(())
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(ref path) = self.path {
+            fs::write(path, serde_json::to_vec(&self.progress)?)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Counts a request against the day's quota, resetting the count on a new
+/// UTC day.
+///
+/// Returns when the quota resets instead, if it has been used up.
+fn take_call(quota_day: &mut u64, calls: &mut u64, daily_limit: u64) -> Option<SystemTime> {
+    let today = utc_day(SystemTime::now());
+
+    if *quota_day != today {
+        *quota_day = today;
+        *calls = 0;
+    }
+
+    if *calls >= daily_limit {
+        return Some(UNIX_EPOCH + Duration::from_secs((today + 1) * SECONDS_PER_DAY));
+    }
+
+    *calls += 1;
+
+    None
+}
+
+/// Sleeps until the given time, if it is in the future.
+fn sleep_until(time: SystemTime) {
+    if let Ok(wait) = time.duration_since(SystemTime::now()) {
+        thread::sleep(wait);
+    }
+}
+
+/// Returns the number of days since the Unix epoch of a time, in UTC.
+fn utc_day(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs() / SECONDS_PER_DAY)
+        .unwrap_or(0)
+}
@@ -0,0 +1,196 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! A terminal dashboard showing the current conditions, the next hours'
+//! temperatures and precipitation, and any active alerts, refreshed
+//! periodically.
+
+use darksky::format::{self, FormatOptions};
+use darksky::models::Forecast;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::widgets::{Block, List, Paragraph, Sparkline};
+use ratatui::{DefaultTerminal, Frame};
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+/// The number of hours shown in the sparklines.
+const HOURS: usize = 24;
+
+/// How long to wait for input before checking whether to refresh.
+const TICK: Duration = Duration::from_millis(250);
+
+/// Runs the dashboard until the user quits, starting with `forecast` and
+/// fetching a new one every `refresh` interval or when `r` is pressed.
+pub fn run<F>(
+    options: &FormatOptions,
+    refresh: Duration,
+    forecast: Forecast,
+    mut fetch: F,
+) -> Result<(), Box<dyn Error>>
+where
+    F: FnMut() -> Result<Forecast, Box<dyn Error>>,
+{
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, options, refresh, forecast, &mut fetch);
+    ratatui::restore();
+
+    result
+}
+
+fn event_loop<F>(
+    terminal: &mut DefaultTerminal,
+    options: &FormatOptions,
+    refresh: Duration,
+    mut forecast: Forecast,
+    fetch: &mut F,
+) -> Result<(), Box<dyn Error>>
+where
+    F: FnMut() -> Result<Forecast, Box<dyn Error>>,
+{
+    let mut status = String::new();
+    let mut last_fetch = Instant::now();
+    let mut requested = false;
+
+    loop {
+        if requested || last_fetch.elapsed() >= refresh {
+            match fetch() {
+                Ok(latest) => {
+                    forecast = latest;
+                    status.clear();
+                }
+                // Keep showing the previous forecast.
+                Err(why) => status = format!("Error refreshing: {}", why),
+            }
+
+            last_fetch = Instant::now();
+            requested = false;
+        }
+
+        terminal.draw(|frame| draw(frame, &forecast, options, &status))?;
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('r') => requested = true,
+                _ => {}
+            }
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, forecast: &Forecast, options: &FormatOptions, status: &str) {
+    let [now, temperature, precipitation, alerts, help] = Layout::vertical([
+        Constraint::Length(4),
+        Constraint::Length(6),
+        Constraint::Length(6),
+        Constraint::Min(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let help_text = if status.is_empty() {
+        "q: quit  r: refresh"
+    } else {
+        status
+    };
+    frame.render_widget(Paragraph::new(help_text), help);
+
+    let currently = forecast
+        .currently
+        .as_ref()
+        .map(|currently| format::datapoint(currently, options))
+        .unwrap_or_default();
+    let today = forecast
+        .daily
+        .as_ref()
+        .and_then(|block| block.summary.as_ref())
+        .map(|summary| summary.to_string())
+        .unwrap_or_default();
+    frame.render_widget(
+        Paragraph::new(format!("{}\n{}", currently, today))
+            .block(Block::bordered().title(forecast.timezone.as_str())),
+        now,
+    );
+
+    let hourly = forecast
+        .hourly
+        .as_ref()
+        .and_then(|block| block.data.as_ref())
+        .map(|data| &data[..data.len().min(HOURS)])
+        .unwrap_or(&[]);
+
+    let temperatures = hourly
+        .iter()
+        .map(|d| d.temperature.unwrap_or(0.0))
+        .collect::<Vec<_>>();
+    let coldest = temperatures.iter().cloned().fold(f64::INFINITY, f64::min);
+    let warmest = temperatures
+        .iter()
+        .cloned()
+        .fold(f64::NEG_INFINITY, f64::max);
+    // Sparklines can only show non-negative values, so temperatures are
+    // shifted to start at zero.
+    let shifted = temperatures
+        .iter()
+        .map(|t| ((t - coldest) * 10.0).round() as u64)
+        .collect::<Vec<_>>();
+    let title = if temperatures.is_empty() {
+        "Temperature".to_owned()
+    } else {
+        format!("Temperature ({:.0}° to {:.0}°)", coldest, warmest)
+    };
+    frame.render_widget(
+        Sparkline::default()
+            .block(Block::bordered().title(title))
+            .data(&shifted),
+        temperature,
+    );
+
+    let probabilities = hourly
+        .iter()
+        .map(|d| (d.precip_probability.unwrap_or(0.0) * 100.0).round() as u64)
+        .collect::<Vec<_>>();
+    frame.render_widget(
+        Sparkline::default()
+            .block(Block::bordered().title("Precipitation probability"))
+            .data(&probabilities)
+            .max(100),
+        precipitation,
+    );
+
+    let items = forecast
+        .alerts
+        .iter()
+        .map(|alert| {
+            let severity = options.get_locale().severity(alert.severity);
+
+            format!("[{}] {}", severity, alert.title)
+        })
+        .collect::<Vec<_>>();
+    let title = format!("Alerts ({})", items.len());
+    frame.render_widget(
+        List::new(items).block(Block::bordered().title(title)),
+        alerts,
+    );
+}
@@ -0,0 +1,358 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! A command line client for the DarkSky API.
+//!
+//! Run `darksky --help` for usage.
+
+extern crate darksky;
+#[cfg(feature = "tui")]
+extern crate ratatui;
+extern crate reqwest;
+extern crate serde;
+extern crate serde_json;
+
+use darksky::format::{self, FormatOptions};
+use darksky::models::{Datapoint, Forecast};
+use darksky::{ApiToken, Block, Coordinate, DarkskyReqwestRequester, Language, Options, Unit};
+use reqwest::blocking::Client;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::error::Error;
+#[cfg(feature = "tui")]
+use std::time::Duration;
+use std::{env, process};
+
+#[cfg(feature = "tui")]
+mod dashboard;
+
+const USAGE: &str = "\
+Usage:
+    darksky now LAT,LON [options]
+    darksky hourly LAT,LON [options]
+    darksky history LAT,LON DATE [options]
+    darksky dashboard LAT,LON [--refresh SECONDS] [options]
+
+DATE is a Unix timestamp, YYYY-MM-DD, or YYYY-MM-DDTHH:MM:SS in the
+location's local time.
+
+The dashboard command is only available when built with the tui feature.
+It refreshes every 300 seconds by default; press r to refresh now or q to
+quit.
+
+Options:
+    --token TOKEN        API token (defaults to $FORECAST_TOKEN)
+    --units UNITS        auto, ca, si, uk2, or us
+    --lang LANGUAGE      language of summaries, e.g. en or fr
+    --exclude BLOCKS     comma-separated blocks to exclude, e.g. minutely,flags
+    --format FORMAT      text (default), json, or csv
+    -h, --help           print this message";
+
+/// The columns of CSV output.
+const CSV_COLUMNS: &str =
+    "time,summary,temperature,apparent_temperature,precip_probability,humidity,wind_speed";
+
+#[derive(Clone, Copy, PartialEq)]
+enum Command {
+    History,
+    Hourly,
+    Now,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Output {
+    Csv,
+    #[cfg(feature = "tui")]
+    Dashboard(Duration),
+    Json,
+    Text,
+}
+
+struct Args {
+    command: Command,
+    date: Option<String>,
+    exclude: Vec<Block>,
+    language: Option<Language>,
+    latitude: f64,
+    longitude: f64,
+    output: Output,
+    token: ApiToken,
+    unit: Option<Unit>,
+}
+
+fn main() {
+    let args = match parse_args(env::args().skip(1).collect()) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+
+            return;
+        }
+        Err(why) => {
+            eprintln!("error: {}\n\n{}", why, USAGE);
+
+            process::exit(2);
+        }
+    };
+
+    if let Err(why) = run(&args) {
+        eprintln!("error: {}", why);
+
+        process::exit(1);
+    }
+}
+
+fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let client = Client::new();
+    let forecast = fetch(&client, args)?;
+
+    let datapoints = match args.command {
+        Command::Now => forecast.currently.iter().collect::<Vec<_>>(),
+        Command::History | Command::Hourly => forecast
+            .hourly
+            .iter()
+            .flat_map(|block| block.data.iter().flatten())
+            .collect(),
+    };
+
+    match args.output {
+        Output::Csv => print_csv(&datapoints),
+        #[cfg(feature = "tui")]
+        Output::Dashboard(refresh) => {
+            dashboard::run(&format_options(args), refresh, forecast, || {
+                fetch(&client, args)
+            })?
+        }
+        Output::Json => print_json(args.command, &forecast)?,
+        Output::Text => print_text(args, &forecast),
+    }
+
+    Ok(())
+}
+
+fn fetch(client: &Client, args: &Args) -> Result<Forecast, Box<dyn Error>> {
+    let options = |mut options: Options| {
+        if !args.exclude.is_empty() {
+            options = options.exclude(args.exclude.clone());
+        }
+
+        if let Some(ref language) = args.language {
+            options = options.language(language.clone());
+        }
+
+        if let Some(unit) = args.unit {
+            options = options.unit(unit);
+        }
+
+        options
+    };
+
+    let location = Coordinate::new(args.latitude, args.longitude);
+    let forecast = match args.date {
+        Some(ref date) => {
+            client.get_forecast_time_machine(args.token.as_str(), location, date, options)?
+        }
+        None => client.get_forecast_with_options(args.token.as_str(), location, options)?,
+    };
+
+    Ok(forecast)
+}
+
+fn print_csv(datapoints: &[&Datapoint]) {
+    let number = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+    println!("{}", CSV_COLUMNS);
+
+    for datapoint in datapoints {
+        let summary = datapoint
+            .summary
+            .as_ref()
+            .map(|summary| format!("\"{}\"", summary.replace('"', "\"\"")))
+            .unwrap_or_default();
+
+        println!(
+            "{},{},{},{},{},{},{}",
+            datapoint.time,
+            summary,
+            number(datapoint.temperature),
+            number(datapoint.apparent_temperature),
+            number(datapoint.precip_probability),
+            number(datapoint.humidity),
+            number(datapoint.wind_speed),
+        );
+    }
+}
+
+fn print_json(command: Command, forecast: &Forecast) -> Result<(), Box<dyn Error>> {
+    let json = match command {
+        Command::Now => serde_json::to_string_pretty(&forecast.currently)?,
+        Command::History | Command::Hourly => serde_json::to_string_pretty(forecast)?,
+    };
+
+    println!("{}", json);
+
+    Ok(())
+}
+
+fn print_text(args: &Args, forecast: &Forecast) {
+    let options = match args.command {
+        Command::Now => format_options(args).hours(0),
+        Command::History | Command::Hourly => format_options(args).hours(usize::MAX),
+    };
+
+    print!("{}", format::forecast(forecast, &options));
+}
+
+fn format_options(args: &Args) -> FormatOptions {
+    let mut options = FormatOptions::default();
+
+    if let Some(ref language) = args.language {
+        options = options.language(language.clone());
+    }
+
+    options
+}
+
+fn parse_args(args: Vec<String>) -> Result<Option<Args>, String> {
+    if args.is_empty() || args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        return Ok(None);
+    }
+
+    let mut positional = Vec::new();
+    let mut exclude = Vec::new();
+    let mut language = None;
+    let mut output = Output::Text;
+    #[cfg(feature = "tui")]
+    let mut refresh = Duration::from_secs(300);
+    let mut token = env::var("FORECAST_TOKEN").ok();
+    let mut unit = None;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            positional.push(arg);
+
+            continue;
+        }
+
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", arg))?;
+
+        match &arg[..] {
+            "--exclude" => {
+                for block in value.split(',') {
+                    exclude.push(parse_name(block, "block")?);
+                }
+            }
+            "--format" => {
+                output = match &value[..] {
+                    "csv" => Output::Csv,
+                    "json" => Output::Json,
+                    "text" => Output::Text,
+                    other => return Err(format!("unknown format: {}", other)),
+                }
+            }
+            "--lang" => language = Some(parse_name(&value, "language")?),
+            #[cfg(feature = "tui")]
+            "--refresh" => {
+                let seconds = value
+                    .parse()
+                    .ok()
+                    .filter(|&seconds| seconds > 0)
+                    .ok_or_else(|| format!("invalid refresh interval: {}", value))?;
+                refresh = Duration::from_secs(seconds);
+            }
+            "--token" => token = Some(value),
+            "--units" => unit = Some(parse_name(&value, "units")?),
+            other => return Err(format!("unknown option: {}", other)),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let command = match positional.next().as_ref().map(|c| &c[..]) {
+        #[cfg(feature = "tui")]
+        Some("dashboard") => {
+            output = Output::Dashboard(refresh);
+
+            Command::Hourly
+        }
+        Some("history") => Command::History,
+        Some("hourly") => Command::Hourly,
+        Some("now") => Command::Now,
+        Some(other) => return Err(format!("unknown command: {}", other)),
+        None => return Err("missing command".to_owned()),
+    };
+    let location = positional.next().ok_or("missing LAT,LON")?;
+    let (latitude, longitude) = parse_location(&location)?;
+    let date = match command {
+        Command::History => Some(parse_date(&positional.next().ok_or("missing DATE")?)?),
+        Command::Hourly | Command::Now => None,
+    };
+
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument: {}", extra));
+    }
+
+    Ok(Some(Args {
+        command,
+        date,
+        exclude,
+        language,
+        latitude,
+        longitude,
+        output,
+        token: ApiToken::parse(&token.ok_or("missing token: pass --token or set FORECAST_TOKEN")?)
+            .map_err(|why| why.to_string())?,
+        unit,
+    }))
+}
+
+/// Parses the API name of a block, language, or unit, such as `minutely` or
+/// `zh-tw`, through its serde representation.
+fn parse_name<T>(name: &str, kind: &str) -> Result<T, String>
+where
+    T: DeserializeOwned,
+{
+    serde_json::from_value(Value::String(name.to_owned()))
+        .map_err(|_| format!("unknown {}: {}", kind, name))
+}
+
+fn parse_location(location: &str) -> Result<(f64, f64), String> {
+    let invalid = || format!("invalid location, expected LAT,LON: {}", location);
+    let mut parts = location.splitn(2, ',');
+    let latitude = parts.next().and_then(|lat| lat.trim().parse().ok());
+    let longitude = parts.next().and_then(|long| long.trim().parse().ok());
+
+    match (latitude, longitude) {
+        (Some(latitude), Some(longitude)) => Ok((latitude, longitude)),
+        _ => Err(invalid()),
+    }
+}
+
+fn parse_date(date: &str) -> Result<String, String> {
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let parts = date.split('-').collect::<Vec<_>>();
+
+    if is_digits(date) {
+        return Ok(date.to_owned());
+    }
+
+    match parts.len() {
+        3 if parts.iter().all(|part| is_digits(part)) => Ok(format!("{}T00:00:00", date)),
+        3 if date.len() == 19 && date.as_bytes()[10] == b'T' => Ok(date.to_owned()),
+        _ => Err(format!("invalid date: {}", date)),
+    }
+}
@@ -0,0 +1,213 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! A blocking client over the hyper bridge, for command line tools and
+//! scripts that only need a forecast now and then.
+//!
+//! The client owns a single-threaded Tokio runtime, and blocks on it for each
+//! request, so no knowledge of async Rust is needed. It connects over HTTPS
+//! with rustls.
+//!
+//! # Examples
+//!
+//! Retrieve a single forecast:
+//!
+//! ```rust,no_run
+//! extern crate darksky;
+//!
+//! # use std::error::Error;
+//! #
+//! # fn try_main() -> Result<(), Box<dyn Error>> {
+//! use std::env;
+//!
+//! let token = env::var("FORECAST_TOKEN")?;
+//! let forecast = darksky::blocking::get_forecast(&token, (37.8267, -122.423))?;
+//!
+//! println!("{:?}", forecast.currently);
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     try_main().unwrap();
+//! # }
+//! ```
+//!
+//! Reuse a [`Client`] for several forecasts:
+//!
+//! ```rust,no_run
+//! extern crate darksky;
+//!
+//! # use std::error::Error;
+//! #
+//! # fn try_main() -> Result<(), Box<dyn Error>> {
+//! use darksky::blocking::Client;
+//! use darksky::Block;
+//! use std::env;
+//!
+//! let token = env::var("FORECAST_TOKEN")?;
+//! let client = Client::new()?;
+//!
+//! for &location in &[(37.8267, -122.423), (49.9, -97.1)] {
+//!     let forecast = client.get_forecast_with_options(&token, location, |o| o
+//!         .exclude(vec![Block::Minutely]))?;
+//!
+//!     println!("{}: {:?}", forecast.timezone, forecast.currently);
+//! }
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     try_main().unwrap();
+//! # }
+//! ```
+//!
+//! [`Client`]: struct.Client.html
+
+use bridge::hyper::DarkskyHyperRequester;
+use hyper::client::HttpConnector;
+use hyper::{Body, Client as HyperClient};
+use hyper_rustls::HttpsConnector;
+use models::Forecast;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use tokio::runtime::{Builder, Runtime};
+use {client, IntoLocation, Options, Result};
+
+/// A blocking client, making requests with hyper on a runtime of its own.
+pub struct Client {
+    client: HyperClient<HttpsConnector<HttpConnector>, Body>,
+    runtime: Runtime,
+}
+
+impl Client {
+    /// Creates a client with its own single-threaded runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the runtime could not be created.
+    ///
+    /// [`Error::Io`]: ../enum.Error.html#variant.Io
+    pub fn new() -> Result<Self> {
+        let runtime = Builder::new_current_thread().enable_all().build()?;
+
+        Ok(Client {
+            client: client::hyper_rustls(),
+            runtime,
+        })
+    }
+
+    /// Retrieve a [`Forecast`] for the given location.
+    ///
+    /// [`Forecast`]: ../models/struct.Forecast.html
+    pub fn get_forecast<L: IntoLocation>(&self, token: &str, location: L) -> Result<Forecast> {
+        self.runtime
+            .block_on(self.client.get_forecast(token, location))
+    }
+
+    /// Retrieve a [`Forecast`] for the given location, setting options where
+    /// needed. For a full list of options, refer to the documentation for the
+    /// [`Options`] builder.
+    ///
+    /// [`Forecast`]: ../models/struct.Forecast.html
+    /// [`Options`]: ../struct.Options.html
+    pub fn get_forecast_with_options<F, L>(
+        &self,
+        token: &str,
+        location: L,
+        options: F,
+    ) -> Result<Forecast>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        let req = self
+            .client
+            .get_forecast_with_options(token, location, options);
+
+        self.runtime.block_on(req)
+    }
+
+    /// Sets the time to request a forecast for by using DarkSky's Time Machine
+    /// API.
+    ///
+    /// Refer to [`DarkskyReqwestRequester::get_forecast_time_machine`] for
+    /// the accepted time formats. The time is not validated.
+    ///
+    /// [`DarkskyReqwestRequester::get_forecast_time_machine`]:
+    ///   ../bridge/reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_time_machine
+    pub fn get_forecast_time_machine<D, F, L>(
+        &self,
+        token: &str,
+        location: L,
+        time: D,
+        options: F,
+    ) -> Result<Forecast>
+    where
+        D: Display,
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        let req = self
+            .client
+            .get_forecast_time_machine(token, location, time, options);
+
+        self.runtime.block_on(req)
+    }
+}
+
+impl Debug for Client {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Client").finish()
+    }
+}
+
+/// Retrieve a [`Forecast`] for the given location with a new [`Client`].
+///
+/// [`Client`]: struct.Client.html
+/// [`Forecast`]: ../models/struct.Forecast.html
+pub fn get_forecast<L: IntoLocation>(token: &str, location: L) -> Result<Forecast> {
+    Client::new()?.get_forecast(token, location)
+}
+
+/// Retrieve a [`Forecast`] for the given location with a new [`Client`],
+/// setting options where needed.
+///
+/// [`Client`]: struct.Client.html
+/// [`Forecast`]: ../models/struct.Forecast.html
+pub fn get_forecast_with_options<F, L>(token: &str, location: L, options: F) -> Result<Forecast>
+where
+    F: FnOnce(Options) -> Options,
+    L: IntoLocation,
+{
+    Client::new()?.get_forecast_with_options(token, location, options)
+}
+
+/// Retrieve a [`Forecast`] for the given time by using DarkSky's Time Machine
+/// API with a new [`Client`].
+///
+/// [`Client`]: struct.Client.html
+/// [`Forecast`]: ../models/struct.Forecast.html
+pub fn get_forecast_time_machine<D, F, L>(
+    token: &str,
+    location: L,
+    time: D,
+    options: F,
+) -> Result<Forecast>
+where
+    D: Display,
+    F: FnOnce(Options) -> Options,
+    L: IntoLocation,
+{
+    Client::new()?.get_forecast_time_machine(token, location, time, options)
+}
@@ -0,0 +1,179 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Bridge to provide DarkSky client implementation for the `attohttpc` crate.
+//!
+//! This is a blocking client that compiles quickly, suited to small command
+//! line tools.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`DarkskyAttohttpcRequester`].
+//!
+//! [`DarkskyAttohttpcRequester`]: trait.DarkskyAttohttpcRequester.html
+
+use super::send::{self, Backend, DarkskyRequester, HttpSend};
+use attohttpc::Session;
+use models::Forecast;
+use std::fmt::Display;
+use std::future::{self, Ready};
+use {IntoLocation, Options, Result};
+
+/// The trait for `attohttpc` implementations to different DarkSky routes.
+///
+/// This is implemented for every [`Backend`] sending requests through an
+/// `attohttpc` `Session`, including those wrapped in other backends. Each
+/// method blocks on the equivalent [`DarkskyRequester`] method, so requests
+/// run the layers of the backends wrapping the session.
+///
+/// [`Backend`]: ../send/trait.Backend.html
+/// [`DarkskyRequester`]: ../send/trait.DarkskyRequester.html
+pub trait DarkskyAttohttpcRequester: HttpSend {
+    /// Retrieve a [`Forecast`] for the given location.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve a forecast for a location, taking a token from the environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate attohttpc;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use attohttpc::Session;
+    /// use darksky::{Coordinate, DarkskyAttohttpcRequester};
+    /// use std::env;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let session = Session::new();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// let req = session.get_forecast(&token, location)?;
+    ///
+    /// println!("Forecast: {:?}", req);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    fn get_forecast<L: IntoLocation>(&self, token: &str, location: L) -> Result<Forecast> {
+        send::block_on(DarkskyRequester::get_forecast(self, token, location))
+    }
+
+    /// Retrieve a [`Forecast`] for the given location, setting options where
+    /// needed. For a full list of options, refer to the documentation for the
+    /// [`Options`] builder.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve an extended forecast, excluding the
+    /// [minutely block][`Block::Minutely`], taking a token from the
+    /// environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate attohttpc;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use attohttpc::Session;
+    /// use darksky::{Block, Coordinate, DarkskyAttohttpcRequester};
+    /// use std::env;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let session = Session::new();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// let req = session.get_forecast_with_options(&token, location, |o| o
+    ///     .exclude(vec![Block::Minutely]))?;
+    ///
+    /// println!("Forecast: {:?}", req);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Block::Minutely`]: ../../enum.Block.html#variant.Minutely
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    /// [`Options`]: ../../struct.Options.html
+    fn get_forecast_with_options<F, L>(
+        &self,
+        token: &str,
+        location: L,
+        options: F,
+    ) -> Result<Forecast>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        send::block_on(DarkskyRequester::get_forecast_with_options(
+            self, token, location, options,
+        ))
+    }
+
+    /// Sets the time to request a forecast for by using DarkSky's Time Machine
+    /// API.
+    ///
+    /// Refer to [`DarkskyReqwestRequester::get_forecast_time_machine`] for
+    /// the accepted time formats. The time is not validated.
+    ///
+    /// [`DarkskyReqwestRequester::get_forecast_time_machine`]:
+    ///   ../reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_time_machine
+    fn get_forecast_time_machine<D, F, L>(
+        &self,
+        token: &str,
+        location: L,
+        time: D,
+        options: F,
+    ) -> Result<Forecast>
+    where
+        D: Display,
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        let req = DarkskyRequester::get_forecast_time_machine(self, token, location, time, options);
+
+        send::block_on(req)
+    }
+}
+
+impl<S: Backend<Client = Session> + ?Sized> DarkskyAttohttpcRequester for S {}
+
+impl Backend for Session {
+    type Client = Session;
+}
+
+impl HttpSend for Session {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        let body = self.get(uri).send().and_then(|res| res.bytes());
+
+        future::ready(body.map_err(From::from))
+    }
+}
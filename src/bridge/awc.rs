@@ -0,0 +1,234 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Bridge to provide DarkSky client implementation for the `awc` crate, the
+//! actix-web client.
+//!
+//! Requests return futures which, like those of `awc` itself, are not
+//! `Send`, so they can be awaited directly within actix handlers and
+//! `actix_rt` systems.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`DarkskyAwcRequester`].
+//!
+//! [`DarkskyAwcRequester`]: trait.DarkskyAwcRequester.html
+
+use super::send::{Backend, DarkskyRequester, ForecastFuture, HttpSend};
+use awc::error::{PayloadError, SendRequestError};
+use awc::{Client, ClientResponse, ResponseBody};
+use bytes::Bytes;
+use futures_core::Stream;
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::result::Result as StdResult;
+use std::task::{Context, Poll};
+use {IntoLocation, Options, Result};
+
+/// The maximum size of a response body to read, in bytes.
+///
+/// Extended hourly forecasts are well above `awc`'s default limit of 2MiB
+/// in some regions, so a larger one is used.
+const MAX_BODY: usize = 8 * 1024 * 1024;
+
+/// A boxed future resolving to the body of a response, returned by the
+/// [`HttpSend`] implementation for `awc`'s `Client`.
+///
+/// [`HttpSend`]: ../send/trait.HttpSend.html
+pub type BodyFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>>>>>;
+
+/// The trait for `awc` implementations to different DarkSky routes.
+///
+/// This is implemented for every [`Backend`] sending requests through an
+/// `awc` `Client`, including those wrapped in other backends. Each method
+/// returns the future of the equivalent [`DarkskyRequester`] method, so
+/// requests run the layers of the backends wrapping the client.
+///
+/// [`Backend`]: ../send/trait.Backend.html
+/// [`DarkskyRequester`]: ../send/trait.DarkskyRequester.html
+pub trait DarkskyAwcRequester: HttpSend {
+    /// Retrieve a [`Forecast`] for the given location.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve a forecast for a location, taking a token from the environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate awc;
+    /// extern crate darksky;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use awc::Client;
+    /// use darksky::{Coordinate, DarkskyAwcRequester};
+    /// use std::env;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let client = Client::default();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// // Await this within an actix handler or `actix_rt` system.
+    /// let forecast = client.get_forecast(&token, location);
+    /// #     drop(forecast);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    fn get_forecast<L: IntoLocation>(
+        &self,
+        token: &str,
+        location: L,
+    ) -> ForecastFuture<Self::Future> {
+        DarkskyRequester::get_forecast(self, token, location)
+    }
+
+    /// Retrieve a [`Forecast`] for the given location, setting options where
+    /// needed. For a full list of options, refer to the documentation for the
+    /// [`Options`] builder.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve an extended forecast, excluding the
+    /// [minutely block][`Block::Minutely`], taking a token from the
+    /// environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate awc;
+    /// extern crate darksky;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use awc::Client;
+    /// use darksky::{Block, Coordinate, DarkskyAwcRequester};
+    /// use std::env;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let client = Client::default();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// let forecast = client.get_forecast_with_options(&token, location, |o| o
+    ///     .exclude(vec![Block::Minutely]));
+    /// #     drop(forecast);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Block::Minutely`]: ../../enum.Block.html#variant.Minutely
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    /// [`Options`]: ../../struct.Options.html
+    fn get_forecast_with_options<F, L>(
+        &self,
+        token: &str,
+        location: L,
+        options: F,
+    ) -> ForecastFuture<Self::Future>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        DarkskyRequester::get_forecast_with_options(self, token, location, options)
+    }
+
+    /// Sets the time to request a forecast for by using DarkSky's Time Machine
+    /// API.
+    ///
+    /// Refer to [`DarkskyReqwestRequester::get_forecast_time_machine`] for
+    /// the accepted time formats. The time is not validated.
+    ///
+    /// [`DarkskyReqwestRequester::get_forecast_time_machine`]:
+    ///   ../reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_time_machine
+    fn get_forecast_time_machine<D, F, L>(
+        &self,
+        token: &str,
+        location: L,
+        time: D,
+        options: F,
+    ) -> ForecastFuture<Self::Future>
+    where
+        D: Display,
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        DarkskyRequester::get_forecast_time_machine(self, token, location, time, options)
+    }
+}
+
+impl<S: Backend<Client = Client> + ?Sized> DarkskyAwcRequester for S {}
+
+impl Backend for Client {
+    type Client = Client;
+}
+
+impl HttpSend for Client {
+    type Future = BodyFuture;
+
+    fn send(&self, uri: String) -> BodyFuture {
+        Box::pin(Fetch {
+            send: self.get(uri).send(),
+            body: None,
+        })
+    }
+}
+
+/// A future sending a request, then reading its response body.
+///
+/// This is generic over the payload stream, as its type depends on which of
+/// `awc`'s decompression features are enabled.
+struct Fetch<R, S> {
+    send: R,
+    body: Option<ResponseBody<S>>,
+}
+
+impl<R, S> Future for Fetch<R, S>
+where
+    R: Future<Output = StdResult<ClientResponse<S>, SendRequestError>> + Unpin,
+    S: Stream<Item = StdResult<Bytes, PayloadError>> + Unpin,
+{
+    type Output = Result<Vec<u8>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Vec<u8>>> {
+        let this = &mut *self;
+
+        if this.body.is_none() {
+            match Pin::new(&mut this.send).poll(cx) {
+                Poll::Ready(Ok(mut res)) => this.body = Some(res.body().limit(MAX_BODY)),
+                Poll::Ready(Err(why)) => return Poll::Ready(Err(why.into())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        match this.body {
+            Some(ref mut body) => Pin::new(body)
+                .poll(cx)
+                .map(|body| body.map(|body| body.to_vec()).map_err(From::from)),
+            None => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,202 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Cooperative cancellation of in-flight requests.
+//!
+//! A [`CancelHandle`] wraps the futures returned by the bridges in
+//! [`Cancellable`]s. Once the handle is cancelled, each of them resolves to
+//! [`Error::Cancelled`] the next time it is polled, dropping the underlying
+//! request. This suits interactive applications, which may abort a fetch
+//! when the user changes location.
+//!
+//! # Examples
+//!
+//! Abort a request which is no longer wanted:
+//!
+//! ```rust
+//! extern crate darksky;
+//!
+//! use darksky::bridge::cancel::CancelHandle;
+//! use darksky::models::Forecast;
+//! use darksky::{Error, Result};
+//! use std::future::{self, Future};
+//! use std::pin::Pin;
+//! use std::task::{Context, Poll, Waker};
+//!
+//! # fn main() {
+//! let handle = CancelHandle::new();
+//! // A request which never completes, standing in for a slow one.
+//! let mut request = handle.cancellable(future::pending::<Result<Forecast>>());
+//!
+//! let mut cx = Context::from_waker(Waker::noop());
+//! assert!(Pin::new(&mut request).poll(&mut cx).is_pending());
+//!
+//! handle.cancel();
+//!
+//! match Pin::new(&mut request).poll(&mut cx) {
+//!     Poll::Ready(Err(Error::Cancelled)) => {}
+//!     _ => panic!("the request was not cancelled"),
+//! }
+//! # }
+//! ```
+//!
+//! [`CancelHandle`]: struct.CancelHandle.html
+//! [`Cancellable`]: struct.Cancellable.html
+//! [`Error::Cancelled`]: ../../enum.Error.html#variant.Cancelled
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use {Error, Result};
+
+/// A handle for cancelling the requests wrapped with it.
+///
+/// Cloning a handle is cheap, and clones cancel the same requests. A handle
+/// can't be reset once cancelled, so a new one should be created for the
+/// next batch of requests.
+#[derive(Clone, Default)]
+pub struct CancelHandle {
+    inner: Arc<Inner>,
+}
+
+/// The state shared between a handle and its requests.
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    next_id: AtomicUsize,
+    /// The wakers of pending requests, keyed by an ID unique to each.
+    wakers: Mutex<HashMap<usize, Waker>>,
+}
+
+impl CancelHandle {
+    /// Creates a new handle, which has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps a request so that it resolves to [`Error::Cancelled`] once the
+    /// handle is cancelled.
+    ///
+    /// If the handle has already been cancelled, then the request is never
+    /// polled.
+    ///
+    /// [`Error::Cancelled`]: ../../enum.Error.html#variant.Cancelled
+    pub fn cancellable<F>(&self, future: F) -> Cancellable<F> {
+        Cancellable {
+            future: Some(future),
+            id: self.inner.next_id.fetch_add(1, Ordering::Relaxed),
+            inner: Arc::clone(&self.inner),
+        }
+    }
+
+    /// Cancels every request wrapped with the handle, waking them so that
+    /// they resolve to [`Error::Cancelled`].
+    ///
+    /// [`Error::Cancelled`]: ../../enum.Error.html#variant.Cancelled
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+
+        let wakers = self.inner.lock().drain().collect::<Vec<_>>();
+
+        for (_, waker) in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Returns whether the handle has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+impl Debug for CancelHandle {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("CancelHandle")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+impl Inner {
+    fn lock(&self) -> MutexGuard<'_, HashMap<usize, Waker>> {
+        self.wakers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A request which resolves to [`Error::Cancelled`] once its
+/// [`CancelHandle`] is cancelled, created by [`CancelHandle::cancellable`].
+///
+/// [`CancelHandle`]: struct.CancelHandle.html
+/// [`CancelHandle::cancellable`]: struct.CancelHandle.html#method.cancellable
+/// [`Error::Cancelled`]: ../../enum.Error.html#variant.Cancelled
+#[must_use = "futures do nothing unless polled"]
+pub struct Cancellable<F> {
+    future: Option<F>,
+    id: usize,
+    inner: Arc<Inner>,
+}
+
+impl<F> Debug for Cancellable<F> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Cancellable")
+            .field("cancelled", &self.inner.cancelled.load(Ordering::SeqCst))
+            .finish()
+    }
+}
+
+impl<F, T> Future for Cancellable<F>
+where
+    F: Future<Output = Result<T>> + Unpin,
+{
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<T>> {
+        let this = &mut *self;
+
+        // The waker is registered before checking for cancellation, so that a
+        // cancellation in between is not missed.
+        this.inner.lock().insert(this.id, cx.waker().clone());
+
+        if this.inner.cancelled.load(Ordering::SeqCst) {
+            // Drop the request, closing its connection.
+            this.future = None;
+
+            return Poll::Ready(Err(Error::Cancelled));
+        }
+
+        let result = match this.future {
+            Some(ref mut future) => Pin::new(future).poll(cx),
+            None => return Poll::Ready(Err(Error::Cancelled)),
+        };
+
+        if result.is_ready() {
+            this.inner.lock().remove(&this.id);
+        }
+
+        result
+    }
+}
+
+impl<F> Drop for Cancellable<F> {
+    fn drop(&mut self) {
+        self.inner.lock().remove(&self.id);
+    }
+}
@@ -0,0 +1,211 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! A circuit breaker refusing requests during upstream outages.
+//!
+//! A [`CircuitBreaker`] is a [`Middleware`] counting the consecutive
+//! requests which fail with a [transient error][`Error::is_transient`], such
+//! as a timeout or a server error from the API. Once a threshold is reached,
+//! the circuit opens, and requests fail at once with [`Error::CircuitOpen`]
+//! without being sent, rather than tying up connections to an unreachable
+//! endpoint.
+//!
+//! After a cool-down, a single trial request is let through. If it
+//! succeeds, the circuit closes again. If it fails, the circuit stays open
+//! for another cool-down.
+//!
+//! # Examples
+//!
+//! Stop sending requests for a minute after 3 consecutive failures, keeping
+//! a handle to the breaker to check on it:
+//!
+//! ```rust
+//! extern crate darksky;
+//!
+//! use darksky::bridge::circuit::CircuitBreaker;
+//! use darksky::bridge::middleware::Layered;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! # let backend = ();
+//!
+//! let breaker = Arc::new(
+//!     CircuitBreaker::new()
+//!         .threshold(3)
+//!         .cool_down(Duration::from_secs(60)),
+//! );
+//! let client = Layered::new(backend).layer(Arc::clone(&breaker));
+//!
+//! assert!(!breaker.is_open());
+//! # drop(client);
+//! ```
+//!
+//! [`CircuitBreaker`]: struct.CircuitBreaker.html
+//! [`Error::CircuitOpen`]: ../../enum.Error.html#variant.CircuitOpen
+//! [`Error::is_transient`]: ../../enum.Error.html#method.is_transient
+//! [`Middleware`]: ../middleware/trait.Middleware.html
+
+use bridge::middleware::{Middleware, RequestContext};
+use internal;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use {Error, Result};
+
+/// A [`Middleware`] refusing requests for a cool-down once a number of
+/// consecutive requests have failed.
+///
+/// Place it after any layer answering requests itself, such as a
+/// [`Cached`] layer, so that cached responses are still served while the
+/// circuit is open.
+///
+/// [`Cached`]: ../../cache/struct.Cached.html
+/// [`Middleware`]: ../middleware/trait.Middleware.html
+pub struct CircuitBreaker {
+    circuit: Mutex<Circuit>,
+    cool_down: Duration,
+    threshold: u32,
+}
+
+/// The failures counted by a [`CircuitBreaker`].
+///
+/// [`CircuitBreaker`]: struct.CircuitBreaker.html
+#[derive(Default)]
+struct Circuit {
+    failures: u32,
+    /// When the circuit last opened, or when its last trial request was let
+    /// through.
+    opened: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Creates a closed breaker, opening after 5 consecutive failures for a
+    /// cool-down of 30 seconds.
+    pub fn new() -> Self {
+        CircuitBreaker {
+            circuit: Mutex::new(Circuit::default()),
+            cool_down: Duration::from_secs(30),
+            threshold: 5,
+        }
+    }
+
+    /// Sets how long requests are refused for once the circuit opens,
+    /// before a trial request is let through.
+    pub fn cool_down(mut self, cool_down: Duration) -> Self {
+        self.cool_down = cool_down;
+
+        self
+    }
+
+    /// Sets the number of consecutive failures opening the circuit.
+    ///
+    /// A value of 0 is treated as 1.
+    pub fn threshold(mut self, threshold: u32) -> Self {
+        self.threshold = threshold;
+
+        self
+    }
+
+    /// Returns how long requests are refused for once the circuit opens.
+    pub fn get_cool_down(&self) -> Duration {
+        self.cool_down
+    }
+
+    /// Returns the number of consecutive failures opening the circuit.
+    pub fn get_threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// Returns the number of consecutive failures counted so far.
+    pub fn failures(&self) -> u32 {
+        self.lock().failures
+    }
+
+    /// Returns whether the circuit is open, so that requests are refused,
+    /// including while a trial request is in flight.
+    pub fn is_open(&self) -> bool {
+        self.lock().opened.is_some()
+    }
+
+    /// Closes the circuit, clearing the failures counted.
+    pub fn reset(&self) {
+        *self.lock() = Circuit::default();
+    }
+
+    /// Locks the circuit, recovering it if another thread panicked while
+    /// holding the lock, as it is never left partially updated.
+    fn lock(&self) -> MutexGuard<'_, Circuit> {
+        self.circuit
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let circuit = self.lock();
+
+        f.debug_struct("CircuitBreaker")
+            .field("cool_down", &self.cool_down)
+            .field("failures", &circuit.failures)
+            .field("open", &circuit.opened.is_some())
+            .field("threshold", &self.threshold)
+            .finish()
+    }
+}
+
+impl Middleware for CircuitBreaker {
+    fn before(&self, _request: &mut RequestContext) -> Option<Result<Vec<u8>>> {
+        let mut circuit = self.lock();
+        let opened = circuit.opened?;
+        let elapsed = opened.elapsed();
+
+        if elapsed < self.cool_down {
+            return Some(Err(Error::CircuitOpen(self.cool_down - elapsed)));
+        }
+
+        // Let this request through as a trial, refusing the others for
+        // another cool-down. If its response never arrives, such as when it
+        // is dropped, another trial is let through after the cool-down.
+        circuit.opened = Some(Instant::now());
+
+        None
+    }
+
+    fn after(&self, request: &RequestContext, response: &mut Result<Vec<u8>>) {
+        // The request was answered by this layer or one after it, so the
+        // response says nothing of the upstream.
+        if request.is_answered() {
+            return;
+        }
+
+        let mut circuit = self.lock();
+
+        if internal::is_transient_failure(response, request.get_meta().status) {
+            circuit.failures = circuit.failures.saturating_add(1);
+
+            if circuit.failures >= self.threshold.max(1) {
+                circuit.opened = Some(Instant::now());
+            }
+        } else {
+            *circuit = Circuit::default();
+        }
+    }
+}
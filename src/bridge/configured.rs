@@ -0,0 +1,216 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Clients using a [`Config`] other than the default, such as one pointing at
+//! a mirror of the API or a mock server in tests.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`ConfiguredClient`].
+//!
+//! [`Config`]: ../../constants/struct.Config.html
+//! [`ConfiguredClient`]: struct.ConfiguredClient.html
+
+use super::pool::BufferPool;
+use super::send::{Backend, HttpSend, MetaSlot};
+use constants::Config;
+use Options;
+
+/// A client which makes requests using a [`Config`].
+///
+/// The bridge traits, such as [`DarkskyReqwestRequester`], are implemented
+/// for configured clients of the same types that they are implemented for.
+/// Being an [`HttpSend`] backend, [`DarkskyRequester`] methods use the
+/// configuration and default options too.
+///
+/// # Examples
+///
+/// Retrieve a forecast from a local mirror of the API:
+///
+#[cfg_attr(feature = "reqwest-blocking", doc = "```rust,no_run")]
+#[cfg_attr(not(feature = "reqwest-blocking"), doc = "```rust,ignore")]
+/// extern crate darksky;
+/// extern crate reqwest;
+///
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<dyn Error>> {
+/// use darksky::bridge::configured::ConfiguredClient;
+/// use darksky::constants::Config;
+/// use darksky::DarkskyReqwestRequester;
+/// use reqwest::blocking::Client;
+///
+/// let config = Config::default().api_url("http://localhost:8080");
+/// let client = ConfiguredClient::new(Client::new(), config);
+///
+/// let forecast = client.get_forecast("token", (37.8267, -122.423))?;
+///
+/// println!("Forecast: {:?}", forecast);
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// Requests are timed out according to [`Config::timeout`]. Wrapping a client
+/// is cheap, so a single request can be given its own timeout by wrapping a
+/// clone of the client with a different configuration.
+///
+/// Default options, such as the units and language of every forecast, can be
+/// set with [`default_options`]. They are used by every request, including
+/// `get_forecast`, and the options given to a request are applied on top of
+/// them:
+///
+#[cfg_attr(feature = "reqwest-blocking", doc = "```rust,no_run")]
+#[cfg_attr(not(feature = "reqwest-blocking"), doc = "```rust,ignore")]
+/// extern crate darksky;
+/// extern crate reqwest;
+///
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<dyn Error>> {
+/// use darksky::bridge::configured::ConfiguredClient;
+/// use darksky::constants::Config;
+/// use darksky::{Block, DarkskyReqwestRequester, Language, Unit};
+/// use reqwest::blocking::Client;
+///
+/// let client = ConfiguredClient::new(Client::new(), Config::default())
+///     .default_options(|o| o.unit(Unit::Si).language(Language::De));
+///
+/// // In SI units and German:
+/// let forecast = client.get_forecast("token", (37.8267, -122.423))?;
+/// // In SI units and German, without minutely data:
+/// let forecast = client.get_forecast_with_options("token", (37.8267, -122.423), |o| o
+///     .exclude(vec![Block::Minutely]))?;
+/// #     drop(forecast);
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// [`Config`]: ../../constants/struct.Config.html
+/// [`Config::timeout`]: ../../constants/struct.Config.html#method.timeout
+/// [`DarkskyReqwestRequester`]: ../reqwest/blocking/trait.DarkskyReqwestRequester.html
+/// [`DarkskyRequester`]: ../send/trait.DarkskyRequester.html
+/// [`HttpSend`]: ../send/trait.HttpSend.html
+/// [`default_options`]: #method.default_options
+#[derive(Clone, Debug)]
+pub struct ConfiguredClient<C> {
+    client: C,
+    config: Config,
+    options: Options,
+}
+
+impl<C> ConfiguredClient<C> {
+    /// Wraps a client with a [`Config`], and no default options.
+    ///
+    /// [`Config`]: ../../constants/struct.Config.html
+    pub fn new(client: C, config: Config) -> Self {
+        ConfiguredClient {
+            client,
+            config,
+            options: Options::default(),
+        }
+    }
+
+    /// Sets the options used by every request. For a full list of options,
+    /// refer to the documentation for the [`Options`] builder.
+    ///
+    /// Forecasts retrieved with `get_forecast` are in `auto` units unless
+    /// the default options set them.
+    ///
+    /// [`Options`]: ../../struct.Options.html
+    pub fn default_options<F: FnOnce(Options) -> Options>(mut self, options: F) -> Self {
+        self.options = options(self.options);
+
+        self
+    }
+
+    /// Returns a reference to the wrapped client.
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Returns a reference to the configuration.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns the options used by every request.
+    pub fn get_default_options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Unwraps the client, dropping the configuration and default options.
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+}
+
+impl<C: HttpSend> HttpSend for ConfiguredClient<C> {
+    type Future = C::Future;
+
+    fn send(&self, uri: String) -> Self::Future {
+        self.client.send_with(uri, &self.config)
+    }
+
+    fn send_with(&self, uri: String, config: &Config) -> Self::Future {
+        self.client.send_with(uri, config)
+    }
+
+    fn send_recording(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: &MetaSlot,
+    ) -> Self::Future {
+        let config = config.unwrap_or(&self.config);
+
+        self.client.send_recording(uri, Some(config), slot)
+    }
+
+    fn send_into(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: Option<&MetaSlot>,
+        buffer: Vec<u8>,
+    ) -> Self::Future {
+        let config = config.unwrap_or(&self.config);
+
+        self.client.send_into(uri, Some(config), slot, buffer)
+    }
+
+    fn uri_config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn buffer_pool(&self) -> Option<&BufferPool> {
+        self.client.buffer_pool()
+    }
+
+    fn base_options(&self) -> Option<&Options> {
+        Some(&self.options)
+    }
+}
+
+impl<C: Backend> Backend for ConfiguredClient<C> {
+    type Client = C::Client;
+}
@@ -0,0 +1,406 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Bridge to provide DarkSky client implementation for the `curl` crate, for
+//! targets that only ship libcurl.
+//!
+//! Single requests are made through [`DarkskyCurlRequester`], implemented
+//! for a [`CurlClient`] wrapping curl's `Easy` handle. Forecasts for several
+//! locations can be retrieved concurrently over a `Multi` handle with
+//! [`get_forecasts`].
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`DarkskyCurlRequester`].
+//!
+//! [`CurlClient`]: struct.CurlClient.html
+//! [`DarkskyCurlRequester`]: trait.DarkskyCurlRequester.html
+//! [`get_forecasts`]: fn.get_forecasts.html
+
+use super::send::{self, Backend, DarkskyRequester, HttpSend, MetaSlot, ResponseMeta};
+use constants::Config;
+use curl::easy::{Easy, Easy2, Handler, List, WriteError};
+use curl::multi::Multi;
+use models::Forecast;
+use std::fmt::Display;
+use std::future::{self, Ready};
+use std::str;
+use std::sync::Mutex;
+use std::time::Duration;
+use {internal, utils, IntoLocation, Options, Result};
+
+/// A client making requests through a curl `Easy` handle.
+///
+/// As curl's handles are reused between transfers, the handle is locked for
+/// the duration of each request, so requests through the same client are
+/// made one at a time.
+#[derive(Debug)]
+pub struct CurlClient(Mutex<Easy>);
+
+impl CurlClient {
+    /// Creates a client with a new `Easy` handle.
+    pub fn new() -> Self {
+        CurlClient::from(Easy::new())
+    }
+
+    /// Unwraps the `Easy` handle.
+    pub fn into_inner(self) -> Easy {
+        self.0
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Performs a GET request, applying the timeout and user agent of a
+    /// configuration if one is given, and recording the metadata of the
+    /// response into the slot if one is given.
+    fn perform(
+        &self,
+        uri: &str,
+        config: Option<&Config>,
+        slot: Option<&MetaSlot>,
+    ) -> Result<Vec<u8>> {
+        let mut easy = self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut body = Vec::new();
+        let mut headers = Vec::new();
+        let mut list = List::new();
+
+        if let Some(user_agent) = config.and_then(Config::get_user_agent) {
+            list.append(&format!("User-Agent: {}", user_agent))?;
+        }
+
+        easy.get(true)?;
+        easy.url(uri)?;
+        easy.http_headers(list)?;
+        // A timeout of zero never times out, clearing that of any previous
+        // request on the handle.
+        easy.timeout(config.and_then(Config::get_timeout).unwrap_or_default())?;
+
+        {
+            let mut transfer = easy.transfer();
+            transfer.header_function(|header| {
+                let header = str::from_utf8(header).unwrap_or_default();
+
+                // Only the headers of the last response are kept when
+                // redirects are followed.
+                if header.starts_with("HTTP/") {
+                    headers.clear();
+                } else if let Some((name, value)) = header.split_once(':') {
+                    headers.push((name.trim().to_owned(), value.trim().to_owned()));
+                }
+
+                true
+            })?;
+            transfer.write_function(|data| {
+                body.extend_from_slice(data);
+
+                Ok(data.len())
+            })?;
+            transfer.perform()?;
+        }
+
+        if let Some(slot) = slot {
+            let status = easy.response_code()? as u16;
+
+            slot.record(ResponseMeta::from_headers(status, |name| {
+                headers
+                    .iter()
+                    .find(|&(header, _)| header.eq_ignore_ascii_case(name))
+                    .map(|(_, value)| value.as_str())
+            }));
+        }
+
+        Ok(body)
+    }
+}
+
+impl Default for CurlClient {
+    fn default() -> Self {
+        CurlClient::new()
+    }
+}
+
+impl From<Easy> for CurlClient {
+    fn from(easy: Easy) -> Self {
+        CurlClient(Mutex::new(easy))
+    }
+}
+
+/// The trait for `curl` implementations to different DarkSky routes.
+///
+/// This is implemented for every [`Backend`] sending requests through a
+/// [`CurlClient`], including those wrapped in other backends. Each method
+/// blocks on the equivalent [`DarkskyRequester`] method, so requests run the
+/// layers of the backends wrapping the client.
+///
+/// [`Backend`]: ../send/trait.Backend.html
+/// [`CurlClient`]: struct.CurlClient.html
+/// [`DarkskyRequester`]: ../send/trait.DarkskyRequester.html
+pub trait DarkskyCurlRequester: HttpSend {
+    /// Retrieve a [`Forecast`] for the given location.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve a forecast for a location, taking a token from the environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate curl;
+    /// extern crate darksky;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::bridge::curl::CurlClient;
+    /// use darksky::{Coordinate, DarkskyCurlRequester};
+    /// use std::env;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let client = CurlClient::new();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// let req = client.get_forecast(&token, location)?;
+    ///
+    /// println!("Forecast: {:?}", req);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    fn get_forecast<L: IntoLocation>(&self, token: &str, location: L) -> Result<Forecast> {
+        send::block_on(DarkskyRequester::get_forecast(self, token, location))
+    }
+
+    /// Retrieve a [`Forecast`] for the given location, setting options where
+    /// needed. For a full list of options, refer to the documentation for the
+    /// [`Options`] builder.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve an extended forecast, excluding the
+    /// [minutely block][`Block::Minutely`], taking a token from the
+    /// environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate curl;
+    /// extern crate darksky;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::bridge::curl::CurlClient;
+    /// use darksky::{Block, Coordinate, DarkskyCurlRequester};
+    /// use std::env;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let client = CurlClient::new();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// let req = client.get_forecast_with_options(&token, location, |o| o
+    ///     .exclude(vec![Block::Minutely]))?;
+    ///
+    /// println!("Forecast: {:?}", req);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Block::Minutely`]: ../../enum.Block.html#variant.Minutely
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    /// [`Options`]: ../../struct.Options.html
+    fn get_forecast_with_options<F, L>(
+        &self,
+        token: &str,
+        location: L,
+        options: F,
+    ) -> Result<Forecast>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        send::block_on(DarkskyRequester::get_forecast_with_options(
+            self, token, location, options,
+        ))
+    }
+
+    /// Sets the time to request a forecast for by using DarkSky's Time Machine
+    /// API.
+    ///
+    /// Refer to [`DarkskyReqwestRequester::get_forecast_time_machine`] for
+    /// the accepted time formats. The time is not validated.
+    ///
+    /// [`DarkskyReqwestRequester::get_forecast_time_machine`]:
+    ///   ../reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_time_machine
+    fn get_forecast_time_machine<D, F, L>(
+        &self,
+        token: &str,
+        location: L,
+        time: D,
+        options: F,
+    ) -> Result<Forecast>
+    where
+        D: Display,
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        let req = DarkskyRequester::get_forecast_time_machine(self, token, location, time, options);
+
+        send::block_on(req)
+    }
+}
+
+impl<S: Backend<Client = CurlClient> + ?Sized> DarkskyCurlRequester for S {}
+
+impl Backend for CurlClient {
+    type Client = CurlClient;
+}
+
+impl HttpSend for CurlClient {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        future::ready(self.perform(&uri, None, None))
+    }
+
+    fn send_with(&self, uri: String, config: &Config) -> Self::Future {
+        future::ready(self.perform(&uri, Some(config), None))
+    }
+
+    fn send_recording(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: &MetaSlot,
+    ) -> Self::Future {
+        future::ready(self.perform(&uri, config, Some(slot)))
+    }
+}
+
+/// Retrieves forecasts for each of the given locations concurrently over a
+/// `Multi` handle, setting options where needed.
+///
+/// The returned results are in the same order as the locations. The outer
+/// result is an error only if driving the `Multi` handle itself fails.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// extern crate curl;
+/// extern crate darksky;
+///
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<dyn Error>> {
+/// use curl::multi::Multi;
+/// use darksky::bridge::curl::get_forecasts;
+/// use darksky::Unit;
+/// use std::env;
+///
+/// let token = env::var("FORECAST_TOKEN")?;
+/// let locations = [(37.8267, -122.423), (49.8951, -97.1384)];
+///
+/// let forecasts = get_forecasts(&Multi::new(), &token, &locations, |o| o.unit(Unit::Si))?;
+///
+/// for forecast in forecasts {
+///     println!("Forecast: {:?}", forecast?);
+/// }
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+pub fn get_forecasts<F, L>(
+    multi: &Multi,
+    token: &str,
+    locations: &[L],
+    options: F,
+) -> Result<Vec<Result<Forecast>>>
+where
+    F: Fn(Options) -> Options,
+    L: IntoLocation + Copy,
+{
+    let mut handles = Vec::with_capacity(locations.len());
+    let mut results = Vec::with_capacity(locations.len());
+
+    for &location in locations {
+        let options = options(Options::default());
+        let handle = utils::located_uri(token, location, options).and_then(|uri| {
+            let mut easy = Easy2::new(Collector(Vec::new()));
+            easy.url(&uri)?;
+
+            multi.add2(easy).map_err(From::from)
+        });
+
+        match handle {
+            Ok(handle) => {
+                handles.push(Some(handle));
+                results.push(None);
+            }
+            Err(why) => {
+                handles.push(None);
+                results.push(Some(Err(why)));
+            }
+        }
+    }
+
+    while multi.perform()? > 0 {
+        multi.wait(&mut [], Duration::from_secs(1))?;
+    }
+
+    multi.messages(|message| {
+        for (handle, result) in handles.iter().zip(results.iter_mut()) {
+            if let Some(Err(why)) = handle.as_ref().and_then(|h| message.result_for2(h)) {
+                *result = Some(Err(why.into()));
+            }
+        }
+    });
+
+    handles
+        .into_iter()
+        .zip(results)
+        .map(|(handle, result)| {
+            let easy = match handle {
+                Some(handle) => multi.remove2(handle)?,
+                None => return Ok(result.expect("failed requests have a result")),
+            };
+
+            Ok(result.unwrap_or_else(|| internal::from_slice(&easy.get_ref().0)))
+        })
+        .collect()
+}
+
+/// Collects a response body for an `Easy2` handle.
+struct Collector(Vec<u8>);
+
+impl Handler for Collector {
+    fn write(&mut self, data: &[u8]) -> ::std::result::Result<usize, WriteError> {
+        self.0.extend_from_slice(data);
+
+        Ok(data.len())
+    }
+}
@@ -14,52 +14,76 @@
 // CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
 // CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 //! Bridged support for the `hyper` library.
+//!
+//! Requests return standard library futures, which can be `.await`ed within
+//! a Tokio runtime, as `hyper`'s `Client` requires.
 
-use futures::{future, Future, Stream};
-use hyper::{
-    body::Payload,
-    client::{connect::Connect, Client},
+use super::send::{
+    self, Backend, DarkskyRequester, ForecastFuture, HttpSend, MetaSlot, ResponseMeta,
 };
-use hyper::{Error as HyperError, Uri};
+use constants::Config;
+use hyper::body::HttpBody;
+use hyper::client::{connect::Connect, Client, ResponseFuture};
+use hyper::header::{HeaderValue, CONTENT_LENGTH, USER_AGENT};
+use hyper::{Body, Request, Response, Uri};
 use models::Forecast;
-use std::collections::HashMap;
-use std::fmt::Display;
+use std::cmp;
+use std::error::Error as StdError;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
 use std::str::FromStr;
-use {internal, utils, Error, Options};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::time::{self, Instant as TokioInstant, Sleep};
+use {Error, IntoLocation, Language, Options, Result};
+
+/// The maximum number of bytes to preallocate for a response body, regardless
+/// of its advertised `Content-Length`.
+const MAX_PREALLOCATION: usize = 4 * 1024 * 1024;
 
 /// The trait for `hyper` implementations to different DarkSky routes.
-pub trait DarkskyHyperRequester {
-    /// Retrieve a [forecast][`Forecast`] for the given latitude and longitude.
+///
+/// This is implemented for every [`Backend`] sending requests through a
+/// `hyper` `Client`, including those wrapped in other backends, such as a
+/// [`PooledClient`] or [`ConfiguredClient`]. Each method returns the future
+/// of the equivalent [`DarkskyRequester`] method, so requests run the layers
+/// of the backends wrapping the client.
+///
+/// [`Backend`]: ../send/trait.Backend.html
+/// [`ConfiguredClient`]: ../configured/struct.ConfiguredClient.html
+/// [`DarkskyRequester`]: ../send/trait.DarkskyRequester.html
+/// [`PooledClient`]: ../pool/struct.PooledClient.html
+pub trait DarkskyHyperRequester: HttpSend {
+    /// Retrieve a [forecast][`Forecast`] for the given location.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// extern crate darksky;
-    /// extern crate futures;
     /// extern crate hyper;
     /// extern crate hyper_tls;
-    /// extern crate tokio_core;
+    /// extern crate tokio;
     ///
     /// # use std::error::Error;
     /// #
-    /// use darksky::{DarkskyHyperRequester, Block};
-    /// use futures::Future;
+    /// use darksky::{Block, Coordinate, DarkskyHyperRequester};
     /// use hyper::{Body, client::{Client, HttpConnector}};
     /// use hyper_tls::HttpsConnector;
     /// use std::env;
-    /// use tokio_core::reactor::Core;
+    /// use tokio::runtime::Runtime;
     ///
-    /// # fn try_main() -> Result<(), Box<Error>> {
-    /// let core = Core::new()?;
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// let runtime = Runtime::new()?;
     /// let client = Client::builder()
-    ///     .build::<_, Body>(HttpsConnector::new(4).unwrap());
+    ///     .build::<_, Body>(HttpsConnector::new());
     ///
     /// let token = env::var("FORECAST_TOKEN")?;
-    /// let lat = 37.8267;
-    /// let long = -122.423;
+    /// let location = Coordinate::new(37.8267, -122.423);
     ///
     /// // We're waiting in this example, but you shouldn't in your code.
-    /// match client.get_forecast(&token, lat, long).wait() {
+    /// match runtime.block_on(client.get_forecast(&token, location)) {
     ///     Ok(forecast) => println!("{:?}", forecast),
     ///     Err(why) => println!("Error getting forecast: {:?}", why),
     /// }
@@ -72,15 +96,16 @@ pub trait DarkskyHyperRequester {
     /// ```
     ///
     /// [`Forecast`]: ../../models/struct.Forecast.html
-    fn get_forecast<'a, 'b, T: AsRef<str>>(
-        &'a self,
+    fn get_forecast<L: IntoLocation, T: AsRef<str>>(
+        &self,
         token: T,
-        latitude: f64,
-        longitude: f64,
-    ) -> Box<dyn Future<Item = Forecast, Error = Error> + 'b>;
+        location: L,
+    ) -> ForecastFuture<Self::Future> {
+        DarkskyRequester::get_forecast(self, token.as_ref(), location)
+    }
 
-    /// Retrieve a [forecast][`Forecast`] for the given latitude and longitude,
-    /// setting options where needed. For a full list of options, refer to the
+    /// Retrieve a [forecast][`Forecast`] for the given location, setting
+    /// options where needed. For a full list of options, refer to the
     /// documentation for the [`Options`] builder.
     ///
     /// # Examples
@@ -90,36 +115,33 @@ pub trait DarkskyHyperRequester {
     ///
     /// ```rust,no_run
     /// extern crate darksky;
-    /// extern crate futures;
     /// extern crate hyper;
     /// extern crate hyper_tls;
-    /// extern crate tokio_core;
+    /// extern crate tokio;
     ///
     /// # use std::error::Error;
     /// #
-    /// use darksky::{DarkskyHyperRequester, Block};
-    /// use futures::Future;
+    /// use darksky::{Block, Coordinate, DarkskyHyperRequester};
     /// use hyper::{Body, client::{Client, HttpConnector}};
     /// use hyper_tls::HttpsConnector;
     /// use std::env;
-    /// use tokio_core::reactor::Core;
+    /// use tokio::runtime::Runtime;
     ///
-    /// # fn try_main() -> Result<(), Box<Error>> {
-    /// let core = Core::new()?;
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// let runtime = Runtime::new()?;
     ///
     /// let client = Client::builder()
-    ///     .build::<_, Body>(HttpsConnector::new(4).unwrap());
+    ///     .build::<_, Body>(HttpsConnector::new());
     ///
     /// let token = env::var("FORECAST_TOKEN").expect("forecast token");
-    /// let lat = 37.8267;
-    /// let long = -122.423;
+    /// let location = Coordinate::new(37.8267, -122.423);
     ///
-    /// let req = client.get_forecast_with_options(&token, lat, long, |o| o
+    /// let req = client.get_forecast_with_options(&token, location, |o| o
     ///     .exclude(vec![Block::Minutely])
     ///     .extend_hourly());
     ///
     /// // We're waiting in this example, but you shouldn't in your code.
-    /// match req.wait() {
+    /// match runtime.block_on(req) {
     ///     Ok(forecast) => println!("{:?}", forecast),
     ///     Err(why) => println!("Error getting forecast: {:?}", why),
     /// }
@@ -134,16 +156,19 @@ pub trait DarkskyHyperRequester {
     /// [`Block::Minutely`]: ../../enum.Block.html#variant.Minutely
     /// [`Forecast`]: ../../models/struct.Forecast.html
     /// [`Options`]: ../../struct.Options.html
-    fn get_forecast_with_options<'a, 'b, F, T>(
-        &'a self,
+    fn get_forecast_with_options<F, L, T>(
+        &self,
         token: T,
-        latitude: f64,
-        longitude: f64,
+        location: L,
         options: F,
-    ) -> Box<dyn Future<Item = Forecast, Error = Error> + 'b>
+    ) -> ForecastFuture<Self::Future>
     where
         F: FnOnce(Options) -> Options,
-        T: AsRef<str>;
+        L: IntoLocation,
+        T: AsRef<str>,
+    {
+        DarkskyRequester::get_forecast_with_options(self, token.as_ref(), location, options)
+    }
 
     /// Sets the time to request a forecast for by using DarkSky's Time Machine
     /// API.
@@ -153,7 +178,7 @@ pub trait DarkskyHyperRequester {
     /// either be:
     ///
     /// - omitted (referring to the local time for the location being
-    /// requested);
+    ///   requested);
     /// - `Z` referring to GMT time;
     /// - or `-[HH][MM]` for an offset from GMT in hours and minutes.
     ///
@@ -169,132 +194,497 @@ pub trait DarkskyHyperRequester {
     /// that of a time library (e.g. Chrono).
     ///
     /// [docs]: https://darksky.net/dev/docs#time-machine-request-parameters
-    fn get_forecast_time_machine<D, F, T>(
+    fn get_forecast_time_machine<D, F, L, T>(
         &self,
         token: T,
-        latitude: f64,
-        longitude: f64,
+        location: L,
         time: D,
         options: F,
-    ) -> Box<dyn Future<Item = Forecast, Error = Error>>
+    ) -> ForecastFuture<Self::Future>
     where
         D: Display,
         F: FnOnce(Options) -> Options,
-        T: AsRef<str>;
-}
-
-impl<B, C> DarkskyHyperRequester for Client<C, B>
-where
-    C: Connect + Sync + 'static,
-    C::Transport: 'static,
-    C::Future: 'static,
-    B: Payload + Send + 'static + Default + Stream<Error = HyperError>,
-    B::Data: Send,
-    B::Item: AsRef<[u8]>,
-{
-    fn get_forecast<'a, 'b, T: AsRef<str>>(
-        &'a self,
-        token: T,
-        latitude: f64,
-        longitude: f64,
-    ) -> Box<dyn Future<Item = Forecast, Error = Error> + 'b> {
-        let url = utils::uri(token.as_ref(), latitude, longitude);
-        let uri = match Uri::from_str(&url) {
-            Ok(v) => v,
-            Err(why) => return Box::new(future::err(Error::Uri(why))),
-        };
-
-        Box::new(
-            self.get(uri)
-                .and_then(|res| res.into_body().concat2())
-                .from_err()
-                .map(internal::from_chunk)
-                .and_then(|x| x),
-        )
+        L: IntoLocation,
+        T: AsRef<str>,
+    {
+        DarkskyRequester::get_forecast_time_machine(self, token.as_ref(), location, time, options)
     }
 
-    fn get_forecast_with_options<'a, 'b, F, T>(
-        &'a self,
+    /// Retrieve a [forecast][`Forecast`] for the given location along with its
+    /// [summaries][`Summaries`] in each of the given [`Language`]s, setting
+    /// options where needed.
+    ///
+    /// A request is made for each language concurrently. Only the response for
+    /// the first language is fully parsed into a forecast; the responses for
+    /// the other languages are only parsed for their summaries. If no
+    /// languages are given, then the API's default language is used and no
+    /// summaries are returned.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve a forecast in English, with summaries in French and Spanish:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate hyper;
+    /// extern crate hyper_tls;
+    /// extern crate tokio;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// use darksky::{DarkskyHyperRequester, Language};
+    /// use hyper::{Body, client::Client};
+    /// use hyper_tls::HttpsConnector;
+    /// use std::env;
+    /// use tokio::runtime::Runtime;
+    ///
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// let runtime = Runtime::new()?;
+    /// let client = Client::builder()
+    ///     .build::<_, Body>(HttpsConnector::new());
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let languages = [Language::En, Language::Fr, Language::Es];
+    ///
+    /// let req = client.get_forecast_multilingual(&token, (37.8267, -122.423), &languages, |o| o);
+    ///
+    /// // We're waiting in this example, but you shouldn't in your code.
+    /// let multilingual = runtime.block_on(req)?;
+    ///
+    /// if let Some(summaries) = multilingual.summaries.get(&Language::Fr) {
+    ///     println!("Currently: {:?}", summaries.currently);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    /// [`Language`]: ../../enum.Language.html
+    /// [`Summaries`]: ../../models/struct.Summaries.html
+    fn get_forecast_multilingual<F, L, T>(
+        &self,
         token: T,
-        latitude: f64,
-        longitude: f64,
+        location: L,
+        languages: &[Language],
         options: F,
-    ) -> Box<dyn Future<Item = Forecast, Error = Error> + 'b>
+    ) -> send::MultilingualFuture<Self::Future>
     where
         F: FnOnce(Options) -> Options,
+        L: IntoLocation,
         T: AsRef<str>,
     {
-        let options = options(Options(HashMap::new())).0;
-        let constructed = utils::uri_optioned(token.as_ref(), latitude, longitude, None, options);
-        let url = match constructed {
-            Ok(v) => v,
-            Err(why) => return Box::new(future::err(why)),
-        };
-        let uri = match Uri::from_str(&url) {
-            Ok(v) => v,
-            Err(why) => return Box::new(future::err(Error::Uri(why))),
-        };
-
-        Box::new(
-            self.get(uri)
-                .and_then(|res| res.into_body().concat2())
-                .from_err()
-                .map(internal::from_chunk)
-                .and_then(|x| x),
+        DarkskyRequester::get_forecast_multilingual(
+            self,
+            token.as_ref(),
+            location,
+            languages,
+            options,
         )
     }
 
-    fn get_forecast_time_machine<D, F, T>(
+    /// Checks the health of the API with a cheap request, for use in
+    /// readiness probes.
+    ///
+    /// Failing to receive a response is reported as an unreachable
+    /// [`Health`] rather than an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate hyper;
+    /// extern crate hyper_tls;
+    /// extern crate tokio;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// use darksky::DarkskyHyperRequester;
+    /// use hyper::{Body, client::Client};
+    /// use hyper_tls::HttpsConnector;
+    /// use std::env;
+    /// use tokio::runtime::Runtime;
+    ///
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// let runtime = Runtime::new()?;
+    /// let client = Client::builder()
+    ///     .build::<_, Body>(HttpsConnector::new());
+    /// let token = env::var("FORECAST_TOKEN")?;
+    ///
+    /// // We're waiting in this example, but you shouldn't in your code.
+    /// let health = runtime.block_on(client.check_status(&token))?;
+    ///
+    /// if !health.is_healthy() {
+    ///     println!("DarkSky is unavailable: {:?}", health);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Health`]: ../../health/struct.Health.html
+    fn check_status<T: AsRef<str>>(&self, token: T) -> send::HealthFuture<Self::Future> {
+        DarkskyRequester::check_status(self, token.as_ref())
+    }
+}
+
+impl<S, B, C> DarkskyHyperRequester for S where S: Backend<Client = Client<C, B>> + ?Sized {}
+
+impl<B, C> Backend for Client<C, B>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+    B: HttpBody + Default + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    type Client = Self;
+}
+
+impl<B, C> HttpSend for Client<C, B>
+where
+    C: Connect + Clone + Send + Sync + 'static,
+    B: HttpBody + Default + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    type Future = BodyFuture;
+
+    fn send(&self, uri: String) -> BodyFuture {
+        send(self, None, uri, None)
+    }
+
+    fn send_with(&self, uri: String, config: &Config) -> BodyFuture {
+        send(self, Some(config), uri, None)
+    }
+
+    fn send_recording(&self, uri: String, config: Option<&Config>, slot: &MetaSlot) -> BodyFuture {
+        let mut future = send(self, config, uri, None);
+        future.slot = Some(slot.clone());
+
+        future
+    }
+
+    fn send_into(
         &self,
-        token: T,
-        latitude: f64,
-        longitude: f64,
-        time: D,
-        options: F,
-    ) -> Box<dyn Future<Item = Forecast, Error = Error>>
-    where
-        D: Display,
-        F: FnOnce(Options) -> Options,
-        T: AsRef<str>,
-    {
-        let time = time.to_string();
+        uri: String,
+        config: Option<&Config>,
+        slot: Option<&MetaSlot>,
+        buffer: Vec<u8>,
+    ) -> BodyFuture {
+        let mut future = send(self, config, uri, Some(buffer));
+        future.slot = slot.cloned();
 
-        forecast_optioned(self, token, latitude, longitude, Some(time), options)
+        future
     }
 }
 
-fn forecast_optioned<B, C, F, T>(
+/// Sends a GET request, with the user agent and timeout of a configuration
+/// if one is given.
+fn send<B, C>(
     client: &Client<C, B>,
-    token: T,
-    latitude: f64,
-    longitude: f64,
-    time: Option<String>,
-    options: F,
-) -> Box<dyn Future<Item = Forecast, Error = Error>>
+    config: Option<&Config>,
+    uri: String,
+    buffer: Option<Vec<u8>>,
+) -> BodyFuture
 where
-    B: Payload + Send + 'static + Default + Stream<Error = HyperError>,
-    B::Item: AsRef<[u8]>,
-    C: Connect + 'static,
-    F: FnOnce(Options) -> Options,
-    T: AsRef<str>,
+    B: HttpBody + Default + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn StdError + Send + Sync>>,
+    C: Connect + Clone + Send + Sync + 'static,
 {
-    let options = options(Options(HashMap::new())).0;
-    let constructed = utils::uri_optioned(token.as_ref(), latitude, longitude, time, options);
-    let url = match constructed {
-        Ok(v) => v,
-        Err(why) => return Box::new(future::err(why)),
+    let user_agent = config.and_then(Config::get_user_agent);
+    let state = match Uri::from_str(&uri) {
+        Ok(uri) => match user_agent {
+            Some(user_agent) => {
+                let mut request = Request::new(B::default());
+                *request.uri_mut() = uri;
+
+                match HeaderValue::from_str(user_agent) {
+                    Ok(value) => {
+                        request.headers_mut().insert(USER_AGENT, value);
+
+                        State::Requesting(client.request(request), buffer)
+                    }
+                    Err(why) => State::Failed(Some(why.into())),
+                }
+            }
+            None => State::Requesting(client.get(uri), buffer),
+        },
+        Err(why) => State::Failed(Some(Error::Uri(why))),
     };
-    let uri = match Uri::from_str(&url) {
-        Ok(v) => v,
-        Err(why) => return Box::new(future::err(Error::Uri(why))),
+    let future = BodyFuture {
+        state,
+        deadline: None,
+        slot: None,
     };
 
-    Box::new(
-        client
-            .get(uri)
-            .and_then(|res| res.into_body().concat2())
-            .from_err()
-            .map(internal::from_chunk)
-            .and_then(|x| x),
-    )
+    match config.and_then(Config::get_timeout) {
+        Some(timeout) => future.timeout(timeout),
+        None => future,
+    }
+}
+
+/// A future resolving to a value decoded from a response body, returned by
+/// [`DarkskyHyperRequester`] methods on a `hyper` `Client`.
+///
+/// The body is read into a single buffer as it is received, and the future
+/// itself is not boxed, so no allocations are made beyond the buffer and
+/// those of `hyper` itself.
+///
+/// [`DarkskyHyperRequester`]: trait.DarkskyHyperRequester.html
+pub type HyperFuture<T = Forecast> = ForecastFuture<BodyFuture, T>;
+
+/// A future resolving to a forecast along with its summaries in other
+/// languages, returned by
+/// [`DarkskyHyperRequester::get_forecast_multilingual`] on a `hyper`
+/// `Client`.
+///
+/// The requests for each language are made concurrently.
+///
+/// [`DarkskyHyperRequester::get_forecast_multilingual`]:
+///   trait.DarkskyHyperRequester.html#method.get_forecast_multilingual
+pub type MultilingualFuture = send::MultilingualFuture<BodyFuture>;
+
+/// A future resolving to the health of the API, returned by
+/// [`DarkskyHyperRequester::check_status`] on a `hyper` `Client`.
+///
+/// [`DarkskyHyperRequester::check_status`]:
+///   trait.DarkskyHyperRequester.html#method.check_status
+pub type HealthFuture = send::HealthFuture<BodyFuture>;
+
+impl<T> ForecastFuture<BodyFuture, T> {
+    /// Fails the request with [`Error::Timeout`] if it has not completed
+    /// within the given duration of now, overriding any timeout from a
+    /// [`Config`].
+    ///
+    /// The timer requires the future to be polled within a `tokio` runtime.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "hyper-native-tls", doc = "```rust,no_run")]
+    #[cfg_attr(not(feature = "hyper-native-tls"), doc = "```rust,ignore")]
+    /// # extern crate darksky;
+    /// # extern crate hyper;
+    /// # extern crate hyper_tls;
+    /// # extern crate tokio;
+    /// #
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::{DarkskyHyperRequester, Error as DarkskyError};
+    /// use hyper::Client;
+    /// use hyper_tls::HttpsConnector;
+    /// use std::time::Duration;
+    /// use tokio::runtime::Runtime;
+    ///
+    /// let client = Client::builder().build::<_, hyper::Body>(HttpsConnector::new());
+    /// let future = client
+    ///     .get_forecast("token", (37.8267, -122.423))
+    ///     .timeout(Duration::from_secs(5));
+    ///
+    /// match Runtime::new()?.block_on(future) {
+    ///     Ok(forecast) => println!("Forecast: {:?}", forecast),
+    ///     Err(DarkskyError::Timeout) => println!("Timed out"),
+    ///     Err(why) => println!("Error: {}", why),
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Config`]: ../../constants/struct.Config.html
+    /// [`Error::Timeout`]: ../../enum.Error.html#variant.Timeout
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.map_body(|body| body.timeout(timeout))
+    }
+}
+
+impl MultilingualFuture {
+    /// Fails the request with [`Error::Timeout`] if the forecast and all of
+    /// its summaries have not been retrieved within the given duration of
+    /// now.
+    ///
+    /// [`Error::Timeout`]: ../../enum.Error.html#variant.Timeout
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.map_bodies(|body| body.timeout(timeout))
+    }
+}
+
+/// A future resolving to the body of a response, read into a single buffer
+/// as it is received.
+///
+/// This is returned by the [`HttpSend`] implementation for `hyper`'s `Client`.
+///
+/// [`HttpSend`]: ../send/trait.HttpSend.html
+#[must_use = "futures do nothing unless polled"]
+pub struct BodyFuture {
+    state: State,
+    deadline: Option<Deadline>,
+    slot: Option<MetaSlot>,
+}
+
+impl BodyFuture {
+    /// Fails the request with [`Error::Timeout`] if it has not completed
+    /// within the given duration of now.
+    ///
+    /// [`Error::Timeout`]: ../../enum.Error.html#variant.Timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Some(Deadline::new(timeout));
+
+        self
+    }
+
+    /// Fails the future if its deadline has passed, once the request itself
+    /// is pending.
+    fn poll_deadline(&mut self, cx: &mut Context) -> Poll<Result<Vec<u8>>> {
+        match self.deadline {
+            Some(ref mut deadline) => match deadline.poll(cx) {
+                Poll::Ready(()) => {
+                    self.state = State::Failed(None);
+
+                    Poll::Ready(Err(Error::Timeout))
+                }
+                Poll::Pending => Poll::Pending,
+            },
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl Debug for BodyFuture {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("BodyFuture").finish()
+    }
+}
+
+/// The state of a [`BodyFuture`].
+///
+/// [`BodyFuture`]: struct.BodyFuture.html
+enum State {
+    /// The request could not be made, such as due to an invalid URI. The
+    /// error is taken when the future is first polled.
+    Failed(Option<Error>),
+    /// Waiting on the response, with a buffer from the pool to read its
+    /// body into if one was given.
+    Requesting(ResponseFuture, Option<Vec<u8>>),
+    /// Reading the response body into the buffer.
+    Reading(Body, Vec<u8>),
+}
+
+impl Future for BodyFuture {
+    type Output = Result<Vec<u8>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Vec<u8>>> {
+        let this = &mut *self;
+
+        loop {
+            let (body, buffer) = match this.state {
+                State::Failed(ref mut why) => {
+                    let why = why.take().expect("BodyFuture polled after completion");
+
+                    return Poll::Ready(Err(why));
+                }
+                State::Requesting(ref mut res, ref mut buffer) => match Pin::new(res).poll(cx) {
+                    Poll::Ready(Ok(res)) => {
+                        if let Some(ref slot) = this.slot {
+                            slot.record(ResponseMeta::from_headers(
+                                res.status().as_u16(),
+                                |name| {
+                                    res.headers()
+                                        .get(name)
+                                        .and_then(|value| value.to_str().ok())
+                                },
+                            ));
+                        }
+
+                        read_body(res, buffer.take())
+                    }
+                    Poll::Ready(Err(why)) => {
+                        this.state = State::Failed(None);
+
+                        return Poll::Ready(Err(why.into()));
+                    }
+                    Poll::Pending => return this.poll_deadline(cx),
+                },
+                State::Reading(ref mut body, ref mut buffer) => {
+                    match Pin::new(body).poll_data(cx) {
+                        Poll::Ready(Some(Ok(chunk))) => {
+                            buffer.extend_from_slice(&chunk);
+
+                            continue;
+                        }
+                        Poll::Ready(Some(Err(why))) => {
+                            this.state = State::Failed(None);
+
+                            return Poll::Ready(Err(why.into()));
+                        }
+                        Poll::Ready(None) => {}
+                        Poll::Pending => return this.poll_deadline(cx),
+                    }
+
+                    let body = mem::take(buffer);
+                    this.state = State::Failed(None);
+
+                    return Poll::Ready(Ok(body));
+                }
+            };
+
+            this.state = State::Reading(body, buffer);
+        }
+    }
+}
+
+/// The point in time at which a request is timed out.
+///
+/// The timer is only created once the future is polled, as `tokio` requires
+/// timers to be created within a runtime while futures may be created
+/// outside of one.
+struct Deadline {
+    at: Instant,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl Deadline {
+    fn new(timeout: Duration) -> Self {
+        Deadline {
+            at: Instant::now() + timeout,
+            sleep: None,
+        }
+    }
+
+    fn poll(&mut self, cx: &mut Context) -> Poll<()> {
+        let at = self.at;
+
+        self.sleep
+            .get_or_insert_with(|| Box::pin(time::sleep_until(TokioInstant::from_std(at))))
+            .as_mut()
+            .poll(cx)
+    }
+}
+
+/// Prepares to read a response body into a buffer as it is received,
+/// preallocating the buffer from the response's `Content-Length` where given.
+///
+/// If a buffer is given, then the body is read into it rather than a newly
+/// allocated one.
+fn read_body(res: Response<Body>, buffer: Option<Vec<u8>>) -> (Body, Vec<u8>) {
+    let capacity = res
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|len| len.to_str().ok())
+        .and_then(|len| len.parse::<usize>().ok())
+        .map_or(0, |len| cmp::min(len, MAX_PREALLOCATION));
+    let mut buffer = buffer.unwrap_or_default();
+    buffer.reserve(capacity);
+
+    (res.into_body(), buffer)
 }
@@ -0,0 +1,426 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Client-side rate limiting of requests over an [`HttpSend`] backend.
+//!
+//! A [`RateLimited`] backend wraps another, delaying requests so that they
+//! stay within the limits of a [`RateLimiter`]: a number of requests per
+//! second, with bursts of up to that many, and a number of requests per UTC
+//! day, after which requests wait until midnight UTC, when DarkSky resets
+//! its quotas. Bulk pulls, such as of historical forecasts, then pace
+//! themselves rather than failing part way through once the quota is used
+//! up.
+//!
+//! A limiter can be shared between several backends, so that they pace
+//! themselves together.
+//!
+//! # Examples
+//!
+//! Send at most 5 requests per second, and stay within the free plan's
+//! daily quota:
+//!
+//! ```rust
+//! extern crate darksky;
+//!
+//! use darksky::bridge::limit::{RateLimited, RateLimiter};
+//! use darksky::constants::DEFAULT_DAILY_LIMIT;
+//! # let backend = ();
+//!
+//! let limiter = RateLimiter::new().per_second(5).per_day(DEFAULT_DAILY_LIMIT);
+//! let client = RateLimited::new(backend, limiter);
+//! # drop(client);
+//! ```
+//!
+//! [`HttpSend`]: ../send/trait.HttpSend.html
+//! [`RateLimited`]: struct.RateLimited.html
+//! [`RateLimiter`]: struct.RateLimiter.html
+
+use bridge::pool::BufferPool;
+use bridge::retry::{self, Delay};
+use bridge::send::{Backend, HttpSend, MetaSlot};
+use constants::Config;
+use std::cmp;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use {utils, Options, Result};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// The limits requests are paced within, shared between its clones.
+///
+/// Each request reserves a slot when it is created, in the order requests
+/// are created, whether or not it is then polled to completion.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::bridge::limit::RateLimiter;
+/// use std::time::Duration;
+///
+/// let limiter = RateLimiter::new().per_second(2);
+///
+/// // A burst of 2 requests is sent at once, after which they are spaced out.
+/// assert_eq!(limiter.reserve(), Duration::from_secs(0));
+/// assert_eq!(limiter.reserve(), Duration::from_secs(0));
+/// assert!(limiter.reserve() > Duration::from_millis(400));
+/// ```
+#[derive(Clone)]
+pub struct RateLimiter {
+    per_day: Option<u64>,
+    per_second: Option<u32>,
+    state: Arc<Mutex<Usage>>,
+}
+
+/// The slots reserved through a [`RateLimiter`].
+///
+/// [`RateLimiter`]: struct.RateLimiter.html
+#[derive(Default)]
+struct Usage {
+    /// The number of requests reserved on `day`.
+    calls: u64,
+    /// The UTC day, as a number of days since the Unix epoch, that requests
+    /// are being reserved on.
+    day: u64,
+    /// When the next request would be sent if no burst were allowed.
+    next: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter with no limits, letting every request through at
+    /// once.
+    pub fn new() -> Self {
+        RateLimiter {
+            per_day: None,
+            per_second: None,
+            state: Arc::new(Mutex::new(Usage::default())),
+        }
+    }
+
+    /// Limits the number of requests per UTC day, such as to
+    /// [`DEFAULT_DAILY_LIMIT`].
+    ///
+    /// Requests past the limit wait until the next midnight UTC. A value of
+    /// 0 removes the limit.
+    ///
+    /// [`DEFAULT_DAILY_LIMIT`]: ../../constants/constant.DEFAULT_DAILY_LIMIT.html
+    pub fn per_day(mut self, limit: u64) -> Self {
+        self.per_day = Some(limit).filter(|&limit| limit > 0);
+
+        self
+    }
+
+    /// Limits the number of requests per second, allowing bursts of up to
+    /// that many requests.
+    ///
+    /// A value of 0 removes the limit.
+    pub fn per_second(mut self, limit: u32) -> Self {
+        self.per_second = Some(limit).filter(|&limit| limit > 0);
+
+        self
+    }
+
+    /// Returns the number of requests allowed per UTC day, if limited.
+    pub fn get_per_day(&self) -> Option<u64> {
+        self.per_day
+    }
+
+    /// Returns the number of requests allowed per second, if limited.
+    pub fn get_per_second(&self) -> Option<u32> {
+        self.per_second
+    }
+
+    /// Reserves a slot for a request, returning how long to wait before
+    /// sending it.
+    pub fn reserve(&self) -> Duration {
+        let now = Instant::now();
+        let mut usage = self.lock();
+        let mut earliest = now;
+
+        if let Some(limit) = self.per_day {
+            let wall = unix_time(SystemTime::now());
+            let today = wall.as_secs() / SECONDS_PER_DAY;
+
+            if usage.day < today {
+                usage.day = today;
+                usage.calls = 0;
+            }
+
+            // Move on to the next day once this one is used up, which may
+            // already be ahead of today if requests are waiting for it.
+            if usage.calls >= limit {
+                usage.day += 1;
+                usage.calls = 0;
+            }
+
+            let start = Duration::from_secs(usage.day * SECONDS_PER_DAY);
+            earliest += start.checked_sub(wall).unwrap_or_default();
+            usage.calls += 1;
+        }
+
+        if let Some(limit) = self.per_second {
+            // Spacing requests evenly, a request is sent once its turn would
+            // come within the burst allowed ahead of it.
+            let interval = Duration::from_secs(1) / limit;
+            let burst = interval * (limit - 1);
+            let turn = cmp::max(usage.next.unwrap_or(earliest), earliest);
+
+            usage.next = Some(turn + interval);
+
+            if let Some(send_at) = turn.checked_sub(burst) {
+                earliest = cmp::max(earliest, send_at);
+            }
+        }
+
+        earliest - now
+    }
+
+    /// Locks the usage, recovering it if another thread panicked while
+    /// holding the lock, as it is never left partially updated.
+    fn lock(&self) -> MutexGuard<'_, Usage> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for RateLimiter {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("RateLimiter")
+            .field("per_day", &self.per_day)
+            .field("per_second", &self.per_second)
+            .finish()
+    }
+}
+
+/// A backend sending requests over another once a [`RateLimiter`] lets
+/// them through.
+///
+/// By default, delays are waited out on a separate thread, so that they
+/// work on any executor. An executor's own timer can be used instead with
+/// [`sleep`].
+///
+/// [`RateLimiter`]: struct.RateLimiter.html
+/// [`sleep`]: #method.sleep
+#[derive(Clone)]
+pub struct RateLimited<S> {
+    inner: Arc<S>,
+    limiter: RateLimiter,
+    sleep: Arc<dyn Fn(Duration) -> Delay + Send + Sync>,
+}
+
+impl<S> RateLimited<S> {
+    /// Wraps a backend, pacing its requests with the given limiter.
+    pub fn new(inner: S, limiter: RateLimiter) -> Self {
+        RateLimited {
+            inner: Arc::new(inner),
+            limiter,
+            sleep: Arc::new(retry::thread_sleep),
+        }
+    }
+
+    /// Sets the function creating the future waiting out each delay, such as
+    /// `tokio::time::sleep`.
+    pub fn sleep<F>(mut self, sleep: F) -> Self
+    where
+        F: Fn(Duration) -> Delay + Send + Sync + 'static,
+    {
+        self.sleep = Arc::new(sleep);
+
+        self
+    }
+
+    /// Returns the limiter requests are paced with.
+    pub fn get_limiter(&self) -> &RateLimiter {
+        &self.limiter
+    }
+
+    /// Returns a reference to the wrapped backend.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S: HttpSend> RateLimited<S> {
+    /// Sends a request once the limiter lets it through, reading its body
+    /// into the given buffer if there is one.
+    fn dispatch(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: MetaSlot,
+        mut buffer: Option<Vec<u8>>,
+    ) -> LimitedFuture<S> {
+        let delay = self.limiter.reserve();
+
+        let state = if delay == Duration::from_secs(0) {
+            State::Sending(send(&*self.inner, &uri, config, &slot, buffer.take()))
+        } else {
+            State::Waiting((self.sleep)(delay))
+        };
+
+        LimitedFuture {
+            buffer,
+            config: config.cloned(),
+            inner: Arc::clone(&self.inner),
+            slot,
+            state,
+            uri,
+        }
+    }
+}
+
+impl<S: Debug> Debug for RateLimited<S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("RateLimited")
+            .field("inner", &self.inner)
+            .field("limiter", &self.limiter)
+            .finish()
+    }
+}
+
+impl<S: HttpSend> HttpSend for RateLimited<S> {
+    type Future = LimitedFuture<S>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        self.dispatch(uri, None, MetaSlot::new(), None)
+    }
+
+    fn send_with(&self, uri: String, config: &Config) -> Self::Future {
+        self.dispatch(uri, Some(config), MetaSlot::new(), None)
+    }
+
+    fn send_recording(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: &MetaSlot,
+    ) -> Self::Future {
+        self.dispatch(uri, config, slot.clone(), None)
+    }
+
+    fn send_into(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: Option<&MetaSlot>,
+        buffer: Vec<u8>,
+    ) -> Self::Future {
+        let slot = slot.cloned().unwrap_or_default();
+
+        self.dispatch(uri, config, slot, Some(buffer))
+    }
+
+    fn uri_config(&self) -> Option<&Config> {
+        self.inner.uri_config()
+    }
+
+    fn buffer_pool(&self) -> Option<&BufferPool> {
+        self.inner.buffer_pool()
+    }
+
+    fn base_options(&self) -> Option<&Options> {
+        self.inner.base_options()
+    }
+}
+
+impl<S: Backend> Backend for RateLimited<S> {
+    type Client = S::Client;
+}
+
+/// A future resolving to the body of a response, sent once its
+/// [`RateLimiter`] lets it through, returned by [`RateLimited`] backends.
+///
+/// [`RateLimited`]: struct.RateLimited.html
+/// [`RateLimiter`]: struct.RateLimiter.html
+#[must_use = "futures do nothing unless polled"]
+pub struct LimitedFuture<S: HttpSend> {
+    buffer: Option<Vec<u8>>,
+    config: Option<Config>,
+    inner: Arc<S>,
+    slot: MetaSlot,
+    state: State<S::Future>,
+    uri: String,
+}
+
+enum State<F> {
+    Sending(F),
+    Waiting(Delay),
+}
+
+impl<S: HttpSend> Debug for LimitedFuture<S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("LimitedFuture")
+            .field("uri", &utils::redact(&self.uri))
+            .field("waiting", &matches!(self.state, State::Waiting(_)))
+            .finish()
+    }
+}
+
+impl<S: HttpSend> Future for LimitedFuture<S> {
+    type Output = Result<Vec<u8>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Vec<u8>>> {
+        let this = &mut *self;
+
+        if let State::Waiting(ref mut delay) = this.state {
+            if delay.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+
+            this.state = State::Sending(send(
+                &*this.inner,
+                &this.uri,
+                this.config.as_ref(),
+                &this.slot,
+                this.buffer.take(),
+            ));
+        }
+
+        match this.state {
+            State::Sending(ref mut future) => Pin::new(future).poll(cx),
+            State::Waiting(_) => unreachable!("delay has elapsed"),
+        }
+    }
+}
+
+/// Sends a request over a backend, reading its body into the buffer if one
+/// is given.
+fn send<S: HttpSend>(
+    inner: &S,
+    uri: &str,
+    config: Option<&Config>,
+    slot: &MetaSlot,
+    buffer: Option<Vec<u8>>,
+) -> S::Future {
+    match buffer {
+        Some(buffer) => inner.send_into(uri.to_owned(), config, Some(slot), buffer),
+        None => inner.send_recording(uri.to_owned(), config, slot),
+    }
+}
+
+/// Returns the time since the Unix epoch.
+fn unix_time(time: SystemTime) -> Duration {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default()
+}
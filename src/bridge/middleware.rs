@@ -0,0 +1,507 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Middleware run around each request sent over an [`HttpSend`] backend.
+//!
+//! A [`Layered`] backend wraps another, running a stack of [`Middleware`]
+//! before each request is sent and after its response is received. This
+//! suits concerns shared by every request, such as logging, injecting a
+//! token, recording metrics, or answering requests from a cache, without
+//! changes to the backend itself.
+//!
+//! Layers run in the order they were added before a request is sent, and in
+//! the reverse order after its response is received, so the first layer
+//! added is the outermost. A layer may answer a request itself from
+//! [`Middleware::before`], in which case the layers after it and the backend
+//! are skipped.
+//!
+//! As a `Layered` backend is itself an `HttpSend` backend, it can be used
+//! with [`DarkskyRequester`] methods and by a [`DarkskyClient`].
+//!
+//! # Examples
+//!
+//! Log each request with its duration, and inject the token into it:
+//!
+//! ```rust
+//! extern crate darksky;
+//!
+//! use darksky::bridge::middleware::Layered;
+//! use darksky::bridge::{DarkskyRequester, HttpSend};
+//! use darksky::Result;
+//! use std::future::{self, Future, Ready};
+//! use std::pin::Pin;
+//! use std::task::{Context, Poll, Waker};
+//!
+//! struct Stub;
+//!
+//! impl HttpSend for Stub {
+//!     type Future = Ready<Result<Vec<u8>>>;
+//!
+//!     fn send(&self, uri: String) -> Self::Future {
+//!         assert!(uri.starts_with("https://api.darksky.net/forecast/secret/"));
+//!
+//!         let body = br#"{"latitude": 49.9, "longitude": -97.1, "timezone": "America/Winnipeg"}"#;
+//!
+//!         future::ready(Ok(body.to_vec()))
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let client = Layered::new(Stub)
+//!     .before(|request| request.set_token("secret"))
+//!     .after(|request, response| {
+//!         println!("{:?} took {:?}: {}", request, request.elapsed(), response.is_ok());
+//!     });
+//!
+//! let mut req = client.get_forecast("placeholder", (49.9, -97.1));
+//!
+//! let mut cx = Context::from_waker(Waker::noop());
+//! match Pin::new(&mut req).poll(&mut cx) {
+//!     Poll::Ready(forecast) => assert_eq!(forecast.unwrap().timezone, "America/Winnipeg"),
+//!     Poll::Pending => unreachable!(),
+//! }
+//! # }
+//! ```
+//!
+//! [`DarkskyClient`]: ../../facade/struct.DarkskyClient.html
+//! [`DarkskyRequester`]: ../send/trait.DarkskyRequester.html
+//! [`HttpSend`]: ../send/trait.HttpSend.html
+//! [`Layered`]: struct.Layered.html
+//! [`Middleware`]: trait.Middleware.html
+//! [`Middleware::before`]: trait.Middleware.html#method.before
+
+use bridge::pool::BufferPool;
+use bridge::send::{Backend, HttpSend, MetaSlot, ResponseMeta};
+use constants::Config;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use {utils, Options, Result};
+
+/// A hook run around each request sent by a [`Layered`] backend.
+///
+/// Both methods do nothing by default, so only those which are needed have
+/// to be implemented.
+///
+/// [`Layered`]: struct.Layered.html
+pub trait Middleware: Send + Sync {
+    /// Runs before the request is sent, and may change its URI.
+    ///
+    /// Returning a response body, or an error, answers the request without
+    /// sending it, such as from a cache.
+    fn before(&self, _request: &mut RequestContext) -> Option<Result<Vec<u8>>> {
+        None
+    }
+
+    /// Runs after the response is received, and may change its body or
+    /// error.
+    fn after(&self, _request: &RequestContext, _response: &mut Result<Vec<u8>>) {}
+
+    /// Runs once when the layer is added, and may change the configuration
+    /// that the URIs of requests are built with, such as to snap their
+    /// coordinates to a [grid].
+    ///
+    /// [grid]: ../../constants/struct.Config.html#method.grid
+    fn configure(&self, _config: &mut Config) {}
+}
+
+impl<T: Middleware + ?Sized> Middleware for &T {
+    fn before(&self, request: &mut RequestContext) -> Option<Result<Vec<u8>>> {
+        (**self).before(request)
+    }
+
+    fn after(&self, request: &RequestContext, response: &mut Result<Vec<u8>>) {
+        (**self).after(request, response)
+    }
+
+    fn configure(&self, config: &mut Config) {
+        (**self).configure(config)
+    }
+}
+
+impl<T: Middleware + ?Sized> Middleware for Box<T> {
+    fn before(&self, request: &mut RequestContext) -> Option<Result<Vec<u8>>> {
+        (**self).before(request)
+    }
+
+    fn after(&self, request: &RequestContext, response: &mut Result<Vec<u8>>) {
+        (**self).after(request, response)
+    }
+
+    fn configure(&self, config: &mut Config) {
+        (**self).configure(config)
+    }
+}
+
+impl<T: Middleware + ?Sized> Middleware for Arc<T> {
+    fn before(&self, request: &mut RequestContext) -> Option<Result<Vec<u8>>> {
+        (**self).before(request)
+    }
+
+    fn after(&self, request: &RequestContext, response: &mut Result<Vec<u8>>) {
+        (**self).after(request, response)
+    }
+
+    fn configure(&self, config: &mut Config) {
+        (**self).configure(config)
+    }
+}
+
+/// A request passing through the layers of a [`Layered`] backend.
+///
+/// The token in the URI is masked when the request is debugged, so that
+/// requests can be logged as-is.
+///
+/// [`Layered`]: struct.Layered.html
+#[derive(Clone)]
+pub struct RequestContext {
+    answered: bool,
+    meta: MetaSlot,
+    started: Instant,
+    uri: String,
+}
+
+impl RequestContext {
+    /// Creates the context of a request to the given URI, starting now.
+    pub fn new<T: Into<String>>(uri: T) -> Self {
+        RequestContext {
+            answered: false,
+            meta: MetaSlot::new(),
+            started: Instant::now(),
+            uri: uri.into(),
+        }
+    }
+
+    /// Returns how long it has been since the request was made.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Returns whether a layer answered the request itself, so that it was
+    /// not sent, such as when it was served from a cache.
+    pub fn is_answered(&self) -> bool {
+        self.answered
+    }
+
+    /// Returns the metadata of the response, such as how long it is fresh
+    /// for, as recorded by the backend.
+    ///
+    /// This is empty before the response is received, and when the backend
+    /// does not record metadata or a layer answered the request.
+    pub fn get_meta(&self) -> ResponseMeta {
+        self.meta.get()
+    }
+
+    /// Records the metadata of the response, replacing that recorded by the
+    /// backend, such as when a layer answers the request itself.
+    pub fn record_meta(&self, meta: ResponseMeta) {
+        self.meta.record(meta);
+    }
+
+    /// Returns the URI the request is sent to.
+    ///
+    /// This includes the token, so should be passed through
+    /// [`utils::redact`] before being logged.
+    ///
+    /// [`utils::redact`]: ../../utils/fn.redact.html
+    pub fn get_uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Sets the URI the request is sent to.
+    pub fn set_uri<T: Into<String>>(&mut self, uri: T) {
+        self.uri = uri.into();
+    }
+
+    /// Replaces the token in the URI of a forecast request, such as with one
+    /// held by the middleware rather than the application.
+    ///
+    /// The URI is left as-is if it isn't that of a forecast.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use darksky::bridge::middleware::RequestContext;
+    ///
+    /// let mut request = RequestContext::new("https://api.darksky.net/forecast/-/49.9,-97.1");
+    /// request.set_token("secret");
+    ///
+    /// assert_eq!(request.get_uri(), "https://api.darksky.net/forecast/secret/49.9,-97.1");
+    /// ```
+    pub fn set_token(&mut self, token: &str) {
+        const PREFIX: &str = "/forecast/";
+
+        let start = match self.uri.find(PREFIX) {
+            Some(idx) => idx + PREFIX.len(),
+            None => return,
+        };
+        let end = self.uri[start..]
+            .find(['/', '?'])
+            .map_or(self.uri.len(), |idx| start + idx);
+
+        self.uri.replace_range(start..end, token);
+    }
+}
+
+impl Debug for RequestContext {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("RequestContext")
+            .field("uri", &utils::redact(&self.uri))
+            .field("answered", &self.answered)
+            .finish()
+    }
+}
+
+/// An [`HttpSend`] backend running a stack of [`Middleware`] around each
+/// request sent over another.
+///
+/// [`HttpSend`]: ../send/trait.HttpSend.html
+/// [`Middleware`]: trait.Middleware.html
+#[derive(Clone)]
+pub struct Layered<S> {
+    config: OnceLock<Config>,
+    inner: S,
+    layers: Vec<Arc<dyn Middleware>>,
+}
+
+impl<S> Layered<S> {
+    /// Wraps a backend, with no layers.
+    pub fn new(inner: S) -> Self {
+        Layered {
+            config: OnceLock::new(),
+            inner,
+            layers: Vec::new(),
+        }
+    }
+
+    /// Adds a layer, run after those added so far before a request is sent,
+    /// and before them after its response is received.
+    pub fn layer<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+        // The configuration is worked out again from that of the wrapped
+        // backend on the next request.
+        self.config = OnceLock::new();
+        self.layers.push(Arc::new(middleware));
+
+        self
+    }
+
+    /// Adds a layer running the given function before each request is sent.
+    pub fn before<F>(self, before: F) -> Self
+    where
+        F: Fn(&mut RequestContext) + Send + Sync + 'static,
+    {
+        self.layer(Before(before))
+    }
+
+    /// Adds a layer running the given function after each response is
+    /// received.
+    pub fn after<F>(self, after: F) -> Self
+    where
+        F: Fn(&RequestContext, &mut Result<Vec<u8>>) + Send + Sync + 'static,
+    {
+        self.layer(After(after))
+    }
+
+    /// Returns a reference to the wrapped backend.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Returns the number of layers.
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns whether there are no layers, so requests are sent as-is.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Unwraps the backend, dropping the layers.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: HttpSend> Layered<S> {
+    /// Runs the layers before a request, sending it over the wrapped backend
+    /// unless one of them answers it.
+    fn dispatch(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: MetaSlot,
+    ) -> LayeredFuture<S::Future> {
+        let mut request = RequestContext::new(uri);
+        request.meta = slot;
+
+        for (idx, layer) in self.layers.iter().enumerate() {
+            if let Some(response) = layer.before(&mut request) {
+                request.answered = true;
+
+                return LayeredFuture {
+                    body: None,
+                    layers: self.layers[..=idx].to_vec(),
+                    request,
+                    response: Some(response),
+                };
+            }
+        }
+
+        LayeredFuture {
+            body: Some(
+                self.inner
+                    .send_recording(request.uri.clone(), config, &request.meta),
+            ),
+            layers: self.layers.clone(),
+            request,
+            response: None,
+        }
+    }
+}
+
+impl<S: Debug> Debug for Layered<S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Layered")
+            .field("inner", &self.inner)
+            .field("layers", &self.layers.len())
+            .finish()
+    }
+}
+
+impl<S: HttpSend> HttpSend for Layered<S> {
+    type Future = LayeredFuture<S::Future>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        self.dispatch(uri, None, MetaSlot::new())
+    }
+
+    fn send_with(&self, uri: String, config: &Config) -> Self::Future {
+        self.dispatch(uri, Some(config), MetaSlot::new())
+    }
+
+    fn send_recording(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: &MetaSlot,
+    ) -> Self::Future {
+        self.dispatch(uri, config, slot.clone())
+    }
+
+    fn uri_config(&self) -> Option<&Config> {
+        // The layers configure the URIs of requests on top of the
+        // configuration of the wrapped backend.
+        Some(self.config.get_or_init(|| {
+            let mut config = self.inner.uri_config().cloned().unwrap_or_default();
+
+            for layer in &self.layers {
+                layer.configure(&mut config);
+            }
+
+            config
+        }))
+    }
+
+    fn buffer_pool(&self) -> Option<&BufferPool> {
+        self.inner.buffer_pool()
+    }
+
+    fn base_options(&self) -> Option<&Options> {
+        self.inner.base_options()
+    }
+}
+
+impl<S: Backend> Backend for Layered<S> {
+    type Client = S::Client;
+}
+
+/// A future resolving to the body of a response once the layers have run
+/// after it, returned by [`Layered`] backends.
+///
+/// [`Layered`]: struct.Layered.html
+#[must_use = "futures do nothing unless polled"]
+pub struct LayeredFuture<F> {
+    body: Option<F>,
+    layers: Vec<Arc<dyn Middleware>>,
+    request: RequestContext,
+    response: Option<Result<Vec<u8>>>,
+}
+
+impl<F> Debug for LayeredFuture<F> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("LayeredFuture")
+            .field("request", &self.request)
+            .finish()
+    }
+}
+
+impl<F> Future for LayeredFuture<F>
+where
+    F: Future<Output = Result<Vec<u8>>> + Unpin,
+{
+    type Output = Result<Vec<u8>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Vec<u8>>> {
+        let this = &mut *self;
+
+        let mut response = match this.body {
+            Some(ref mut body) => match Pin::new(body).poll(cx) {
+                Poll::Ready(response) => response,
+                Poll::Pending => return Poll::Pending,
+            },
+            None => this
+                .response
+                .take()
+                .expect("LayeredFuture polled after completion"),
+        };
+        this.body = None;
+
+        for layer in this.layers.iter().rev() {
+            layer.after(&this.request, &mut response);
+        }
+
+        Poll::Ready(response)
+    }
+}
+
+/// A layer running a function before each request.
+struct Before<F>(F);
+
+impl<F> Middleware for Before<F>
+where
+    F: Fn(&mut RequestContext) + Send + Sync,
+{
+    fn before(&self, request: &mut RequestContext) -> Option<Result<Vec<u8>>> {
+        (self.0)(request);
+
+        None
+    }
+}
+
+/// A layer running a function after each response.
+struct After<F>(F);
+
+impl<F> Middleware for After<F>
+where
+    F: Fn(&RequestContext, &mut Result<Vec<u8>>) + Send + Sync,
+{
+    fn after(&self, request: &RequestContext, response: &mut Result<Vec<u8>>) {
+        (self.0)(request, response)
+    }
+}
@@ -0,0 +1,111 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Bridge to provide DarkSky client implementation for the `minreq` crate.
+//!
+//! `minreq` is a small blocking client with few dependencies, using rustls
+//! for TLS, which suits statically linked musl binaries.
+//!
+//! [`HttpSend`] is implemented for [`MinreqClient`], so forecasts are
+//! requested through [`DarkskyRequester`], and its requests can be wrapped
+//! in middleware like those of any other backend. Requests are made as they
+//! are sent, so [`block_on`] returns their forecasts straight away.
+//!
+//! # Examples
+//!
+//! Retrieve a forecast for a location, taking a token from the environment:
+//!
+//! ```rust,no_run
+//! extern crate darksky;
+//!
+//! # use std::error::Error;
+//! #
+//! # fn try_main() -> Result<(), Box<dyn Error>> {
+//! use darksky::bridge::minreq::MinreqClient;
+//! use darksky::bridge::{send, DarkskyRequester};
+//! use darksky::{Block, Coordinate};
+//! use std::env;
+//!
+//! let token = env::var("FORECAST_TOKEN")?;
+//! let client = MinreqClient::new().timeout(10);
+//!
+//! let location = Coordinate::new(37.8267, -122.423);
+//!
+//! let req = client.get_forecast_with_options(&token, location, |o| o
+//!     .exclude(vec![Block::Minutely]));
+//!
+//! println!("Forecast: {:?}", send::block_on(req)?);
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     try_main().unwrap();
+//! # }
+//! ```
+//!
+//! [`DarkskyRequester`]: ../send/trait.DarkskyRequester.html
+//! [`HttpSend`]: ../send/trait.HttpSend.html
+//! [`MinreqClient`]: struct.MinreqClient.html
+//! [`block_on`]: ../send/fn.block_on.html
+
+use super::send::HttpSend;
+use std::future::{self, Ready};
+use Result;
+
+/// A client sending requests with `minreq`.
+///
+/// `minreq` keeps no client state of its own, so this only holds settings
+/// applied to each request.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MinreqClient {
+    timeout: Option<u64>,
+}
+
+impl MinreqClient {
+    /// Creates a client with no request timeout.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the timeout of each request, in seconds.
+    pub fn timeout(mut self, seconds: u64) -> Self {
+        self.timeout = Some(seconds);
+
+        self
+    }
+
+    /// Returns the timeout of each request, in seconds, if one is set.
+    pub fn get_timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+
+    fn fetch(&self, uri: &str) -> Result<Vec<u8>> {
+        let mut req = minreq::get(uri);
+
+        if let Some(timeout) = self.timeout {
+            req = req.with_timeout(timeout);
+        }
+
+        Ok(req.send()?.into_bytes())
+    }
+}
+
+impl HttpSend for MinreqClient {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        future::ready(self.fetch(&uri))
+    }
+}
@@ -14,13 +14,69 @@
 // CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
 // CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 //! Bridged support for various HTTP clients.
+//!
+//! Each bridge implements [`HttpSend`] on its client, for which the shared
+//! [`DarkskyRequester`] is implemented. The requester trait of each bridge,
+//! such as [`DarkskyUreqRequester`], wraps `DarkskyRequester` for the
+//! [backends] of its client, blocking on the request where the client is
+//! blocking. Other HTTP clients can be supported by implementing `HttpSend`.
+//!
+//! Pooled buffers, configuration, and middleware are each added by wrapping a
+//! backend in another, such as a [`PooledClient`], [`ConfiguredClient`], or
+//! [`Layered`] backend, and apply to every bridge.
+//!
+//! [`ConfiguredClient`]: configured/struct.ConfiguredClient.html
+//! [`DarkskyRequester`]: send/trait.DarkskyRequester.html
+//! [`DarkskyUreqRequester`]: ureq/trait.DarkskyUreqRequester.html
+//! [`HttpSend`]: send/trait.HttpSend.html
+//! [`Layered`]: middleware/struct.Layered.html
+//! [`PooledClient`]: pool/struct.PooledClient.html
+//! [backends]: send/trait.Backend.html
 
+#[cfg(feature = "attohttpc")]
+pub mod attohttpc;
+#[cfg(feature = "awc")]
+pub mod awc;
+pub mod cancel;
+pub mod circuit;
+pub mod configured;
+#[cfg(feature = "curl")]
+pub mod curl;
 #[cfg(feature = "hyper")]
 pub mod hyper;
-#[cfg(feature = "reqwest")]
+pub mod limit;
+pub mod middleware;
+#[cfg(feature = "minreq")]
+pub mod minreq;
+pub mod pool;
+#[cfg(any(feature = "reqwest-async", feature = "reqwest-blocking"))]
 pub mod reqwest;
+pub mod retry;
+pub mod send;
+pub mod snap;
+#[cfg(feature = "surf")]
+pub mod surf;
+#[cfg(feature = "ureq")]
+pub mod ureq;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+#[cfg(feature = "attohttpc")]
+pub use self::attohttpc::DarkskyAttohttpcRequester;
+#[cfg(feature = "awc")]
+pub use self::awc::DarkskyAwcRequester;
+#[cfg(feature = "curl")]
+pub use self::curl::DarkskyCurlRequester;
 #[cfg(feature = "hyper")]
 pub use self::hyper::DarkskyHyperRequester;
-#[cfg(feature = "reqwest")]
+#[cfg(feature = "reqwest-async")]
+pub use self::reqwest::DarkskyReqwestAsyncRequester;
+#[cfg(feature = "reqwest-blocking")]
 pub use self::reqwest::DarkskyReqwestRequester;
+pub use self::send::{Backend, DarkskyRequester, DynDarkskyRequester, HttpSend};
+#[cfg(feature = "surf")]
+pub use self::surf::DarkskySurfRequester;
+#[cfg(feature = "ureq")]
+pub use self::ureq::DarkskyUreqRequester;
+#[cfg(feature = "wasm")]
+pub use self::wasm::DarkskyWasmRequester;
@@ -0,0 +1,252 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Reuse of response body buffers across requests.
+//!
+//! Wrapping a client in a [`PooledClient`] reads response bodies into buffers
+//! taken from its [`BufferPool`], returning them to the pool once the body has
+//! been deserialized. This avoids allocating a fresh buffer for every request
+//! in services making many requests.
+//!
+//! A `PooledClient` is itself an [`HttpSend`] backend, so it can be wrapped in
+//! other backends such as a [`Layered`] backend. Buffers are only read into by
+//! the `hyper` and blocking `reqwest` backends, which implement
+//! [`HttpSend::send_into`].
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`PooledClient`].
+//!
+//! [`BufferPool`]: struct.BufferPool.html
+//! [`HttpSend`]: ../send/trait.HttpSend.html
+//! [`HttpSend::send_into`]: ../send/trait.HttpSend.html#method.send_into
+//! [`Layered`]: ../middleware/struct.Layered.html
+//! [`PooledClient`]: struct.PooledClient.html
+
+use super::send::{Backend, HttpSend, MetaSlot};
+use constants::Config;
+use std::sync::{Arc, Mutex, MutexGuard};
+use Options;
+
+/// The default maximum number of idle buffers retained by a [`BufferPool`].
+///
+/// [`BufferPool`]: struct.BufferPool.html
+pub const DEFAULT_MAX_BUFFERS: usize = 16;
+
+/// A pool of reusable buffers for reading response bodies into.
+///
+/// Cloning a pool is cheap, and clones share the same buffers.
+#[derive(Clone, Debug)]
+pub struct BufferPool {
+    buffers: Arc<Mutex<Vec<Vec<u8>>>>,
+    max_buffers: usize,
+}
+
+impl BufferPool {
+    /// Creates a new, empty pool retaining up to [`DEFAULT_MAX_BUFFERS`] idle
+    /// buffers.
+    ///
+    /// [`DEFAULT_MAX_BUFFERS`]: constant.DEFAULT_MAX_BUFFERS.html
+    pub fn new() -> Self {
+        Self::with_max_buffers(DEFAULT_MAX_BUFFERS)
+    }
+
+    /// Creates a new, empty pool retaining up to the given number of idle
+    /// buffers.
+    ///
+    /// This should be at least the number of requests that are expected to be
+    /// in flight at once.
+    pub fn with_max_buffers(max_buffers: usize) -> Self {
+        BufferPool {
+            buffers: Arc::new(Mutex::new(Vec::with_capacity(max_buffers))),
+            max_buffers,
+        }
+    }
+
+    /// Returns whether the pool has no idle buffers.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Returns the number of idle buffers in the pool.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Takes an empty buffer from the pool, allocating a new one if none are
+    /// idle.
+    pub(crate) fn take(&self) -> Vec<u8> {
+        self.lock().pop().unwrap_or_default()
+    }
+
+    /// Returns a buffer to the pool, dropping it if the pool is full.
+    pub(crate) fn give(&self, mut buffer: Vec<u8>) {
+        let mut buffers = self.lock();
+
+        if buffers.len() < self.max_buffers {
+            buffer.clear();
+            buffers.push(buffer);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Vec<u8>>> {
+        // A panic while holding the lock can not leave the buffers in an
+        // inconsistent state, so poisoning is ignored.
+        self.buffers.lock().unwrap_or_else(|why| why.into_inner())
+    }
+}
+
+impl Default for BufferPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A client which reads response bodies into buffers from a [`BufferPool`].
+///
+/// The bridge traits, such as [`DarkskyHyperRequester`], are implemented for
+/// pooled clients of the same types that they are implemented for, as are
+/// [`DarkskyRequester`] methods.
+///
+/// # Examples
+///
+/// Retrieve forecasts for many locations, reusing response buffers:
+///
+/// ```rust,no_run
+/// extern crate darksky;
+/// extern crate hyper;
+/// extern crate hyper_tls;
+/// extern crate tokio;
+///
+/// # use std::error::Error;
+/// #
+/// use darksky::bridge::pool::PooledClient;
+/// use darksky::DarkskyHyperRequester;
+/// use hyper::{Body, Client};
+/// use hyper_tls::HttpsConnector;
+/// use std::env;
+/// use tokio::runtime::Runtime;
+///
+/// # fn try_main() -> Result<(), Box<dyn Error>> {
+/// let runtime = Runtime::new()?;
+/// let client = PooledClient::new(
+///     Client::builder().build::<_, Body>(HttpsConnector::new()),
+/// );
+///
+/// let token = env::var("FORECAST_TOKEN")?;
+///
+/// for &location in &[(37.8267, -122.423), (49.9, -97.1)] {
+///     // We're waiting in this example, but you shouldn't in your code.
+///     let forecast = runtime.block_on(client.get_forecast(&token, location))?;
+///
+///     println!("Forecast: {:?}", forecast);
+/// }
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// [`BufferPool`]: struct.BufferPool.html
+/// [`DarkskyHyperRequester`]: ../hyper/trait.DarkskyHyperRequester.html
+/// [`DarkskyRequester`]: ../send/trait.DarkskyRequester.html
+#[derive(Clone, Debug)]
+pub struct PooledClient<C> {
+    client: C,
+    pool: BufferPool,
+}
+
+impl<C> PooledClient<C> {
+    /// Wraps a client with a new [`BufferPool`].
+    ///
+    /// [`BufferPool`]: struct.BufferPool.html
+    pub fn new(client: C) -> Self {
+        Self::with_pool(client, BufferPool::new())
+    }
+
+    /// Wraps a client with an existing [`BufferPool`], which may be shared
+    /// with other clients.
+    ///
+    /// [`BufferPool`]: struct.BufferPool.html
+    pub fn with_pool(client: C, pool: BufferPool) -> Self {
+        PooledClient { client, pool }
+    }
+
+    /// Returns a reference to the wrapped client.
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    /// Returns a reference to the pool of buffers.
+    pub fn pool(&self) -> &BufferPool {
+        &self.pool
+    }
+
+    /// Unwraps the client, dropping the pool.
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+}
+
+impl<C: HttpSend> HttpSend for PooledClient<C> {
+    type Future = C::Future;
+
+    fn send(&self, uri: String) -> Self::Future {
+        self.client.send_into(uri, None, None, self.pool.take())
+    }
+
+    fn send_with(&self, uri: String, config: &Config) -> Self::Future {
+        self.client
+            .send_into(uri, Some(config), None, self.pool.take())
+    }
+
+    fn send_recording(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: &MetaSlot,
+    ) -> Self::Future {
+        self.client
+            .send_into(uri, config, Some(slot), self.pool.take())
+    }
+
+    fn send_into(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: Option<&MetaSlot>,
+        buffer: Vec<u8>,
+    ) -> Self::Future {
+        self.client.send_into(uri, config, slot, buffer)
+    }
+
+    fn uri_config(&self) -> Option<&Config> {
+        self.client.uri_config()
+    }
+
+    fn buffer_pool(&self) -> Option<&BufferPool> {
+        Some(&self.pool)
+    }
+
+    fn base_options(&self) -> Option<&Options> {
+        self.client.base_options()
+    }
+}
+
+impl<C: Backend> Backend for PooledClient<C> {
+    type Client = C::Client;
+}
@@ -0,0 +1,363 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Bridge to provide DarkSky client implementation for the blocking `reqwest`
+//! client.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`DarkskyReqwestRequester`].
+//!
+//! [`DarkskyReqwestRequester`]: trait.DarkskyReqwestRequester.html
+
+use bridge::send::{self, Backend, DarkskyRequester, HttpSend, MetaSlot, ResponseMeta};
+use constants::Config;
+use health::Health;
+use models::{Forecast, MultilingualForecast};
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::header::USER_AGENT;
+use std::fmt::Display;
+use std::future::{self, Ready};
+use std::io::Read;
+use {Error, IntoLocation, Language, Options, Result};
+
+/// The trait for `reqwest` implementations to different DarkSky routes.
+///
+/// This is implemented for every [`Backend`] sending requests through a
+/// blocking `reqwest` `Client`, including those wrapped in other backends,
+/// such as a [`PooledClient`] or [`ConfiguredClient`]. Each method blocks on
+/// the equivalent [`DarkskyRequester`] method, so requests run the layers of
+/// the backends wrapping the client.
+///
+/// [`Backend`]: ../../send/trait.Backend.html
+/// [`ConfiguredClient`]: ../../configured/struct.ConfiguredClient.html
+/// [`DarkskyRequester`]: ../../send/trait.DarkskyRequester.html
+/// [`PooledClient`]: ../../pool/struct.PooledClient.html
+pub trait DarkskyReqwestRequester: HttpSend {
+    /// Retrieve a [`Forecast`] for the given location.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve a forecast for a location, taking a token from the environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate reqwest;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::{Coordinate, DarkskyReqwestRequester};
+    /// use reqwest::blocking::Client;
+    /// use std::env;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let client = Client::new();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// let req = client.get_forecast(&token, location)?;
+    ///
+    /// println!("Forecast: {:?}", req);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Block::Minutely`]: ../../../enum.Block.html#variant.Minutely
+    /// [`Forecast`]: ../../../models/struct.Forecast.html
+    fn get_forecast<L: IntoLocation>(&self, token: &str, location: L) -> Result<Forecast> {
+        send::block_on(DarkskyRequester::get_forecast(self, token, location))
+    }
+
+    /// Retrieve a [`Forecast`] for the given location, setting options where
+    /// needed. For a full list of options, refer to the documentation for the
+    /// [`Options`] builder.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve an extended forecast, excluding the
+    /// [minutely block][`Block::Minutely`], taking a token from the
+    /// environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate reqwest;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::{Block, Coordinate, DarkskyReqwestRequester};
+    /// use reqwest::blocking::Client;
+    /// use std::env;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let client = Client::new();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// let req = client.get_forecast_with_options(&token, location, |o| o
+    ///     .exclude(vec![Block::Minutely]))?;
+    ///
+    /// println!("Forecast: {:?}", req);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Block::Minutely`]: ../../../enum.Block.html#variant.Minutely
+    /// [`Forecast`]: ../../../models/struct.Forecast.html
+    /// [`Options`]: ../../../struct.Options.html
+    fn get_forecast_with_options<F, L>(
+        &self,
+        token: &str,
+        location: L,
+        options: F,
+    ) -> Result<Forecast>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        send::block_on(DarkskyRequester::get_forecast_with_options(
+            self, token, location, options,
+        ))
+    }
+
+    /// Sets the time to request a forecast for by using DarkSky's Time Machine
+    /// API.
+    ///
+    /// This accepts either a Unix timestamp or a string in the format of
+    /// `[YYYY]-[MM]-[DD]T[HH]:[MM]:[SS][timezone`, where `timezone` should
+    /// either be:
+    ///
+    /// - omitted (referring to the local time for the location being
+    ///   requested);
+    /// - `Z` referring to GMT time;
+    /// - or `-[HH][MM]` for an offset from GMT in hours and minutes.
+    ///
+    /// The timezone is only used for determining the time of the request. The
+    /// response will always be relative to the local time zone.
+    ///
+    /// Refer to DarkSky's documentation on
+    /// [Time Machine Request Parameters][docs]for information.
+    ///
+    /// This function accepts anything that implements the `Display` trait, so
+    /// you should validate data beforehand. This is to avoid implementing a
+    /// time validation scheme, while avoiding locking the parameter type to
+    /// that of a time library (e.g. Chrono).
+    ///
+    /// [docs]: https://darksky.net/dev/docs#time-machine-request-parameters
+    fn get_forecast_time_machine<D, F, L>(
+        &self,
+        token: &str,
+        location: L,
+        time: D,
+        options: F,
+    ) -> Result<Forecast>
+    where
+        D: Display,
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        let req = DarkskyRequester::get_forecast_time_machine(self, token, location, time, options);
+
+        send::block_on(req)
+    }
+
+    /// Retrieve a [`Forecast`] for the given location along with its
+    /// [summaries][`Summaries`] in each of the given [`Language`]s, setting
+    /// options where needed.
+    ///
+    /// A request is made for each language in turn. Only the response for
+    /// the first language is fully parsed into a forecast; the responses for
+    /// the other languages are only parsed for their summaries. If no
+    /// languages are given, then the API's default language is used and no
+    /// summaries are returned.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve a forecast in English, with summaries in French and Spanish:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate reqwest;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::{DarkskyReqwestRequester, Language};
+    /// use reqwest::blocking::Client;
+    /// use std::env;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let client = Client::new();
+    /// let languages = [Language::En, Language::Fr, Language::Es];
+    ///
+    /// let multilingual =
+    ///     client.get_forecast_multilingual(&token, (37.8267, -122.423), &languages, |o| o)?;
+    ///
+    /// if let Some(summaries) = multilingual.summaries.get(&Language::Fr) {
+    ///     println!("Currently: {:?}", summaries.currently);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Forecast`]: ../../../models/struct.Forecast.html
+    /// [`Language`]: ../../../enum.Language.html
+    /// [`Summaries`]: ../../../models/struct.Summaries.html
+    fn get_forecast_multilingual<F, L>(
+        &self,
+        token: &str,
+        location: L,
+        languages: &[Language],
+        options: F,
+    ) -> Result<MultilingualForecast>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        let req =
+            DarkskyRequester::get_forecast_multilingual(self, token, location, languages, options);
+
+        send::block_on(req)
+    }
+
+    /// Checks the health of the API with a cheap request, for use in
+    /// readiness probes.
+    ///
+    /// Failing to receive a response is reported as an unreachable
+    /// [`Health`] rather than an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate reqwest;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::DarkskyReqwestRequester;
+    /// use reqwest::blocking::Client;
+    /// use std::env;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let health = Client::new().check_status(&token)?;
+    ///
+    /// if !health.is_healthy() {
+    ///     println!("DarkSky is unavailable: {:?}", health);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Health`]: ../../../health/struct.Health.html
+    fn check_status(&self, token: &str) -> Result<Health> {
+        send::block_on(DarkskyRequester::check_status(self, token))
+    }
+}
+
+impl<S: Backend<Client = Client> + ?Sized> DarkskyReqwestRequester for S {}
+
+impl Backend for Client {
+    type Client = Client;
+}
+
+impl HttpSend for Client {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        send(self.get(&uri), None, Vec::new())
+    }
+
+    fn send_with(&self, uri: String, config: &Config) -> Self::Future {
+        send(configure(self.get(&uri), Some(config)), None, Vec::new())
+    }
+
+    fn send_recording(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: &MetaSlot,
+    ) -> Self::Future {
+        send(configure(self.get(&uri), config), Some(slot), Vec::new())
+    }
+
+    fn send_into(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: Option<&MetaSlot>,
+        buffer: Vec<u8>,
+    ) -> Self::Future {
+        send(configure(self.get(&uri), config), slot, buffer)
+    }
+}
+
+/// Sends a request, reading the whole response body into the given buffer,
+/// and recording the metadata of the response into the slot if one is given.
+fn send(
+    request: RequestBuilder,
+    slot: Option<&MetaSlot>,
+    mut buffer: Vec<u8>,
+) -> Ready<Result<Vec<u8>>> {
+    let result = request.send().map_err(Error::from).and_then(|mut res| {
+        if let Some(slot) = slot {
+            slot.record(ResponseMeta::from_headers(res.status().as_u16(), |name| {
+                res.headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+            }));
+        }
+
+        res.read_to_end(&mut buffer).map_err(From::from)
+    });
+
+    future::ready(result.map(|_| buffer))
+}
+
+/// Applies the timeout and user agent of a configuration to a request, if
+/// one is given, overriding the client's own.
+fn configure(mut request: RequestBuilder, config: Option<&Config>) -> RequestBuilder {
+    let config = match config {
+        Some(config) => config,
+        None => return request,
+    };
+
+    if let Some(timeout) = config.get_timeout() {
+        request = request.timeout(timeout);
+    }
+
+    if let Some(user_agent) = config.get_user_agent() {
+        request = request.header(USER_AGENT, user_agent);
+    }
+
+    request
+}
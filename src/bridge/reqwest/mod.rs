@@ -0,0 +1,37 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Bridges to provide DarkSky client implementations for the `reqwest` crate.
+//!
+//! The [`blocking`] module, enabled by the `reqwest-blocking` feature,
+//! implements [`DarkskyReqwestRequester`] on `reqwest::blocking::Client`. The
+//! [`nonblocking`] module, enabled by the `reqwest-async` feature, implements
+//! [`DarkskyReqwestAsyncRequester`] on the async `reqwest::Client`.
+//!
+//! [`DarkskyReqwestAsyncRequester`]: nonblocking/trait.DarkskyReqwestAsyncRequester.html
+//! [`DarkskyReqwestRequester`]: blocking/trait.DarkskyReqwestRequester.html
+//! [`blocking`]: blocking/index.html
+//! [`nonblocking`]: nonblocking/index.html
+
+#[cfg(feature = "reqwest-blocking")]
+pub mod blocking;
+#[cfg(feature = "reqwest-async")]
+pub mod nonblocking;
+
+#[cfg(feature = "reqwest-blocking")]
+pub use self::blocking::DarkskyReqwestRequester;
+#[cfg(feature = "reqwest-async")]
+pub use self::nonblocking::DarkskyReqwestAsyncRequester;
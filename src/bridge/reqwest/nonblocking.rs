@@ -0,0 +1,279 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Bridge to provide DarkSky client implementation for the non-blocking
+//! `reqwest` client.
+//!
+//! Requests return futures, which must be awaited within a Tokio 1 runtime,
+//! as `reqwest` requires.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`DarkskyReqwestAsyncRequester`].
+//!
+//! [`DarkskyReqwestAsyncRequester`]: trait.DarkskyReqwestAsyncRequester.html
+
+use bridge::send::{Backend, DarkskyRequester, ForecastFuture, HttpSend, MetaSlot, ResponseMeta};
+use constants::Config;
+use reqwest::header::USER_AGENT;
+use reqwest::{Client, RequestBuilder, Response, Result as ReqwestResult};
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use {IntoLocation, Options, Result};
+
+/// A boxed future resolving to the body of a response, returned by the
+/// [`HttpSend`] implementation for the async `reqwest` `Client`.
+///
+/// [`HttpSend`]: ../../send/trait.HttpSend.html
+pub type BodyFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>>> + Send>>;
+
+/// The trait for async `reqwest` implementations to different DarkSky routes.
+///
+/// This is implemented for every [`Backend`] sending requests through an
+/// async `reqwest` `Client`, including those wrapped in other backends. Each
+/// method returns the future of the equivalent [`DarkskyRequester`] method,
+/// so requests run the layers of the backends wrapping the client.
+///
+/// [`Backend`]: ../../send/trait.Backend.html
+/// [`DarkskyRequester`]: ../../send/trait.DarkskyRequester.html
+pub trait DarkskyReqwestAsyncRequester: HttpSend {
+    /// Retrieve a [`Forecast`] for the given location.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve a forecast for a location, taking a token from the environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate reqwest;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::{Coordinate, DarkskyReqwestAsyncRequester};
+    /// use std::env;
+    /// use reqwest::Client;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let client = Client::new();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// // Await this within a Tokio 1 runtime.
+    /// let forecast = client.get_forecast(&token, location);
+    /// #     drop(forecast);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Forecast`]: ../../../models/struct.Forecast.html
+    fn get_forecast<L: IntoLocation>(
+        &self,
+        token: &str,
+        location: L,
+    ) -> ForecastFuture<Self::Future> {
+        DarkskyRequester::get_forecast(self, token, location)
+    }
+
+    /// Retrieve a [`Forecast`] for the given location, setting options where
+    /// needed. For a full list of options, refer to the documentation for the
+    /// [`Options`] builder.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve an extended forecast, excluding the
+    /// [minutely block][`Block::Minutely`], taking a token from the
+    /// environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate reqwest;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::{Block, Coordinate, DarkskyReqwestAsyncRequester};
+    /// use std::env;
+    /// use reqwest::Client;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let client = Client::new();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// let forecast = client.get_forecast_with_options(&token, location, |o| o
+    ///     .exclude(vec![Block::Minutely]));
+    /// #     drop(forecast);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Block::Minutely`]: ../../../enum.Block.html#variant.Minutely
+    /// [`Forecast`]: ../../../models/struct.Forecast.html
+    /// [`Options`]: ../../../struct.Options.html
+    fn get_forecast_with_options<F, L>(
+        &self,
+        token: &str,
+        location: L,
+        options: F,
+    ) -> ForecastFuture<Self::Future>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        DarkskyRequester::get_forecast_with_options(self, token, location, options)
+    }
+
+    /// Sets the time to request a forecast for by using DarkSky's Time Machine
+    /// API.
+    ///
+    /// Refer to [`DarkskyReqwestRequester::get_forecast_time_machine`] for
+    /// the accepted time formats. The time is not validated.
+    ///
+    /// [`DarkskyReqwestRequester::get_forecast_time_machine`]:
+    ///   ../blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_time_machine
+    fn get_forecast_time_machine<D, F, L>(
+        &self,
+        token: &str,
+        location: L,
+        time: D,
+        options: F,
+    ) -> ForecastFuture<Self::Future>
+    where
+        D: Display,
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        DarkskyRequester::get_forecast_time_machine(self, token, location, time, options)
+    }
+}
+
+impl<S: Backend<Client = Client> + ?Sized> DarkskyReqwestAsyncRequester for S {}
+
+impl Backend for Client {
+    type Client = Client;
+}
+
+impl HttpSend for Client {
+    type Future = BodyFuture;
+
+    fn send(&self, uri: String) -> BodyFuture {
+        send(self.get(&uri), None)
+    }
+
+    fn send_with(&self, uri: String, config: &Config) -> BodyFuture {
+        send(configure(self.get(&uri), Some(config)), None)
+    }
+
+    fn send_recording(&self, uri: String, config: Option<&Config>, slot: &MetaSlot) -> BodyFuture {
+        send(configure(self.get(&uri), config), Some(slot))
+    }
+}
+
+/// Sends a request, recording the metadata of the response into the slot if
+/// one is given.
+fn send(request: RequestBuilder, slot: Option<&MetaSlot>) -> BodyFuture {
+    Box::pin(Fetch {
+        send: Box::pin(request.send()),
+        body: None,
+        slot: slot.cloned(),
+    })
+}
+
+/// Applies the timeout and user agent of a configuration to a request, if
+/// one is given, overriding the client's own.
+fn configure(mut request: RequestBuilder, config: Option<&Config>) -> RequestBuilder {
+    let config = match config {
+        Some(config) => config,
+        None => return request,
+    };
+
+    if let Some(timeout) = config.get_timeout() {
+        request = request.timeout(timeout);
+    }
+
+    if let Some(user_agent) = config.get_user_agent() {
+        request = request.header(USER_AGENT, user_agent);
+    }
+
+    request
+}
+
+/// A future sending a request, then reading its response body.
+struct Fetch {
+    send: Pin<Box<dyn Future<Output = ReqwestResult<Response>> + Send>>,
+    body: Option<BodyFuture>,
+    slot: Option<MetaSlot>,
+}
+
+impl Future for Fetch {
+    type Output = Result<Vec<u8>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Vec<u8>>> {
+        let this = &mut *self;
+
+        if this.body.is_none() {
+            match this.send.as_mut().poll(cx) {
+                Poll::Ready(Ok(res)) => {
+                    if let Some(ref slot) = this.slot {
+                        slot.record(ResponseMeta::from_headers(res.status().as_u16(), |name| {
+                            res.headers()
+                                .get(name)
+                                .and_then(|value| value.to_str().ok())
+                        }));
+                    }
+
+                    this.body = Some(Box::pin(Body(Box::pin(res.bytes()))));
+                }
+                Poll::Ready(Err(why)) => return Poll::Ready(Err(why.into())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        match this.body {
+            Some(ref mut body) => body.as_mut().poll(cx),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// A future reading a response body, mapping its error.
+struct Body<F>(Pin<Box<F>>);
+
+impl<F, B> Future for Body<F>
+where
+    F: Future<Output = ReqwestResult<B>>,
+    B: AsRef<[u8]>,
+{
+    type Output = Result<Vec<u8>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Vec<u8>>> {
+        self.0
+            .as_mut()
+            .poll(cx)
+            .map(|body| body.map(|body| body.as_ref().to_vec()).map_err(From::from))
+    }
+}
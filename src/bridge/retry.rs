@@ -0,0 +1,493 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Retrying requests over an [`HttpSend`] backend after transient failures.
+//!
+//! A [`Retrying`] backend wraps another, sending a request again when it
+//! fails with a [transient error][`Error::is_transient`], such as a reset
+//! connection or a timeout, or when the API, or a proxy in front of it,
+//! answers with a server error or too many requests. The delay between
+//! attempts grows exponentially, with random jitter so that many clients
+//! failing at once don't retry in lockstep, and the number of attempts is
+//! capped by a [`RetryPolicy`].
+//!
+//! Errors which would fail again, such as a bad request or an exhausted
+//! token, are returned at once.
+//!
+//! As a `Retrying` backend is itself an `HttpSend` backend, it works with
+//! every bridge, and can be wrapped in or wrap a [`Layered`] backend.
+//!
+//! # Examples
+//!
+//! Retry each request up to 5 times, starting from a delay of half a second:
+//!
+//! ```rust
+//! extern crate darksky;
+//!
+//! use darksky::bridge::retry::{RetryPolicy, Retrying};
+//! use std::time::Duration;
+//! # let backend = ();
+//!
+//! let policy = RetryPolicy::new()
+//!     .max_attempts(5)
+//!     .base_delay(Duration::from_millis(500));
+//! let client = Retrying::new(backend).policy(policy);
+//! # drop(client);
+//! ```
+//!
+//! [`Error::is_transient`]: ../../enum.Error.html#method.is_transient
+//! [`HttpSend`]: ../send/trait.HttpSend.html
+//! [`Layered`]: ../middleware/struct.Layered.html
+//! [`RetryPolicy`]: struct.RetryPolicy.html
+//! [`Retrying`]: struct.Retrying.html
+
+use bridge::pool::BufferPool;
+use bridge::send::{Backend, HttpSend, MetaSlot};
+use constants::Config;
+use std::cmp;
+use std::collections::hash_map::RandomState;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+use std::time::Duration;
+use {internal, utils, Options, Result};
+
+/// A future completing once a delay between attempts has passed.
+pub type Delay = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// How many times a request is attempted, and how long to wait between
+/// attempts.
+///
+/// The delay before the `n`th retry is `base_delay * 2^(n - 1)`, capped at
+/// `max_delay`. With jitter, a random delay between half of that and all of
+/// it is used instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::bridge::retry::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .base_delay(Duration::from_secs(1))
+///     .max_delay(Duration::from_secs(5))
+///     .jitter(false);
+///
+/// assert_eq!(policy.delay(1), Duration::from_secs(1));
+/// assert_eq!(policy.delay(2), Duration::from_secs(2));
+/// assert_eq!(policy.delay(3), Duration::from_secs(4));
+/// assert_eq!(policy.delay(4), Duration::from_secs(5));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RetryPolicy {
+    base_delay: Duration,
+    jitter: bool,
+    max_attempts: u32,
+    max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a policy attempting a request 3 times, starting from a delay
+    /// of 200 milliseconds, capped at 10 seconds, with jitter.
+    pub fn new() -> Self {
+        RetryPolicy {
+            base_delay: Duration::from_millis(200),
+            jitter: true,
+            max_attempts: 3,
+            max_delay: Duration::from_secs(10),
+        }
+    }
+
+    /// Sets the delay before the first retry, which is doubled after each
+    /// subsequent attempt.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+
+        self
+    }
+
+    /// Sets whether delays are randomized.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+
+        self
+    }
+
+    /// Sets the number of times a request is attempted, including the first.
+    ///
+    /// A value of 0 or 1 disables retries.
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+
+        self
+    }
+
+    /// Sets the longest delay between attempts.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+
+        self
+    }
+
+    /// Returns the delay before the first retry.
+    pub fn get_base_delay(&self) -> Duration {
+        self.base_delay
+    }
+
+    /// Returns whether delays are randomized.
+    pub fn get_jitter(&self) -> bool {
+        self.jitter
+    }
+
+    /// Returns the number of times a request is attempted.
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the longest delay between attempts.
+    pub fn get_max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    /// Returns the delay before the given retry, counting from 1.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let factor = 1u32.checked_shl(retry.saturating_sub(1)).unwrap_or(0);
+        let delay = match self.base_delay.checked_mul(factor) {
+            Some(delay) if factor != 0 => cmp::min(delay, self.max_delay),
+            _ => self.max_delay,
+        };
+
+        if !self.jitter {
+            return delay;
+        }
+
+        let half = delay / 2;
+        let spread = (delay - half).as_nanos() as u64;
+
+        match spread {
+            0 => delay,
+            spread => half + Duration::from_nanos(random() % (spread + 1)),
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A backend sending requests over another, retrying those which fail with
+/// a transient error according to a [`RetryPolicy`].
+///
+/// By default, delays are waited out on a separate thread, so that they
+/// work on any executor. An executor's own timer can be used instead with
+/// [`sleep`].
+///
+/// [`RetryPolicy`]: struct.RetryPolicy.html
+/// [`sleep`]: #method.sleep
+#[derive(Clone)]
+pub struct Retrying<S> {
+    inner: Arc<S>,
+    policy: RetryPolicy,
+    sleep: Arc<dyn Fn(Duration) -> Delay + Send + Sync>,
+}
+
+impl<S> Retrying<S> {
+    /// Wraps a backend, with the default [`RetryPolicy`].
+    ///
+    /// [`RetryPolicy`]: struct.RetryPolicy.html
+    pub fn new(inner: S) -> Self {
+        Retrying {
+            inner: Arc::new(inner),
+            policy: RetryPolicy::new(),
+            sleep: Arc::new(thread_sleep),
+        }
+    }
+
+    /// Sets the policy deciding how many times, and how often, requests are
+    /// attempted.
+    pub fn policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+
+        self
+    }
+
+    /// Sets the function creating the future waiting out each delay, such as
+    /// `tokio::time::sleep`.
+    pub fn sleep<F>(mut self, sleep: F) -> Self
+    where
+        F: Fn(Duration) -> Delay + Send + Sync + 'static,
+    {
+        self.sleep = Arc::new(sleep);
+
+        self
+    }
+
+    /// Returns the policy requests are attempted with.
+    pub fn get_policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    /// Returns a reference to the wrapped backend.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S: HttpSend> Retrying<S> {
+    /// Sends the first attempt of a request, reading its body into the given
+    /// buffer if there is one.
+    fn dispatch(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: MetaSlot,
+        buffer: Option<Vec<u8>>,
+    ) -> RetryFuture<S> {
+        let first = match buffer {
+            Some(buffer) => self
+                .inner
+                .send_into(uri.clone(), config, Some(&slot), buffer),
+            None => self.inner.send_recording(uri.clone(), config, &slot),
+        };
+
+        RetryFuture {
+            attempt: 1,
+            config: config.cloned(),
+            inner: Arc::clone(&self.inner),
+            policy: self.policy,
+            sleep: Arc::clone(&self.sleep),
+            slot,
+            state: State::Sending(first),
+            uri,
+        }
+    }
+}
+
+impl<S: Debug> Debug for Retrying<S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Retrying")
+            .field("inner", &self.inner)
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl<S: HttpSend> HttpSend for Retrying<S> {
+    type Future = RetryFuture<S>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        self.dispatch(uri, None, MetaSlot::new(), None)
+    }
+
+    fn send_with(&self, uri: String, config: &Config) -> Self::Future {
+        self.dispatch(uri, Some(config), MetaSlot::new(), None)
+    }
+
+    fn send_recording(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: &MetaSlot,
+    ) -> Self::Future {
+        self.dispatch(uri, config, slot.clone(), None)
+    }
+
+    fn send_into(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: Option<&MetaSlot>,
+        buffer: Vec<u8>,
+    ) -> Self::Future {
+        let slot = slot.cloned().unwrap_or_default();
+
+        self.dispatch(uri, config, slot, Some(buffer))
+    }
+
+    fn uri_config(&self) -> Option<&Config> {
+        self.inner.uri_config()
+    }
+
+    fn buffer_pool(&self) -> Option<&BufferPool> {
+        self.inner.buffer_pool()
+    }
+
+    fn base_options(&self) -> Option<&Options> {
+        self.inner.base_options()
+    }
+}
+
+impl<S: Backend> Backend for Retrying<S> {
+    type Client = S::Client;
+}
+
+/// A future resolving to the body of a response once a request has
+/// succeeded, failed permanently, or run out of attempts, returned by
+/// [`Retrying`] backends.
+///
+/// [`Retrying`]: struct.Retrying.html
+#[must_use = "futures do nothing unless polled"]
+pub struct RetryFuture<S: HttpSend> {
+    attempt: u32,
+    config: Option<Config>,
+    inner: Arc<S>,
+    policy: RetryPolicy,
+    sleep: Arc<dyn Fn(Duration) -> Delay + Send + Sync>,
+    slot: MetaSlot,
+    state: State<S::Future>,
+    uri: String,
+}
+
+enum State<F> {
+    Sending(F),
+    Waiting(Delay),
+}
+
+impl<S: HttpSend> Debug for RetryFuture<S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("RetryFuture")
+            .field("attempt", &self.attempt)
+            .field("uri", &utils::redact(&self.uri))
+            .finish()
+    }
+}
+
+impl<S: HttpSend> Future for RetryFuture<S> {
+    type Output = Result<Vec<u8>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Vec<u8>>> {
+        let this = &mut *self;
+
+        loop {
+            let next = match this.state {
+                State::Sending(ref mut future) => {
+                    let response = match Pin::new(future).poll(cx) {
+                        Poll::Ready(response) => response,
+                        Poll::Pending => return Poll::Pending,
+                    };
+
+                    if this.attempt >= this.policy.max_attempts
+                        || !internal::is_transient_failure(&response, this.slot.get().status)
+                    {
+                        return Poll::Ready(response);
+                    }
+
+                    State::Waiting((this.sleep)(this.policy.delay(this.attempt)))
+                }
+                State::Waiting(ref mut delay) => {
+                    if delay.as_mut().poll(cx).is_pending() {
+                        return Poll::Pending;
+                    }
+
+                    this.attempt += 1;
+
+                    State::Sending(this.inner.send_recording(
+                        this.uri.clone(),
+                        this.config.as_ref(),
+                        &this.slot,
+                    ))
+                }
+            };
+
+            this.state = next;
+        }
+    }
+}
+
+/// Returns a random number, from the randomly seeded hasher of the standard
+/// library, which is enough to spread out retries.
+fn random() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+/// Creates a delay waited out on a separate thread, for use on any
+/// executor.
+pub(crate) fn thread_sleep(duration: Duration) -> Delay {
+    Box::pin(ThreadDelay::new(duration))
+}
+
+/// A delay waited out by sleeping on a thread spawned when it is first
+/// polled.
+struct ThreadDelay {
+    duration: Duration,
+    shared: Option<Arc<Mutex<Elapsed>>>,
+}
+
+/// The state shared between a [`ThreadDelay`] and its thread.
+///
+/// [`ThreadDelay`]: struct.ThreadDelay.html
+#[derive(Default)]
+struct Elapsed {
+    done: bool,
+    waker: Option<Waker>,
+}
+
+impl ThreadDelay {
+    fn new(duration: Duration) -> Self {
+        ThreadDelay {
+            duration,
+            shared: None,
+        }
+    }
+}
+
+impl Future for ThreadDelay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        if self.duration == Duration::from_secs(0) {
+            return Poll::Ready(());
+        }
+
+        let duration = self.duration;
+        let shared = self.shared.get_or_insert_with(|| {
+            let shared = Arc::new(Mutex::new(Elapsed::default()));
+            let thread_shared = Arc::clone(&shared);
+
+            thread::spawn(move || {
+                thread::sleep(duration);
+
+                let mut state = thread_shared
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                state.done = true;
+
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            });
+
+            shared
+        });
+
+        let mut state = shared
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if state.done {
+            Poll::Ready(())
+        } else {
+            state.waker = Some(cx.waker().clone());
+
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,942 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! A single requester shared by all HTTP backends.
+//!
+//! Rather than implementing the full set of DarkSky routes, a backend only
+//! needs to implement [`HttpSend`], sending a `GET` request and resolving to
+//! the response body. [`DarkskyRequester`] is then implemented for it,
+//! building request URIs and decoding forecasts in one place.
+//!
+//! `HttpSend` is implemented for the `hyper`, `surf`, and `awc` clients, and
+//! for the blocking `reqwest`, `ureq`, `attohttpc`, and `minreq` clients,
+//! when their features are enabled. Blocking backends perform the request
+//! when it is made, returning an already resolved future, which
+//! [`block_on`] unwraps.
+//!
+//! Any backend can be wrapped in a [`Layered`] backend, running
+//! [middleware] before each request is sent and after its response is
+//! received.
+//!
+//! # Examples
+//!
+//! Implement a stub backend returning a canned response, such as for tests:
+//!
+//! ```rust
+//! extern crate darksky;
+//!
+//! use darksky::bridge::{DarkskyRequester, HttpSend};
+//! use darksky::{Coordinate, Result};
+//! use std::future::{self, Future, Ready};
+//! use std::pin::Pin;
+//! use std::task::{Context, Poll, Waker};
+//!
+//! struct Stub;
+//!
+//! impl HttpSend for Stub {
+//!     type Future = Ready<Result<Vec<u8>>>;
+//!
+//!     fn send(&self, uri: String) -> Self::Future {
+//!         assert!(uri.starts_with("https://api.darksky.net/forecast/token/"));
+//!
+//!         let body = br#"{"latitude": 49.9, "longitude": -97.1, "timezone": "America/Winnipeg"}"#;
+//!
+//!         future::ready(Ok(body.to_vec()))
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let mut req = Stub.get_forecast("token", Coordinate::new(49.9, -97.1));
+//!
+//! // Stub responses are ready immediately, so they can be polled once.
+//! let mut cx = Context::from_waker(Waker::noop());
+//! match Pin::new(&mut req).poll(&mut cx) {
+//!     Poll::Ready(forecast) => assert_eq!(forecast.unwrap().timezone, "America/Winnipeg"),
+//!     Poll::Pending => unreachable!(),
+//! }
+//! # }
+//! ```
+//!
+//! [`DarkskyRequester`]: trait.DarkskyRequester.html
+//! [`HttpSend`]: trait.HttpSend.html
+//! [`block_on`]: fn.block_on.html
+//! [`Layered`]: ../middleware/struct.Layered.html
+//! [middleware]: ../middleware/index.html
+
+use super::pool::BufferPool;
+#[cfg(any(
+    feature = "curl",
+    feature = "hyper",
+    feature = "reqwest-async",
+    feature = "reqwest-blocking",
+    feature = "ureq"
+))]
+use cache::CACHE_CONTROL_HEADER;
+use cache::Freshness;
+#[cfg(any(
+    feature = "curl",
+    feature = "hyper",
+    feature = "reqwest-async",
+    feature = "reqwest-blocking",
+    feature = "ureq"
+))]
+use constants::API_CALLS_HEADER;
+use constants::Config;
+use health::{self, Health};
+use internal::RawSummaries;
+use models::{Forecast, MultilingualForecast, Summaries};
+use quota::CallCount;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::result::Result as StdResult;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::{Duration, Instant};
+use {
+    internal, utils, Coordinate, Error, IntoLocation, Language, Options, Result, TokenProvider,
+    Unit,
+};
+
+/// A backend able to send a `GET` request, resolving to the response body.
+pub trait HttpSend {
+    /// The future resolving to the body of a response.
+    type Future: Future<Output = Result<Vec<u8>>> + Unpin;
+
+    /// Sends a `GET` request to the given URI.
+    fn send(&self, uri: String) -> Self::Future;
+
+    /// Sends a `GET` request to the given URI, with the timeout and user
+    /// agent of a [`Config`], as used by a [`DarkskyClient`].
+    ///
+    /// These are applied by the `hyper`, blocking `reqwest`, and `ureq`
+    /// backends. By default, the configuration is ignored and the request is
+    /// sent with [`send`].
+    ///
+    /// [`Config`]: ../../constants/struct.Config.html
+    /// [`DarkskyClient`]: ../../facade/struct.DarkskyClient.html
+    /// [`send`]: #tymethod.send
+    fn send_with(&self, uri: String, _config: &Config) -> Self::Future {
+        self.send(uri)
+    }
+
+    /// Sends a `GET` request to the given URI, with the configuration if one
+    /// is given, recording the [`ResponseMeta`] of the response into the
+    /// slot once it is received.
+    ///
+    /// This is implemented by the `hyper`, blocking `reqwest`, and `ureq`
+    /// backends. By default, nothing is recorded and the request is sent
+    /// with [`send_with`] or [`send`].
+    ///
+    /// [`ResponseMeta`]: struct.ResponseMeta.html
+    /// [`send`]: #tymethod.send
+    /// [`send_with`]: #method.send_with
+    fn send_recording(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        _slot: &MetaSlot,
+    ) -> Self::Future {
+        match config {
+            Some(config) => self.send_with(uri, config),
+            None => self.send(uri),
+        }
+    }
+
+    /// Returns the configuration that [`DarkskyRequester`] methods build the
+    /// URIs of requests with, such as to snap their coordinates to a
+    /// [grid].
+    ///
+    /// This is set by the layers of a [`Layered`] backend. By default, there
+    /// is none, and URIs are built as they are given.
+    ///
+    /// [`DarkskyRequester`]: trait.DarkskyRequester.html
+    /// [`Layered`]: ../middleware/struct.Layered.html
+    /// [grid]: ../../constants/struct.Config.html#method.grid
+    fn uri_config(&self) -> Option<&Config> {
+        None
+    }
+
+    /// Sends a `GET` request to the given URI, as with [`send_recording`],
+    /// reading the response body into the given buffer.
+    ///
+    /// This is implemented by the `hyper` and blocking `reqwest` backends,
+    /// and used by a [`PooledClient`] to reuse the buffers of its pool. By
+    /// default, the buffer is dropped and the request is sent with
+    /// [`send_recording`], [`send_with`], or [`send`].
+    ///
+    /// [`PooledClient`]: ../pool/struct.PooledClient.html
+    /// [`send`]: #tymethod.send
+    /// [`send_recording`]: #method.send_recording
+    /// [`send_with`]: #method.send_with
+    fn send_into(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: Option<&MetaSlot>,
+        _buffer: Vec<u8>,
+    ) -> Self::Future {
+        match (slot, config) {
+            (Some(slot), config) => self.send_recording(uri, config, slot),
+            (None, Some(config)) => self.send_with(uri, config),
+            (None, None) => self.send(uri),
+        }
+    }
+
+    /// Returns the pool that response bodies are returned to once decoded by
+    /// [`DarkskyRequester`] methods.
+    ///
+    /// This is set by a [`PooledClient`]. By default, there is none, and
+    /// response bodies are dropped.
+    ///
+    /// [`DarkskyRequester`]: trait.DarkskyRequester.html
+    /// [`PooledClient`]: ../pool/struct.PooledClient.html
+    fn buffer_pool(&self) -> Option<&BufferPool> {
+        None
+    }
+
+    /// Returns the options that [`DarkskyRequester`] methods apply the
+    /// options of each request on top of.
+    ///
+    /// This is set by a [`ConfiguredClient`]. By default, there are none.
+    ///
+    /// [`ConfiguredClient`]: ../configured/struct.ConfiguredClient.html
+    /// [`DarkskyRequester`]: trait.DarkskyRequester.html
+    fn base_options(&self) -> Option<&Options> {
+        None
+    }
+}
+
+/// An [`HttpSend`] backend sending its requests through the client of a
+/// bridged library, either directly or through backends wrapping it, such
+/// as a [`Layered`] or [`PooledClient`] backend.
+///
+/// The requester trait of each bridge, such as [`DarkskyUreqRequester`], is
+/// implemented for every backend of its client, so that its methods run the
+/// layers of the backends wrapping it.
+///
+/// [`DarkskyUreqRequester`]: ../ureq/trait.DarkskyUreqRequester.html
+/// [`HttpSend`]: trait.HttpSend.html
+/// [`Layered`]: ../middleware/struct.Layered.html
+/// [`PooledClient`]: ../pool/struct.PooledClient.html
+pub trait Backend: HttpSend {
+    /// The client of the bridged library that requests are sent through.
+    type Client;
+}
+
+/// The metadata of a response, read from its headers by the backends
+/// implementing [`HttpSend::send_recording`].
+///
+/// [`HttpSend::send_recording`]: trait.HttpSend.html#method.send_recording
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResponseMeta {
+    /// The number of API calls made with the token on the current UTC day,
+    /// from the `X-Forecast-API-Calls` header.
+    pub api_calls: Option<u64>,
+    /// How long the response is fresh for, from its `Cache-Control` header.
+    pub freshness: Option<Freshness>,
+    /// The age of a stale response served in place of an error, such as by
+    /// a [`Cached`] layer.
+    ///
+    /// [`Cached`]: ../../cache/struct.Cached.html
+    pub stale: Option<Duration>,
+    /// The status code of the response.
+    pub status: Option<u16>,
+}
+
+impl ResponseMeta {
+    /// Reads the metadata of a response received now, with its status code,
+    /// looking up its headers by name.
+    #[cfg(any(
+    feature = "curl",
+    feature = "hyper",
+    feature = "reqwest-async",
+    feature = "reqwest-blocking",
+    feature = "ureq"
+))]
+    pub(crate) fn from_headers<'a, F>(status: u16, header: F) -> Self
+    where
+        F: Fn(&str) -> Option<&'a str>,
+    {
+        ResponseMeta {
+            api_calls: header(API_CALLS_HEADER).and_then(|calls| calls.trim().parse().ok()),
+            freshness: header(CACHE_CONTROL_HEADER).and_then(Freshness::from_cache_control),
+            stale: None,
+            status: Some(status),
+        }
+    }
+}
+
+/// A slot shared with a backend, into which it records the [`ResponseMeta`]
+/// of a response once received.
+///
+/// [`ResponseMeta`]: struct.ResponseMeta.html
+#[derive(Clone, Debug, Default)]
+pub struct MetaSlot(Arc<Mutex<ResponseMeta>>);
+
+impl MetaSlot {
+    /// Creates an empty slot.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the recorded metadata, which is empty until a response has
+    /// been received.
+    pub fn get(&self) -> ResponseMeta {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Records the metadata of a response, replacing any recorded so far.
+    pub fn record(&self, meta: ResponseMeta) {
+        *self.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = meta;
+    }
+}
+
+/// The trait for requesting DarkSky routes over any [`HttpSend`] backend.
+///
+/// This is implemented for every `HttpSend` implementation. Requests are
+/// made with the [default options] of the backend, if it has any, and the
+/// URIs of requests are built with its [configuration].
+///
+/// [`HttpSend`]: trait.HttpSend.html
+/// [configuration]: trait.HttpSend.html#method.uri_config
+/// [default options]: trait.HttpSend.html#method.base_options
+pub trait DarkskyRequester: HttpSend {
+    /// Retrieve a [`Forecast`] for the given location.
+    ///
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    fn get_forecast<L: IntoLocation>(
+        &self,
+        token: &str,
+        location: L,
+    ) -> ForecastFuture<Self::Future> {
+        if self.base_options().is_some() {
+            return self.get_forecast_with_options(token, location, |mut options| {
+                options.unit.get_or_insert(Unit::Auto);
+
+                options
+            });
+        }
+
+        let uri = match self.uri_config() {
+            Some(config) => utils::configured_uri(config, token, location, None),
+            None => utils::checked_uri(token, location),
+        };
+
+        ForecastFuture::new(self, uri)
+    }
+
+    /// Retrieve a [`Forecast`] for the given location, setting options where
+    /// needed. For a full list of options, refer to the documentation for the
+    /// [`Options`] builder.
+    ///
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    /// [`Options`]: ../../struct.Options.html
+    fn get_forecast_with_options<F, L>(
+        &self,
+        token: &str,
+        location: L,
+        options: F,
+    ) -> ForecastFuture<Self::Future>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        let options = options(self.base_options().cloned().unwrap_or_default());
+        let uri = match self.uri_config() {
+            Some(config) => utils::configured_uri(config, token, location, Some(options)),
+            None => utils::located_uri(token, location, options),
+        };
+
+        ForecastFuture::new(self, uri)
+    }
+
+    /// Sets the time to request a forecast for by using DarkSky's Time Machine
+    /// API.
+    ///
+    /// This is equivalent to [`get_forecast_with_options`] with
+    /// [`Options::time`], which takes the accepted time formats. The time is
+    /// not validated.
+    ///
+    /// [`Options::time`]: ../../struct.Options.html#method.time
+    /// [`get_forecast_with_options`]: #method.get_forecast_with_options
+    fn get_forecast_time_machine<D, F, L>(
+        &self,
+        token: &str,
+        location: L,
+        time: D,
+        options: F,
+    ) -> ForecastFuture<Self::Future>
+    where
+        D: Display,
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        self.get_forecast_with_options(token, location, |opt| options(opt).time(time))
+    }
+
+    /// Retrieve a [forecast][`Forecast`] for the given location along with its
+    /// [summaries][`Summaries`] in each of the given [`Language`]s, setting
+    /// options where needed.
+    ///
+    /// A request is made for each language, which are in flight at once over
+    /// asynchronous backends. Only the response for the first language is
+    /// fully parsed into a forecast; the responses for the other languages
+    /// are only parsed for their summaries. If no languages are given, then
+    /// the API's default language is used and no summaries are returned.
+    ///
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    /// [`Language`]: ../../enum.Language.html
+    /// [`Summaries`]: ../../models/struct.Summaries.html
+    fn get_forecast_multilingual<F, L>(
+        &self,
+        token: &str,
+        location: L,
+        languages: &[Language],
+        options: F,
+    ) -> MultilingualFuture<Self::Future>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        let primary = languages.first().cloned();
+        let location = match utils::checked_location(location) {
+            Ok(location) => location,
+            Err(why) => {
+                return MultilingualFuture::new(primary, ForecastFuture::new(self, Err(why)))
+            }
+        };
+        let options = options(self.base_options().cloned().unwrap_or_default());
+        let default = Config::default();
+        let config = self.uri_config().unwrap_or(&default);
+        let uri = |language| utils::language_uri(config, token, location, &options, language);
+
+        let mut future =
+            MultilingualFuture::new(primary.clone(), ForecastFuture::new(self, uri(primary)));
+        future.others = languages
+            .iter()
+            .skip(1)
+            .cloned()
+            .map(|language| {
+                let summaries = ForecastFuture::new(self, uri(Some(language.clone())));
+
+                (language, summaries, None)
+            })
+            .collect();
+
+        future
+    }
+
+    /// Checks the health of the API with a cheap request, for use in
+    /// readiness probes.
+    ///
+    /// Failing to receive a response is reported as an unreachable
+    /// [`Health`] rather than an error. The status of the response is read
+    /// from its [`ResponseMeta`] where the backend records it, and otherwise
+    /// from the error in its body, if any.
+    ///
+    /// [`Health`]: ../../health/struct.Health.html
+    /// [`ResponseMeta`]: struct.ResponseMeta.html
+    fn check_status(&self, token: &str) -> HealthFuture<Self::Future> {
+        let uri = match self.uri_config() {
+            Some(config) => health::uri(config, token),
+            None => health::uri(&Config::default(), token),
+        };
+        let slot = MetaSlot::new();
+
+        HealthFuture {
+            body: uri
+                .map(|uri| self.send_recording(uri, None, &slot))
+                .map_err(Some),
+            slot,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl<S: HttpSend + ?Sized> DarkskyRequester for S {}
+
+/// Blocks the current thread until a future resolves, such as one returned
+/// by [`DarkskyRequester`] methods over a blocking backend.
+///
+/// Blocking backends make each request as it is sent, so their futures are
+/// ready when first polled. Other futures, such as those of a [`Retrying`]
+/// backend waiting out a delay, park the thread until they are woken.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::bridge::send;
+/// use std::future;
+///
+/// assert_eq!(send::block_on(future::ready(7)), 7);
+/// ```
+///
+/// [`DarkskyRequester`]: trait.DarkskyRequester.html
+/// [`Retrying`]: ../retry/struct.Retrying.html
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+
+        thread::park();
+    }
+}
+
+/// A boxed future resolving to a forecast, returned by
+/// [`DynDarkskyRequester`] methods.
+///
+/// [`DynDarkskyRequester`]: trait.DynDarkskyRequester.html
+pub type BoxForecastFuture = Pin<Box<dyn Future<Output = Result<Forecast>> + Send>>;
+
+/// An object-safe variant of [`DarkskyRequester`], so that a requester can be
+/// stored as a `Box<dyn DynDarkskyRequester>`, such as to use a real client in
+/// production and a stub in tests.
+///
+/// Locations are taken as a [`Coordinate`] and options directly rather than
+/// built by a closure, and returned futures are boxed.
+///
+/// This is implemented for every [`HttpSend`] implementation that is `Send`
+/// and `Sync`, with a `Send` future. As its methods share names with those of
+/// `DarkskyRequester`, only one of the two traits should be imported where
+/// both apply.
+///
+/// # Examples
+///
+/// Choose a requester at runtime:
+///
+#[cfg_attr(feature = "hyper", doc = "```rust,no_run")]
+#[cfg_attr(not(feature = "hyper"), doc = "```rust,ignore")]
+/// extern crate darksky;
+/// extern crate hyper;
+///
+/// use darksky::bridge::DynDarkskyRequester;
+/// use darksky::{Block, Coordinate, Options};
+/// use hyper::Client;
+///
+/// # fn main() {
+/// let requester: Box<dyn DynDarkskyRequester> = Box::new(Client::new());
+///
+/// let location = Coordinate::new(37.8267, -122.423);
+/// let options = Options::default().exclude(vec![Block::Minutely]);
+/// let forecast = requester.get_forecast_with_options("token", location, options);
+/// #     drop(forecast);
+/// # }
+/// ```
+///
+/// [`Coordinate`]: ../../location/struct.Coordinate.html
+/// [`DarkskyRequester`]: trait.DarkskyRequester.html
+/// [`HttpSend`]: trait.HttpSend.html
+pub trait DynDarkskyRequester: Send + Sync {
+    /// Retrieve a [`Forecast`] for the given location.
+    ///
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    fn get_forecast(&self, token: &str, location: Coordinate) -> BoxForecastFuture;
+
+    /// Retrieve a [`Forecast`] for the given location, with the given
+    /// [`Options`].
+    ///
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    /// [`Options`]: ../../struct.Options.html
+    fn get_forecast_with_options(
+        &self,
+        token: &str,
+        location: Coordinate,
+        options: Options,
+    ) -> BoxForecastFuture;
+
+    /// Retrieve a [`Forecast`] for the given time by using DarkSky's Time
+    /// Machine API, with the given [`Options`].
+    ///
+    /// Refer to [`DarkskyReqwestRequester::get_forecast_time_machine`] for
+    /// the accepted time formats. The time is not validated.
+    ///
+    /// [`DarkskyReqwestRequester::get_forecast_time_machine`]:
+    ///   ../reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_time_machine
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    /// [`Options`]: ../../struct.Options.html
+    fn get_forecast_time_machine(
+        &self,
+        token: &str,
+        location: Coordinate,
+        time: &str,
+        options: Options,
+    ) -> BoxForecastFuture;
+}
+
+impl<S> DynDarkskyRequester for S
+where
+    S: HttpSend + Send + Sync,
+    S::Future: Send + 'static,
+{
+    fn get_forecast(&self, token: &str, location: Coordinate) -> BoxForecastFuture {
+        Box::pin(DarkskyRequester::get_forecast(self, token, location))
+    }
+
+    fn get_forecast_with_options(
+        &self,
+        token: &str,
+        location: Coordinate,
+        options: Options,
+    ) -> BoxForecastFuture {
+        Box::pin(DarkskyRequester::get_forecast_with_options(
+            self,
+            token,
+            location,
+            |_| options,
+        ))
+    }
+
+    fn get_forecast_time_machine(
+        &self,
+        token: &str,
+        location: Coordinate,
+        time: &str,
+        options: Options,
+    ) -> BoxForecastFuture {
+        Box::pin(DarkskyRequester::get_forecast_time_machine(
+            self,
+            token,
+            location,
+            time,
+            |_| options,
+        ))
+    }
+}
+
+/// A future resolving to a forecast, returned by [`DarkskyRequester`]
+/// methods.
+///
+/// [`DarkskyRequester`]: trait.DarkskyRequester.html
+#[must_use = "futures do nothing unless polled"]
+pub struct ForecastFuture<F, T = Forecast> {
+    body: Option<F>,
+    calls: Option<(MetaSlot, Arc<CallCount>)>,
+    error: Option<Error>,
+    marker: PhantomData<fn() -> T>,
+    pool: Option<BufferPool>,
+    report: Option<(Arc<dyn TokenProvider>, String)>,
+}
+
+impl<F, T> ForecastFuture<F, T> {
+    pub(crate) fn new<S>(sender: &S, uri: Result<String>) -> Self
+    where
+        S: HttpSend<Future = F> + ?Sized,
+    {
+        Self::from_body(sender, uri.map(|uri| sender.send(uri)))
+    }
+
+    /// Creates a future sending the request with the timeout and user agent
+    /// of a configuration.
+    pub(crate) fn configured<S>(sender: &S, config: &Config, uri: Result<String>) -> Self
+    where
+        S: HttpSend<Future = F> + ?Sized,
+    {
+        Self::from_body(sender, uri.map(|uri| sender.send_with(uri, config)))
+    }
+
+    /// Creates a future sending the request with the timeout and user agent
+    /// of a configuration, counting the API calls reported by its response.
+    pub(crate) fn counted<S>(
+        sender: &S,
+        config: &Config,
+        uri: Result<String>,
+        calls: &Arc<CallCount>,
+    ) -> Self
+    where
+        S: HttpSend<Future = F> + ?Sized,
+    {
+        let slot = MetaSlot::new();
+        let body = uri.map(|uri| sender.send_recording(uri, Some(config), &slot));
+        let mut future = Self::from_body(sender, body);
+        future.calls = Some((slot, Arc::clone(calls)));
+
+        future
+    }
+
+    /// Reports an error of the request to the provider of its token.
+    pub(crate) fn reporting_to(mut self, provider: &Arc<dyn TokenProvider>, token: &str) -> Self {
+        self.report = Some((Arc::clone(provider), token.to_owned()));
+
+        self
+    }
+
+    /// Replaces the future resolving to the response body, such as to give
+    /// it a timeout.
+    #[cfg(feature = "hyper")]
+    pub(crate) fn map_body<M: FnOnce(F) -> F>(mut self, map: M) -> Self {
+        self.body = self.body.map(map);
+
+        self
+    }
+
+    fn from_body<S>(sender: &S, body: Result<F>) -> Self
+    where
+        S: HttpSend<Future = F> + ?Sized,
+    {
+        let (body, error) = match body {
+            Ok(body) => (Some(body), None),
+            Err(why) => (None, Some(why)),
+        };
+
+        ForecastFuture {
+            body,
+            calls: None,
+            error,
+            marker: PhantomData,
+            pool: sender.buffer_pool().cloned(),
+            report: None,
+        }
+    }
+}
+
+impl<F, T> Debug for ForecastFuture<F, T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("ForecastFuture")
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<F, T> Future for ForecastFuture<F, T>
+where
+    F: Future<Output = Result<Vec<u8>>> + Unpin,
+    T: DeserializeOwned,
+{
+    type Output = Result<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<T>> {
+        let this = &mut *self;
+
+        let poll = match this.body {
+            Some(ref mut body) => Pin::new(body).poll(cx).map(|body| {
+                let body = body?;
+                let result = internal::from_slice(&body);
+
+                if let Some(ref pool) = this.pool {
+                    pool.give(body);
+                }
+
+                result
+            }),
+            None => {
+                let why = this
+                    .error
+                    .take()
+                    .expect("ForecastFuture polled after completion");
+
+                Poll::Ready(Err(why))
+            }
+        };
+
+        if poll.is_ready() {
+            this.body = None;
+
+            if let Some((ref slot, ref calls)) = this.calls {
+                if let Some(api_calls) = slot.get().api_calls {
+                    calls.record(api_calls);
+                }
+            }
+        }
+
+        if let Poll::Ready(Err(ref why)) = poll {
+            if let Some((ref provider, ref token)) = this.report {
+                provider.report_error(token, why);
+            }
+        }
+
+        poll
+    }
+}
+
+/// A future resolving to a forecast along with its summaries in other
+/// languages, returned by [`DarkskyRequester::get_forecast_multilingual`].
+///
+/// [`DarkskyRequester::get_forecast_multilingual`]:
+///   trait.DarkskyRequester.html#method.get_forecast_multilingual
+#[must_use = "futures do nothing unless polled"]
+pub struct MultilingualFuture<F> {
+    decoded: Option<Forecast>,
+    forecast: ForecastFuture<F>,
+    others: Vec<(Language, ForecastFuture<F, RawSummaries>, Option<Summaries>)>,
+    primary: Option<Language>,
+}
+
+impl<F> MultilingualFuture<F> {
+    fn new(primary: Option<Language>, forecast: ForecastFuture<F>) -> Self {
+        MultilingualFuture {
+            decoded: None,
+            forecast,
+            others: Vec::new(),
+            primary,
+        }
+    }
+
+    /// Replaces the futures resolving to each response body, such as to give
+    /// them a timeout.
+    #[cfg(feature = "hyper")]
+    pub(crate) fn map_bodies<M: Fn(F) -> F>(self, map: M) -> Self {
+        MultilingualFuture {
+            forecast: self.forecast.map_body(&map),
+            others: self
+                .others
+                .into_iter()
+                .map(|(language, future, summaries)| (language, future.map_body(&map), summaries))
+                .collect(),
+            ..self
+        }
+    }
+}
+
+impl<F> Debug for MultilingualFuture<F> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("MultilingualFuture")
+            .field("primary", &self.primary)
+            .finish()
+    }
+}
+
+impl<F> Future for MultilingualFuture<F>
+where
+    F: Future<Output = Result<Vec<u8>>> + Unpin,
+{
+    type Output = Result<MultilingualForecast>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<MultilingualForecast>> {
+        let this = &mut *self;
+        let mut pending = false;
+
+        if this.decoded.is_none() {
+            match Pin::new(&mut this.forecast).poll(cx) {
+                Poll::Ready(Ok(forecast)) => this.decoded = Some(forecast),
+                Poll::Ready(Err(why)) => return Poll::Ready(Err(why)),
+                Poll::Pending => pending = true,
+            }
+        }
+
+        for &mut (_, ref mut future, ref mut summaries) in &mut this.others {
+            if summaries.is_some() {
+                continue;
+            }
+
+            match Pin::new(future).poll(cx) {
+                Poll::Ready(Ok(raw)) => *summaries = Some(Summaries::from(raw)),
+                Poll::Ready(Err(why)) => return Poll::Ready(Err(why)),
+                Poll::Pending => pending = true,
+            }
+        }
+
+        if pending {
+            return Poll::Pending;
+        }
+
+        let forecast = this
+            .decoded
+            .take()
+            .expect("MultilingualFuture polled after completion");
+        let mut summaries = HashMap::with_capacity(this.others.len() + 1);
+
+        if let Some(language) = this.primary.take() {
+            summaries.insert(language, Summaries::from(&forecast));
+        }
+
+        summaries.extend(this.others.iter_mut().filter_map(
+            |&mut (ref language, _, ref mut summaries)| {
+                summaries
+                    .take()
+                    .map(|summaries| (language.clone(), summaries))
+            },
+        ));
+
+        Poll::Ready(Ok(MultilingualForecast {
+            forecast,
+            summaries,
+        }))
+    }
+}
+
+/// A future resolving to the health of the API, returned by
+/// [`DarkskyRequester::check_status`].
+///
+/// [`DarkskyRequester::check_status`]:
+///   trait.DarkskyRequester.html#method.check_status
+#[must_use = "futures do nothing unless polled"]
+pub struct HealthFuture<F> {
+    body: StdResult<F, Option<Error>>,
+    slot: MetaSlot,
+    start: Instant,
+}
+
+impl<F> Debug for HealthFuture<F> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("HealthFuture")
+            .field("start", &self.start)
+            .finish()
+    }
+}
+
+impl<F> Future for HealthFuture<F>
+where
+    F: Future<Output = Result<Vec<u8>>> + Unpin,
+{
+    type Output = Result<Health>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Health>> {
+        let this = &mut *self;
+
+        let response = match this.body {
+            Ok(ref mut body) => match Pin::new(body).poll(cx) {
+                Poll::Ready(response) => response,
+                Poll::Pending => return Poll::Pending,
+            },
+            Err(ref mut why) => {
+                let why = why.take().expect("HealthFuture polled after completion");
+
+                return Poll::Ready(Err(why));
+            }
+        };
+        this.body = Err(None);
+
+        let meta = this.slot.get();
+        let latency = this.start.elapsed();
+        let status = match (meta.status, response) {
+            (Some(status), _) => status,
+            (None, Ok(body)) => internal::api_error_code(&body).unwrap_or(200),
+            (None, Err(_)) => return Poll::Ready(Ok(Health::unreachable(latency))),
+        };
+
+        Poll::Ready(Ok(Health::responded(status, meta.api_calls, latency)))
+    }
+}
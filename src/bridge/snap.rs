@@ -0,0 +1,129 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Snapping the coordinates of requests, so that nearby locations share
+//! responses.
+//!
+//! A [`Snap`] is a [`Middleware`] rounding the latitude and longitude of
+//! each forecast request to a grid, such as 3 decimal places, as its URI is
+//! built. Requests made from jittery GPS fixes a few meters apart then
+//! resolve to the same URI, and so to the same [`cache_key`], rather than
+//! each making an API call.
+//!
+//! This applies the same snapping as a [`Config`] with a [grid] does for a
+//! [`DarkskyClient`], to requests made through any [`DarkskyRequester`].
+//!
+//! # Examples
+//!
+//! Round coordinates to 3 decimal places before looking them up in a cache:
+//!
+//! ```rust
+//! extern crate darksky;
+//!
+//! use darksky::bridge::middleware::Layered;
+//! use darksky::bridge::snap::Snap;
+//! use darksky::cache::{Cached, MemoryStore};
+//! # let backend = ();
+//!
+//! let client = Layered::new(backend)
+//!     .layer(Snap::decimals(3))
+//!     .layer(Cached::new(MemoryStore::new(1000)));
+//! # drop(client);
+//! ```
+//!
+//! [`Config`]: ../../constants/struct.Config.html
+//! [`DarkskyClient`]: ../../facade/struct.DarkskyClient.html
+//! [`DarkskyRequester`]: ../send/trait.DarkskyRequester.html
+//! [`Middleware`]: ../middleware/trait.Middleware.html
+//! [`Snap`]: struct.Snap.html
+//! [`cache_key`]: ../../cache/fn.cache_key.html
+//! [grid]: ../../constants/struct.Config.html#method.grid
+
+use bridge::middleware::Middleware;
+use constants::Config;
+use std::mem;
+
+/// A [`Middleware`] snapping the coordinates of forecast requests to a grid.
+///
+/// This sets the [grid] of the configuration that a [`Layered`] backend
+/// builds the URIs of requests with, so every layer sees the snapped
+/// coordinates, wherever it is placed.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::bridge::middleware::Middleware;
+/// use darksky::bridge::snap::Snap;
+/// use darksky::constants::Config;
+///
+/// let mut config = Config::default();
+/// Snap::decimals(3).configure(&mut config);
+///
+/// assert_eq!(config.snap(49.895121, -97.138437), (49.895, -97.138));
+/// ```
+///
+/// [`Layered`]: ../middleware/struct.Layered.html
+/// [`Middleware`]: ../middleware/trait.Middleware.html
+/// [grid]: ../../constants/struct.Config.html#method.grid
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snap {
+    config: Config,
+}
+
+impl Snap {
+    /// Creates a layer snapping coordinates to the [grid] of a [`Config`],
+    /// such as that of a client, leaving them unchanged if it has none.
+    ///
+    /// [`Config`]: ../../constants/struct.Config.html
+    /// [grid]: ../../constants/struct.Config.html#method.grid
+    pub fn new(config: &Config) -> Self {
+        let config = match config.get_grid() {
+            Some(grid) => Config::default().grid(grid),
+            None => Config::default(),
+        };
+
+        Snap { config }
+    }
+
+    /// Creates a layer rounding coordinates to the given number of decimal
+    /// places.
+    pub fn decimals(places: u32) -> Self {
+        Snap {
+            config: Config::default().decimals(places),
+        }
+    }
+
+    /// Creates a layer snapping coordinates to a grid of the given size in
+    /// degrees, such as `0.01`.
+    pub fn grid(degrees: f64) -> Self {
+        Snap {
+            config: Config::default().grid(degrees),
+        }
+    }
+
+    /// Returns the size in degrees of the grid that coordinates are snapped
+    /// to, if any.
+    pub fn get_grid(&self) -> Option<f64> {
+        self.config.get_grid()
+    }
+}
+
+impl Middleware for Snap {
+    fn configure(&self, config: &mut Config) {
+        if let Some(grid) = self.config.get_grid() {
+            *config = mem::take(config).grid(grid);
+        }
+    }
+}
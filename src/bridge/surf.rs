@@ -0,0 +1,196 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Bridge to provide DarkSky client implementation for the `surf` crate.
+//!
+//! Requests return futures, which can be awaited on any executor that
+//! `surf`'s HTTP client supports, such as `async-std`.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`DarkskySurfRequester`].
+//!
+//! [`DarkskySurfRequester`]: trait.DarkskySurfRequester.html
+
+use super::send::{Backend, DarkskyRequester, ForecastFuture, HttpSend};
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use surf::{Client, Result as SurfResult};
+use {IntoLocation, Options, Result};
+
+/// A boxed future resolving to the body of a response, returned by the
+/// [`HttpSend`] implementation for `surf`'s `Client`.
+///
+/// [`HttpSend`]: ../send/trait.HttpSend.html
+pub type BodyFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>>> + Send>>;
+
+/// The trait for `surf` implementations to different DarkSky routes.
+///
+/// This is implemented for every [`Backend`] sending requests through a
+/// `surf` `Client`, including those wrapped in other backends. Each method
+/// returns the future of the equivalent [`DarkskyRequester`] method, so
+/// requests run the layers of the backends wrapping the client.
+///
+/// [`Backend`]: ../send/trait.Backend.html
+/// [`DarkskyRequester`]: ../send/trait.DarkskyRequester.html
+pub trait DarkskySurfRequester: HttpSend {
+    /// Retrieve a [`Forecast`] for the given location.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve a forecast for a location, taking a token from the environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate surf;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::{Coordinate, DarkskySurfRequester};
+    /// use std::env;
+    /// use surf::Client;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let client = Client::new();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// // Await this on an executor, e.g. with `async_std::task::block_on`.
+    /// let forecast = client.get_forecast(&token, location);
+    /// #     drop(forecast);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    fn get_forecast<L: IntoLocation>(
+        &self,
+        token: &str,
+        location: L,
+    ) -> ForecastFuture<Self::Future> {
+        DarkskyRequester::get_forecast(self, token, location)
+    }
+
+    /// Retrieve a [`Forecast`] for the given location, setting options where
+    /// needed. For a full list of options, refer to the documentation for the
+    /// [`Options`] builder.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve an extended forecast, excluding the
+    /// [minutely block][`Block::Minutely`], taking a token from the
+    /// environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate surf;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::{Block, Coordinate, DarkskySurfRequester};
+    /// use std::env;
+    /// use surf::Client;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let client = Client::new();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// let forecast = client.get_forecast_with_options(&token, location, |o| o
+    ///     .exclude(vec![Block::Minutely]));
+    /// #     drop(forecast);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Block::Minutely`]: ../../enum.Block.html#variant.Minutely
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    /// [`Options`]: ../../struct.Options.html
+    fn get_forecast_with_options<F, L>(
+        &self,
+        token: &str,
+        location: L,
+        options: F,
+    ) -> ForecastFuture<Self::Future>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        DarkskyRequester::get_forecast_with_options(self, token, location, options)
+    }
+
+    /// Sets the time to request a forecast for by using DarkSky's Time Machine
+    /// API.
+    ///
+    /// Refer to [`DarkskyReqwestRequester::get_forecast_time_machine`] for
+    /// the accepted time formats. The time is not validated.
+    ///
+    /// [`DarkskyReqwestRequester::get_forecast_time_machine`]:
+    ///   ../reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_time_machine
+    fn get_forecast_time_machine<D, F, L>(
+        &self,
+        token: &str,
+        location: L,
+        time: D,
+        options: F,
+    ) -> ForecastFuture<Self::Future>
+    where
+        D: Display,
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        DarkskyRequester::get_forecast_time_machine(self, token, location, time, options)
+    }
+}
+
+impl<S: Backend<Client = Client> + ?Sized> DarkskySurfRequester for S {}
+
+impl Backend for Client {
+    type Client = Client;
+}
+
+impl HttpSend for Client {
+    type Future = BodyFuture;
+
+    fn send(&self, uri: String) -> BodyFuture {
+        let body = self.get(uri).recv_bytes();
+
+        Box::pin(Body(Box::pin(body)))
+    }
+}
+
+/// A future reading a response body, mapping its error.
+struct Body(Pin<Box<dyn Future<Output = SurfResult<Vec<u8>>> + Send>>);
+
+impl Future for Body {
+    type Output = Result<Vec<u8>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Vec<u8>>> {
+        self.0.as_mut().poll(cx).map(|body| body.map_err(From::from))
+    }
+}
@@ -0,0 +1,228 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+//! Bridge to provide DarkSky client implementation for the `ureq` crate.
+//!
+//! This is a small blocking client suited to simple synchronous programs,
+//! such as cron jobs and status bars.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`DarkskyUreqRequester`].
+//!
+//! [`DarkskyUreqRequester`]: trait.DarkskyUreqRequester.html
+
+use super::send::{self, Backend, DarkskyRequester, HttpSend, MetaSlot, ResponseMeta};
+use constants::Config;
+use models::Forecast;
+use std::fmt::Display;
+use std::future::{self, Ready};
+use std::io::Read;
+use ureq::{Agent, Request};
+use {Error, IntoLocation, Options, Result};
+
+/// The trait for `ureq` implementations to different DarkSky routes.
+///
+/// This is implemented for every [`Backend`] sending requests through a
+/// `ureq` `Agent`, including those wrapped in other backends. Each method
+/// blocks on the equivalent [`DarkskyRequester`] method, so requests run
+/// the layers of the backends wrapping the agent.
+///
+/// [`Backend`]: ../send/trait.Backend.html
+/// [`DarkskyRequester`]: ../send/trait.DarkskyRequester.html
+pub trait DarkskyUreqRequester: HttpSend {
+    /// Retrieve a [`Forecast`] for the given location.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve a forecast for a location, taking a token from the environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate ureq;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::{Coordinate, DarkskyUreqRequester};
+    /// use std::env;
+    /// use ureq::Agent;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let agent = Agent::new();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// let req = agent.get_forecast(&token, location)?;
+    ///
+    /// println!("Forecast: {:?}", req);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    fn get_forecast<L: IntoLocation>(&self, token: &str, location: L) -> Result<Forecast> {
+        send::block_on(DarkskyRequester::get_forecast(self, token, location))
+    }
+
+    /// Retrieve a [`Forecast`] for the given location, setting options where
+    /// needed. For a full list of options, refer to the documentation for the
+    /// [`Options`] builder.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve an extended forecast, excluding the
+    /// [minutely block][`Block::Minutely`], taking a token from the
+    /// environment:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate ureq;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// use darksky::{Block, Coordinate, DarkskyUreqRequester};
+    /// use std::env;
+    /// use ureq::Agent;
+    ///
+    /// let token = env::var("FORECAST_TOKEN")?;
+    /// let agent = Agent::new();
+    ///
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// let req = agent.get_forecast_with_options(&token, location, |o| o
+    ///     .exclude(vec![Block::Minutely]))?;
+    ///
+    /// println!("Forecast: {:?}", req);
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Block::Minutely`]: ../../enum.Block.html#variant.Minutely
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    /// [`Options`]: ../../struct.Options.html
+    fn get_forecast_with_options<F, L>(
+        &self,
+        token: &str,
+        location: L,
+        options: F,
+    ) -> Result<Forecast>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        send::block_on(DarkskyRequester::get_forecast_with_options(
+            self, token, location, options,
+        ))
+    }
+
+    /// Sets the time to request a forecast for by using DarkSky's Time Machine
+    /// API.
+    ///
+    /// Refer to [`DarkskyReqwestRequester::get_forecast_time_machine`] for
+    /// the accepted time formats. The time is not validated.
+    ///
+    /// [`DarkskyReqwestRequester::get_forecast_time_machine`]:
+    ///   ../reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_time_machine
+    fn get_forecast_time_machine<D, F, L>(
+        &self,
+        token: &str,
+        location: L,
+        time: D,
+        options: F,
+    ) -> Result<Forecast>
+    where
+        D: Display,
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        let req = DarkskyRequester::get_forecast_time_machine(self, token, location, time, options);
+
+        send::block_on(req)
+    }
+}
+
+impl<S: Backend<Client = Agent> + ?Sized> DarkskyUreqRequester for S {}
+
+impl Backend for Agent {
+    type Client = Agent;
+}
+
+impl HttpSend for Agent {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        send(self.get(&uri), None)
+    }
+
+    fn send_with(&self, uri: String, config: &Config) -> Self::Future {
+        send(configure(self.get(&uri), config), None)
+    }
+
+    fn send_recording(
+        &self,
+        uri: String,
+        config: Option<&Config>,
+        slot: &MetaSlot,
+    ) -> Self::Future {
+        let request = match config {
+            Some(config) => configure(self.get(&uri), config),
+            None => self.get(&uri),
+        };
+
+        send(request, Some(slot))
+    }
+}
+
+/// Applies the timeout and user agent of a configuration to a request,
+/// overriding the agent's own.
+fn configure(mut request: Request, config: &Config) -> Request {
+    if let Some(timeout) = config.get_timeout() {
+        request = request.timeout(timeout);
+    }
+
+    if let Some(user_agent) = config.get_user_agent() {
+        request = request.set("User-Agent", user_agent);
+    }
+
+    request
+}
+
+/// Sends a request, reading the whole response body, and recording the
+/// metadata of the response into the slot if one is given.
+fn send(request: Request, slot: Option<&MetaSlot>) -> Ready<Result<Vec<u8>>> {
+    let mut body = Vec::new();
+    let result = request.call().map_err(Error::from).and_then(|res| {
+        if let Some(slot) = slot {
+            slot.record(ResponseMeta::from_headers(res.status(), |name| {
+                res.header(name)
+            }));
+        }
+
+        res.into_reader().read_to_end(&mut body).map_err(From::from)
+    });
+
+    future::ready(result.map(|_| body))
+}
@@ -0,0 +1,230 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Bridge to provide DarkSky client implementation for the browser's fetch
+//! API, through the `gloo-net` crate.
+//!
+//! This is for use when compiled to WebAssembly, such as within browser
+//! dashboards and Yew or Tauri apps. Requests return futures which,
+//! like JavaScript values, are not `Send`, and can be awaited within
+//! `wasm_bindgen_futures::spawn_local` or converted to a JavaScript
+//! `Promise`.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`DarkskyWasmRequester`].
+//!
+//! [`DarkskyWasmRequester`]: trait.DarkskyWasmRequester.html
+
+use super::send::{Backend, DarkskyRequester, ForecastFuture, HttpSend};
+use gloo_net::http::{Request, Response};
+use gloo_net::Error as GlooError;
+use js_sys::{Error as JsError, Uint8Array};
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::Pin;
+use std::result::Result as StdResult;
+use std::task::{Context, Poll};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::Response as RawResponse;
+use {Error, IntoLocation, Options, Result};
+
+/// A boxed future resolving to the body of a response, returned by the
+/// [`HttpSend`] implementation for [`FetchClient`].
+///
+/// [`FetchClient`]: struct.FetchClient.html
+/// [`HttpSend`]: ../send/trait.HttpSend.html
+pub type BodyFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>>>>>;
+
+/// A client making requests through the browser's fetch API.
+///
+/// The fetch API keeps no client state of its own, so this only exists to
+/// implement [`HttpSend`] on.
+///
+/// [`HttpSend`]: ../send/trait.HttpSend.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FetchClient;
+
+/// The trait for browser fetch implementations to different DarkSky routes.
+///
+/// This is implemented for every [`Backend`] sending requests through a
+/// [`FetchClient`], including those wrapped in other backends. Each method
+/// returns the future of the equivalent [`DarkskyRequester`] method, so
+/// requests run the layers of the backends wrapping the client.
+///
+/// [`Backend`]: ../send/trait.Backend.html
+/// [`DarkskyRequester`]: ../send/trait.DarkskyRequester.html
+/// [`FetchClient`]: struct.FetchClient.html
+pub trait DarkskyWasmRequester: HttpSend {
+    /// Retrieve a [`Forecast`] for the given location.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve a forecast for a location:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    ///
+    /// use darksky::bridge::wasm::FetchClient;
+    /// use darksky::{Coordinate, DarkskyWasmRequester};
+    ///
+    /// # fn main() {
+    /// let token = "token";
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// // Await this within `wasm_bindgen_futures::spawn_local`.
+    /// let forecast = FetchClient.get_forecast(token, location);
+    /// #     drop(forecast);
+    /// # }
+    /// ```
+    ///
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    fn get_forecast<L: IntoLocation>(
+        &self,
+        token: &str,
+        location: L,
+    ) -> ForecastFuture<Self::Future> {
+        DarkskyRequester::get_forecast(self, token, location)
+    }
+
+    /// Retrieve a [`Forecast`] for the given location, setting options where
+    /// needed. For a full list of options, refer to the documentation for the
+    /// [`Options`] builder.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve an extended forecast, excluding the
+    /// [minutely block][`Block::Minutely`]:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    ///
+    /// use darksky::bridge::wasm::FetchClient;
+    /// use darksky::{Block, Coordinate, DarkskyWasmRequester};
+    ///
+    /// # fn main() {
+    /// let token = "token";
+    /// let location = Coordinate::new(37.8267, -122.423);
+    ///
+    /// let forecast = FetchClient.get_forecast_with_options(token, location, |o| o
+    ///     .exclude(vec![Block::Minutely]));
+    /// #     drop(forecast);
+    /// # }
+    /// ```
+    ///
+    /// [`Block::Minutely`]: ../../enum.Block.html#variant.Minutely
+    /// [`Forecast`]: ../../models/struct.Forecast.html
+    /// [`Options`]: ../../struct.Options.html
+    fn get_forecast_with_options<F, L>(
+        &self,
+        token: &str,
+        location: L,
+        options: F,
+    ) -> ForecastFuture<Self::Future>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        DarkskyRequester::get_forecast_with_options(self, token, location, options)
+    }
+
+    /// Sets the time to request a forecast for by using DarkSky's Time Machine
+    /// API.
+    ///
+    /// Refer to [`DarkskyReqwestRequester::get_forecast_time_machine`] for
+    /// the accepted time formats. The time is not validated.
+    ///
+    /// [`DarkskyReqwestRequester::get_forecast_time_machine`]:
+    ///   ../reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_time_machine
+    fn get_forecast_time_machine<D, F, L>(
+        &self,
+        token: &str,
+        location: L,
+        time: D,
+        options: F,
+    ) -> ForecastFuture<Self::Future>
+    where
+        D: Display,
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        DarkskyRequester::get_forecast_time_machine(self, token, location, time, options)
+    }
+}
+
+impl<S: Backend<Client = FetchClient> + ?Sized> DarkskyWasmRequester for S {}
+
+impl Backend for FetchClient {
+    type Client = FetchClient;
+}
+
+impl HttpSend for FetchClient {
+    type Future = BodyFuture;
+
+    fn send(&self, uri: String) -> BodyFuture {
+        Box::pin(Fetch {
+            send: Box::pin(Request::get(&uri).send()),
+            body: None,
+        })
+    }
+}
+
+/// A future sending a request, then reading its response body.
+///
+/// The body is read as an `ArrayBuffer` directly from the underlying
+/// `web_sys::Response`, as `gloo-net`'s own body futures borrow the response
+/// and so can't be stored alongside it.
+struct Fetch {
+    send: Pin<Box<dyn Future<Output = StdResult<Response, GlooError>>>>,
+    body: Option<JsFuture>,
+}
+
+impl Future for Fetch {
+    type Output = Result<Vec<u8>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Result<Vec<u8>>> {
+        let this = &mut *self;
+
+        if this.body.is_none() {
+            match this.send.as_mut().poll(cx) {
+                Poll::Ready(Ok(res)) => match RawResponse::from(res).array_buffer() {
+                    Ok(promise) => this.body = Some(JsFuture::from(promise)),
+                    Err(why) => return Poll::Ready(Err(js_error(why))),
+                },
+                Poll::Ready(Err(why)) => return Poll::Ready(Err(why.into())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        match this.body {
+            Some(ref mut body) => Pin::new(body)
+                .poll(cx)
+                .map(|buffer| Ok(Uint8Array::new(&buffer.map_err(js_error)?).to_vec())),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// Converts a value thrown by JavaScript into an error, keeping only its
+/// message as JavaScript values can not be sent across threads.
+fn js_error(value: JsValue) -> Error {
+    let message = match value.dyn_ref::<JsError>() {
+        Some(error) => String::from(error.message()),
+        None => format!("{:?}", value),
+    };
+
+    Error::Wasm(GlooError::GlooError(message))
+}
@@ -0,0 +1,208 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+use super::store::CacheStore;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::str;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use Result;
+
+/// A [`CacheStore`] keeping each response in a file of a directory, so that
+/// it outlives the process.
+///
+/// This suits short-lived programs run often, such as a status bar script
+/// run every minute, which would otherwise request a forecast on each run.
+///
+/// Each file holds the time its response expires, in milliseconds since the
+/// Unix epoch, and the key it is stored under, followed by the body. Files
+/// are named after a hash of their key, and are written whole before
+/// replacing any existing file, so that processes sharing the directory
+/// never read a partial response. Expired files are removed when they are
+/// next read, or by [`purge`].
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::cache::{CacheStore, FileStore};
+/// use std::env;
+/// use std::time::Duration;
+///
+/// let dir = env::temp_dir().join("darksky-file-store-doc");
+/// let store = FileStore::new(&dir).unwrap();
+///
+/// store.put("key", b"{}".to_vec(), Duration::from_secs(60)).unwrap();
+///
+/// // Another process using the same directory sees the response.
+/// let other = FileStore::new(&dir).unwrap();
+/// assert_eq!(other.get("key").unwrap(), Some(b"{}".to_vec()));
+/// #
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+///
+/// [`CacheStore`]: trait.CacheStore.html
+/// [`purge`]: #method.purge
+#[derive(Clone, Debug)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    /// Creates a store in the given directory, creating it if it does not
+    /// exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the directory could not be created.
+    ///
+    /// [`Error::Io`]: ../enum.Error.html#variant.Io
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        Ok(FileStore { dir })
+    }
+
+    /// Returns the directory responses are kept in.
+    pub fn get_dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Removes the files of every expired response, returning how many were
+    /// removed.
+    ///
+    /// Files which cannot be read as responses, such as those of an
+    /// interrupted write, are removed as well.
+    pub fn purge(&self) -> Result<usize> {
+        let now = unix_millis();
+        let mut removed = 0;
+
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+
+            if path.extension().is_none_or(|ext| ext != EXTENSION) {
+                continue;
+            }
+
+            let expired = match fs::read(&path) {
+                Ok(contents) => parse(&contents).is_none_or(|file| file.expires <= now),
+                Err(ref why) if why.kind() == ErrorKind::NotFound => continue,
+                Err(why) => return Err(why.into()),
+            };
+
+            if expired && remove(&path)? {
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.{}", fnv1a(key), EXTENSION))
+    }
+}
+
+impl CacheStore for FileStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.path(key);
+        let contents = match fs::read(&path) {
+            Ok(contents) => contents,
+            Err(ref why) if why.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(why) => return Err(why.into()),
+        };
+
+        match parse(&contents) {
+            Some(ref file) if file.key != key => Ok(None),
+            Some(ref file) if file.expires > unix_millis() => Ok(Some(file.body.to_vec())),
+            _ => {
+                remove(&path)?;
+
+                Ok(None)
+            }
+        }
+    }
+
+    fn put(&self, key: &str, body: Vec<u8>, ttl: Duration) -> Result<()> {
+        let expires = unix_millis().saturating_add(ttl.as_millis() as u64);
+        let mut contents = format!("{}\n{}\n", expires, key).into_bytes();
+        contents.extend_from_slice(&body);
+
+        let path = self.path(key);
+        let temp = path.with_extension(format!("{}.tmp", process::id()));
+        fs::write(&temp, contents)?;
+
+        if let Err(why) = fs::rename(&temp, &path) {
+            let _ = fs::remove_file(&temp);
+
+            return Err(why.into());
+        }
+
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        remove(&self.path(key)).map(|_| ())
+    }
+}
+
+/// The extension of the files of responses.
+const EXTENSION: &str = "cache";
+
+/// A response read from its file.
+struct CacheFile<'a> {
+    expires: u64,
+    key: &'a str,
+    body: &'a [u8],
+}
+
+/// Parses the contents of a file, returning `None` if they are malformed.
+fn parse(contents: &[u8]) -> Option<CacheFile<'_>> {
+    let mut parts = contents.splitn(3, |&byte| byte == b'\n');
+    let expires = str::from_utf8(parts.next()?).ok()?.parse().ok()?;
+    let key = str::from_utf8(parts.next()?).ok()?;
+    let body = parts.next()?;
+
+    Some(CacheFile { expires, key, body })
+}
+
+/// Removes a file, returning whether it existed.
+fn remove(path: &Path) -> Result<bool> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(true),
+        Err(ref why) if why.kind() == ErrorKind::NotFound => Ok(false),
+        Err(why) => Err(why.into()),
+    }
+}
+
+/// Hashes a key with 64-bit FNV-1a, which unlike the standard library's
+/// hasher is stable between releases, so that files are found by later
+/// builds.
+fn fnv1a(key: &str) -> u64 {
+    key.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Returns the number of milliseconds since the Unix epoch.
+pub(super) fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_millis() as u64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,109 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+use std::time::{Duration, SystemTime};
+
+/// The response header giving how long a response may be cached for.
+pub const CACHE_CONTROL_HEADER: &str = "Cache-Control";
+
+/// How long a response is fresh for, as given by the `max-age` directive of
+/// its `Cache-Control` header.
+///
+/// A response which must not be cached, through a `no-store` or `no-cache`
+/// directive, has a `max_age` of zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::cache::Freshness;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let received = UNIX_EPOCH + Duration::from_secs(1_509_993_277);
+/// let freshness = Freshness::at("public, max-age=3600", received).unwrap();
+///
+/// assert_eq!(freshness.max_age, Duration::from_secs(3600));
+/// assert_eq!(freshness.expires_at(), received + Duration::from_secs(3600));
+///
+/// assert!(Freshness::at("no-store", received).unwrap().max_age == Duration::from_secs(0));
+/// assert!(Freshness::at("public", received).is_none());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Freshness {
+    /// How long after being received the response is fresh for.
+    pub max_age: Duration,
+    /// When the response was received.
+    pub received: SystemTime,
+}
+
+impl Freshness {
+    /// Parses the value of a `Cache-Control` header of a response received
+    /// now.
+    ///
+    /// Returns `None` if the header gives neither a `max-age` nor forbids
+    /// caching.
+    pub fn from_cache_control(value: &str) -> Option<Self> {
+        Self::at(value, SystemTime::now())
+    }
+
+    /// Parses the value of a `Cache-Control` header of a response received
+    /// at the given time.
+    ///
+    /// Returns `None` if the header gives neither a `max-age` nor forbids
+    /// caching.
+    pub fn at(value: &str, received: SystemTime) -> Option<Self> {
+        let mut max_age = None;
+
+        for directive in value.split(',') {
+            let mut parts = directive.splitn(2, '=');
+            let name = parts.next().unwrap_or("").trim();
+            let argument = parts.next().map(|arg| arg.trim().trim_matches('"'));
+
+            if name.eq_ignore_ascii_case("no-store") || name.eq_ignore_ascii_case("no-cache") {
+                return Some(Freshness {
+                    max_age: Duration::from_secs(0),
+                    received,
+                });
+            }
+
+            if name.eq_ignore_ascii_case("max-age") {
+                max_age = argument.and_then(|secs| secs.parse().ok());
+            }
+        }
+
+        max_age.map(|secs| Freshness {
+            max_age: Duration::from_secs(secs),
+            received,
+        })
+    }
+
+    /// Returns when the response stops being fresh.
+    pub fn expires_at(&self) -> SystemTime {
+        self.received + self.max_age
+    }
+
+    /// Returns whether the response is still fresh.
+    pub fn is_fresh(&self) -> bool {
+        self.remaining() > Duration::from_secs(0)
+    }
+
+    /// Returns how much longer the response is fresh for, being zero once it
+    /// has expired.
+    pub fn remaining(&self) -> Duration {
+        self.expires_at()
+            .duration_since(SystemTime::now())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,186 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+use super::store::CacheStore;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use Result;
+
+/// A [`CacheStore`] keeping responses in memory, evicting the least recently
+/// used once it holds its capacity.
+///
+/// Expired responses are dropped when they are next read, or evicted along
+/// with the others.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::cache::{CacheStore, MemoryStore};
+/// use std::time::Duration;
+///
+/// let store = MemoryStore::new(2);
+/// let ttl = Duration::from_secs(60);
+///
+/// store.put("a", b"1".to_vec(), ttl).unwrap();
+/// store.put("b", b"2".to_vec(), ttl).unwrap();
+/// store.get("a").unwrap();
+/// store.put("c", b"3".to_vec(), ttl).unwrap();
+///
+/// // "b" was the least recently used, so was evicted for "c".
+/// assert_eq!(store.get("a").unwrap(), Some(b"1".to_vec()));
+/// assert_eq!(store.get("b").unwrap(), None);
+/// assert_eq!(store.len(), 2);
+/// ```
+///
+/// [`CacheStore`]: trait.CacheStore.html
+pub struct MemoryStore {
+    capacity: usize,
+    lru: Mutex<Lru>,
+}
+
+impl MemoryStore {
+    /// Creates an empty store, holding at most the given number of responses.
+    pub fn new(capacity: usize) -> Self {
+        MemoryStore {
+            capacity,
+            lru: Mutex::new(Lru::default()),
+        }
+    }
+
+    /// Removes every response.
+    pub fn clear(&self) {
+        *self.lock() = Lru::default();
+    }
+
+    /// Returns the number of responses held, including any which have
+    /// expired but not yet been dropped.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Returns whether no responses are held.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of responses held.
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Locks the entries, recovering them if another thread panicked while
+    /// holding the lock, as they are never left partially updated.
+    fn lock(&self) -> MutexGuard<'_, Lru> {
+        self.lru.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Debug for MemoryStore {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("MemoryStore")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let mut lru = self.lock();
+        let lru = &mut *lru;
+
+        let expired = match lru.entries.get(key) {
+            Some(entry) => entry.expires <= Instant::now(),
+            None => return Ok(None),
+        };
+
+        if expired {
+            lru.remove(key);
+
+            return Ok(None);
+        }
+
+        lru.tick += 1;
+        let tick = lru.tick;
+        let entry = lru.entries.get_mut(key).expect("entry present");
+        let key = lru.order.remove(&entry.used).expect("entry ordered");
+        lru.order.insert(tick, key);
+        entry.used = tick;
+
+        Ok(Some(entry.body.clone()))
+    }
+
+    fn put(&self, key: &str, body: Vec<u8>, ttl: Duration) -> Result<()> {
+        if self.capacity == 0 {
+            return Ok(());
+        }
+
+        let mut lru = self.lock();
+        lru.remove(key);
+        lru.tick += 1;
+
+        let tick = lru.tick;
+        lru.order.insert(tick, key.to_owned());
+        lru.entries.insert(
+            key.to_owned(),
+            Entry {
+                body,
+                expires: Instant::now() + ttl,
+                used: tick,
+            },
+        );
+
+        while lru.entries.len() > self.capacity {
+            let (_, oldest) = lru.order.pop_first().expect("entries ordered");
+            lru.entries.remove(&oldest);
+        }
+
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        self.lock().remove(key);
+
+        Ok(())
+    }
+}
+
+/// The entries of a [`MemoryStore`], with the order in which they were last
+/// used.
+///
+/// [`MemoryStore`]: struct.MemoryStore.html
+#[derive(Default)]
+struct Lru {
+    entries: HashMap<String, Entry>,
+    order: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl Lru {
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&entry.used);
+        }
+    }
+}
+
+struct Entry {
+    body: Vec<u8>,
+    expires: Instant,
+    used: u64,
+}
@@ -0,0 +1,316 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Caching of forecasts, to reduce the number of API calls made.
+//!
+//! A [`BlockCache`] tracks the age of each block of a forecast separately, so
+//! that a dashboard showing the minutely, hourly, and daily blocks only
+//! re-requests the blocks that have gone stale, according to its
+//! [`BlockTtl`].
+//!
+//! A [`CacheStore`] instead holds whole response bodies, keyed on the
+//! location, time, and options of their request, and is used by the
+//! [`Cached`] middleware to answer repeated requests locally. The
+//! [`MemoryStore`] keeps recently used responses in memory, and the
+//! [`FileStore`] keeps them in a directory, so that they outlive the process.
+//! With the `cache-redis` feature, the [`RedisStore`] keeps them in Redis, to
+//! be shared by several processes.
+//!
+//! Responses are kept for as long as their `Cache-Control` header allows,
+//! where the backend reports it, as a [`Freshness`].
+//!
+//! [`BlockCache`]: struct.BlockCache.html
+//! [`BlockTtl`]: struct.BlockTtl.html
+//! [`CacheStore`]: trait.CacheStore.html
+//! [`Cached`]: struct.Cached.html
+//! [`FileStore`]: struct.FileStore.html
+//! [`Freshness`]: struct.Freshness.html
+//! [`MemoryStore`]: struct.MemoryStore.html
+//! [`RedisStore`]: struct.RedisStore.html
+
+mod file;
+mod freshness;
+mod memory;
+#[cfg(feature = "cache-redis")]
+mod redis;
+mod store;
+
+pub use self::file::FileStore;
+pub use self::freshness::{Freshness, CACHE_CONTROL_HEADER};
+pub use self::memory::MemoryStore;
+#[cfg(feature = "cache-redis")]
+pub use self::redis::RedisStore;
+pub use self::store::{cache_key, CacheStore, Cached};
+
+use models::{Forecast, SharedForecast};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use {Block, Result};
+
+/// The blocks whose age is tracked by a [`BlockCache`].
+///
+/// [`BlockCache`]: struct.BlockCache.html
+const CACHED_BLOCKS: [Block; 4] = [
+    Block::Currently,
+    Block::Daily,
+    Block::Hourly,
+    Block::Minutely,
+];
+
+/// How long each block of a forecast stays fresh.
+///
+/// By default, the `currently` and `minutely` blocks are fresh for 5 minutes,
+/// the `hourly` block for 30 minutes, and the `daily` block for 3 hours.
+/// Alerts and flags are refreshed along with any other block.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct BlockTtl {
+    currently: Duration,
+    daily: Duration,
+    hourly: Duration,
+    minutely: Duration,
+}
+
+impl BlockTtl {
+    /// Sets how long the `currently` block stays fresh.
+    pub fn currently(mut self, ttl: Duration) -> Self {
+        self.currently = ttl;
+
+        self
+    }
+
+    /// Sets how long the `daily` block stays fresh.
+    pub fn daily(mut self, ttl: Duration) -> Self {
+        self.daily = ttl;
+
+        self
+    }
+
+    /// Sets how long the `hourly` block stays fresh.
+    pub fn hourly(mut self, ttl: Duration) -> Self {
+        self.hourly = ttl;
+
+        self
+    }
+
+    /// Sets how long the `minutely` block stays fresh.
+    pub fn minutely(mut self, ttl: Duration) -> Self {
+        self.minutely = ttl;
+
+        self
+    }
+
+    /// Returns how long the given block stays fresh.
+    ///
+    /// The [`Flags`][`Block::Flags`] block is as fresh as the freshest block
+    /// it was received with, and so is never stale on its own.
+    ///
+    /// [`Block::Flags`]: ../enum.Block.html#variant.Flags
+    pub fn get(&self, block: Block) -> Duration {
+        match block {
+            Block::Currently => self.currently,
+            Block::Daily => self.daily,
+            Block::Flags => Duration::from_secs(u64::MAX),
+            Block::Hourly => self.hourly,
+            Block::Minutely => self.minutely,
+        }
+    }
+}
+
+impl Default for BlockTtl {
+    fn default() -> Self {
+        BlockTtl {
+            currently: Duration::from_secs(5 * 60),
+            daily: Duration::from_secs(3 * 60 * 60),
+            hourly: Duration::from_secs(30 * 60),
+            minutely: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// A cache of the blocks of a single location's forecast, each kept for as
+/// long as its [`BlockTtl`] allows.
+///
+/// The cached forecast is a composite of blocks received at different times.
+/// Only the blocks that are stale are requested when it is refreshed, with the
+/// fresh blocks excluded from the request.
+///
+/// # Examples
+///
+/// Refresh the `currently` block on every call, but keep the `daily` block:
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate serde_json;
+///
+/// use darksky::cache::{BlockCache, BlockTtl};
+/// use darksky::models::Forecast;
+/// use darksky::Block;
+/// use std::time::Duration;
+///
+/// # fn try_main() -> darksky::Result<()> {
+/// let mut cache = BlockCache::new(BlockTtl::default().currently(Duration::from_secs(0)));
+/// let mut calls = 0;
+///
+/// let mut fetch = |exclude: Vec<Block>| -> darksky::Result<Forecast> {
+///     calls += 1;
+///     let daily = if exclude.contains(&Block::Daily) {
+///         ""
+///     } else {
+///         r#", "daily": {"data": [{"time": 1509948000, "temperatureHigh": 2.5}]}"#
+///     };
+///
+///     Ok(serde_json::from_str(&format!(r#"{{
+///         "latitude": 49.9,
+///         "longitude": -97.1,
+///         "timezone": "America/Winnipeg",
+///         "currently": {{"time": {}}}{}
+///     }}"#, 1509993277 + calls, daily))?)
+/// };
+///
+/// cache.get_or_fetch(&mut fetch)?;
+/// let forecast = cache.get_or_fetch(&mut fetch)?;
+///
+/// // The second request excluded the daily block, which was still fresh.
+/// assert_eq!(forecast.currently.as_ref().unwrap().time, 1509993279);
+/// assert!(forecast.daily.is_some());
+/// assert_eq!(cache.stale_blocks(), [Block::Currently]);
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// [`BlockTtl`]: struct.BlockTtl.html
+#[derive(Clone, Debug, Default)]
+pub struct BlockCache {
+    fetched: HashMap<Block, Instant>,
+    forecast: Option<SharedForecast>,
+    ttl: BlockTtl,
+}
+
+impl BlockCache {
+    /// Creates an empty cache, keeping blocks for as long as the given TTLs
+    /// allow.
+    pub fn new(ttl: BlockTtl) -> Self {
+        BlockCache {
+            ttl,
+            ..Default::default()
+        }
+    }
+
+    /// Returns how long ago the given block was received, if it is cached.
+    pub fn age(&self, block: Block) -> Option<Duration> {
+        self.fetched.get(&block).map(Instant::elapsed)
+    }
+
+    /// Returns the cached composite forecast, regardless of the freshness of
+    /// its blocks.
+    pub fn forecast(&self) -> Option<&SharedForecast> {
+        self.forecast.as_ref()
+    }
+
+    /// Returns the blocks that need to be requested, because they are not
+    /// cached or have outlived their TTL.
+    pub fn stale_blocks(&self) -> Vec<Block> {
+        CACHED_BLOCKS
+            .iter()
+            .cloned()
+            .filter(|&block| match self.age(block) {
+                Some(age) => age >= self.ttl.get(block),
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Returns the composite forecast, first refreshing any stale blocks.
+    ///
+    /// If any block is stale, `fetch` is called once with the blocks to
+    /// exclude from the request, being the ones that are still fresh. These
+    /// can be passed to [`Options::exclude`]. The blocks in its response then
+    /// replace the cached ones, along with the alerts, flags, and location.
+    /// Blocks that were requested but not returned, such as the `minutely`
+    /// block in areas where it is unavailable, are cached as absent.
+    ///
+    /// The forecast is returned as a [`SharedForecast`], so that it can be
+    /// handed out to many consumers without copying its datapoints. A merge
+    /// only copies the cached forecast if a consumer still holds it.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from `fetch`, leaving the cache unchanged.
+    ///
+    /// [`Options::exclude`]: ../struct.Options.html#method.exclude
+    /// [`SharedForecast`]: ../models/struct.SharedForecast.html
+    pub fn get_or_fetch<F>(&mut self, fetch: F) -> Result<SharedForecast>
+    where
+        F: FnOnce(Vec<Block>) -> Result<Forecast>,
+    {
+        let stale = self.stale_blocks();
+
+        if !stale.is_empty() || self.forecast.is_none() {
+            let exclude = CACHED_BLOCKS
+                .iter()
+                .cloned()
+                .filter(|block| !stale.contains(block))
+                .collect();
+            let fetched = fetch(exclude)?;
+            let now = Instant::now();
+
+            for &block in &stale {
+                self.fetched.insert(block, now);
+            }
+
+            self.merge(fetched, &stale);
+        }
+
+        Ok(self.forecast.clone().expect("forecast cached"))
+    }
+
+    /// Merges the requested blocks of a forecast into the cached one.
+    fn merge(&mut self, mut fetched: Forecast, requested: &[Block]) {
+        let cached = match self.forecast.take() {
+            Some(cached) => cached.into_inner(),
+            None => {
+                self.forecast = Some(fetched.into());
+
+                return;
+            }
+        };
+
+        if !requested.contains(&Block::Currently) {
+            fetched.currently = cached.currently;
+        }
+
+        if !requested.contains(&Block::Daily) {
+            fetched.daily = cached.daily;
+        }
+
+        if !requested.contains(&Block::Hourly) {
+            fetched.hourly = cached.hourly;
+        }
+
+        if !requested.contains(&Block::Minutely) {
+            fetched.minutely = cached.minutely;
+        }
+
+        if fetched.flags.is_none() {
+            fetched.flags = cached.flags;
+        }
+
+        self.forecast = Some(fetched.into());
+    }
+}
@@ -0,0 +1,169 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+use super::store::CacheStore;
+use redis::{Client, Commands, Connection, RedisResult};
+use std::borrow::Cow;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use Result;
+
+/// A [`CacheStore`] keeping responses in Redis, so that several instances
+/// of a service share one cache, and together make fewer API calls.
+///
+/// Responses are stored as strings under their key with a prefix, which
+/// defaults to `darksky:`, and expire through Redis' own time to live.
+///
+/// A single connection is opened lazily and shared between requests. If a
+/// command fails, the connection is dropped and reopened by the next one, so
+/// that the cache recovers once Redis is reachable again.
+///
+/// # Examples
+///
+/// Share a cache of responses between the instances of a service:
+///
+/// ```rust,no_run
+/// extern crate darksky;
+///
+/// # fn try_main() -> darksky::Result<()> {
+/// use darksky::bridge::middleware::Layered;
+/// use darksky::cache::{Cached, RedisStore};
+/// # let backend = ();
+///
+/// let store = RedisStore::open("redis://127.0.0.1/")?.prefix("weather:");
+/// let client = Layered::new(backend).layer(Cached::new(store));
+/// #     drop(client);
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// [`CacheStore`]: trait.CacheStore.html
+pub struct RedisStore {
+    client: Client,
+    connection: Mutex<Option<Connection>>,
+    prefix: Cow<'static, str>,
+}
+
+impl RedisStore {
+    /// Creates a store using the given client.
+    pub fn new(client: Client) -> Self {
+        RedisStore {
+            client,
+            connection: Mutex::new(None),
+            prefix: Cow::Borrowed("darksky:"),
+        }
+    }
+
+    /// Creates a store for the Redis server at the given URL, such as
+    /// `redis://127.0.0.1/`.
+    ///
+    /// No connection is made until the store is first used.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Redis`] if the URL is invalid.
+    ///
+    /// [`Error::Redis`]: ../enum.Error.html#variant.Redis
+    pub fn open(url: &str) -> Result<Self> {
+        Ok(Self::new(Client::open(url)?))
+    }
+
+    /// Sets the prefix of every key, such as to keep the responses of
+    /// separate deployments apart on a shared server.
+    pub fn prefix<T: Into<Cow<'static, str>>>(mut self, prefix: T) -> Self {
+        self.prefix = prefix.into();
+
+        self
+    }
+
+    /// Returns the prefix of every key.
+    pub fn get_prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Runs a command on the shared connection, opening it if needed, and
+    /// dropping it if the command fails.
+    fn run<T, F>(&self, command: F) -> Result<T>
+    where
+        F: FnOnce(&mut Connection) -> RedisResult<T>,
+    {
+        let mut connection = self.lock();
+
+        if connection.is_none() {
+            *connection = Some(self.client.get_connection()?);
+        }
+
+        let result = command(connection.as_mut().expect("connection opened"));
+
+        if result.is_err() {
+            *connection = None;
+        }
+
+        result.map_err(From::from)
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// Locks the connection, recovering it if another thread panicked while
+    /// holding the lock.
+    fn lock(&self) -> MutexGuard<'_, Option<Connection>> {
+        self.connection
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Debug for RedisStore {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("RedisStore")
+            .field("prefix", &self.prefix)
+            .finish()
+    }
+}
+
+impl CacheStore for RedisStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let key = self.key(key);
+
+        self.run(|connection| connection.get(key))
+    }
+
+    fn put(&self, key: &str, body: Vec<u8>, ttl: Duration) -> Result<()> {
+        let key = self.key(key);
+        let millis = ttl.as_millis() as u64;
+
+        // Redis rejects a time to live of zero, and such a response would
+        // expire at once anyway.
+        if millis == 0 {
+            return self.run(|connection| connection.del(key));
+        }
+
+        self.run(|connection| connection.pset_ex(key, body, millis))
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        let key = self.key(key);
+
+        self.run(|connection| connection.del(key))
+    }
+}
@@ -0,0 +1,400 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+use super::file::unix_millis;
+use bridge::middleware::{Middleware, RequestContext};
+use bridge::send::ResponseMeta;
+use models::SharedForecast;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::str;
+use std::sync::Arc;
+use std::time::Duration;
+use {internal, utils, Result};
+
+/// A store of response bodies, each kept for a time to live.
+///
+/// Stores are shared between requests, so take `&self` and handle their own
+/// locking. Errors of a store are not fatal to a request: the [`Cached`]
+/// middleware treats a failed read as a miss, and ignores a failed write.
+///
+/// [`Cached`]: struct.Cached.html
+pub trait CacheStore: Send + Sync {
+    /// Returns the body stored under the key, if there is one which has not
+    /// outlived its time to live.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Stores a body under the key for the given time to live, replacing any
+    /// body already stored under it.
+    fn put(&self, key: &str, body: Vec<u8>, ttl: Duration) -> Result<()>;
+
+    /// Removes the body stored under the key, if there is one.
+    fn remove(&self, key: &str) -> Result<()>;
+}
+
+impl<T: CacheStore + ?Sized> CacheStore for &T {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: &str, body: Vec<u8>, ttl: Duration) -> Result<()> {
+        (**self).put(key, body, ttl)
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        (**self).remove(key)
+    }
+}
+
+impl<T: CacheStore + ?Sized> CacheStore for Box<T> {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: &str, body: Vec<u8>, ttl: Duration) -> Result<()> {
+        (**self).put(key, body, ttl)
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        (**self).remove(key)
+    }
+}
+
+impl<T: CacheStore + ?Sized> CacheStore for Arc<T> {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: &str, body: Vec<u8>, ttl: Duration) -> Result<()> {
+        (**self).put(key, body, ttl)
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        (**self).remove(key)
+    }
+}
+
+/// Returns the key a request URI is cached under.
+///
+/// The key is made of the base URI, location, time, and options of the
+/// request, without its token, so that requests made with different tokens
+/// share responses. The options are sorted, as their order in a URI is not
+/// stable between requests.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::cache;
+///
+/// let a = cache::cache_key("https://api.darksky.net/forecast/abc/49.9,-97.1?units=si&lang=fr&");
+/// let b = cache::cache_key("https://api.darksky.net/forecast/def/49.9,-97.1?lang=fr&units=si&");
+///
+/// assert_eq!(a, b);
+/// assert_eq!(a, "https://api.darksky.net/forecast/****/49.9,-97.1?lang=fr&units=si");
+/// ```
+pub fn cache_key(uri: &str) -> String {
+    let uri = utils::redact(uri);
+    let (path, query) = match uri.find('?') {
+        Some(idx) => (&uri[..idx], &uri[idx + 1..]),
+        None => return uri.into_owned(),
+    };
+
+    let mut pairs = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .collect::<Vec<_>>();
+    pairs.sort_unstable();
+
+    let mut key = String::with_capacity(uri.len());
+    key.push_str(path);
+    key.push('?');
+    key.push_str(&pairs.join("&"));
+
+    key
+}
+
+/// A [`Middleware`] answering requests from a [`CacheStore`], and storing
+/// the responses of those which were sent.
+///
+/// Responses are stored under their [`cache_key`] for as long as their
+/// `Cache-Control` header allows, where the backend records it as the
+/// [`Freshness`] of the response. Otherwise, they are stored for the layer's
+/// time to live, which defaults to 5 minutes. Errors, including error bodies
+/// from the API, and responses which must not be cached are never stored.
+///
+/// # Examples
+///
+/// Serve repeated requests for a location from memory:
+///
+/// ```rust
+/// extern crate darksky;
+///
+/// use darksky::bridge::middleware::Layered;
+/// use darksky::bridge::{DarkskyRequester, HttpSend};
+/// use darksky::cache::{Cached, MemoryStore};
+/// use darksky::Result;
+/// use std::future::{self, Future, Ready};
+/// use std::pin::Pin;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::task::{Context, Poll, Waker};
+///
+/// #[derive(Default)]
+/// struct Stub(AtomicUsize);
+///
+/// impl HttpSend for Stub {
+///     type Future = Ready<Result<Vec<u8>>>;
+///
+///     fn send(&self, _: String) -> Self::Future {
+///         self.0.fetch_add(1, Ordering::SeqCst);
+///
+///         let body = br#"{"latitude": 49.9, "longitude": -97.1, "timezone": "America/Winnipeg"}"#;
+///
+///         future::ready(Ok(body.to_vec()))
+///     }
+/// }
+///
+/// # fn main() {
+/// let client = Layered::new(Stub::default()).layer(Cached::new(MemoryStore::new(100)));
+/// let mut cx = Context::from_waker(Waker::noop());
+///
+/// for _ in 0..3 {
+///     let mut req = client.get_forecast("token", (49.9, -97.1));
+///
+///     match Pin::new(&mut req).poll(&mut cx) {
+///         Poll::Ready(forecast) => assert_eq!(forecast.unwrap().timezone, "America/Winnipeg"),
+///         Poll::Pending => unreachable!(),
+///     }
+/// }
+///
+/// assert_eq!(client.get_ref().0.load(Ordering::SeqCst), 1);
+/// # }
+/// ```
+///
+/// [`CacheStore`]: trait.CacheStore.html
+/// [`Freshness`]: struct.Freshness.html
+/// [`Middleware`]: ../bridge/middleware/trait.Middleware.html
+/// [`cache_key`]: fn.cache_key.html
+pub struct Cached<C> {
+    cache_control: bool,
+    max_stale: Option<Duration>,
+    store: C,
+    ttl: Duration,
+}
+
+impl<C: CacheStore> Cached<C> {
+    /// Creates a layer over the given store, with the default time to live.
+    pub fn new(store: C) -> Self {
+        Cached {
+            cache_control: true,
+            max_stale: None,
+            store,
+            ttl: Duration::from_secs(5 * 60),
+        }
+    }
+
+    /// Sets whether responses are stored for as long as their
+    /// `Cache-Control` header allows, rather than for the layer's time to
+    /// live. This is enabled by default.
+    pub fn cache_control(mut self, cache_control: bool) -> Self {
+        self.cache_control = cache_control;
+
+        self
+    }
+
+    /// Serves the most recently stored response for a request in place of an
+    /// error, for up to the given duration after it stopped being fresh.
+    ///
+    /// This covers errors sending the request, and error bodies from the
+    /// API, such as when it is down or the daily limit is reached. The age of
+    /// a stale response is recorded as the [`ResponseMeta::stale`] of the
+    /// request, so that it can be flagged by an outer layer. This is
+    /// disabled by default.
+    ///
+    /// [`ResponseMeta::stale`]: ../bridge/send/struct.ResponseMeta.html#structfield.stale
+    pub fn stale_on_error(mut self, max_stale: Duration) -> Self {
+        self.max_stale = Some(max_stale);
+
+        self
+    }
+
+    /// Sets how long responses are stored for when their freshness is not
+    /// known, or for every response if `Cache-Control` headers are not
+    /// honoured.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+
+        self
+    }
+
+    /// Returns the forecast stored for a request URI, if there is one which
+    /// is still fresh.
+    ///
+    /// The forecast is returned as a [`SharedForecast`], so that it can be
+    /// handed out to many consumers, such as web handlers, without copying
+    /// its datapoints.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use darksky::cache::{Cached, MemoryStore};
+    /// use darksky::utils;
+    ///
+    /// let cached = Cached::new(MemoryStore::new(100));
+    /// let uri = utils::uri("token", 49.9, -97.1);
+    ///
+    /// assert!(cached.get_forecast(&uri).unwrap().is_none());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Json`] if the stored body is not that of a forecast.
+    ///
+    /// [`Error::Json`]: ../enum.Error.html#variant.Json
+    /// [`SharedForecast`]: ../models/struct.SharedForecast.html
+    pub fn get_forecast(&self, uri: &str) -> Result<Option<SharedForecast>> {
+        match self.entry(uri) {
+            Some(ref entry) if entry.fresh_until > unix_millis() => {
+                internal::from_slice(&entry.body).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns a reference to the store.
+    pub fn get_ref(&self) -> &C {
+        &self.store
+    }
+
+    /// Returns how long responses are stored for when their freshness is not
+    /// known.
+    pub fn get_ttl(&self) -> Duration {
+        self.ttl
+    }
+}
+
+impl<C> Debug for Cached<C> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Cached")
+            .field("cache_control", &self.cache_control)
+            .field("max_stale", &self.max_stale)
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+impl<C: CacheStore> Cached<C> {
+    /// Reads the entry stored for a request URI, if there is one.
+    fn entry(&self, uri: &str) -> Option<Entry> {
+        match self.store.get(&cache_key(uri)) {
+            Ok(Some(stored)) => Entry::decode(stored),
+            Ok(None) | Err(_) => None,
+        }
+    }
+}
+
+impl<C: CacheStore> Middleware for Cached<C> {
+    fn before(&self, request: &mut RequestContext) -> Option<Result<Vec<u8>>> {
+        self.entry(request.get_uri())
+            .filter(|entry| entry.fresh_until > unix_millis())
+            .map(|entry| Ok(entry.body))
+    }
+
+    fn after(&self, request: &RequestContext, response: &mut Result<Vec<u8>>) {
+        if request.is_answered() {
+            return;
+        }
+
+        let body = match *response {
+            Ok(ref body) if !internal::is_api_error(body) => body,
+            _ => {
+                if self.max_stale.is_some() {
+                    if let Some(entry) = self.entry(request.get_uri()) {
+                        let age = unix_millis().saturating_sub(entry.stored_at);
+
+                        request.record_meta(ResponseMeta {
+                            api_calls: request.get_meta().api_calls,
+                            stale: Some(Duration::from_millis(age)),
+                            ..ResponseMeta::default()
+                        });
+                        *response = Ok(entry.body);
+                    }
+                }
+
+                return;
+            }
+        };
+
+        let ttl = match request.get_meta().freshness {
+            Some(freshness) if self.cache_control => freshness.remaining(),
+            _ => self.ttl,
+        };
+
+        if ttl > Duration::from_secs(0) {
+            let key = cache_key(request.get_uri());
+            let now = unix_millis();
+            let entry = Entry {
+                stored_at: now,
+                fresh_until: now.saturating_add(ttl.as_millis() as u64),
+                body: body.clone(),
+            };
+            let kept = ttl + self.max_stale.unwrap_or_default();
+
+            let _ = self.store.put(&key, entry.encode(), kept);
+        }
+    }
+}
+
+/// A response stored by a [`Cached`] layer, with the times it was stored and
+/// stops being fresh, in milliseconds since the Unix epoch.
+///
+/// Entries are kept past their freshness when stale responses may be served
+/// on errors, so the store's own time to live can't tell whether they are
+/// fresh.
+///
+/// [`Cached`]: struct.Cached.html
+struct Entry {
+    stored_at: u64,
+    fresh_until: u64,
+    body: Vec<u8>,
+}
+
+impl Entry {
+    /// Decodes an entry, returning `None` if it is malformed.
+    fn decode(mut stored: Vec<u8>) -> Option<Self> {
+        let (stored_at, fresh_until, len) = {
+            let mut parts = stored.splitn(3, |&byte| byte == b'\n');
+            let stored_at = str::from_utf8(parts.next()?).ok()?;
+            let fresh_until = str::from_utf8(parts.next()?).ok()?;
+            parts.next()?;
+            let len = stored_at.len() + fresh_until.len() + 2;
+
+            (stored_at.parse().ok()?, fresh_until.parse().ok()?, len)
+        };
+        stored.drain(..len);
+
+        Some(Entry {
+            stored_at,
+            fresh_until,
+            body: stored,
+        })
+    }
+
+    fn encode(self) -> Vec<u8> {
+        let mut encoded = format!("{}\n{}\n", self.stored_at, self.fresh_until).into_bytes();
+        encoded.extend_from_slice(&self.body);
+
+        encoded
+    }
+}
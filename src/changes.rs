@@ -0,0 +1,250 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Structured, field-level change events between successive forecasts for a
+//! location, so that automation can react to specific changes rather than
+//! re-scanning whole forecasts.
+
+use alerts::{AlertEvent, AlertQuery, AlertWatcher};
+use models::{Alert, Forecast, LocalDate, Severity};
+use std::collections::HashMap;
+
+/// A change between successive forecasts, as detected by a
+/// [`ChangeWatcher`].
+///
+/// [`ChangeWatcher`]: struct.ChangeWatcher.html
+#[derive(Clone, Debug)]
+pub enum ChangeEvent {
+    /// An alert that was not present in the previous forecast.
+    AlertIssued(Alert),
+    /// An alert that was present in the previous forecast, but with a lower
+    /// severity.
+    AlertEscalated {
+        /// The alert with its new severity.
+        alert: Alert,
+        /// The severity of the alert in the previous forecast.
+        previous: Severity,
+    },
+    /// The probability of precipitation for an hour changed by at least the
+    /// watcher's [threshold][`ChangeWatcher::precip_threshold`].
+    ///
+    /// [`ChangeWatcher::precip_threshold`]: struct.ChangeWatcher.html#method.precip_threshold
+    PrecipProbabilityChanged {
+        /// The Unix timestamp of the hour.
+        at: u64,
+        /// The previous probability, or `0` if none was given.
+        from: f64,
+        /// The new probability, or `0` if none was given.
+        to: f64,
+    },
+    /// The forecast high temperature of a day shifted by at least the
+    /// watcher's [threshold][`ChangeWatcher::temperature_threshold`].
+    ///
+    /// [`ChangeWatcher::temperature_threshold`]: struct.ChangeWatcher.html#method.temperature_threshold
+    TemperatureForecastShifted {
+        /// The day, in the forecast's local time.
+        date: LocalDate,
+        /// The previous high temperature.
+        from: f64,
+        /// The new high temperature.
+        to: f64,
+    },
+}
+
+impl From<AlertEvent> for ChangeEvent {
+    fn from(event: AlertEvent) -> Self {
+        match event {
+            AlertEvent::Issued(alert) => ChangeEvent::AlertIssued(alert),
+            AlertEvent::Escalated { alert, previous } => {
+                ChangeEvent::AlertEscalated { alert, previous }
+            }
+        }
+    }
+}
+
+/// Watches successive forecasts for a single location, reporting the changes
+/// between each forecast and the previous one as [`ChangeEvent`]s.
+///
+/// Alerts are watched as with an [`AlertWatcher`]. Hours and days are
+/// compared when they are present in both forecasts, so nothing is reported
+/// for the first forecast other than its alerts.
+///
+/// By default, changes in the probability of precipitation of at least `0.2`
+/// and shifts in the high temperature of at least `3` degrees are reported.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate serde_json;
+///
+/// use darksky::changes::{ChangeEvent, ChangeWatcher};
+/// use darksky::models::Forecast;
+///
+/// # fn main() {
+/// let forecast = |precip: f64, high: f64| -> Forecast {
+///     serde_json::from_str(&format!(r#"{{
+///         "latitude": 49.9,
+///         "longitude": -97.1,
+///         "timezone": "America/Winnipeg",
+///         "offset": -6,
+///         "hourly": {{"data": [{{"time": 1509991200, "precipProbability": {}}}]}},
+///         "daily": {{"data": [{{"time": 1509948000, "temperatureHigh": {}}}]}}
+///     }}"#, precip, high)).unwrap()
+/// };
+///
+/// let mut watcher = ChangeWatcher::new();
+/// assert!(watcher.check(&forecast(0.1, 2.5)).is_empty());
+///
+/// // Small changes are not reported.
+/// assert!(watcher.check(&forecast(0.2, 3.0)).is_empty());
+///
+/// let events = watcher.check(&forecast(0.7, -1.0));
+/// assert!(match events[0] {
+///     ChangeEvent::PrecipProbabilityChanged { at, from, to } => {
+///         at == 1509991200 && from == 0.2 && to == 0.7
+///     },
+///     _ => false,
+/// });
+/// assert!(match events[1] {
+///     ChangeEvent::TemperatureForecastShifted { date, from, to } => {
+///         date.to_string() == "2017-11-06" && from == 3.0 && to == -1.0
+///     },
+///     _ => false,
+/// });
+/// # }
+/// ```
+///
+/// [`AlertWatcher`]: ../alerts/struct.AlertWatcher.html
+/// [`ChangeEvent`]: enum.ChangeEvent.html
+#[derive(Clone, Debug)]
+pub struct ChangeWatcher {
+    alerts: AlertWatcher,
+    highs: HashMap<LocalDate, f64>,
+    precip: HashMap<u64, f64>,
+    precip_threshold: f64,
+    temperature_threshold: f64,
+}
+
+impl ChangeWatcher {
+    /// Creates a new watcher that has not yet seen any forecasts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a filter for the alerts that are watched. Refer to
+    /// [`AlertWatcher::filter`] for details.
+    ///
+    /// [`AlertWatcher::filter`]: ../alerts/struct.AlertWatcher.html#method.filter
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: FnOnce(AlertQuery) -> AlertQuery,
+    {
+        self.alerts = self.alerts.filter(filter);
+
+        self
+    }
+
+    /// Sets the minimum change in the probability of precipitation of an hour
+    /// that is reported.
+    pub fn precip_threshold(mut self, threshold: f64) -> Self {
+        self.precip_threshold = threshold;
+
+        self
+    }
+
+    /// Sets the minimum shift in the high temperature of a day that is
+    /// reported, in the forecast's units.
+    pub fn temperature_threshold(mut self, threshold: f64) -> Self {
+        self.temperature_threshold = threshold;
+
+        self
+    }
+
+    /// Compares a forecast against the previously checked forecast, returning
+    /// the changes between them.
+    ///
+    /// Alert events are returned first, followed by hourly and then daily
+    /// changes, each in chronological order.
+    pub fn check(&mut self, forecast: &Forecast) -> Vec<ChangeEvent> {
+        let mut events = self
+            .alerts
+            .check(forecast)
+            .into_iter()
+            .map(ChangeEvent::from)
+            .collect::<Vec<_>>();
+
+        let hourly = forecast
+            .hourly
+            .as_ref()
+            .and_then(|hourly| hourly.data.as_ref())
+            .map_or(&[][..], |data| &data[..]);
+        let mut precip = HashMap::with_capacity(hourly.len());
+
+        for hour in hourly {
+            let to = hour.precip_probability.unwrap_or(0.0);
+
+            if let Some(&from) = self.precip.get(&hour.time) {
+                if (to - from).abs() >= self.precip_threshold {
+                    events.push(ChangeEvent::PrecipProbabilityChanged {
+                        at: hour.time,
+                        from,
+                        to,
+                    });
+                }
+            }
+
+            precip.insert(hour.time, to);
+        }
+
+        let mut highs = HashMap::new();
+
+        for day in forecast.daily_highs_lows() {
+            let to = match day.high {
+                Some(high) => high,
+                None => continue,
+            };
+
+            if let Some(&from) = self.highs.get(&day.date) {
+                if (to - from).abs() >= self.temperature_threshold {
+                    events.push(ChangeEvent::TemperatureForecastShifted {
+                        date: day.date,
+                        from,
+                        to,
+                    });
+                }
+            }
+
+            highs.insert(day.date, to);
+        }
+
+        self.precip = precip;
+        self.highs = highs;
+
+        events
+    }
+}
+
+impl Default for ChangeWatcher {
+    fn default() -> Self {
+        ChangeWatcher {
+            alerts: AlertWatcher::new(),
+            highs: HashMap::new(),
+            precip: HashMap::new(),
+            precip_threshold: 0.2,
+            temperature_threshold: 3.0,
+        }
+    }
+}
@@ -0,0 +1,602 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Ready-made HTTPS clients for the DarkSky API.
+//!
+//! Each of these returns a client that only connects over HTTPS, as the API
+//! does, and which can be used with the respective bridge's requester trait
+//! directly. Clients for mirrors served over plain HTTP, such as those used
+//! with a [`ConfiguredClient`], should be built by hand instead.
+//!
+//! # Examples
+//!
+//! Retrieve a forecast with a hyper client using native TLS:
+//!
+#![cfg_attr(feature = "hyper-native-tls", doc = "```rust,no_run")]
+#![cfg_attr(not(feature = "hyper-native-tls"), doc = "```rust,ignore")]
+//! extern crate darksky;
+//! extern crate tokio;
+//!
+//! # use std::error::Error;
+//! #
+//! # fn try_main() -> Result<(), Box<dyn Error>> {
+//! use darksky::{client, DarkskyHyperRequester};
+//! use std::env;
+//! use tokio::runtime::Runtime;
+//!
+//! let token = env::var("FORECAST_TOKEN")?;
+//! let client = client::hyper_native_tls();
+//!
+//! let forecast = Runtime::new()?.block_on(client.get_forecast(&token, (37.8267, -122.423)))?;
+//! #     drop(forecast);
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     try_main().unwrap();
+//! # }
+//! ```
+//!
+//! Clients for networks where all egress goes through a proxy can be created
+//! from a [`Proxy`] with the `_with_proxy` functions. HTTP proxies are always
+//! supported, and SOCKS5 proxies are supported with the socks feature. The
+//! hyper clients do not support proxies.
+//!
+//! The hyper and reqwest clients can also be created from a
+//! [`ConnectionConfig`] with the `_with_config` functions, overriding each
+//! library's defaults for pooling and keeping connections alive. This helps
+//! when making many requests, where reconnecting for each one would dominate
+//! their latency.
+//!
+//! [`ConfiguredClient`]: ../bridge/configured/struct.ConfiguredClient.html
+//! [`ConnectionConfig`]: struct.ConnectionConfig.html
+//! [`Proxy`]: struct.Proxy.html
+
+#[cfg(any(feature = "hyper-native-tls", feature = "hyper-rustls"))]
+use hyper::client::Builder as HyperBuilder;
+#[cfg(any(feature = "hyper-native-tls", feature = "hyper-rustls"))]
+use hyper::client::HttpConnector;
+#[cfg(any(feature = "hyper-native-tls", feature = "hyper-rustls"))]
+use hyper::{Body, Client};
+#[cfg(feature = "hyper-rustls")]
+use hyper_rustls::HttpsConnectorBuilder;
+#[cfg(feature = "reqwest-blocking")]
+use reqwest::blocking::{Client as BlockingClient, ClientBuilder as BlockingClientBuilder};
+#[cfg(any(feature = "reqwest-async", feature = "reqwest-blocking"))]
+use reqwest::Proxy as ReqwestProxy;
+#[cfg(feature = "reqwest-async")]
+use reqwest::{Client as ReqwestClient, ClientBuilder as ReqwestClientBuilder};
+#[cfg(any(
+    feature = "reqwest-async",
+    feature = "reqwest-blocking",
+    feature = "ureq"
+))]
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+#[cfg(any(
+    feature = "hyper-native-tls",
+    feature = "hyper-rustls",
+    feature = "reqwest-async",
+    feature = "reqwest-blocking"
+))]
+use std::time::Duration;
+#[cfg(feature = "ureq")]
+use ureq::{Agent, AgentBuilder, Proxy as UreqProxy};
+#[cfg(any(
+    feature = "reqwest-async",
+    feature = "reqwest-blocking",
+    feature = "ureq"
+))]
+use Result;
+
+/// The user agent sent by the reqwest and ureq clients.
+#[cfg(any(
+    feature = "reqwest-async",
+    feature = "reqwest-blocking",
+    feature = "ureq"
+))]
+const USER_AGENT: &str = concat!("darksky.rs/", env!("CARGO_PKG_VERSION"));
+
+/// Settings for the connections made by a client, overriding the defaults
+/// of the underlying library where given.
+///
+/// This is used by the `_with_config` functions to create hyper and reqwest
+/// clients.
+///
+/// # Examples
+///
+/// Keep up to 32 idle connections to the API open for five minutes, such as
+/// when polling many locations:
+///
+/// ```rust
+/// use darksky::client::ConnectionConfig;
+/// use std::time::Duration;
+///
+/// let config = ConnectionConfig::new()
+///     .max_idle_per_host(32)
+///     .idle_timeout(Duration::from_secs(300))
+///     .tcp_keepalive(Duration::from_secs(60));
+///
+/// assert_eq!(config.get_max_idle_per_host(), Some(32));
+/// assert_eq!(config.get_idle_timeout(), Some(Duration::from_secs(300)));
+/// assert_eq!(config.get_tcp_keepalive(), Some(Duration::from_secs(60)));
+/// ```
+///
+/// Multiplex concurrent requests over a single HTTP/2 connection, negotiated
+/// with the API when connecting:
+///
+/// ```rust
+/// use darksky::client::{ConnectionConfig, Http2};
+///
+/// let config = ConnectionConfig::new().http2(Http2::Alpn);
+///
+/// assert_eq!(config.get_http2(), Some(Http2::Alpn));
+/// ```
+#[cfg(any(
+    feature = "hyper-native-tls",
+    feature = "hyper-rustls",
+    feature = "reqwest-async",
+    feature = "reqwest-blocking"
+))]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConnectionConfig {
+    http2: Option<Http2>,
+    idle_timeout: Option<Duration>,
+    max_idle_per_host: Option<usize>,
+    tcp_keepalive: Option<Duration>,
+}
+
+#[cfg(any(
+    feature = "hyper-native-tls",
+    feature = "hyper-rustls",
+    feature = "reqwest-async",
+    feature = "reqwest-blocking"
+))]
+impl ConnectionConfig {
+    /// Creates a configuration using the library's defaults for everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables HTTP/2, so that concurrent requests are multiplexed over a
+    /// single connection rather than each opening their own.
+    ///
+    /// Only HTTP/1.1 is used by default.
+    pub fn http2(mut self, http2: Http2) -> Self {
+        self.http2 = Some(http2);
+
+        self
+    }
+
+    /// Sets how long an idle connection is kept in the pool before it is
+    /// closed.
+    pub fn idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+
+        self
+    }
+
+    /// Sets the maximum number of idle connections kept in the pool for each
+    /// host.
+    ///
+    /// This should be at least the number of requests that are expected to be
+    /// in flight at once. A maximum of `0` disables pooling.
+    pub fn max_idle_per_host(mut self, max_idle_per_host: usize) -> Self {
+        self.max_idle_per_host = Some(max_idle_per_host);
+
+        self
+    }
+
+    /// Sets the interval of TCP keep-alive probes sent on open connections,
+    /// which keep them from being dropped by intermediaries while idle.
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+
+        self
+    }
+
+    /// Returns how HTTP/2 is used, if it is enabled.
+    pub fn get_http2(&self) -> Option<Http2> {
+        self.http2
+    }
+
+    /// Returns how long an idle connection is kept in the pool, if set.
+    pub fn get_idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout
+    }
+
+    /// Returns the maximum number of idle connections kept for each host, if
+    /// set.
+    pub fn get_max_idle_per_host(&self) -> Option<usize> {
+        self.max_idle_per_host
+    }
+
+    /// Returns the interval of TCP keep-alive probes, if set.
+    pub fn get_tcp_keepalive(&self) -> Option<Duration> {
+        self.tcp_keepalive
+    }
+
+    /// Creates a hyper client builder with the pool settings applied.
+    #[cfg(any(feature = "hyper-native-tls", feature = "hyper-rustls"))]
+    fn hyper_builder(&self) -> HyperBuilder {
+        let mut builder = Client::builder();
+
+        if let Some(max_idle_per_host) = self.max_idle_per_host {
+            builder.pool_max_idle_per_host(max_idle_per_host);
+        }
+
+        if let Some(idle_timeout) = self.idle_timeout {
+            builder.pool_idle_timeout(idle_timeout);
+        }
+
+        if self.http2 == Some(Http2::PriorKnowledge) {
+            builder.http2_only(true);
+        }
+
+        builder
+    }
+
+    /// Creates a hyper connector for wrapping with TLS, with the keep-alive
+    /// setting applied.
+    #[cfg(any(feature = "hyper-native-tls", feature = "hyper-rustls"))]
+    fn http_connector(&self) -> HttpConnector {
+        let mut connector = HttpConnector::new();
+        connector.enforce_http(false);
+        connector.set_keepalive(self.tcp_keepalive);
+
+        connector
+    }
+
+    /// Applies the settings to a reqwest blocking client builder.
+    #[cfg(feature = "reqwest-blocking")]
+    fn reqwest_blocking(&self, mut builder: BlockingClientBuilder) -> BlockingClientBuilder {
+        if let Some(max_idle_per_host) = self.max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle_per_host);
+        }
+
+        if let Some(idle_timeout) = self.idle_timeout {
+            builder = builder.pool_idle_timeout(idle_timeout);
+        }
+
+        if self.http2 == Some(Http2::PriorKnowledge) {
+            builder = builder.http2_prior_knowledge();
+        }
+
+        builder.tcp_keepalive(self.tcp_keepalive)
+    }
+
+    /// Applies the settings to a reqwest non-blocking client builder.
+    #[cfg(feature = "reqwest-async")]
+    fn reqwest_async(&self, mut builder: ReqwestClientBuilder) -> ReqwestClientBuilder {
+        if let Some(max_idle_per_host) = self.max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle_per_host);
+        }
+
+        if let Some(idle_timeout) = self.idle_timeout {
+            builder = builder.pool_idle_timeout(idle_timeout);
+        }
+
+        if self.http2 == Some(Http2::PriorKnowledge) {
+            builder = builder.http2_prior_knowledge();
+        }
+
+        builder.tcp_keepalive(self.tcp_keepalive)
+    }
+}
+
+/// How a client uses HTTP/2, as set by [`ConnectionConfig::http2`].
+///
+/// [`ConnectionConfig::http2`]: struct.ConnectionConfig.html#method.http2
+#[cfg(any(
+    feature = "hyper-native-tls",
+    feature = "hyper-rustls",
+    feature = "reqwest-async",
+    feature = "reqwest-blocking"
+))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Http2 {
+    /// Offer both HTTP/2 and HTTP/1.1 when connecting, using HTTP/2 if the
+    /// server accepts it through ALPN.
+    ///
+    /// This is only supported by the hyper client using rustls. The others
+    /// fall back to HTTP/1.1.
+    Alpn,
+    /// Use HTTP/2 for every connection, without negotiating it first.
+    ///
+    /// Requests fail if the server does not support HTTP/2.
+    PriorKnowledge,
+}
+
+/// Creates a hyper client connecting over HTTPS with the system's native TLS
+/// implementation, through hyper-tls.
+///
+/// # Panics
+///
+/// Panics if the native TLS implementation could not be initialized, as
+/// hyper-tls does.
+#[cfg(feature = "hyper-native-tls")]
+pub fn hyper_native_tls() -> Client<hyper_tls::HttpsConnector<HttpConnector>, Body> {
+    hyper_native_tls_with_config(&ConnectionConfig::default())
+}
+
+/// Creates a hyper client connecting over HTTPS with the system's native TLS
+/// implementation, with the given connection settings.
+///
+/// # Panics
+///
+/// Panics if the native TLS implementation could not be initialized, as
+/// hyper-tls does.
+#[cfg(feature = "hyper-native-tls")]
+pub fn hyper_native_tls_with_config(
+    config: &ConnectionConfig,
+) -> Client<hyper_tls::HttpsConnector<HttpConnector>, Body> {
+    let mut connector = hyper_tls::HttpsConnector::new_with_connector(config.http_connector());
+    connector.https_only(true);
+
+    config.hyper_builder().build(connector)
+}
+
+/// Creates a hyper client connecting over HTTPS with rustls, through
+/// hyper-rustls, trusting the Mozilla root certificates from webpki-roots.
+///
+/// This needs no system TLS libraries, so suits statically linked binaries.
+#[cfg(feature = "hyper-rustls")]
+pub fn hyper_rustls() -> Client<hyper_rustls::HttpsConnector<HttpConnector>, Body> {
+    hyper_rustls_with_config(&ConnectionConfig::default())
+}
+
+/// Creates a hyper client connecting over HTTPS with rustls, with the given
+/// connection settings.
+#[cfg(feature = "hyper-rustls")]
+pub fn hyper_rustls_with_config(
+    config: &ConnectionConfig,
+) -> Client<hyper_rustls::HttpsConnector<HttpConnector>, Body> {
+    let builder = HttpsConnectorBuilder::new()
+        .with_webpki_roots()
+        .https_only();
+    let http = config.http_connector();
+    let connector = match config.http2 {
+        Some(Http2::Alpn) => builder.enable_all_versions().wrap_connector(http),
+        Some(Http2::PriorKnowledge) => builder.enable_http2().wrap_connector(http),
+        None => builder.enable_http1().wrap_connector(http),
+    };
+
+    config.hyper_builder().build(connector)
+}
+
+/// Creates a reqwest blocking client connecting only over HTTPS.
+///
+/// # Errors
+///
+/// Returns [`Error::Reqwest`] if the TLS backend could not be initialized.
+///
+/// [`Error::Reqwest`]: ../enum.Error.html#variant.Reqwest
+#[cfg(feature = "reqwest-blocking")]
+pub fn reqwest_blocking() -> Result<BlockingClient> {
+    reqwest_blocking_with_config(&ConnectionConfig::default())
+}
+
+/// Creates a reqwest blocking client connecting only over HTTPS, with the
+/// given connection settings.
+///
+/// # Errors
+///
+/// Returns [`Error::Reqwest`] if the TLS backend could not be initialized.
+///
+/// [`Error::Reqwest`]: ../enum.Error.html#variant.Reqwest
+#[cfg(feature = "reqwest-blocking")]
+pub fn reqwest_blocking_with_config(config: &ConnectionConfig) -> Result<BlockingClient> {
+    let builder = BlockingClient::builder()
+        .https_only(true)
+        .user_agent(USER_AGENT);
+
+    Ok(config.reqwest_blocking(builder).build()?)
+}
+
+/// Creates a reqwest non-blocking client connecting only over HTTPS.
+///
+/// # Errors
+///
+/// Returns [`Error::Reqwest`] if the TLS backend could not be initialized.
+///
+/// [`Error::Reqwest`]: ../enum.Error.html#variant.Reqwest
+#[cfg(feature = "reqwest-async")]
+pub fn reqwest_async() -> Result<ReqwestClient> {
+    reqwest_async_with_config(&ConnectionConfig::default())
+}
+
+/// Creates a reqwest non-blocking client connecting only over HTTPS, with
+/// the given connection settings.
+///
+/// # Errors
+///
+/// Returns [`Error::Reqwest`] if the TLS backend could not be initialized.
+///
+/// [`Error::Reqwest`]: ../enum.Error.html#variant.Reqwest
+#[cfg(feature = "reqwest-async")]
+pub fn reqwest_async_with_config(config: &ConnectionConfig) -> Result<ReqwestClient> {
+    let builder = ReqwestClient::builder()
+        .https_only(true)
+        .user_agent(USER_AGENT);
+
+    Ok(config.reqwest_async(builder).build()?)
+}
+
+/// A proxy to route requests to the API through, with optional credentials.
+///
+/// The URL's scheme selects the kind of proxy: `http://` for an HTTP proxy,
+/// or `socks5://` for a SOCKS5 proxy, which needs the socks feature. Use
+/// `socks5h://` to have the proxy resolve the API's host name.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::client::Proxy;
+///
+/// let proxy = Proxy::new("http://proxy.example.com:3128")
+///     .basic_auth("user", "hunter2");
+///
+/// assert_eq!(proxy.get_url(), "http://proxy.example.com:3128");
+/// assert_eq!(proxy.get_username(), Some("user"));
+/// ```
+#[cfg(any(
+    feature = "reqwest-async",
+    feature = "reqwest-blocking",
+    feature = "ureq"
+))]
+#[derive(Clone, Eq, PartialEq)]
+pub struct Proxy {
+    auth: Option<(String, String)>,
+    url: String,
+}
+
+#[cfg(any(
+    feature = "reqwest-async",
+    feature = "reqwest-blocking",
+    feature = "ureq"
+))]
+impl Proxy {
+    /// Creates a proxy at the given URL, such as
+    /// `http://proxy.example.com:3128` or `socks5://127.0.0.1:1080`.
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        Proxy {
+            auth: None,
+            url: url.into(),
+        }
+    }
+
+    /// Sets the username and password to authenticate with the proxy.
+    ///
+    /// These are sent as basic authentication to HTTP proxies, and as
+    /// username and password authentication to SOCKS5 proxies. They must not
+    /// contain characters that need percent-encoding in a URL.
+    pub fn basic_auth<U, P>(mut self, username: U, password: P) -> Self
+    where
+        U: Into<String>,
+        P: Into<String>,
+    {
+        self.auth = Some((username.into(), password.into()));
+
+        self
+    }
+
+    /// Returns the URL of the proxy, without credentials.
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the username to authenticate with, if any.
+    pub fn get_username(&self) -> Option<&str> {
+        self.auth.as_ref().map(|(username, _)| &username[..])
+    }
+
+    /// Returns the URL of the proxy with any credentials inserted after its
+    /// scheme, as both reqwest and ureq take them.
+    fn url_with_auth(&self) -> String {
+        let (username, password) = match self.auth {
+            Some((ref username, ref password)) => (username, password),
+            None => return self.url.clone(),
+        };
+
+        match self.url.find("://") {
+            Some(idx) => {
+                let (scheme, rest) = self.url.split_at(idx + 3);
+
+                format!("{}{}:{}@{}", scheme, username, password, rest)
+            }
+            None => format!("{}:{}@{}", username, password, self.url),
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "reqwest-async",
+    feature = "reqwest-blocking",
+    feature = "ureq"
+))]
+impl Debug for Proxy {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("Proxy")
+            .field("url", &self.url)
+            .field("username", &self.get_username())
+            .finish()
+    }
+}
+
+/// Creates a reqwest blocking client connecting only over HTTPS, through the
+/// given proxy.
+///
+/// # Errors
+///
+/// Returns [`Error::Reqwest`] if the proxy's URL is invalid, or if the TLS
+/// backend could not be initialized.
+///
+/// [`Error::Reqwest`]: ../enum.Error.html#variant.Reqwest
+#[cfg(feature = "reqwest-blocking")]
+pub fn reqwest_blocking_with_proxy(proxy: &Proxy) -> Result<BlockingClient> {
+    let client = BlockingClient::builder()
+        .https_only(true)
+        .proxy(ReqwestProxy::all(proxy.url_with_auth())?)
+        .user_agent(USER_AGENT)
+        .build()?;
+
+    Ok(client)
+}
+
+/// Creates a reqwest non-blocking client connecting only over HTTPS, through
+/// the given proxy.
+///
+/// # Errors
+///
+/// Returns [`Error::Reqwest`] if the proxy's URL is invalid, or if the TLS
+/// backend could not be initialized.
+///
+/// [`Error::Reqwest`]: ../enum.Error.html#variant.Reqwest
+#[cfg(feature = "reqwest-async")]
+pub fn reqwest_async_with_proxy(proxy: &Proxy) -> Result<ReqwestClient> {
+    let client = ReqwestClient::builder()
+        .https_only(true)
+        .proxy(ReqwestProxy::all(proxy.url_with_auth())?)
+        .user_agent(USER_AGENT)
+        .build()?;
+
+    Ok(client)
+}
+
+/// Creates a ureq agent connecting only over HTTPS.
+#[cfg(feature = "ureq")]
+pub fn ureq() -> Agent {
+    AgentBuilder::new()
+        .https_only(true)
+        .user_agent(USER_AGENT)
+        .build()
+}
+
+/// Creates a ureq agent connecting only over HTTPS, through the given proxy.
+///
+/// # Errors
+///
+/// Returns [`Error::Ureq`] if the proxy's URL is invalid.
+///
+/// [`Error::Ureq`]: ../enum.Error.html#variant.Ureq
+#[cfg(feature = "ureq")]
+pub fn ureq_with_proxy(proxy: &Proxy) -> Result<Agent> {
+    let agent = AgentBuilder::new()
+        .https_only(true)
+        .proxy(UreqProxy::new(proxy.url_with_auth())?)
+        .user_agent(USER_AGENT)
+        .build();
+
+    Ok(agent)
+}
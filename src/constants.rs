@@ -1,4 +1,270 @@
-//! A set of constants used by the library.
+//! A set of constants used by the library, and the [`Config`] that clients
+//! accept to override them.
+//!
+//! The defaults describe the public DarkSky API. Forks, mirrors, and tests
+//! can point a client at another server or budget for a different plan by
+//! wrapping it in a [`ConfiguredClient`] with their own [`Config`].
+//!
+//! [`Config`]: struct.Config.html
+//! [`ConfiguredClient`]: ../bridge/configured/struct.ConfiguredClient.html
+
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::time::Duration;
 
 /// The base URI to the API.
-pub const API_URL: &'static str = "https://api.darksky.net";
+///
+/// This is the default, which can be overridden at runtime with
+/// [`Config::api_url`], such as to point a client at a mock server or a
+/// compatible proxy.
+///
+/// [`Config::api_url`]: struct.Config.html#method.api_url
+pub const API_URL: &str = "https://api.darksky.net";
+
+/// The response header containing the number of API calls made with a token
+/// on the current UTC day.
+pub const API_CALLS_HEADER: &str = "X-Forecast-API-Calls";
+
+/// The names of the blocks of a response, as accepted by the `exclude`
+/// option.
+///
+/// Unlike [`Block`], this includes `alerts`.
+///
+/// [`Block`]: ../enum.Block.html
+pub const BLOCK_NAMES: [&str; 6] = [
+    "alerts",
+    "currently",
+    "daily",
+    "flags",
+    "hourly",
+    "minutely",
+];
+
+/// The number of API calls per day included in DarkSky's free plan, after
+/// which requests are rejected unless billing is enabled.
+pub const DEFAULT_DAILY_LIMIT: u64 = 1000;
+
+/// The units requested when no units are given.
+pub const DEFAULT_UNITS: &str = "auto";
+
+/// The number of datapoints in the `daily` block, covering today and the
+/// next week.
+pub const DAILY_DAYS: usize = 8;
+
+/// The number of datapoints in the `hourly` block, unless it is extended.
+pub const HOURLY_HOURS: usize = 48;
+
+/// The number of datapoints in the `hourly` block when it is extended with
+/// [`Options::extend_hourly`].
+///
+/// [`Options::extend_hourly`]: ../struct.Options.html#method.extend_hourly
+pub const MAX_EXTENDED_HOURS: usize = 168;
+
+/// The number of datapoints in the `minutely` block, covering the next hour.
+pub const MINUTELY_MINUTES: usize = 61;
+
+/// Configuration overriding the defaults used by a client.
+///
+/// # Examples
+///
+/// Point a client at a local mirror, such as a mock server in tests:
+///
+/// ```rust
+/// use darksky::constants::{Config, API_URL, DEFAULT_DAILY_LIMIT};
+///
+/// let config = Config::default();
+/// assert_eq!(config.get_api_url(), API_URL);
+/// assert_eq!(config.get_daily_limit(), DEFAULT_DAILY_LIMIT);
+///
+/// let config = Config::default()
+///     .api_url("http://localhost:8080")
+///     .daily_limit(10_000);
+/// assert_eq!(config.get_api_url(), "http://localhost:8080");
+/// assert_eq!(config.get_daily_limit(), 10_000);
+/// ```
+///
+/// Snap coordinates to a grid of 0.01°, about a kilometer, so that nearby
+/// users share requests and cache entries:
+///
+/// ```rust
+/// use darksky::constants::Config;
+///
+/// let config = Config::default().grid(0.01);
+///
+/// assert_eq!(config.snap(49.89512, -97.13843), (49.9, -97.14));
+/// ```
+///
+/// Or round them to 3 decimal places, about a hundred meters, to absorb the
+/// jitter of GPS fixes from mobile clients:
+///
+/// ```rust
+/// use darksky::constants::Config;
+///
+/// let config = Config::default().decimals(3);
+///
+/// assert_eq!(config.snap(49.895121, -97.138437), (49.895, -97.138));
+/// assert_eq!(config.snap(49.895372, -97.138219), (49.895, -97.138));
+/// ```
+///
+/// Give up on requests which take longer than ten seconds, rather than
+/// waiting on a hung connection forever:
+///
+/// ```rust
+/// use darksky::constants::Config;
+/// use std::time::Duration;
+///
+/// let config = Config::default().timeout(Duration::from_secs(10));
+///
+/// assert_eq!(config.get_timeout(), Some(Duration::from_secs(10)));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    api_url: Cow<'static, str>,
+    daily_limit: u64,
+    grid: Option<f64>,
+    timeout: Option<Duration>,
+    user_agent: Option<Cow<'static, str>>,
+}
+
+#[cfg(feature = "std")]
+impl Config {
+    /// Sets the base URI of the API, without a trailing slash.
+    ///
+    /// Defaults to [`API_URL`].
+    ///
+    /// [`API_URL`]: constant.API_URL.html
+    pub fn api_url<T: Into<Cow<'static, str>>>(mut self, api_url: T) -> Self {
+        self.api_url = api_url.into();
+
+        self
+    }
+
+    /// Sets the number of API calls allowed per day, as used by
+    /// [`Health::quota_remaining`].
+    ///
+    /// Defaults to [`DEFAULT_DAILY_LIMIT`].
+    ///
+    /// [`DEFAULT_DAILY_LIMIT`]: constant.DEFAULT_DAILY_LIMIT.html
+    /// [`Health::quota_remaining`]: ../health/struct.Health.html#method.quota_remaining
+    pub fn daily_limit(mut self, daily_limit: u64) -> Self {
+        self.daily_limit = daily_limit;
+
+        self
+    }
+
+    /// Rounds requested coordinates to the given number of decimal places
+    /// before they are used in URIs.
+    ///
+    /// This is a [grid] of `10^-places` degrees, replacing any other grid.
+    ///
+    /// [grid]: #method.grid
+    pub fn decimals(self, places: u32) -> Self {
+        self.grid(1.0 / 10f64.powi(places as i32))
+    }
+
+    /// Snaps requested coordinates to a grid of the given size in degrees,
+    /// such as `0.01`, before they are used in URIs.
+    ///
+    /// Requests for nearby locations then resolve to the same coordinates,
+    /// which improves cache hit rates when many users are clustered in the
+    /// same area. Coordinates are not snapped by default.
+    pub fn grid(mut self, degrees: f64) -> Self {
+        self.grid = Some(degrees);
+
+        self
+    }
+
+    /// Sets how long a request may take, including connecting and reading the
+    /// response body, before it fails with [`Error::Timeout`].
+    ///
+    /// This is the default for every request made by a client using the
+    /// configuration. Requests are not timed out by default.
+    ///
+    /// [`Error::Timeout`]: ../enum.Error.html#variant.Timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with each request, replacing that of
+    /// the underlying HTTP client.
+    ///
+    /// The header is left to the HTTP client by default.
+    pub fn user_agent<T: Into<Cow<'static, str>>>(mut self, user_agent: T) -> Self {
+        self.user_agent = Some(user_agent.into());
+
+        self
+    }
+
+    /// Returns the base URI of the API.
+    pub fn get_api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    /// Returns the number of API calls allowed per day.
+    pub fn get_daily_limit(&self) -> u64 {
+        self.daily_limit
+    }
+
+    /// Returns the size in degrees of the grid that coordinates are snapped
+    /// to, if any.
+    pub fn get_grid(&self) -> Option<f64> {
+        self.grid
+    }
+
+    /// Returns how long a request may take before it is timed out, if there
+    /// is a limit.
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Returns the `User-Agent` header sent with each request, if it is
+    /// overridden.
+    pub fn get_user_agent(&self) -> Option<&str> {
+        self.user_agent.as_ref().map(|user_agent| &user_agent[..])
+    }
+
+    /// Snaps a latitude and longitude to the nearest point of the configured
+    /// [grid], returning them unchanged if there is none.
+    ///
+    /// [grid]: #method.grid
+    pub fn snap(&self, latitude: f64, longitude: f64) -> (f64, f64) {
+        match self.grid {
+            Some(grid) if grid > 0.0 && grid.is_finite() => {
+                (snap(latitude, grid), snap(longitude, grid))
+            }
+            _ => (latitude, longitude),
+        }
+    }
+}
+
+/// Rounds a coordinate to the nearest multiple of a grid size.
+#[cfg(feature = "std")]
+fn snap(value: f64, grid: f64) -> f64 {
+    let cells = 1.0 / grid;
+
+    // Dividing by a whole number of cells per degree, rather than multiplying
+    // by the grid size, gives the closest float to the snapped value, so that
+    // it is formatted without noise such as `49.900000000000006`.
+    if (cells - cells.round()).abs() < 1e-9 {
+        (value * cells.round()).round() / cells.round()
+    } else {
+        (value / grid).round() * grid
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            api_url: Cow::Borrowed(API_URL),
+            daily_limit: DEFAULT_DAILY_LIMIT,
+            grid: None,
+            timeout: None,
+            user_agent: None,
+        }
+    }
+}
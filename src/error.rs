@@ -14,18 +14,56 @@
 // CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
 // CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 
+use alloc::string::String;
+#[cfg(feature = "std")]
+use core::fmt::Write;
+use core::fmt::{Debug, Display, Error as FmtError, Formatter, Result as FmtResult};
+use core::result::Result as StdResult;
+use core::time::Duration;
 use serde_json::{Error as JsonError, Value};
+#[cfg(feature = "std")]
+use std::env::VarError;
+#[cfg(feature = "std")]
 use std::error::Error as StdError;
-use std::fmt::{Display, Error as FmtError, Formatter, Result as FmtResult};
+#[cfg(feature = "std")]
 use std::io::Error as IoError;
-use std::result::Result as StdResult;
+#[cfg(feature = "std")]
+use utils;
 
-#[cfg(feature = "hyper")]
+#[cfg(feature = "attohttpc")]
+use attohttpc::Error as AttohttpcError;
+#[cfg(feature = "awc")]
+use awc::error::{PayloadError as AwcPayloadError, SendRequestError as AwcSendError};
+#[cfg(feature = "curl")]
+use curl::{Error as CurlError, MultiError as CurlMultiError};
+#[cfg(feature = "geohash")]
+use geohash::GeohashError;
+#[cfg(feature = "wasm")]
+use gloo_net::Error as GlooError;
+#[cfg(feature = "http")]
+use http::header::InvalidHeaderValue;
+#[cfg(feature = "http")]
 use http::uri::InvalidUri;
 #[cfg(feature = "hyper")]
-use hyper::error::Error as HyperError;
-#[cfg(feature = "reqwest")]
+use hyper::Error as HyperError;
+#[cfg(feature = "minreq")]
+use minreq::Error as MinreqError;
+#[cfg(feature = "prometheus")]
+use prometheus::Error as PrometheusError;
+#[cfg(feature = "cache-redis")]
+use redis::RedisError;
+#[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
 use reqwest::Error as ReqwestError;
+#[cfg(feature = "mqtt")]
+use rumqttc::ClientError as MqttError;
+#[cfg(feature = "quota-sqlite")]
+use rusqlite::Error as SqliteError;
+#[cfg(feature = "surf")]
+use surf::Error as SurfError;
+#[cfg(feature = "ureq")]
+use ureq::Error as UreqError;
+#[cfg(feature = "url")]
+use url::ParseError as UrlError;
 
 /// A generic result type for all public-facing functions within the library.
 pub type Result<T> = StdResult<T, Error>;
@@ -33,27 +71,221 @@ pub type Result<T> = StdResult<T, Error>;
 /// Common result type for the library's [`Result`] type. Includes errors for
 /// JSON decoding, Io errors, etc.
 ///
+/// The API token is masked with [`utils::redact`] in the `Display` and `Debug`
+/// output of errors from the HTTP clients, whose messages may contain the URI
+/// of the request.
+///
 /// [`Result`]: type.Result.html
-#[derive(Debug)]
+/// [`utils::redact`]: utils/fn.redact.html
 pub enum Error {
+    /// The API responded with an error, with the code and message of its
+    /// body, such as a `403` when the token is invalid or has reached its
+    /// daily limit.
+    Api(u16, String),
+    /// An `attohttpc` crate error
+    #[cfg(feature = "attohttpc")]
+    Attohttpc(AttohttpcError),
+    /// An `awc` crate error while reading a response body
+    #[cfg(feature = "awc")]
+    AwcPayload(AwcPayloadError),
+    /// An `awc` crate error while sending a request, with the error's
+    /// description.
+    ///
+    /// Only the description is kept, as `awc`'s errors are not `Send`.
+    #[cfg(feature = "awc")]
+    AwcSend(String),
+    /// A request was cancelled through its [`CancelHandle`].
+    ///
+    /// [`CancelHandle`]: bridge/cancel/struct.CancelHandle.html
+    Cancelled,
+    /// A request was refused by an open [`CircuitBreaker`] without being
+    /// sent, with how long until the breaker lets a request through again.
+    ///
+    /// [`CircuitBreaker`]: bridge/circuit/struct.CircuitBreaker.html
+    CircuitOpen(Duration),
+    /// A `curl` crate error
+    #[cfg(feature = "curl")]
+    Curl(CurlError),
+    /// A `curl` crate error from a `Multi` handle
+    #[cfg(feature = "curl")]
+    CurlMulti(CurlMultiError),
     /// A json decoding error, with a description and the value. This occurs
     /// when the received value type is not of the expected type.
     Decode(&'static str, Value),
+    /// An environment variable, such as one read by an [`EnvToken`], was
+    /// not set or was not unicode.
+    ///
+    /// [`EnvToken`]: struct.EnvToken.html
+    #[cfg(feature = "std")]
+    Env(VarError),
     /// A `std::fmt` error
     Fmt(FmtError),
+    /// A geohash passed as a location could not be decoded.
+    #[cfg(feature = "geohash")]
+    Geohash(GeohashError),
+    /// A header value, such as a configured user agent, contained
+    /// characters which are not allowed in headers.
+    #[cfg(feature = "http")]
+    Header(InvalidHeaderValue),
     /// A `hyper` crate error
     #[cfg(feature = "hyper")]
     Hyper(HyperError),
+    /// A latitude outside of `[-90, 90]` or a longitude outside of
+    /// `[-180, 180]`, or either not being finite, with the latitude and
+    /// longitude requested. Such requests are rejected before being sent.
+    InvalidCoordinate(f64, f64),
     /// A `serde_json` crate error
     Json(JsonError),
+    /// A `minreq` crate error
+    #[cfg(feature = "minreq")]
+    Minreq(MinreqError),
+    /// A `rumqttc` crate error
+    #[cfg(feature = "mqtt")]
+    Mqtt(MqttError),
     /// A `std::io` module error
+    #[cfg(feature = "std")]
     Io(IoError),
-    #[cfg(feature = "reqwest")]
+    /// An API token was malformed, with a description of the problem.
+    InvalidToken(&'static str),
+    /// An [`Ensemble`] was asked for a forecast without any providers.
+    ///
+    /// [`Ensemble`]: adapters/ensemble/struct.Ensemble.html
+    NoProviders,
+    /// A non-finite value, such as `NaN`, was found in the named numeric
+    /// field while checking a model with [`NonFinite::Reject`].
+    ///
+    /// [`NonFinite::Reject`]: models/enum.NonFinite.html#variant.Reject
+    NonFinite(&'static str),
+    /// A `prometheus` crate error
+    #[cfg(feature = "prometheus")]
+    Prometheus(PrometheusError),
+    /// A `redis` crate error
+    #[cfg(feature = "cache-redis")]
+    Redis(RedisError),
+    #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
     /// A `reqwest` crate error
     Reqwest(ReqwestError),
+    /// A `rusqlite` crate error
+    #[cfg(feature = "quota-sqlite")]
+    Sqlite(SqliteError),
+    /// A `surf` crate error
+    #[cfg(feature = "surf")]
+    Surf(SurfError),
+    /// Every token of a [`TokenPool`] was rejected or has reached its daily
+    /// limit.
+    ///
+    /// [`TokenPool`]: struct.TokenPool.html
+    #[cfg(feature = "std")]
+    TokensExhausted,
+    /// A request took longer than its timeout, such as the one set by
+    /// [`Config::timeout`].
+    ///
+    /// [`Config::timeout`]: constants/struct.Config.html#method.timeout
+    Timeout,
+    /// A `ureq` crate error
+    #[cfg(feature = "ureq")]
+    Ureq(Box<UreqError>),
     /// An error while parsing a URI.
-    #[cfg(feature = "hyper")]
+    #[cfg(feature = "http")]
     Uri(InvalidUri),
+    /// An error while parsing a URL with the `url` crate.
+    #[cfg(feature = "url")]
+    Url(UrlError),
+    /// A `gloo-net` crate error from the browser's fetch API
+    #[cfg(feature = "wasm")]
+    Wasm(GlooError),
+}
+
+impl Error {
+    /// Returns whether the error is likely to be transient, so that the
+    /// request may succeed if it is retried.
+    ///
+    /// This is the case for timeouts, for errors of the HTTP clients sending
+    /// a request or reading its response, such as a reset connection, and
+    /// for server errors from the API. Errors from the API for a bad request
+    /// or an exhausted token are not transient.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use darksky::Error;
+    ///
+    /// assert!(Error::Timeout.is_transient());
+    /// assert!(Error::Api(503, "Service Unavailable".to_owned()).is_transient());
+    /// assert!(!Error::Api(403, "daily usage limit exceeded".to_owned()).is_transient());
+    /// ```
+    pub fn is_transient(&self) -> bool {
+        match *self {
+            Error::Api(code, _) => code >= 500,
+            #[cfg(feature = "attohttpc")]
+            Error::Attohttpc(_) => true,
+            #[cfg(feature = "awc")]
+            Error::AwcPayload(_) | Error::AwcSend(_) => true,
+            #[cfg(feature = "curl")]
+            Error::Curl(_) | Error::CurlMulti(_) => true,
+            #[cfg(feature = "hyper")]
+            Error::Hyper(ref inner) => !inner.is_user() && !inner.is_parse(),
+            #[cfg(feature = "std")]
+            Error::Io(_) => true,
+            #[cfg(feature = "minreq")]
+            Error::Minreq(_) => true,
+            #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+            Error::Reqwest(ref inner) => match inner.status() {
+                Some(status) => status.is_server_error(),
+                None => !inner.is_builder() && !inner.is_decode() && !inner.is_redirect(),
+            },
+            #[cfg(feature = "surf")]
+            Error::Surf(ref inner) => inner.status().is_server_error(),
+            Error::Timeout => true,
+            #[cfg(feature = "ureq")]
+            Error::Ureq(ref inner) => match **inner {
+                UreqError::Status(code, _) => code >= 500,
+                UreqError::Transport(_) => true,
+            },
+            #[cfg(feature = "wasm")]
+            Error::Wasm(_) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "attohttpc")]
+impl From<AttohttpcError> for Error {
+    fn from(err: AttohttpcError) -> Error {
+        Error::Attohttpc(err)
+    }
+}
+
+#[cfg(feature = "awc")]
+impl From<AwcPayloadError> for Error {
+    fn from(err: AwcPayloadError) -> Error {
+        Error::AwcPayload(err)
+    }
+}
+
+#[cfg(feature = "awc")]
+impl From<AwcSendError> for Error {
+    fn from(err: AwcSendError) -> Error {
+        Error::AwcSend(err.to_string())
+    }
+}
+
+#[cfg(feature = "curl")]
+impl From<CurlError> for Error {
+    fn from(err: CurlError) -> Error {
+        if err.is_operation_timedout() {
+            return Error::Timeout;
+        }
+
+        Error::Curl(err)
+    }
+}
+
+#[cfg(feature = "curl")]
+impl From<CurlMultiError> for Error {
+    fn from(err: CurlMultiError) -> Error {
+        Error::CurlMulti(err)
+    }
 }
 
 impl From<FmtError> for Error {
@@ -62,6 +294,20 @@ impl From<FmtError> for Error {
     }
 }
 
+#[cfg(feature = "geohash")]
+impl From<GeohashError> for Error {
+    fn from(err: GeohashError) -> Error {
+        Error::Geohash(err)
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<InvalidHeaderValue> for Error {
+    fn from(err: InvalidHeaderValue) -> Error {
+        Error::Header(err)
+    }
+}
+
 #[cfg(feature = "hyper")]
 impl From<HyperError> for Error {
     fn from(err: HyperError) -> Error {
@@ -69,6 +315,14 @@ impl From<HyperError> for Error {
     }
 }
 
+#[cfg(feature = "std")]
+impl From<VarError> for Error {
+    fn from(err: VarError) -> Error {
+        Error::Env(err)
+    }
+}
+
+#[cfg(feature = "std")]
 impl From<IoError> for Error {
     fn from(err: IoError) -> Error {
         Error::Io(err)
@@ -81,17 +335,256 @@ impl From<JsonError> for Error {
     }
 }
 
-#[cfg(feature = "reqwest")]
+#[cfg(feature = "minreq")]
+impl From<MinreqError> for Error {
+    fn from(err: MinreqError) -> Error {
+        Error::Minreq(err)
+    }
+}
+
+#[cfg(feature = "mqtt")]
+impl From<MqttError> for Error {
+    fn from(err: MqttError) -> Error {
+        Error::Mqtt(err)
+    }
+}
+
+#[cfg(feature = "prometheus")]
+impl From<PrometheusError> for Error {
+    fn from(err: PrometheusError) -> Error {
+        Error::Prometheus(err)
+    }
+}
+
+#[cfg(feature = "cache-redis")]
+impl From<RedisError> for Error {
+    fn from(err: RedisError) -> Error {
+        Error::Redis(err)
+    }
+}
+
+#[cfg(feature = "quota-sqlite")]
+impl From<SqliteError> for Error {
+    fn from(err: SqliteError) -> Error {
+        Error::Sqlite(err)
+    }
+}
+
+#[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
 impl From<ReqwestError> for Error {
     fn from(err: ReqwestError) -> Error {
+        if err.is_timeout() {
+            return Error::Timeout;
+        }
+
         Error::Reqwest(err)
     }
 }
 
+#[cfg(feature = "surf")]
+impl From<SurfError> for Error {
+    fn from(err: SurfError) -> Error {
+        Error::Surf(err)
+    }
+}
+
+#[cfg(feature = "ureq")]
+impl From<UreqError> for Error {
+    fn from(err: UreqError) -> Error {
+        Error::Ureq(Box::new(err))
+    }
+}
+
+#[cfg(feature = "url")]
+impl From<UrlError> for Error {
+    fn from(err: UrlError) -> Error {
+        Error::Url(err)
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<GlooError> for Error {
+    fn from(err: GlooError) -> Error {
+        Error::Wasm(err)
+    }
+}
+
+impl Debug for Error {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            Error::Api(code, ref message) => {
+                f.debug_tuple("Api").field(&code).field(message).finish()
+            }
+            #[cfg(feature = "attohttpc")]
+            Error::Attohttpc(ref inner) => {
+                f.debug_tuple("Attohttpc").field(&Redacted(inner)).finish()
+            }
+            #[cfg(feature = "awc")]
+            Error::AwcPayload(ref inner) => {
+                f.debug_tuple("AwcPayload").field(&Redacted(inner)).finish()
+            }
+            #[cfg(feature = "awc")]
+            Error::AwcSend(ref why) => f.debug_tuple("AwcSend").field(&Redacted(why)).finish(),
+            Error::Cancelled => f.write_str("Cancelled"),
+            Error::CircuitOpen(ref retry_in) => {
+                f.debug_tuple("CircuitOpen").field(retry_in).finish()
+            }
+            #[cfg(feature = "curl")]
+            Error::Curl(ref inner) => f.debug_tuple("Curl").field(&Redacted(inner)).finish(),
+            #[cfg(feature = "curl")]
+            Error::CurlMulti(ref inner) => {
+                f.debug_tuple("CurlMulti").field(&Redacted(inner)).finish()
+            }
+            Error::Decode(msg, ref value) => {
+                f.debug_tuple("Decode").field(&msg).field(value).finish()
+            }
+            #[cfg(feature = "std")]
+            Error::Env(ref inner) => f.debug_tuple("Env").field(inner).finish(),
+            Error::Fmt(ref inner) => f.debug_tuple("Fmt").field(inner).finish(),
+            #[cfg(feature = "geohash")]
+            Error::Geohash(ref inner) => f.debug_tuple("Geohash").field(inner).finish(),
+            #[cfg(feature = "http")]
+            Error::Header(ref inner) => f.debug_tuple("Header").field(inner).finish(),
+            #[cfg(feature = "hyper")]
+            Error::Hyper(ref inner) => f.debug_tuple("Hyper").field(&Redacted(inner)).finish(),
+            Error::InvalidCoordinate(latitude, longitude) => f
+                .debug_tuple("InvalidCoordinate")
+                .field(&latitude)
+                .field(&longitude)
+                .finish(),
+            Error::Json(ref inner) => f.debug_tuple("Json").field(inner).finish(),
+            #[cfg(feature = "minreq")]
+            Error::Minreq(ref inner) => f.debug_tuple("Minreq").field(&Redacted(inner)).finish(),
+            #[cfg(feature = "mqtt")]
+            Error::Mqtt(ref inner) => f.debug_tuple("Mqtt").field(&Redacted(inner)).finish(),
+            #[cfg(feature = "std")]
+            Error::Io(ref inner) => f.debug_tuple("Io").field(&Redacted(inner)).finish(),
+            Error::InvalidToken(why) => f.debug_tuple("InvalidToken").field(&why).finish(),
+            Error::NoProviders => f.write_str("NoProviders"),
+            Error::NonFinite(field) => f.debug_tuple("NonFinite").field(&field).finish(),
+            #[cfg(feature = "prometheus")]
+            Error::Prometheus(ref inner) => {
+                f.debug_tuple("Prometheus").field(&Redacted(inner)).finish()
+            }
+            #[cfg(feature = "cache-redis")]
+            Error::Redis(ref inner) => f.debug_tuple("Redis").field(inner).finish(),
+            #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+            Error::Reqwest(ref inner) => f.debug_tuple("Reqwest").field(&Redacted(inner)).finish(),
+            #[cfg(feature = "quota-sqlite")]
+            Error::Sqlite(ref inner) => f.debug_tuple("Sqlite").field(inner).finish(),
+            #[cfg(feature = "surf")]
+            Error::Surf(ref inner) => f.debug_tuple("Surf").field(&Redacted(inner)).finish(),
+            Error::Timeout => f.write_str("Timeout"),
+            #[cfg(feature = "std")]
+            Error::TokensExhausted => f.write_str("TokensExhausted"),
+            #[cfg(feature = "ureq")]
+            Error::Ureq(ref inner) => f.debug_tuple("Ureq").field(&Redacted(inner)).finish(),
+            #[cfg(feature = "http")]
+            Error::Uri(ref inner) => f.debug_tuple("Uri").field(inner).finish(),
+            #[cfg(feature = "url")]
+            Error::Url(ref inner) => f.debug_tuple("Url").field(inner).finish(),
+            #[cfg(feature = "wasm")]
+            Error::Wasm(ref inner) => f.debug_tuple("Wasm").field(&Redacted(inner)).finish(),
+        }
+    }
+}
+
+/// Formats an error of an HTTP client with the token masked in any URIs.
+#[cfg(feature = "std")]
+struct Redacted<'a, T: ?Sized + 'a>(&'a T);
+
+#[cfg(feature = "std")]
+impl<'a, T: Debug + ?Sized> Debug for Redacted<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let mut buffer = String::new();
+
+        if f.alternate() {
+            write!(buffer, "{:#?}", self.0)?;
+        } else {
+            write!(buffer, "{:?}", self.0)?;
+        }
+
+        f.write_str(&utils::redact(&buffer))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, T: Display + ?Sized> Display for Redacted<'a, T> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let mut buffer = String::new();
+        write!(buffer, "{}", self.0)?;
+
+        f.write_str(&utils::redact(&buffer))
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        write!(f, "{}", self)
+        match *self {
+            Error::Api(code, ref message) => write!(f, "API error {}: {}", code, message),
+            #[cfg(feature = "attohttpc")]
+            Error::Attohttpc(ref inner) => Display::fmt(&Redacted(inner), f),
+            #[cfg(feature = "awc")]
+            Error::AwcPayload(ref inner) => Display::fmt(&Redacted(inner), f),
+            #[cfg(feature = "awc")]
+            Error::AwcSend(ref why) => f.write_str(&utils::redact(why)),
+            Error::Cancelled => f.write_str("request was cancelled"),
+            Error::CircuitOpen(ref retry_in) => {
+                write!(f, "circuit breaker is open, retrying in {:?}", retry_in)
+            }
+            #[cfg(feature = "curl")]
+            Error::Curl(ref inner) => Display::fmt(&Redacted(inner), f),
+            #[cfg(feature = "curl")]
+            Error::CurlMulti(ref inner) => Display::fmt(&Redacted(inner), f),
+            Error::Decode(msg, ref value) => write!(f, "{}: {}", msg, value),
+            #[cfg(feature = "std")]
+            Error::Env(ref inner) => Display::fmt(inner, f),
+            Error::Fmt(ref inner) => Display::fmt(inner, f),
+            #[cfg(feature = "geohash")]
+            Error::Geohash(ref inner) => Display::fmt(inner, f),
+            #[cfg(feature = "http")]
+            Error::Header(ref inner) => Display::fmt(inner, f),
+            #[cfg(feature = "hyper")]
+            Error::Hyper(ref inner) => Display::fmt(&Redacted(inner), f),
+            Error::InvalidCoordinate(latitude, longitude) => write!(
+                f,
+                "invalid coordinate ({}, {}): latitude must be within [-90, 90] and longitude within [-180, 180]",
+                latitude, longitude
+            ),
+            Error::Json(ref inner) => Display::fmt(inner, f),
+            #[cfg(feature = "minreq")]
+            Error::Minreq(ref inner) => Display::fmt(&Redacted(inner), f),
+            #[cfg(feature = "mqtt")]
+            Error::Mqtt(ref inner) => Display::fmt(&Redacted(inner), f),
+            #[cfg(feature = "std")]
+            Error::Io(ref inner) => Display::fmt(&Redacted(inner), f),
+            Error::InvalidToken(why) => write!(f, "invalid API token: {}", why),
+            Error::NoProviders => f.write_str("no providers to request a forecast from"),
+            Error::NonFinite(field) => write!(f, "non-finite value in `{}`", field),
+            #[cfg(feature = "prometheus")]
+            Error::Prometheus(ref inner) => Display::fmt(inner, f),
+            #[cfg(feature = "cache-redis")]
+            Error::Redis(ref inner) => Display::fmt(inner, f),
+            #[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
+            Error::Reqwest(ref inner) => Display::fmt(&Redacted(inner), f),
+            #[cfg(feature = "quota-sqlite")]
+            Error::Sqlite(ref inner) => Display::fmt(inner, f),
+            #[cfg(feature = "surf")]
+            Error::Surf(ref inner) => Display::fmt(&Redacted(inner), f),
+            Error::Timeout => f.write_str("request timed out"),
+            #[cfg(feature = "std")]
+            Error::TokensExhausted => f.write_str("every token in the pool is exhausted"),
+            #[cfg(feature = "ureq")]
+            Error::Ureq(ref inner) => Display::fmt(&Redacted(inner), f),
+            #[cfg(feature = "http")]
+            Error::Uri(ref inner) => Display::fmt(inner, f),
+            #[cfg(feature = "url")]
+            Error::Url(ref inner) => Display::fmt(inner, f),
+            #[cfg(feature = "wasm")]
+            Error::Wasm(ref inner) => Display::fmt(&Redacted(inner), f),
+        }
     }
 }
 
+#[cfg(feature = "std")]
 impl StdError for Error {}
@@ -0,0 +1,199 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Exporting of the latest weather values as Prometheus gauges via the
+//! `prometheus` crate.
+//!
+//! Every gauge is labeled by `location`, so a single [`WeatherExporter`] can
+//! track any number of locations.
+//!
+//! [`WeatherExporter`]: struct.WeatherExporter.html
+
+use models::{Datapoint, Forecast};
+use prometheus::core::{Collector, Desc};
+use prometheus::proto::MetricFamily;
+use prometheus::{GaugeVec, Opts};
+use Result;
+
+/// A Prometheus collector exposing the most recently observed weather values
+/// for each location.
+///
+/// The exposed gauges are:
+///
+/// - `darksky_temperature`
+/// - `darksky_apparent_temperature`
+/// - `darksky_humidity`
+/// - `darksky_pressure`
+/// - `darksky_precip_probability`
+/// - `darksky_precip_intensity`
+/// - `darksky_wind_speed`
+/// - `darksky_cloud_cover`
+/// - `darksky_alerts`, the number of active alerts
+///
+/// Values are in whichever [`Unit`]s the forecast was requested in.
+///
+/// Cloning an exporter is cheap, and clones share the same gauges.
+///
+/// # Examples
+///
+/// Register an exporter and record a forecast:
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate prometheus;
+/// extern crate serde_json;
+///
+/// use darksky::exporter::WeatherExporter;
+/// use darksky::models::Forecast;
+/// use prometheus::{Encoder, Registry, TextEncoder};
+///
+/// # fn main() {
+/// let registry = Registry::new();
+/// let exporter = WeatherExporter::new().unwrap();
+/// registry.register(Box::new(exporter.clone())).unwrap();
+///
+/// let forecast: Forecast = serde_json::from_str(r#"{
+///     "latitude": 49.9,
+///     "longitude": -97.1,
+///     "timezone": "America/Winnipeg",
+///     "currently": {"time": 1509993277, "temperature": -3.5, "humidity": 0.8}
+/// }"#).unwrap();
+/// exporter.observe("winnipeg", &forecast);
+///
+/// let mut buffer = Vec::new();
+/// TextEncoder::new().encode(&registry.gather(), &mut buffer).unwrap();
+/// let output = String::from_utf8(buffer).unwrap();
+///
+/// assert!(output.contains(r#"darksky_temperature{location="winnipeg"} -3.5"#));
+/// assert!(output.contains(r#"darksky_humidity{location="winnipeg"} 0.8"#));
+/// # }
+/// ```
+///
+/// [`Unit`]: ../enum.Unit.html
+#[derive(Clone, Debug)]
+pub struct WeatherExporter {
+    alerts: GaugeVec,
+    apparent_temperature: GaugeVec,
+    cloud_cover: GaugeVec,
+    humidity: GaugeVec,
+    precip_intensity: GaugeVec,
+    precip_probability: GaugeVec,
+    pressure: GaugeVec,
+    temperature: GaugeVec,
+    wind_speed: GaugeVec,
+}
+
+impl WeatherExporter {
+    /// Creates a new exporter with no observed locations.
+    pub fn new() -> Result<Self> {
+        Ok(WeatherExporter {
+            alerts: gauge("alerts", "Number of active severe weather alerts.")?,
+            apparent_temperature: gauge(
+                "apparent_temperature",
+                "Apparent (feels like) temperature.",
+            )?,
+            cloud_cover: gauge("cloud_cover", "Fraction of sky occluded by clouds.")?,
+            humidity: gauge("humidity", "Relative humidity, between 0 and 1.")?,
+            precip_intensity: gauge("precip_intensity", "Precipitation intensity.")?,
+            precip_probability: gauge(
+                "precip_probability",
+                "Probability of precipitation, between 0 and 1.",
+            )?,
+            pressure: gauge("pressure", "Sea-level air pressure.")?,
+            temperature: gauge("temperature", "Air temperature.")?,
+            wind_speed: gauge("wind_speed", "Wind speed.")?,
+        })
+    }
+
+    /// Records the [`currently`] datapoint and alerts of a forecast for the
+    /// named location.
+    ///
+    /// Gauges for values missing from the forecast are removed for the
+    /// location, rather than left at a stale value.
+    ///
+    /// [`currently`]: ../models/struct.Forecast.html#structfield.currently
+    pub fn observe(&self, location: &str, forecast: &Forecast) {
+        let currently = forecast.currently.as_ref();
+        let value = |f: fn(&Datapoint) -> Option<f64>| currently.and_then(f);
+
+        set(
+            &self.apparent_temperature,
+            location,
+            value(|d| d.apparent_temperature),
+        );
+        set(&self.cloud_cover, location, value(|d| d.cloud_cover));
+        set(&self.humidity, location, value(|d| d.humidity));
+        set(
+            &self.precip_intensity,
+            location,
+            value(|d| d.precip_intensity),
+        );
+        set(
+            &self.precip_probability,
+            location,
+            value(|d| d.precip_probability),
+        );
+        set(&self.pressure, location, value(|d| d.pressure));
+        set(&self.temperature, location, value(|d| d.temperature));
+        set(&self.wind_speed, location, value(|d| d.wind_speed));
+        set(&self.alerts, location, Some(forecast.alerts.len() as f64));
+    }
+
+    /// Removes all gauges for the named location.
+    pub fn remove(&self, location: &str) {
+        for gauge in self.gauges() {
+            let _ = gauge.remove_label_values(&[location]);
+        }
+    }
+
+    fn gauges(&self) -> [&GaugeVec; 9] {
+        [
+            &self.alerts,
+            &self.apparent_temperature,
+            &self.cloud_cover,
+            &self.humidity,
+            &self.precip_intensity,
+            &self.precip_probability,
+            &self.pressure,
+            &self.temperature,
+            &self.wind_speed,
+        ]
+    }
+}
+
+impl Collector for WeatherExporter {
+    fn desc(&self) -> Vec<&Desc> {
+        self.gauges().iter().flat_map(|g| g.desc()).collect()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        self.gauges().iter().flat_map(|g| g.collect()).collect()
+    }
+}
+
+fn gauge(name: &str, help: &str) -> Result<GaugeVec> {
+    let opts = Opts::new(name, help).namespace("darksky");
+
+    GaugeVec::new(opts, &["location"]).map_err(From::from)
+}
+
+fn set(gauge: &GaugeVec, location: &str, value: Option<f64>) {
+    match value {
+        Some(value) => gauge.with_label_values(&[location]).set(value),
+        None => {
+            let _ = gauge.remove_label_values(&[location]);
+        }
+    }
+}
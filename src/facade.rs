@@ -0,0 +1,458 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! A high-level client owning a token and default options, so that
+//! application code doesn't need to pass them to every request.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`DarkskyClient`] and
+//! [`DarkskyClientBuilder`].
+//!
+//! [`DarkskyClient`]: struct.DarkskyClient.html
+//! [`DarkskyClientBuilder`]: struct.DarkskyClientBuilder.html
+
+use bridge::send::{ForecastFuture, HttpSend};
+use constants::Config;
+use quota::{CallCount, QuotaStore};
+use std::borrow::Cow;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::sync::Arc;
+use std::time::Duration;
+use {utils, Block, Coordinate, IntoLocation, Language, Options, TokenProvider, Unit};
+
+/// A client for the API which owns a token, an HTTP backend, and the default
+/// options of each request.
+///
+/// Requests are sent over any [`HttpSend`] backend, such as a hyper
+/// `Client`, and return futures resolving to a [`Forecast`]. Forecasts are
+/// in `auto` units unless the options say otherwise.
+///
+/// Client-wide defaults, such as the language, units, and base URI of the
+/// API, can be set at construction with a [`DarkskyClientBuilder`].
+///
+/// The token is given by a [`TokenProvider`], such as a string or an
+/// [`EnvToken`], and is retrieved for each request. A failure to retrieve it
+/// fails the request, and failed requests are reported to the provider.
+///
+/// # Examples
+///
+/// Retrieve current and past forecasts in French, with a hyper client:
+///
+#[cfg_attr(feature = "hyper", doc = "```rust,no_run")]
+#[cfg_attr(not(feature = "hyper"), doc = "```rust,ignore")]
+/// extern crate darksky;
+/// extern crate hyper;
+/// extern crate tokio;
+///
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<dyn Error>> {
+/// use darksky::{Coordinate, DarkskyClient, EnvToken, Language};
+/// use hyper::Client;
+/// use tokio::runtime::Runtime;
+///
+/// let client = DarkskyClient::new(Client::new(), EnvToken::new("FORECAST_TOKEN"))
+///     .default_options(|o| o.language(Language::Fr));
+/// let location = Coordinate::new(37.8267, -122.423);
+///
+/// let runtime = Runtime::new()?;
+/// let now = runtime.block_on(client.forecast(location))?;
+/// let then = runtime.block_on(client.forecast_at(location, 1_450_000_000))?;
+/// #     drop((now, then));
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// [`DarkskyClientBuilder`]: struct.DarkskyClientBuilder.html
+/// [`EnvToken`]: ../struct.EnvToken.html
+/// [`Forecast`]: ../models/struct.Forecast.html
+/// [`HttpSend`]: ../bridge/send/trait.HttpSend.html
+/// [`TokenProvider`]: ../trait.TokenProvider.html
+#[derive(Clone)]
+pub struct DarkskyClient<S> {
+    calls: Arc<CallCount>,
+    config: Config,
+    options: Options,
+    sender: S,
+    token: Arc<dyn TokenProvider>,
+}
+
+impl<S: HttpSend> DarkskyClient<S> {
+    /// Creates a client sending requests over the given backend, using the
+    /// token of the given provider and no default options.
+    pub fn new<T: TokenProvider + 'static>(sender: S, token: T) -> Self {
+        DarkskyClient {
+            calls: Arc::default(),
+            config: Config::default(),
+            options: Options::default(),
+            sender,
+            token: Arc::new(token),
+        }
+    }
+
+    /// Sets the options used by every request. For a full list of options,
+    /// refer to the documentation for the [`Options`] builder.
+    ///
+    /// [`Options`]: ../struct.Options.html
+    pub fn default_options<F: FnOnce(Options) -> Options>(mut self, options: F) -> Self {
+        self.options = options(self.options);
+
+        self
+    }
+
+    /// Persists the count of the day's API calls to a store, so that
+    /// [`calls_today`] is known before the first response of a short-lived
+    /// process, and includes the calls of other processes sharing the store.
+    ///
+    /// [`calls_today`]: #method.calls_today
+    pub fn quota_store<Q: QuotaStore + 'static>(mut self, store: Q) -> Self {
+        self.calls = Arc::new(CallCount::persisted(Arc::new(store)));
+
+        self
+    }
+
+    /// Returns a copy of the client sending requests to another base URI of
+    /// the API, without a trailing slash, such as that of a staging proxy.
+    ///
+    /// The copy shares the token provider and keeps the options and the rest
+    /// of the configuration, so that a single call can be pointed elsewhere
+    /// without rebuilding the client. Backends such as hyper's `Client` are
+    /// cheap to clone, as they share their connection pool.
+    ///
+    /// # Examples
+    ///
+    #[cfg_attr(feature = "hyper", doc = "```rust")]
+    #[cfg_attr(not(feature = "hyper"), doc = "```rust,ignore")]
+    /// extern crate darksky;
+    /// extern crate hyper;
+    ///
+    /// use darksky::DarkskyClient;
+    /// use hyper::Client;
+    ///
+    /// # fn main() {
+    /// let client = DarkskyClient::new(Client::new(), "token");
+    /// let staging = client.with_api_url("https://staging.example.com");
+    ///
+    /// assert_eq!(staging.get_config().get_api_url(), "https://staging.example.com");
+    /// assert_eq!(client.get_config().get_api_url(), "https://api.darksky.net");
+    /// #     drop(staging.forecast((37.8267, -122.423)));
+    /// # }
+    /// ```
+    pub fn with_api_url<T: Into<Cow<'static, str>>>(&self, api_url: T) -> Self
+    where
+        S: Clone,
+    {
+        DarkskyClient {
+            calls: Arc::default(),
+            config: self.config.clone().api_url(api_url),
+            options: self.options.clone(),
+            sender: self.sender.clone(),
+            token: Arc::clone(&self.token),
+        }
+    }
+
+    /// Retrieves a current forecast for the given location.
+    pub fn forecast<L: IntoLocation>(&self, location: L) -> ForecastFuture<S::Future> {
+        self.forecast_with_options(location, |o| o)
+    }
+
+    /// Retrieves a current forecast for the given location, with options
+    /// applied on top of the client's default options.
+    pub fn forecast_with_options<F, L>(&self, location: L, options: F) -> ForecastFuture<S::Future>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        self.request(location, options)
+    }
+
+    /// Retrieves a forecast for the given location at a past or future time,
+    /// using DarkSky's Time Machine API.
+    ///
+    /// Refer to [`DarkskyReqwestRequester::get_forecast_time_machine`] for
+    /// the accepted time formats. The time is not validated.
+    ///
+    /// [`DarkskyReqwestRequester::get_forecast_time_machine`]:
+    ///   ../bridge/reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_time_machine
+    pub fn forecast_at<D: Display, L: IntoLocation>(
+        &self,
+        location: L,
+        time: D,
+    ) -> ForecastFuture<S::Future> {
+        self.forecast_at_with_options(location, time, |o| o)
+    }
+
+    /// Retrieves a forecast for the given location at a past or future time,
+    /// with options applied on top of the client's default options.
+    pub fn forecast_at_with_options<D, F, L>(
+        &self,
+        location: L,
+        time: D,
+        options: F,
+    ) -> ForecastFuture<S::Future>
+    where
+        D: Display,
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        self.forecast_with_options(location, |opt| options(opt).time(time))
+    }
+
+    /// Returns the number of API calls made with the token on the current UTC
+    /// day, as reported by the `X-Forecast-API-Calls` header of the latest
+    /// response, such as to warn before reaching [`DEFAULT_DAILY_LIMIT`].
+    ///
+    /// Returns `None` until a response has reported it today, such as when
+    /// the backend does not [record response metadata].
+    ///
+    /// Clones of the client share the count, while a client created with
+    /// [`with_api_url`] starts its own. The count only outlives the client
+    /// if it has a [`quota_store`].
+    ///
+    /// [`DEFAULT_DAILY_LIMIT`]: ../constants/constant.DEFAULT_DAILY_LIMIT.html
+    /// [`quota_store`]: #method.quota_store
+    /// [`with_api_url`]: #method.with_api_url
+    /// [record response metadata]: ../bridge/send/trait.HttpSend.html#method.send_recording
+    pub fn calls_today(&self) -> Option<u64> {
+        self.calls.today()
+    }
+
+    /// Returns the configuration of the client, such as the base URI of the
+    /// API.
+    pub fn get_config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns a reference to the backend.
+    pub fn get_ref(&self) -> &S {
+        &self.sender
+    }
+
+    /// Returns the options used by every request.
+    pub fn get_default_options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Returns the provider of the token used by every request.
+    pub fn get_token_provider(&self) -> &dyn TokenProvider {
+        &*self.token
+    }
+
+    fn request<F, L>(&self, location: L, options: F) -> ForecastFuture<S::Future>
+    where
+        F: FnOnce(Options) -> Options,
+        L: IntoLocation,
+    {
+        let mut options = options(self.options.clone());
+        options.unit.get_or_insert(Unit::Auto);
+
+        let token = match self.token.token() {
+            Ok(token) => token,
+            Err(why) => return ForecastFuture::configured(&self.sender, &self.config, Err(why)),
+        };
+        let uri = location
+            .into_location()
+            .and_then(|Coordinate { lat, lon }| utils::snapped(self.sender.uri_config(), lat, lon))
+            .and_then(|(lat, lon)| {
+                utils::uri_configured(&self.config, &token, lat, lon, None, options)
+            });
+
+        ForecastFuture::counted(&self.sender, &self.config, uri, &self.calls)
+            .reporting_to(&self.token, &token)
+    }
+}
+
+impl<S: Debug> Debug for DarkskyClient<S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("DarkskyClient")
+            .field("config", &self.config)
+            .field("options", &self.options)
+            .field("sender", &self.sender)
+            .finish()
+    }
+}
+
+/// A builder for a [`DarkskyClient`], setting defaults for every request
+/// once at construction, rather than in each request's options.
+///
+/// # Examples
+///
+/// Build a client for French forecasts in SI units, without minutely data,
+/// from a local mirror of the API:
+///
+#[cfg_attr(feature = "hyper", doc = "```rust")]
+#[cfg_attr(not(feature = "hyper"), doc = "```rust,ignore")]
+/// extern crate darksky;
+/// extern crate hyper;
+///
+/// use darksky::{Block, DarkskyClientBuilder, Language, Unit};
+/// use hyper::Client;
+/// use std::time::Duration;
+///
+/// # fn main() {
+/// let client = DarkskyClientBuilder::new("token")
+///     .language(Language::Fr)
+///     .unit(Unit::Si)
+///     .exclude(vec![Block::Minutely])
+///     .api_url("http://localhost:8080")
+///     .timeout(Duration::from_secs(10))
+///     .user_agent("my-weather-app/1.0")
+///     .build(Client::new());
+///
+/// assert_eq!(client.get_config().get_api_url(), "http://localhost:8080");
+/// # }
+/// ```
+///
+/// [`DarkskyClient`]: struct.DarkskyClient.html
+#[derive(Clone)]
+pub struct DarkskyClientBuilder {
+    config: Config,
+    options: Options,
+    quota: Option<Arc<dyn QuotaStore>>,
+    token: Arc<dyn TokenProvider>,
+}
+
+impl DarkskyClientBuilder {
+    /// Creates a builder for a client using the token of the given provider,
+    /// with no defaults set.
+    pub fn new<T: TokenProvider + 'static>(token: T) -> Self {
+        DarkskyClientBuilder {
+            config: Config::default(),
+            options: Options::default(),
+            quota: None,
+            token: Arc::new(token),
+        }
+    }
+
+    /// Sets the base URI of the API, without a trailing slash, such as that
+    /// of a mock server or a compatible proxy.
+    ///
+    /// Refer to [`Config::api_url`].
+    ///
+    /// [`Config::api_url`]: ../constants/struct.Config.html#method.api_url
+    pub fn api_url<T: Into<Cow<'static, str>>>(mut self, api_url: T) -> Self {
+        self.config = self.config.api_url(api_url);
+
+        self
+    }
+
+    /// Sets the configuration of the client, replacing any base URI, timeout,
+    /// or user agent set so far.
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+
+        self
+    }
+
+    /// Adds blocks excluded from every forecast.
+    ///
+    /// Blocks excluded by the options of a request are excluded along with
+    /// these.
+    pub fn exclude<I: IntoIterator<Item = Block>>(mut self, blocks: I) -> Self {
+        self.options = self.options.exclude(blocks);
+
+        self
+    }
+
+    /// Sets the language of the summaries of every forecast.
+    pub fn language(mut self, language: Language) -> Self {
+        self.options = self.options.language(language);
+
+        self
+    }
+
+    /// Sets any other options used by every request. For a full list of
+    /// options, refer to the documentation for the [`Options`] builder.
+    ///
+    /// [`Options`]: ../struct.Options.html
+    pub fn options<F: FnOnce(Options) -> Options>(mut self, options: F) -> Self {
+        self.options = options(self.options);
+
+        self
+    }
+
+    /// Sets the store the count of the day's API calls is persisted to.
+    ///
+    /// Refer to [`DarkskyClient::quota_store`].
+    ///
+    /// [`DarkskyClient::quota_store`]: struct.DarkskyClient.html#method.quota_store
+    pub fn quota_store<Q: QuotaStore + 'static>(mut self, store: Q) -> Self {
+        self.quota = Some(Arc::new(store));
+
+        self
+    }
+
+    /// Sets how long each request may take before it fails with
+    /// [`Error::Timeout`].
+    ///
+    /// This is applied by the backends listed in [`HttpSend::send_with`].
+    ///
+    /// [`Error::Timeout`]: ../enum.Error.html#variant.Timeout
+    /// [`HttpSend::send_with`]: ../bridge/send/trait.HttpSend.html#method.send_with
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config = self.config.timeout(timeout);
+
+        self
+    }
+
+    /// Sets the units of every forecast.
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.options = self.options.unit(unit);
+
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with each request.
+    ///
+    /// This is applied by the backends listed in [`HttpSend::send_with`].
+    ///
+    /// [`HttpSend::send_with`]: ../bridge/send/trait.HttpSend.html#method.send_with
+    pub fn user_agent<T: Into<Cow<'static, str>>>(mut self, user_agent: T) -> Self {
+        self.config = self.config.user_agent(user_agent);
+
+        self
+    }
+
+    /// Builds a client sending requests over the given backend.
+    pub fn build<S: HttpSend>(self, sender: S) -> DarkskyClient<S> {
+        let calls = match self.quota {
+            Some(store) => CallCount::persisted(store),
+            None => CallCount::default(),
+        };
+
+        DarkskyClient {
+            calls: Arc::new(calls),
+            config: self.config,
+            options: self.options,
+            sender,
+            token: self.token,
+        }
+    }
+}
+
+impl Debug for DarkskyClientBuilder {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("DarkskyClientBuilder")
+            .field("config", &self.config)
+            .field("options", &self.options)
+            .field("quota", &self.quota.is_some())
+            .finish()
+    }
+}
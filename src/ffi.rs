@@ -0,0 +1,646 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! A C ABI over the models and the reqwest client, so that C and C++ programs
+//! can reuse this crate rather than writing their own DarkSky client.
+//!
+//! Forecasts are passed around as opaque `DarkskyForecast` handles, which are
+//! created by [`darksky_forecast_from_json`] or [`darksky_fetch_forecast`] and
+//! released with [`darksky_forecast_free`]. Datapoints and alerts are borrowed
+//! from their forecast, and are only valid until it is freed.
+//!
+//! Missing numbers are returned as `NAN`, and strings are copied into a
+//! caller-provided buffer in the style of `snprintf`. A C header is provided
+//! at `include/darksky.h`, which can be regenerated with cbindgen using the
+//! repository's `cbindgen.toml`.
+//!
+//! A shared or static library can be built with:
+//!
+//! ```sh
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! [`darksky_fetch_forecast`]: fn.darksky_fetch_forecast.html
+//! [`darksky_forecast_free`]: fn.darksky_forecast_free.html
+//! [`darksky_forecast_from_json`]: fn.darksky_forecast_from_json.html
+
+use bridge::DarkskyReqwestRequester;
+use models::{Alert, Datablock, Datapoint, Forecast, Severity};
+use reqwest::blocking::Client;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_void};
+use std::{ptr, slice, thread};
+use Coordinate;
+
+/// The severity of an alert.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(C)]
+pub enum DarkskySeverity {
+    /// An individual should be aware of potentially severe weather.
+    Advisory,
+    /// An individual should prepare for potentially severe weather.
+    Watch,
+    /// An individual should take immediate action.
+    Warning,
+}
+
+impl From<Severity> for DarkskySeverity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Advisory => DarkskySeverity::Advisory,
+            Severity::Watch => DarkskySeverity::Watch,
+            Severity::Warning => DarkskySeverity::Warning,
+        }
+    }
+}
+
+/// The callback of [`darksky_fetch_forecast`].
+///
+/// On success, `forecast` is a new forecast owned by the callback, which must
+/// be freed with [`darksky_forecast_free`], and `error` is null. On failure,
+/// `forecast` is null and `error` is a message that is only valid for the
+/// duration of the callback.
+///
+/// [`darksky_fetch_forecast`]: fn.darksky_fetch_forecast.html
+/// [`darksky_forecast_free`]: fn.darksky_forecast_free.html
+pub type DarkskyFetchCallback =
+    extern "C" fn(user_data: *mut c_void, forecast: *mut Forecast, error: *const c_char);
+
+/// A pointer handed back to the caller's callback from another thread.
+struct UserData(*mut c_void);
+
+unsafe impl Send for UserData {}
+
+/// Parses a forecast from `len` bytes of JSON, returning null if it is not a
+/// valid forecast.
+///
+/// # Safety
+///
+/// `json` must point to at least `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_forecast_from_json(
+    json: *const c_char,
+    len: usize,
+) -> *mut Forecast {
+    if json.is_null() {
+        return ptr::null_mut();
+    }
+
+    let bytes = slice::from_raw_parts(json as *const u8, len);
+
+    match Forecast::from_slice(bytes) {
+        Ok(forecast) => Box::into_raw(Box::new(forecast)),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Retrieves the forecast for a location on a background thread, calling
+/// `callback` with `user_data` once it completes.
+///
+/// Returns whether the request was started, which is false if `token` is
+/// null or not valid UTF-8.
+///
+/// # Safety
+///
+/// `token` must be null or a NUL-terminated string, and `user_data` must be
+/// safe to use from another thread.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_fetch_forecast(
+    token: *const c_char,
+    latitude: f64,
+    longitude: f64,
+    callback: DarkskyFetchCallback,
+    user_data: *mut c_void,
+) -> bool {
+    let token = match token.as_ref().map(|token| CStr::from_ptr(token).to_str()) {
+        Some(Ok(token)) => token.to_owned(),
+        _ => return false,
+    };
+    let user_data = UserData(user_data);
+
+    thread::spawn(move || {
+        let user_data = user_data;
+
+        match Client::new().get_forecast(&token, Coordinate::new(latitude, longitude)) {
+            Ok(forecast) => callback(user_data.0, Box::into_raw(Box::new(forecast)), ptr::null()),
+            Err(why) => {
+                let message = CString::new(why.to_string()).unwrap_or_default();
+
+                callback(user_data.0, ptr::null_mut(), message.as_ptr());
+            }
+        }
+    });
+
+    true
+}
+
+/// Frees a forecast, along with any datapoints or alerts borrowed from it.
+///
+/// # Safety
+///
+/// `forecast` must be null or a forecast that has not yet been freed.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_forecast_free(forecast: *mut Forecast) {
+    if !forecast.is_null() {
+        drop(Box::from_raw(forecast));
+    }
+}
+
+/// Returns the latitude of a forecast.
+///
+/// # Safety
+///
+/// `forecast` must be null or a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_forecast_latitude(forecast: *const Forecast) -> f64 {
+    forecast
+        .as_ref()
+        .map_or(f64::NAN, |forecast| forecast.latitude)
+}
+
+/// Returns the longitude of a forecast.
+///
+/// # Safety
+///
+/// `forecast` must be null or a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_forecast_longitude(forecast: *const Forecast) -> f64 {
+    forecast
+        .as_ref()
+        .map_or(f64::NAN, |forecast| forecast.longitude)
+}
+
+/// Copies the name of a forecast's timezone into `buffer`, returning its full
+/// length.
+///
+/// # Safety
+///
+/// `forecast` must be null or a valid forecast, and `buffer` must be null or
+/// point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_forecast_timezone(
+    forecast: *const Forecast,
+    buffer: *mut c_char,
+    len: usize,
+) -> usize {
+    copy_str(
+        forecast.as_ref().map(|forecast| &forecast.timezone[..]),
+        buffer,
+        len,
+    )
+}
+
+/// Returns the current conditions of a forecast, or null if there are none.
+///
+/// # Safety
+///
+/// `forecast` must be null or a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_forecast_currently(forecast: *const Forecast) -> *const Datapoint {
+    forecast
+        .as_ref()
+        .and_then(|forecast| forecast.currently.as_ref())
+        .map_or(ptr::null(), |datapoint| datapoint as *const _)
+}
+
+/// Returns the number of hourly datapoints in a forecast.
+///
+/// # Safety
+///
+/// `forecast` must be null or a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_forecast_hourly_len(forecast: *const Forecast) -> usize {
+    data(forecast, |forecast| &forecast.hourly).len()
+}
+
+/// Returns the hourly datapoint at `index`, or null if it is out of bounds.
+///
+/// # Safety
+///
+/// `forecast` must be null or a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_forecast_hourly(
+    forecast: *const Forecast,
+    index: usize,
+) -> *const Datapoint {
+    get(data(forecast, |forecast| &forecast.hourly), index)
+}
+
+/// Returns the number of daily datapoints in a forecast.
+///
+/// # Safety
+///
+/// `forecast` must be null or a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_forecast_daily_len(forecast: *const Forecast) -> usize {
+    data(forecast, |forecast| &forecast.daily).len()
+}
+
+/// Returns the daily datapoint at `index`, or null if it is out of bounds.
+///
+/// # Safety
+///
+/// `forecast` must be null or a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_forecast_daily(
+    forecast: *const Forecast,
+    index: usize,
+) -> *const Datapoint {
+    get(data(forecast, |forecast| &forecast.daily), index)
+}
+
+/// Returns the number of alerts in a forecast.
+///
+/// # Safety
+///
+/// `forecast` must be null or a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_forecast_alerts_len(forecast: *const Forecast) -> usize {
+    forecast
+        .as_ref()
+        .map_or(0, |forecast| forecast.alerts.len())
+}
+
+/// Returns the alert at `index`, or null if it is out of bounds.
+///
+/// # Safety
+///
+/// `forecast` must be null or a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_forecast_alert(
+    forecast: *const Forecast,
+    index: usize,
+) -> *const Alert {
+    let alerts = forecast
+        .as_ref()
+        .map_or(&[][..], |forecast| &forecast.alerts[..]);
+
+    get(alerts, index)
+}
+
+/// Returns the unix timestamp at which a datapoint begins, or `0` if
+/// `datapoint` is null.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_time(datapoint: *const Datapoint) -> u64 {
+    datapoint.as_ref().map_or(0, |datapoint| datapoint.time)
+}
+
+/// Copies the summary of a datapoint into `buffer`, returning its full
+/// length.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast, and `buffer`
+/// must be null or point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_summary(
+    datapoint: *const Datapoint,
+    buffer: *mut c_char,
+    len: usize,
+) -> usize {
+    let summary = datapoint
+        .as_ref()
+        .and_then(|datapoint| datapoint.summary.as_ref())
+        .map(|summary| &summary[..]);
+
+    copy_str(summary, buffer, len)
+}
+
+/// Returns the apparent (or "feels like") temperature of a datapoint, or `NAN`
+/// if it is not present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_apparent_temperature(
+    datapoint: *const Datapoint,
+) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.apparent_temperature)
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the percentage of sky occluded by clouds at a datapoint, or `NAN` if
+/// it is not present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_cloud_cover(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.cloud_cover)
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the dew point of a datapoint, or `NAN` if it is not present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_dew_point(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.dew_point)
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the relative humidity of a datapoint, or `NAN` if it is not present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_humidity(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.humidity)
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the intensity of precipitation of a datapoint, or `NAN` if it is not
+/// present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_precip_intensity(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.precip_intensity)
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the probability of precipitation of a datapoint, or `NAN` if it is
+/// not present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_precip_probability(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.precip_probability)
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the sea-level air pressure of a datapoint, or `NAN` if it is not
+/// present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_pressure(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.pressure)
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the air temperature of a datapoint, or `NAN` if it is not present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_temperature(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.temperature)
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the maximum temperature of a daily datapoint, or `NAN` if it is not
+/// present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_temperature_max(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.temperature_max())
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the minimum temperature of a daily datapoint, or `NAN` if it is not
+/// present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_temperature_min(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.temperature_min())
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the UV index of a datapoint, or `NAN` if it is not present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_uv_index(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.uv_index.map(|index| index as f64))
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the average visibility of a datapoint, or `NAN` if it is not
+/// present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_visibility(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.visibility)
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the direction the wind is coming from at a datapoint, or `NAN` if it
+/// is not present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_wind_bearing(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.wind_bearing)
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the wind gust speed of a datapoint, or `NAN` if it is not present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_wind_gust(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.wind_gust)
+        .unwrap_or(f64::NAN)
+}
+
+/// Returns the wind speed of a datapoint, or `NAN` if it is not present.
+///
+/// # Safety
+///
+/// `datapoint` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_datapoint_wind_speed(datapoint: *const Datapoint) -> f64 {
+    datapoint
+        .as_ref()
+        .and_then(|d| d.wind_speed)
+        .unwrap_or(f64::NAN)
+}
+
+/// Copies the title of an alert into `buffer`, returning its full length.
+///
+/// # Safety
+///
+/// `alert` must be null or borrowed from a valid forecast, and `buffer` must
+/// be null or point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_alert_title(
+    alert: *const Alert,
+    buffer: *mut c_char,
+    len: usize,
+) -> usize {
+    copy_str(alert.as_ref().map(|alert| &alert.title[..]), buffer, len)
+}
+
+/// Copies the description of an alert into `buffer`, returning its full
+/// length.
+///
+/// # Safety
+///
+/// `alert` must be null or borrowed from a valid forecast, and `buffer` must
+/// be null or point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_alert_description(
+    alert: *const Alert,
+    buffer: *mut c_char,
+    len: usize,
+) -> usize {
+    copy_str(
+        alert.as_ref().map(|alert| &alert.description[..]),
+        buffer,
+        len,
+    )
+}
+
+/// Copies the URI of an alert into `buffer`, returning its full length.
+///
+/// # Safety
+///
+/// `alert` must be null or borrowed from a valid forecast, and `buffer` must
+/// be null or point to at least `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_alert_uri(
+    alert: *const Alert,
+    buffer: *mut c_char,
+    len: usize,
+) -> usize {
+    copy_str(alert.as_ref().map(|alert| &alert.uri[..]), buffer, len)
+}
+
+/// Returns the unix timestamp at which an alert was issued, or `0` if `alert`
+/// is null.
+///
+/// # Safety
+///
+/// `alert` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_alert_time(alert: *const Alert) -> u64 {
+    alert.as_ref().map_or(0, |alert| alert.time)
+}
+
+/// Returns the unix timestamp at which an alert expires, or `0` if `alert` is
+/// null.
+///
+/// # Safety
+///
+/// `alert` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_alert_expires(alert: *const Alert) -> u64 {
+    alert.as_ref().map_or(0, |alert| alert.expires)
+}
+
+/// Returns the severity of an alert, or an advisory if `alert` is null.
+///
+/// # Safety
+///
+/// `alert` must be null or borrowed from a valid forecast.
+#[no_mangle]
+pub unsafe extern "C" fn darksky_alert_severity(alert: *const Alert) -> DarkskySeverity {
+    alert
+        .as_ref()
+        .map_or(DarkskySeverity::Advisory, |alert| alert.severity.into())
+}
+
+/// Copies as much of `value` as fits into `buffer` followed by a NUL byte,
+/// returning the full length of `value`. A missing value is copied as an
+/// empty string.
+unsafe fn copy_str(value: Option<&str>, buffer: *mut c_char, len: usize) -> usize {
+    let value = value.unwrap_or("");
+
+    if !buffer.is_null() && len > 0 {
+        let count = value.len().min(len - 1);
+
+        ptr::copy_nonoverlapping(value.as_ptr() as *const c_char, buffer, count);
+        *buffer.add(count) = 0;
+    }
+
+    value.len()
+}
+
+unsafe fn data<'a, F>(forecast: *const Forecast, block: F) -> &'a [Datapoint]
+where
+    F: FnOnce(&'a Forecast) -> &'a Option<Datablock>,
+{
+    forecast
+        .as_ref()
+        .and_then(|forecast| block(forecast).as_ref())
+        .and_then(|block| block.data.as_ref())
+        .map_or(&[][..], |data| &data[..])
+}
+
+fn get<T>(values: &[T], index: usize) -> *const T {
+    values
+        .get(index)
+        .map_or(ptr::null(), |value| value as *const _)
+}
@@ -0,0 +1,429 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Rendering of a [`Forecast`] or [`Datapoint`] into presentable,
+//! human-readable text, for use by command line tools and bots.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`forecast`].
+//!
+//! [`Datapoint`]: ../models/struct.Datapoint.html
+//! [`Forecast`]: ../models/struct.Forecast.html
+//! [`forecast`]: fn.forecast.html
+
+use locale::Locale;
+use models::{Alert, Datapoint, Forecast};
+use narrative::hour;
+use std::fmt::Write;
+use {Language, Unit};
+
+/// Options for rendering forecasts and datapoints.
+///
+/// By default, the next 6 hours and any active alerts are rendered in
+/// English, with unit symbols for the forecast's [`units`][`Flags::units`]
+/// flag.
+///
+/// [`Flags::units`]: ../models/struct.Flags.html#structfield.units
+#[derive(Clone, Debug)]
+pub struct FormatOptions {
+    alerts: bool,
+    hours: usize,
+    locale: &'static Locale,
+    unit: Option<Unit>,
+    unit_symbols: bool,
+}
+
+impl FormatOptions {
+    /// Sets whether active alerts are rendered.
+    pub fn alerts(mut self, alerts: bool) -> Self {
+        self.alerts = alerts;
+
+        self
+    }
+
+    /// Sets the number of upcoming hours that are rendered. Passing `0` omits
+    /// the hourly section.
+    pub fn hours(mut self, hours: usize) -> Self {
+        self.hours = hours;
+
+        self
+    }
+
+    /// Sets the language of the rendered labels. Refer to the [`locale`]
+    /// module for the supported languages.
+    ///
+    /// This should usually match the [language][`Options::language`] that the
+    /// forecast was requested in, so that labels match its summaries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::format::{self, FormatOptions};
+    /// use darksky::models::Datapoint;
+    /// use darksky::{Language, Unit};
+    ///
+    /// # fn main() {
+    /// let datapoint: Datapoint = serde_json::from_str(r#"{
+    ///     "time": 1509993277,
+    ///     "summary": "Ciel dégagé",
+    ///     "temperature": 21.2,
+    ///     "humidity": 0.4
+    /// }"#).unwrap();
+    ///
+    /// let options = FormatOptions::default()
+    ///     .language(Language::Fr)
+    ///     .unit(Unit::Si);
+    ///
+    /// assert_eq!(
+    ///     format::datapoint(&datapoint, &options),
+    ///     "Ciel dégagé, 21°C, humidité 40%",
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// [`Options::language`]: ../struct.Options.html#method.language
+    /// [`locale`]: ../locale/index.html
+    pub fn language(mut self, language: Language) -> Self {
+        self.locale = Locale::for_language(language);
+
+        self
+    }
+
+    /// Sets the units that values are in, overriding the forecast's
+    /// [`units`][`Flags::units`] flag.
+    ///
+    /// This only affects the symbols that are displayed; values are not
+    /// converted.
+    ///
+    /// [`Flags::units`]: ../models/struct.Flags.html#structfield.units
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+
+        self
+    }
+
+    /// Sets whether unit symbols, such as `°C` or `km/h`, are displayed after
+    /// values.
+    pub fn unit_symbols(mut self, unit_symbols: bool) -> Self {
+        self.unit_symbols = unit_symbols;
+
+        self
+    }
+
+    /// Returns the locale that labels are rendered in, as set by
+    /// [`language`].
+    ///
+    /// [`language`]: #method.language
+    pub fn get_locale(&self) -> &'static Locale {
+        self.locale
+    }
+
+    fn style(&self, forecast: Option<&Forecast>) -> Style {
+        let unit = self.unit.or_else(|| {
+            let units = forecast?.flags.as_ref()?.units.as_ref()?;
+
+            Some(match &units[..] {
+                "ca" => Unit::Ca,
+                "si" => Unit::Si,
+                "uk2" => Unit::Uk2,
+                _ => Unit::Us,
+            })
+        });
+
+        let (temperature, wind_speed) = match unit.unwrap_or(Unit::Us) {
+            _ if !self.unit_symbols => ("°", ""),
+            Unit::Ca => ("°C", " km/h"),
+            Unit::Si => ("°C", " m/s"),
+            Unit::Uk2 => ("°C", " mph"),
+            Unit::Auto | Unit::Us => ("°F", " mph"),
+        };
+
+        Style {
+            locale: self.locale,
+            temperature,
+            wind_speed,
+        }
+    }
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            alerts: true,
+            hours: 6,
+            locale: Locale::for_language(Language::En),
+            unit: None,
+            unit_symbols: true,
+        }
+    }
+}
+
+/// The labels and unit symbols that values are rendered with.
+struct Style {
+    locale: &'static Locale,
+    temperature: &'static str,
+    wind_speed: &'static str,
+}
+
+/// Renders a forecast into multi-line text, covering the current conditions,
+/// the next hours, today's temperature range, and any active alerts.
+///
+/// Sections without data are omitted. Alerts are active if they have been
+/// issued and have not expired as of the [`currently`] datapoint. Times are in
+/// the forecast's local time, as given by its [`offset`].
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate serde_json;
+///
+/// use darksky::format::{self, FormatOptions};
+/// use darksky::models::Forecast;
+///
+/// # fn main() {
+/// let forecast: Forecast = serde_json::from_str(r#"{
+///     "latitude": 51.5,
+///     "longitude": -0.12,
+///     "timezone": "Europe/London",
+///     "offset": 0,
+///     "flags": {"units": "si"},
+///     "currently": {
+///         "time": 1509993277,
+///         "summary": "Partly Cloudy",
+///         "temperature": 11.6,
+///         "apparentTemperature": 10.2,
+///         "humidity": 0.71,
+///         "windSpeed": 4.2
+///     },
+///     "hourly": {"data": [
+///         {"time": 1509994800, "summary": "Partly Cloudy", "temperature": 11.2, "precipProbability": 0.1},
+///         {"time": 1509998400, "summary": "Light Rain", "temperature": 10.4, "precipProbability": 0.6}
+///     ]},
+///     "daily": {"data": [
+///         {"time": 1509926400, "temperatureHigh": 12.9, "temperatureLow": 6.3}
+///     ]}
+/// }"#).unwrap();
+///
+/// let options = FormatOptions::default().hours(2);
+///
+/// assert_eq!(format::forecast(&forecast, &options), "\
+/// Now: Partly Cloudy, 12°C (feels like 10°C), humidity 71%, wind 4 m/s
+///
+/// Next 2 hours:
+///    7pm  11°C  Partly Cloudy (10% precipitation)
+///    8pm  10°C  Light Rain (60% precipitation)
+///
+/// Today: high of 13°C, low of 6°C
+/// ");
+/// # }
+/// ```
+///
+/// [`currently`]: ../models/struct.Forecast.html#structfield.currently
+/// [`offset`]: ../models/struct.Forecast.html#structfield.offset
+pub fn forecast(forecast: &Forecast, options: &FormatOptions) -> String {
+    let style = options.style(Some(forecast));
+    let locale = style.locale;
+    let offset = forecast.offset_seconds();
+    let mut sections = Vec::new();
+
+    if let Some(ref currently) = forecast.currently {
+        sections.push(format!(
+            "{}: {}\n",
+            locale.now,
+            conditions(currently, &style)
+        ));
+    }
+
+    let hourly = forecast
+        .hourly
+        .as_ref()
+        .and_then(|block| block.data.as_ref())
+        .map(|data| &data[..data.len().min(options.hours)])
+        .unwrap_or(&[]);
+
+    if !hourly.is_empty() {
+        let heading = locale.next_hours.replace("{}", &hourly.len().to_string());
+        let mut section = format!("{}:\n", heading);
+
+        for datapoint in hourly {
+            let _ = writeln!(
+                section,
+                "  {:>4}  {}",
+                hour(datapoint.time, offset),
+                upcoming(datapoint, &style),
+            );
+        }
+
+        sections.push(section);
+    }
+
+    let today = forecast
+        .daily
+        .as_ref()
+        .and_then(|block| block.data.as_ref())
+        .and_then(|data| data.first())
+        .and_then(|today| range(today, &style));
+
+    if let Some(today) = today {
+        sections.push(format!("{}: {}\n", locale.today, today));
+    }
+
+    // Alerts are active as of the current conditions, or all alerts are
+    // rendered if there are none.
+    let alerts = match forecast.currently {
+        Some(ref currently) => forecast.alerts_matching(|query| query.active_at(currently.time)),
+        None => forecast.alerts.iter().collect(),
+    };
+
+    if options.alerts && !alerts.is_empty() {
+        let mut section = format!("{}:\n", locale.alerts);
+
+        for alert in alerts {
+            let _ = writeln!(section, "  {}", self::alert(alert, offset, locale));
+        }
+
+        sections.push(section);
+    }
+
+    sections.join("\n")
+}
+
+/// Renders the conditions of a single datapoint into one line of text.
+///
+/// As a lone datapoint has no [`units`][`Flags::units`] flag, unit symbols
+/// are for the [unit][`FormatOptions::unit`] of the options, defaulting to
+/// [US units][`Unit::Us`].
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate serde_json;
+///
+/// use darksky::format::{self, FormatOptions};
+/// use darksky::models::Datapoint;
+/// use darksky::Unit;
+///
+/// # fn main() {
+/// let datapoint: Datapoint = serde_json::from_str(r#"{
+///     "time": 1509993277,
+///     "summary": "Clear",
+///     "temperature": -3.5,
+///     "windSpeed": 12.4
+/// }"#).unwrap();
+///
+/// let options = FormatOptions::default().unit(Unit::Ca);
+///
+/// assert_eq!(format::datapoint(&datapoint, &options), "Clear, -4°C, wind 12 km/h");
+/// # }
+/// ```
+///
+/// [`Flags::units`]: ../models/struct.Flags.html#structfield.units
+/// [`FormatOptions::unit`]: struct.FormatOptions.html#method.unit
+/// [`Unit::Us`]: ../enum.Unit.html#variant.Us
+pub fn datapoint(datapoint: &Datapoint, options: &FormatOptions) -> String {
+    conditions(datapoint, &options.style(None))
+}
+
+fn conditions(datapoint: &Datapoint, style: &Style) -> String {
+    let locale = style.locale;
+    let mut parts = Vec::new();
+
+    if let Some(ref summary) = datapoint.summary {
+        parts.push(summary.to_string());
+    }
+
+    if let Some(temperature) = datapoint.temperature {
+        let mut part = format!("{:.0}{}", temperature, style.temperature);
+
+        if let Some(apparent) = datapoint.apparent_temperature {
+            if apparent.round() != temperature.round() {
+                let _ = write!(
+                    part,
+                    " ({} {:.0}{})",
+                    locale.feels_like, apparent, style.temperature,
+                );
+            }
+        }
+
+        parts.push(part);
+    }
+
+    if let Some(humidity) = datapoint.humidity {
+        parts.push(format!("{} {:.0}%", locale.humidity, humidity * 100.0));
+    }
+
+    if let Some(wind_speed) = datapoint.wind_speed {
+        parts.push(format!(
+            "{} {:.0}{}",
+            locale.wind, wind_speed, style.wind_speed,
+        ));
+    }
+
+    parts.join(", ")
+}
+
+fn upcoming(datapoint: &Datapoint, style: &Style) -> String {
+    let mut line = String::new();
+
+    if let Some(temperature) = datapoint.temperature {
+        let _ = write!(line, "{:.0}{}  ", temperature, style.temperature);
+    }
+
+    if let Some(ref summary) = datapoint.summary {
+        line.push_str(summary);
+    }
+
+    if let Some(probability) = datapoint.precip_probability {
+        if probability > 0.0 {
+            let _ = write!(
+                line,
+                " ({:.0}% {})",
+                probability * 100.0,
+                style.locale.precipitation,
+            );
+        }
+    }
+
+    line.trim_end().to_owned()
+}
+
+fn range(today: &Datapoint, style: &Style) -> Option<String> {
+    let locale = style.locale;
+    let high = today.temperature_high().or_else(|| today.temperature_max());
+    let low = today.temperature_low().or_else(|| today.temperature_min());
+    let high = high.map(|high| format!("{} {:.0}{}", locale.high, high, style.temperature));
+    let low = low.map(|low| format!("{} {:.0}{}", locale.low, low, style.temperature));
+
+    match (high, low) {
+        (Some(high), Some(low)) => Some(format!("{}, {}", high, low)),
+        (high, low) => high.or(low),
+    }
+}
+
+fn alert(alert: &Alert, offset: i64, locale: &Locale) -> String {
+    format!(
+        "[{}] {} ({} {})",
+        locale.severity(alert.severity),
+        alert.title,
+        locale.until,
+        hour(alert.expires, offset),
+    )
+}
@@ -0,0 +1,108 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Checking the health of the DarkSky API, for wiring its reachability into
+//! readiness probes.
+//!
+//! A [`Health`] is returned by [`DarkskyRequester::check_status`], which
+//! makes a cheap request for a forecast with every block excluded.
+//!
+//! [`DarkskyRequester::check_status`]:
+//!   ../bridge/send/trait.DarkskyRequester.html#method.check_status
+//! [`Health`]: struct.Health.html
+
+pub use constants::API_CALLS_HEADER;
+
+use constants::{Config, BLOCK_NAMES};
+use std::time::Duration;
+use {utils, Options, Result};
+
+/// The result of a health check of the DarkSky API.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::health::Health;
+/// use std::time::Duration;
+///
+/// let health = Health {
+///     api_calls: Some(990),
+///     authorized: true,
+///     latency: Duration::from_millis(120),
+///     reachable: true,
+/// };
+///
+/// assert!(health.is_healthy());
+/// assert_eq!(health.quota_remaining(1000), Some(10));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Health {
+    /// The number of API calls made with the token on the current day, if
+    /// the API reported it.
+    pub api_calls: Option<u64>,
+    /// Whether the API accepted the token.
+    pub authorized: bool,
+    /// How long the API took to respond, or to fail.
+    pub latency: Duration,
+    /// Whether the API responded without a server error.
+    pub reachable: bool,
+}
+
+impl Health {
+    /// Returns whether the API is reachable and accepted the token.
+    pub fn is_healthy(&self) -> bool {
+        self.reachable && self.authorized
+    }
+
+    /// Returns the number of API calls remaining on the current day, given
+    /// the token's daily limit, if the API reported the number of calls made.
+    pub fn quota_remaining(&self, daily_limit: u64) -> Option<u64> {
+        self.api_calls
+            .map(|calls| daily_limit.saturating_sub(calls))
+    }
+
+    /// Creates the result of a check that received a response.
+    pub(crate) fn responded(status: u16, api_calls: Option<u64>, latency: Duration) -> Self {
+        Health {
+            api_calls,
+            authorized: status != 401 && status != 403,
+            latency,
+            reachable: status < 500,
+        }
+    }
+
+    /// Creates the result of a check that failed to receive a response.
+    pub(crate) fn unreachable(latency: Duration) -> Self {
+        Health {
+            api_calls: None,
+            authorized: false,
+            latency,
+            reachable: false,
+        }
+    }
+}
+
+/// Returns the URI of a forecast with every block excluded, which is the
+/// cheapest request that still checks the token.
+pub(crate) fn uri(config: &Config, token: &str) -> Result<String> {
+    // `alerts` can't be excluded through `Options`, so the blocks are
+    // excluded by name.
+    let mut uri = utils::uri_configured(config, token, 0.0, 0.0, None, Options::default())?;
+    uri.push_str("exclude=");
+    uri.push_str(&BLOCK_NAMES.join(","));
+    uri.push('&');
+
+    Ok(uri)
+}
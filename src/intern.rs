@@ -0,0 +1,190 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Interning of repeated strings, such as the station identifiers in
+//! [`Flags`].
+//!
+//! Forecasts for nearby locations, or for the same location over time, tend to
+//! list the same stations. Services holding many forecasts can intern their
+//! flags with a shared [`Interner`] so that each distinct identifier is only
+//! stored once.
+//!
+//! [`Flags`]: ../models/struct.Flags.html
+//! [`Interner`]: struct.Interner.html
+
+use models::Flags;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// A cache of shared strings.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::intern::Interner;
+/// use std::sync::Arc;
+///
+/// let mut interner = Interner::new();
+///
+/// let a = interner.intern("724943-99999");
+/// let b = interner.intern("724943-99999");
+///
+/// assert!(Arc::ptr_eq(&a, &b));
+/// assert_eq!(interner.len(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    strings: HashSet<Arc<str>>,
+}
+
+impl Interner {
+    /// Creates a new, empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the shared copy of a string, adding it to the cache if it is
+    /// not already present.
+    pub fn intern(&mut self, value: &str) -> Arc<str> {
+        if let Some(existing) = self.strings.get(value) {
+            return Arc::clone(existing);
+        }
+
+        let value: Arc<str> = Arc::from(value);
+        self.strings.insert(Arc::clone(&value));
+
+        value
+    }
+
+    /// Interns each of the strings in [`Flags`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::intern::Interner;
+    /// use darksky::models::Flags;
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() {
+    /// let flags: Flags = serde_json::from_str(r#"{
+    ///     "isd-stations": ["724943-99999", "745039-99999"],
+    ///     "sources": ["isd", "nearest-precip"],
+    ///     "units": "us"
+    /// }"#).unwrap();
+    ///
+    /// let mut interner = Interner::new();
+    /// let first = interner.intern_flags(&flags);
+    /// let second = interner.intern_flags(&flags);
+    ///
+    /// let (a, b) = (first.isd_stations.unwrap(), second.isd_stations.unwrap());
+    /// assert!(Arc::ptr_eq(&a[0], &b[0]));
+    /// # }
+    /// ```
+    ///
+    /// [`Flags`]: ../models/struct.Flags.html
+    pub fn intern_flags(&mut self, flags: &Flags) -> InternedFlags {
+        InternedFlags {
+            darksky_stations: self.intern_all(&flags.darksky_stations),
+            darksky_unavailable: self.intern_one(&flags.darksky_unavailable),
+            datapoint_stations: self.intern_all(&flags.datapoint_stations),
+            isd_stations: self.intern_all(&flags.isd_stations),
+            lamp_stations: self.intern_all(&flags.lamp_stations),
+            metar_stations: self.intern_all(&flags.metar_stations),
+            metno_license: self.intern_one(&flags.metno_license),
+            sources: self.intern_all(&flags.sources),
+            units: self.intern_one(&flags.units),
+        }
+    }
+
+    /// Returns whether the interner is empty.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Returns the number of distinct strings in the interner.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Removes strings that are no longer referenced outside of the interner.
+    pub fn shrink(&mut self) {
+        self.strings.retain(|value| Arc::strong_count(value) > 1);
+    }
+
+    fn intern_all(&mut self, values: &Option<Vec<String>>) -> Option<Vec<Arc<str>>> {
+        values
+            .as_ref()
+            .map(|values| values.iter().map(|value| self.intern(value)).collect())
+    }
+
+    fn intern_one(&mut self, value: &Option<String>) -> Option<Arc<str>> {
+        value.as_ref().map(|value| self.intern(value))
+    }
+}
+
+/// A set of [`Flags`] whose strings are shared through an [`Interner`].
+///
+/// [`Flags`]: ../models/struct.Flags.html
+/// [`Interner`]: struct.Interner.html
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InternedFlags {
+    /// A list of DarkSky stations used for the forecast.
+    pub darksky_stations: Option<Vec<Arc<str>>>,
+    /// A list of the unavailable DarkSky stations.
+    pub darksky_unavailable: Option<Arc<str>>,
+    /// A list of the datapoint stations used.
+    pub datapoint_stations: Option<Vec<Arc<str>>>,
+    /// A list of ISD stations used.
+    pub isd_stations: Option<Vec<Arc<str>>>,
+    /// A list of LAMP stations used to obtain the information.
+    pub lamp_stations: Option<Vec<Arc<str>>>,
+    /// A list of METAR stations used to obtain the information.
+    pub metar_stations: Option<Vec<Arc<str>>>,
+    /// The METNO license used.
+    pub metno_license: Option<Arc<str>>,
+    /// A list of sources used to obtain the information.
+    pub sources: Option<Vec<Arc<str>>>,
+    /// The units used to format the data.
+    pub units: Option<Arc<str>>,
+}
+
+impl InternedFlags {
+    /// Converts the interned flags back into regular [`Flags`].
+    ///
+    /// [`Flags`]: ../models/struct.Flags.html
+    pub fn to_flags(&self) -> Flags {
+        let all = |values: &Option<Vec<Arc<str>>>| {
+            values
+                .as_ref()
+                .map(|values| values.iter().map(|value| value.to_string()).collect())
+        };
+        let one = |value: &Option<Arc<str>>| value.as_ref().map(|value| value.to_string());
+
+        Flags {
+            darksky_stations: all(&self.darksky_stations),
+            darksky_unavailable: one(&self.darksky_unavailable),
+            datapoint_stations: all(&self.datapoint_stations),
+            isd_stations: all(&self.isd_stations),
+            lamp_stations: all(&self.lamp_stations),
+            metar_stations: all(&self.metar_stations),
+            metno_license: one(&self.metno_license),
+            sources: all(&self.sources),
+            units: one(&self.units),
+        }
+    }
+}
@@ -14,16 +14,16 @@
 // CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
 // CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 
+use alloc::string::String;
+#[cfg(feature = "std")]
+use models::{BlockSummaries, Summaries, SummaryText};
 use serde::de::DeserializeOwned;
 use serde_json;
-#[cfg(feature = "reqwest")]
+#[cfg(feature = "air-quality")]
 use std::io::Read;
-use Result;
+use {Error, Result};
 
-#[cfg(feature = "hyper")]
-use hyper::Chunk;
-
-#[cfg(feature = "reqwest")]
+#[cfg(feature = "air-quality")]
 pub fn from_reader<R, T>(reader: R) -> Result<T>
 where
     R: Read,
@@ -32,7 +32,107 @@ where
     serde_json::from_reader(reader).map_err(From::from)
 }
 
-#[cfg(feature = "hyper")]
-pub fn from_chunk<T: DeserializeOwned>(chunk: Chunk) -> Result<T> {
-    serde_json::from_slice(&chunk).map_err(From::from)
+/// Deserializes a response body, returning [`Error::Api`] if it is the body
+/// of an error from the API instead.
+///
+/// [`Error::Api`]: ../enum.Error.html#variant.Api
+pub fn from_slice<T: DeserializeOwned>(slice: &[u8]) -> Result<T> {
+    serde_json::from_slice(slice).map_err(|why| match serde_json::from_slice(slice) {
+        Ok(ApiError { code, error }) => Error::Api(code, error),
+        Err(_) => Error::Json(why),
+    })
+}
+
+/// Returns whether a response body is that of an error from the API.
+#[cfg(feature = "std")]
+pub fn is_api_error(slice: &[u8]) -> bool {
+    api_error_code(slice).is_some()
+}
+
+/// Returns the code of an error from the API, if the response body is that
+/// of one.
+#[cfg(feature = "std")]
+pub fn api_error_code(slice: &[u8]) -> Option<u16> {
+    serde_json::from_slice::<ApiError>(slice)
+        .ok()
+        .map(|error| error.code)
+}
+
+/// Returns whether a request may succeed if it is sent again, being the case
+/// for transient errors, and for server errors or rate limiting, whether
+/// given by the status of the response or by an error from the API.
+///
+/// The status is checked first, as a response from a proxy in front of the
+/// API may not have a body that is JSON.
+#[cfg(feature = "std")]
+pub fn is_transient_failure(response: &Result<Vec<u8>>, status: Option<u16>) -> bool {
+    match *response {
+        Ok(_) if status.is_some_and(is_transient_status) => true,
+        Ok(ref body) => api_error_code(body).is_some_and(is_transient_status),
+        Err(ref why) => why.is_transient(),
+    }
+}
+
+/// Returns whether a status code is that of a server error or of too many
+/// requests.
+#[cfg(feature = "std")]
+fn is_transient_status(status: u16) -> bool {
+    status >= 500 || status == 429
+}
+
+/// The body of an error response, such as
+/// `{"code": 403, "error": "daily usage limit exceeded"}`.
+#[derive(Deserialize)]
+struct ApiError {
+    code: u16,
+    error: String,
+}
+
+#[cfg(feature = "std")]
+/// A response containing only the summaries of a forecast, allowing the rest
+/// of the response to be skipped over when deserializing.
+#[derive(Deserialize)]
+pub struct RawSummaries {
+    currently: Option<RawSummary>,
+    daily: Option<RawBlockSummaries>,
+    hourly: Option<RawBlockSummaries>,
+    minutely: Option<RawBlockSummaries>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Deserialize)]
+struct RawSummary {
+    summary: Option<SummaryText>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Deserialize)]
+struct RawBlockSummaries {
+    summary: Option<SummaryText>,
+    data: Option<Vec<RawSummary>>,
+}
+
+#[cfg(feature = "std")]
+impl From<RawBlockSummaries> for BlockSummaries {
+    fn from(raw: RawBlockSummaries) -> Self {
+        BlockSummaries {
+            summary: raw.summary,
+            data: raw
+                .data
+                .map(|data| data.into_iter().map(|d| d.summary).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<RawSummaries> for Summaries {
+    fn from(raw: RawSummaries) -> Self {
+        Summaries {
+            currently: raw.currently.and_then(|d| d.summary),
+            daily: raw.daily.map(From::from),
+            hourly: raw.hourly.map(From::from),
+            minutely: raw.minutely.map(From::from),
+        }
+    }
 }
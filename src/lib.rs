@@ -43,40 +43,36 @@
 //! ### Examples
 //!
 //! Retrieve a [forecast][`Forecast`] for the given latitude and longitude,
-//! using a hyper client with a `hyper_native_tls` connector:
-//!
-//! ```rust,no_run
-//! extern crate darksky;
-//! extern crate futures;
-//! extern crate hyper;
-//! extern crate hyper_tls;
-//! extern crate tokio_core;
+//! using a hyper client with a `hyper_tls` connector. Requests return
+//! standard library futures, so they can be `.await`ed from any async
+//! context:
 //!
+#![cfg_attr(feature = "hyper", doc = "```rust,no_run,edition2018")]
+#![cfg_attr(not(feature = "hyper"), doc = "```rust,ignore")]
 //! # use std::error::Error;
 //! #
-//! use darksky::DarkskyHyperRequester;
-//! use futures::Future;
+//! use darksky::{Coordinate, DarkskyHyperRequester};
 //! use hyper::{Body, client::{Client, HttpConnector}};
 //! use hyper_tls::HttpsConnector;
 //! use std::env;
-//! use tokio_core::reactor::Core;
+//! use tokio::runtime::Runtime;
 //!
-//! # fn try_main() -> Result<(), Box<Error>> {
-//! let core = Core::new()?;
-//! let handle = core.handle();
+//! async fn print_forecast(token: &str) {
+//!     let client = Client::builder()
+//!         .build::<_, Body>(HttpsConnector::new());
 //!
-//! let client = Client::builder()
-//!     .build::<_, Body>(HttpsConnector::new(4).unwrap());
+//!     let location = Coordinate::new(37.8267, -122.423);
+//!
+//!     match client.get_forecast(token, location).await {
+//!         Ok(forecast) => println!("{:?}", forecast),
+//!         Err(why) => println!("Error getting forecast: {:?}", why),
+//!     }
+//! }
 //!
+//! # fn try_main() -> Result<(), Box<dyn Error>> {
 //! let token = env::var("FORECAST_TOKEN")?;
-//! let lat = 37.8267;
-//! let long = -122.423;
 //!
-//! // We're waiting in this example, but you shouldn't in your code.
-//! match client.get_forecast(&token, lat, long).wait() {
-//!     Ok(forecast) => println!("{:?}", forecast),
-//!     Err(why) => println!("Error getting forecast: {:?}", why),
-//! }
+//! Runtime::new()?.block_on(print_forecast(&token));
 //! #     Ok(())
 //! # }
 //! #
@@ -90,54 +86,336 @@
 //! **hyper**: Enables an implementation of [`DarkskyHyperRequester`] on hyper's
 //! `Client` (enabled by default).
 //!
-//! **reqwest**: Enables an implementation of [`DarkskyReqwestRequester`] on
-//! reqwest's `Client`.
+//! **hyper-native-tls**, **hyper-rustls**: Enable [`client::hyper_native_tls`]
+//! and [`client::hyper_rustls`] respectively, creating hyper clients ready to
+//! connect to the API over HTTPS. Helpers for reqwest's and ureq's clients,
+//! including ones routing requests through a [`Proxy`], are included with
+//! their features.
+//!
+//! **std**: Links the standard library (enabled by default, and by every
+//! feature below other than compact and cow-summaries).
+//!
+//! With `default-features = false` and only std enabled, only the [models],
+//! [`Options`], and parsing helpers such as [`Forecast::from_slice`] are built,
+//! with no HTTP dependencies. This suits services receiving DarkSky-shaped JSON
+//! from elsewhere. Without std, the crate is `no_std` and only needs `alloc`:
+//! the models can still be parsed with [`Forecast::from_slice`], which is
+//! useful for embedded gateways decoding cached or relayed forecasts.
+//!
+//! **reqwest-blocking**: Enables an implementation of
+//! [`DarkskyReqwestRequester`] on reqwest's `blocking::Client`. The
+//! **reqwest** feature is an alias for this.
+//!
+//! **reqwest-async**: Enables an implementation of
+//! [`DarkskyReqwestAsyncRequester`] on reqwest's non-blocking `Client`,
+//! returning futures so that executors aren't blocked.
+//!
+//! **cli**: Builds the `darksky` command line client, using reqwest's
+//! `Client`. Run `darksky --help` for usage.
+//!
+//! **air-quality**: Enables the [`air_quality`] module, which attaches air
+//! quality readings from a pluggable provider, such as OpenAQ, to forecasts.
+//!
+//! **attohttpc**: Enables an implementation of [`DarkskyAttohttpcRequester`] on
+//! attohttpc's `Session`, a blocking client that compiles quickly.
+//!
+//! **awc**: Enables an implementation of [`DarkskyAwcRequester`] on the
+//! actix-web client's `Client`, for use natively within actix handlers.
+//!
+//! **blocking**: Enables the [`blocking`] module, a synchronous client over the
+//! hyper bridge that owns its runtime, for tools that don't otherwise need
+//! async Rust.
+//!
+//! **cache-redis**: Enables the [`RedisStore`], a [`CacheStore`] kept in
+//! Redis, so that several instances of a service share one cache of
+//! forecasts.
+//!
+//! **compact**: Enables the [compact models], which store values as `f32`s
+//! and timestamps as `u32`s to roughly halve memory usage.
+//!
+//! **cow-summaries**: Stores the [`SummaryText`] of `summary` fields in
+//! static storage for commonly repeated summaries, rather than allocating them.
+//!
+//! **curl**: Enables an implementation of [`DarkskyCurlRequester`] for a
+//! client wrapping curl's `Easy` handle, and concurrent requests over its
+//! `Multi` handle, for targets that only ship libcurl.
+//!
+//! **ffi**: Enables the [`ffi`] module, a C ABI over the models and reqwest's
+//! `Client`, with a C header at `include/darksky.h`.
+//!
+//! **geo**: Implements [`IntoLocation`] for `geo_types`' `Point` and `Coord`,
+//! so that locations from the geo ecosystem can be passed to forecast
+//! methods.
+//!
+//! **geohash**: Implements [`IntoLocation`] for geohash strings, which are
+//! decoded to the centre of their cell, so that geohashes such as cache keys
+//! can be passed to forecast methods as they are.
+//!
+//! **http**: Enables the [`request`] module, which builds `http::Request`s and
+//! parses responses without performing any I/O, for driving requests over
+//! HTTP stacks that no bridge supports. This is enabled by the hyper feature.
+//!
+//! **minreq**: Enables an implementation of [`HttpSend`] on a
+//! [minreq-based client][`MinreqClient`], a small blocking client using
+//! rustls, suited to statically linked musl binaries.
+//!
+//! **mqtt**: Enables an implementation of [`DarkskyMqttPublisher`] on
+//! rumqttc's `Client`, for publishing forecasts to an MQTT broker.
+//!
+//! **prometheus**: Enables the [`WeatherExporter`], which exposes the latest
+//! weather values for each location as Prometheus gauges.
+//!
+//! **quota-sqlite**: Enables the [`SqliteQuotaStore`], a [`QuotaStore`] kept
+//! in a SQLite database, so that the day's API calls are counted across runs
+//! and processes.
+//!
+//! **socks**: Enables SOCKS5 proxies for the reqwest and ureq clients created
+//! by the [`client`] module.
+//!
+//! **solar**: Adds the [`solar`] field to datapoints, holding the solar
+//! radiation data that some compatible providers, such as Pirate Weather,
+//! include.
+//!
+//! **surf**: Enables an implementation of [`DarkskySurfRequester`] on surf's
+//! `Client`, returning futures for use in async code.
+//!
+//! **tower**: Enables the [`ForecastService`], a `tower::Service` requesting
+//! forecasts over any [`HttpSend`] backend, for composing with tower
+//! middleware.
+//!
+//! **tui**: Adds a `dashboard` command to the command line client, showing the
+//! current conditions, hourly temperatures and precipitation, and active
+//! alerts in the terminal with ratatui.
+//!
+//! **ureq**: Enables an implementation of [`DarkskyUreqRequester`] on ureq's
+//! `Agent`, a small blocking client for simple synchronous programs.
+//!
+//! **url**: Enables [`Alert::parsed_uri`], which parses an alert's URI with the
+//! url crate, and [`utils::url_optioned`] and friends, which build request
+//! URIs as typed `Url`s.
+//!
+//! **wasm**: Enables an implementation of [`DarkskyWasmRequester`] on the
+//! browser's fetch API through gloo-net, for WebAssembly apps such as browser
+//! dashboards and Yew or Tauri frontends.
 //!
+//! **webhook**: Enables the [`WebhookNotifier`], which posts to a webhook when
+//! new or escalated alerts are detected, using reqwest's `Client`.
+//!
+//! [`Alert::parsed_uri`]: models/struct.Alert.html#method.parsed_uri
+//! [`CacheStore`]: cache/trait.CacheStore.html
+//! [`DarkskyAttohttpcRequester`]: bridge/attohttpc/trait.DarkskyAttohttpcRequester.html
+//! [`DarkskyAwcRequester`]: bridge/awc/trait.DarkskyAwcRequester.html
+//! [`DarkskyCurlRequester`]: bridge/curl/trait.DarkskyCurlRequester.html
 //! [`DarkskyHyperRequester`]: bridge/hyper/trait.DarkskyHyperRequester.html
-//! [`DarkskyReqwestRequester`]: bridge/reqwest/trait.DarkskyReqwestRequester.html
+//! [`DarkskyMqttPublisher`]: mqtt/trait.DarkskyMqttPublisher.html
+//! [`DarkskyReqwestAsyncRequester`]: bridge/reqwest/nonblocking/trait.DarkskyReqwestAsyncRequester.html
+//! [`DarkskyReqwestRequester`]: bridge/reqwest/blocking/trait.DarkskyReqwestRequester.html
+//! [`DarkskySurfRequester`]: bridge/surf/trait.DarkskySurfRequester.html
+//! [`DarkskyUreqRequester`]: bridge/ureq/trait.DarkskyUreqRequester.html
+//! [`DarkskyWasmRequester`]: bridge/wasm/trait.DarkskyWasmRequester.html
 //! [`Forecast`]: models/struct.Forecast.html
+//! [`ForecastService`]: service/struct.ForecastService.html
+//! [`Forecast::from_slice`]: models/struct.Forecast.html#method.from_slice
+//! [`HttpSend`]: bridge/send/trait.HttpSend.html
+//! [`IntoLocation`]: location/trait.IntoLocation.html
+//! [`MinreqClient`]: bridge/minreq/struct.MinreqClient.html
+//! [`Options`]: struct.Options.html
+//! [`Proxy`]: client/struct.Proxy.html
+//! [`QuotaStore`]: quota/trait.QuotaStore.html
+//! [`RedisStore`]: cache/struct.RedisStore.html
+//! [`SqliteQuotaStore`]: quota/struct.SqliteQuotaStore.html
+//! [`SummaryText`]: models/struct.SummaryText.html
+//! [`WeatherExporter`]: exporter/struct.WeatherExporter.html
+//! [`WebhookNotifier`]: webhook/struct.WebhookNotifier.html
+//! [`air_quality`]: air_quality/index.html
+//! [`blocking`]: blocking/index.html
+//! [`client`]: client/index.html
+//! [`client::hyper_native_tls`]: client/fn.hyper_native_tls.html
+//! [`client::hyper_rustls`]: client/fn.hyper_rustls.html
+//! [`ffi`]: ffi/index.html
+//! [`request`]: request/index.html
+//! [`solar`]: models/struct.Datapoint.html#structfield.solar
+//! [`utils::url_optioned`]: utils/fn.url_optioned.html
+//! [compact models]: models/compact/index.html
 //! [DarkSky]: https://darksky.net
 //! [change in name]: http://status.darksky.net/2016/09/20/forecast-api-is-now-dark-sky-api.html
 //! [crates.io]: https://crates.io
 //! [devportal]: https://darksky.net/dev
 //! [docs]: https://darksky.net/dev/docs
+//! [models]: models/index.html
 //! [status]: http://status.darksky.net
-#![cfg_attr(feature = "cargo-clippy", allow(doc_markdown))]
+#![allow(clippy::doc_markdown)]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 
 #[macro_use]
 extern crate serde_derive;
 
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate core;
+
+extern crate itoa;
+extern crate ryu;
 extern crate serde;
 extern crate serde_json;
 
+#[cfg(feature = "attohttpc")]
+extern crate attohttpc;
+#[cfg(feature = "awc")]
+extern crate awc;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "curl")]
+extern crate curl;
 #[cfg(feature = "futures")]
 extern crate futures;
-#[cfg(feature = "hyper")]
+#[cfg(feature = "futures-core")]
+extern crate futures_core;
+#[cfg(feature = "geo")]
+extern crate geo_types;
+#[cfg(feature = "geohash")]
+extern crate geohash;
+#[cfg(feature = "wasm")]
+extern crate gloo_net;
+#[cfg(feature = "http")]
 extern crate http;
 #[cfg(feature = "hyper")]
 extern crate hyper;
-#[cfg(feature = "reqwest")]
+#[cfg(feature = "hyper-rustls")]
+extern crate hyper_rustls;
+#[cfg(feature = "hyper-native-tls")]
+extern crate hyper_tls;
+#[cfg(feature = "wasm")]
+extern crate js_sys;
+#[cfg(feature = "minreq")]
+extern crate minreq;
+#[cfg(feature = "prometheus")]
+extern crate prometheus;
+#[cfg(feature = "cache-redis")]
+extern crate redis;
+#[cfg(any(feature = "reqwest-blocking", feature = "reqwest-async"))]
 extern crate reqwest;
+#[cfg(feature = "mqtt")]
+extern crate rumqttc;
+#[cfg(feature = "quota-sqlite")]
+extern crate rusqlite;
+#[cfg(feature = "surf")]
+extern crate surf;
+#[cfg(any(feature = "blocking", feature = "hyper"))]
+extern crate tokio;
+#[cfg(feature = "tower")]
+extern crate tower_service;
+#[cfg(feature = "ureq")]
+extern crate ureq;
+#[cfg(feature = "url")]
+extern crate url;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen_futures;
+#[cfg(feature = "wasm")]
+extern crate web_sys;
 
 pub mod constants;
+pub mod location;
 pub mod models;
-pub mod utils;
 
-#[cfg(any(feature = "hyper", feature = "reqwest"))]
+#[cfg(feature = "std")]
+pub mod adapters;
+#[cfg(feature = "std")]
+pub mod alerts;
+#[cfg(feature = "std")]
+pub mod backfill;
+#[cfg(feature = "std")]
 pub mod bridge;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod changes;
+#[cfg(feature = "std")]
+pub mod facade;
+#[cfg(feature = "std")]
+pub mod format;
+#[cfg(feature = "std")]
+pub mod intern;
+#[cfg(feature = "std")]
+pub mod locale;
+#[cfg(feature = "std")]
+pub mod narrative;
+#[cfg(feature = "std")]
+pub mod quota;
+#[cfg(feature = "std")]
+pub mod template;
+#[cfg(feature = "std")]
+pub mod text;
+#[cfg(feature = "std")]
+pub mod utils;
+
+#[cfg(feature = "air-quality")]
+pub mod air_quality;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(any(
+    feature = "hyper-native-tls",
+    feature = "hyper-rustls",
+    feature = "reqwest-async",
+    feature = "reqwest-blocking",
+    feature = "ureq"
+))]
+pub mod client;
+#[cfg(feature = "prometheus")]
+pub mod exporter;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod health;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+#[cfg(feature = "http")]
+pub mod request;
+#[cfg(feature = "tower")]
+pub mod service;
+#[cfg(feature = "webhook")]
+pub mod webhook;
 
 mod error;
 mod internal;
+mod token;
 
 pub use error::{Error, Result};
+pub use location::{Coordinate, IntoLocation};
+pub use token::{ApiToken, TokenProvider};
+#[cfg(feature = "std")]
+pub use token::{EnvToken, FileToken, TokenPool, TokenUsage};
+
+#[cfg(feature = "std")]
+pub use facade::{DarkskyClient, DarkskyClientBuilder};
 
+#[cfg(feature = "attohttpc")]
+pub use bridge::DarkskyAttohttpcRequester;
+#[cfg(feature = "awc")]
+pub use bridge::DarkskyAwcRequester;
+#[cfg(feature = "curl")]
+pub use bridge::DarkskyCurlRequester;
 #[cfg(feature = "hyper")]
 pub use bridge::DarkskyHyperRequester;
-#[cfg(feature = "reqwest")]
+#[cfg(feature = "reqwest-async")]
+pub use bridge::DarkskyReqwestAsyncRequester;
+#[cfg(feature = "reqwest-blocking")]
 pub use bridge::DarkskyReqwestRequester;
+#[cfg(feature = "surf")]
+pub use bridge::DarkskySurfRequester;
+#[cfg(feature = "ureq")]
+pub use bridge::DarkskyUreqRequester;
+#[cfg(feature = "wasm")]
+pub use bridge::DarkskyWasmRequester;
+#[cfg(feature = "mqtt")]
+pub use mqtt::DarkskyMqttPublisher;
 
-use std::collections::HashMap;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::fmt::Display;
 
 /// A block is a name of a [`Datablock`] returned from the API. This can be used
 /// to exclude datablocks from being returned from the API, to reduce bandwidth.
@@ -163,6 +441,7 @@ pub enum Block {
     Minutely,
 }
 
+#[cfg(feature = "std")]
 impl Block {
     fn name(&self) -> &str {
         use Block::*;
@@ -177,14 +456,46 @@ impl Block {
     }
 }
 
+/// A block of a forecast to extend beyond its usual length, as accepted by the
+/// `extend` option.
+///
+/// DarkSky only supports extending the [hourly][`Extend::Hourly`] block, but
+/// compatible providers may accept other values, which can be given with
+/// [`Extend::Other`].
+///
+/// [`Extend::Hourly`]: #variant.Hourly
+/// [`Extend::Other`]: #variant.Other
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Extend {
+    /// Extends the hourly block to the full `7` days ahead, rather than only
+    /// the first `2` days.
+    Hourly,
+    /// Any other value of the `extend` option, sent as given.
+    Other(String),
+}
+
+#[cfg(feature = "std")]
+impl Extend {
+    fn name(&self) -> &str {
+        match *self {
+            Extend::Hourly => "hourly",
+            Extend::Other(ref name) => name,
+        }
+    }
+}
+
 /// The language to return from the API for the [`summary`] field.
 ///
 /// The language is automatically [English][`Language::En`], so specifying
 /// English is not required.
 ///
+/// Languages the API supports but which are not listed here can be given by
+/// their code with [`Language::Other`].
+///
 /// [`Language::En`]: #variant.En
+/// [`Language::Other`]: #variant.Other
 /// [`summary`]: models/struct.Datapoint.html#structfield.summary
-#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
 pub enum Language {
     /// Arabic
     #[serde(rename = "ar")]
@@ -195,12 +506,27 @@ pub enum Language {
     /// Belarusian
     #[serde(rename = "be")]
     Be,
+    /// Bulgarian
+    #[serde(rename = "bg")]
+    Bg,
+    /// Bengali
+    #[serde(rename = "bn")]
+    Bn,
     /// Bosnian
     #[serde(rename = "bs")]
     Bs,
+    /// Catalan
+    #[serde(rename = "ca")]
+    Ca,
     /// Czech
     #[serde(rename = "cs")]
     Cs,
+    /// Welsh
+    #[serde(rename = "cy")]
+    Cy,
+    /// Danish
+    #[serde(rename = "da")]
+    Da,
     /// German
     #[serde(rename = "de")]
     De,
@@ -210,12 +536,27 @@ pub enum Language {
     /// English
     #[serde(rename = "en")]
     En,
+    /// Esperanto
+    #[serde(rename = "eo")]
+    Eo,
     /// Spanish
     #[serde(rename = "es")]
     Es,
+    /// Estonian
+    #[serde(rename = "et")]
+    Et,
+    /// Finnish
+    #[serde(rename = "fi")]
+    Fi,
     /// French
     #[serde(rename = "fr")]
     Fr,
+    /// Hebrew
+    #[serde(rename = "he")]
+    He,
+    /// Hindi
+    #[serde(rename = "hi")]
+    Hi,
     /// Croatian
     #[serde(rename = "hr")]
     Hr,
@@ -231,33 +572,74 @@ pub enum Language {
     /// Icelandic
     #[serde(rename = "is")]
     Is,
+    /// Japanese
+    #[serde(rename = "ja")]
+    Ja,
+    /// Georgian
+    #[serde(rename = "ka")]
+    Ka,
+    /// Kannada
+    #[serde(rename = "kn")]
+    Kn,
+    /// Korean
+    #[serde(rename = "ko")]
+    Ko,
     /// Cornish
     #[serde(rename = "kw")]
     Kw,
+    /// Latvian
+    #[serde(rename = "lv")]
+    Lv,
+    /// Malayalam
+    #[serde(rename = "ml")]
+    Ml,
+    /// Marathi
+    #[serde(rename = "mr")]
+    Mr,
     /// Norwegian Bokmål
     #[serde(rename = "nb")]
     Nb,
     /// Dutch
     #[serde(rename = "nl")]
     Nl,
+    /// Norwegian Bokmål, an alias of [Nb][`Language::Nb`]
+    ///
+    /// [`Language::Nb`]: #variant.Nb
+    #[serde(rename = "no")]
+    No,
+    /// Punjabi
+    #[serde(rename = "pa")]
+    Pa,
     /// Polish
     #[serde(rename = "pl")]
     Pl,
     /// Portuguese
     #[serde(rename = "pt")]
     Pt,
+    /// Romanian
+    #[serde(rename = "ro")]
+    Ro,
     /// Russian
     #[serde(rename = "ru")]
     Ru,
     /// Slovak
     #[serde(rename = "sk")]
     Sk,
+    /// Slovenian
+    #[serde(rename = "sl")]
+    Sl,
     /// Serbian
     #[serde(rename = "sr")]
     Sr,
     /// Swedish
     #[serde(rename = "sv")]
     Sv,
+    /// Tamil
+    #[serde(rename = "ta")]
+    Ta,
+    /// Telugu
+    #[serde(rename = "te")]
+    Te,
     /// Tetum
     #[serde(rename = "tet")]
     Tet,
@@ -267,6 +649,9 @@ pub enum Language {
     /// Ukrainian
     #[serde(rename = "uk")]
     Uk,
+    /// Urdu
+    #[serde(rename = "ur")]
+    Ur,
     /// Igpay Atinlay
     #[serde(rename = "x-pig-latin")]
     XPigLatin,
@@ -276,8 +661,14 @@ pub enum Language {
     /// Traditional Chinese
     #[serde(rename = "zh-tw")]
     ZhTw,
+    /// Any other language, by the code the API uses for it, sent as given.
+    ///
+    /// Codes which are not of a listed language deserialize to this.
+    #[serde(untagged)]
+    Other(String),
 }
 
+#[cfg(feature = "std")]
 impl Language {
     fn name(&self) -> &str {
         use Language::*;
@@ -286,33 +677,58 @@ impl Language {
             Ar => "ar",
             Az => "az",
             Be => "be",
+            Bg => "bg",
+            Bn => "bn",
             Bs => "bs",
+            Ca => "ca",
             Cs => "cs",
+            Cy => "cy",
+            Da => "da",
             De => "de",
             El => "el",
             En => "en",
+            Eo => "eo",
             Es => "es",
+            Et => "et",
+            Fi => "fi",
             Fr => "fr",
+            He => "he",
+            Hi => "hi",
             Hr => "hr",
             Hu => "hu",
             Id => "id",
             It => "it",
             Is => "is",
+            Ja => "ja",
+            Ka => "ka",
+            Kn => "kn",
+            Ko => "ko",
             Kw => "kw",
+            Lv => "lv",
+            Ml => "ml",
+            Mr => "mr",
             Nb => "nb",
             Nl => "nl",
+            No => "no",
+            Pa => "pa",
             Pl => "pl",
             Pt => "pt",
+            Ro => "ro",
             Ru => "ru",
             Sk => "sk",
+            Sl => "sl",
             Sr => "sr",
             Sv => "sv",
+            Ta => "ta",
+            Te => "te",
             Tet => "tet",
             Tr => "tr",
             Uk => "uk",
+            Ur => "ur",
             XPigLatin => "x-pig-latin",
             Zh => "zh",
             ZhTw => "zh-tw",
+            Other(ref code) => code,
         }
     }
 }
@@ -331,9 +747,9 @@ impl Language {
 ///
 /// [`Options::unit`]: struct.Options.html#method.unit
 /// [hyper `get_forecast_with_options`]:
-///   bridge/hyper/trait.DarkskyHyperRequester.html#tymethod.get_forecast_with_options
+///   bridge/hyper/trait.DarkskyHyperRequester.html#method.get_forecast_with_options
 /// [reqwest `get_forecast_with_options`]:
-///   bridge/reqwest/trait.DarkskyReqwestRequester.html#tymethod.get_forecast_with_options
+///   bridge/reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_with_options
 /// [docs]: https://darksky.net/dev/docs/forecast
 #[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
 pub enum Unit {
@@ -364,6 +780,7 @@ pub enum Unit {
     Us,
 }
 
+#[cfg(feature = "std")]
 impl Unit {
     fn name(&self) -> &str {
         use Unit::*;
@@ -398,24 +815,70 @@ impl Unit {
 /// [`Options::extend_hourly`]: struct.Options.html#method.extend_hourly
 /// [`Unit`]: enum.Unit.html
 /// [hyper `get_forecast_with_options`]:
-///   bridge/hyper/trait.DarkskyHyperRequester.html#tymethod.get_forecast_with_options
+///   bridge/hyper/trait.DarkskyHyperRequester.html#method.get_forecast_with_options
 /// [reqwest `get_forecast_with_options`]:
-///   bridge/reqwest/trait.DarkskyReqwestRequester.html#tymethod.get_forecast_with_options
-#[derive(Clone, Debug, Default)]
-pub struct Options(HashMap<&'static str, String>);
+///   bridge/reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_with_options
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Options {
+    exclude: Vec<Block>,
+    extend: Option<Extend>,
+    language: Option<Language>,
+    time: Option<String>,
+    unit: Option<Unit>,
+}
 
+#[cfg(feature = "std")]
 impl Options {
-    /// Set the list of [`Datablock`]s to exclude. For a full list of potential
+    /// Adds [`Datablock`]s to exclude. For a full list of potential
     /// datablocks to exclude, refer to [`Block`].
     ///
+    /// Blocks are added to those already excluded, so that options built up
+    /// in several places don't lose each other's exclusions, and each is only
+    /// excluded once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use darksky::{Block, Options};
+    ///
+    /// let options = Options::default()
+    ///     .exclude(vec![Block::Minutely, Block::Hourly])
+    ///     .exclude(Some(Block::Minutely))
+    ///     .exclude([Block::Flags]);
+    ///
+    /// assert_eq!(options.get_exclude(), [Block::Minutely, Block::Hourly, Block::Flags]);
+    /// assert_eq!(options.to_query(), "exclude=minutely,hourly,flags");
+    /// ```
+    ///
     /// [`Block`]: enum.Block.html
     /// [`Datablock`]: models/struct.Datablock.html
-    pub fn exclude(mut self, blocks: Vec<Block>) -> Self {
-        let block_names = blocks.iter().map(|b| b.name()).collect::<Vec<_>>();
+    pub fn exclude<I: IntoIterator<Item = Block>>(mut self, blocks: I) -> Self {
+        for block in blocks {
+            if !self.exclude.contains(&block) {
+                self.exclude.push(block);
+            }
+        }
 
-        let list = block_names.join(",");
+        self
+    }
 
-        self.0.insert("exclude", list.to_owned());
+    /// Extends a block of the [forecast][`Forecast`] beyond its usual length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use darksky::{Extend, Options};
+    ///
+    /// let options = Options::default().extend(Extend::Other("daily".to_owned()));
+    ///
+    /// assert_eq!(options.get_extend(), Some(Extend::Other("daily".to_owned())));
+    /// assert_eq!(options.to_query(), "extend=daily");
+    /// ```
+    ///
+    /// [`Forecast`]: models/struct.Forecast.html
+    pub fn extend(mut self, extend: Extend) -> Self {
+        self.extend = Some(extend);
 
         self
     }
@@ -423,81 +886,153 @@ impl Options {
     /// Extends the hourly [forecast][`Forecast`] to the full `7` days ahead,
     /// rather than only the first `2` days.
     ///
+    /// This is equivalent to [`extend`] with [`Extend::Hourly`].
+    ///
+    /// [`Extend::Hourly`]: enum.Extend.html#variant.Hourly
     /// [`Forecast`]: models/struct.Forecast.html
-    pub fn extend_hourly(mut self) -> Self {
-        self.0.insert("extend", "hourly".to_owned());
+    /// [`extend`]: #method.extend
+    pub fn extend_hourly(self) -> Self {
+        self.extend(Extend::Hourly)
+    }
+
+    /// Set the language of the [`summary`] provided.
+    ///
+    /// [`summary`]: models/struct.Datapoint.html#structfield.summary
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
 
         self
     }
 
-    /// Gets a mutable reference to the underlying HashMap.
+    /// Requests the forecast at the given time, through the Time Machine API,
+    /// rather than the current forecast.
+    ///
+    /// The time is either a UNIX timestamp or a string formatted as
+    /// `[YYYY]-[MM]-[DD]T[HH]:[MM]:[SS][timezone]`, and is not validated, but
+    /// is percent-encoded where it could not otherwise be part of the path of
+    /// a URI.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use darksky::Options;
-    ///
-    /// let mut options = Options::default().extend_hourly();
+    /// use darksky::{utils, Options};
     ///
-    /// // Note: you probably shouldn't add keys yourself. If there is a key the
-    /// // library does not support, please submit a PR.
-    /// let mut inner = options.get_mut();
-    /// inner.insert("foo", "bar".to_owned());
+    /// let options = Options::default().time(1_450_000_000);
+    /// assert_eq!(options.get_time(), Some("1450000000"));
     ///
-    /// assert_eq!(inner.len(), 2);
+    /// let uri = utils::uri_optioned("def", 49.9, -97.1, None, options).unwrap();
+    /// assert_eq!(uri, "https://api.darksky.net/forecast/def/49.9,-97.1,1450000000?");
     /// ```
-    pub fn get_mut(&mut self) -> &mut HashMap<&'static str, String> {
-        &mut self.0
+    pub fn time<D: Display>(mut self, time: D) -> Self {
+        self.time = Some(time.to_string());
+
+        self
     }
 
-    /// Gets an immuatble reference to the underlying HashMap.
-    ///
-    /// # Examples
-    ///
-    /// ```rust
-    /// use darksky::{Block, Options};
-    ///
-    /// let options = Options::default().exclude(vec![Block::Hourly]);
+    /// Sets the unit type returned from the API. Refer to the
+    /// [DarkSky documentation][docs] or the [`Unit`] docs for more info.
     ///
-    /// assert_eq!(options.get_ref().len(), 1);
-    /// ```
-    pub fn get_ref(&self) -> &HashMap<&'static str, String> {
-        &self.0
+    /// [`Unit`]: enum.Unit.html
+    /// [docs]: https://darksky.net/dev/docs
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+
+        self
+    }
+
+    /// Returns the blocks that are excluded.
+    pub fn get_exclude(&self) -> &[Block] {
+        &self.exclude
+    }
+
+    /// Returns the extension that was requested, if any.
+    pub fn get_extend(&self) -> Option<Extend> {
+        self.extend.clone()
+    }
+
+    /// Returns the language of the summaries, if any.
+    pub fn get_language(&self) -> Option<Language> {
+        self.language.clone()
+    }
+
+    /// Returns the time the forecast is requested at, if any.
+    pub fn get_time(&self) -> Option<&str> {
+        self.time.as_ref().map(|time| &time[..])
+    }
+
+    /// Returns the unit type returned from the API, if any.
+    pub fn get_unit(&self) -> Option<Unit> {
+        self.unit
     }
 
-    /// Unwraps the struct, returning the underlying HashMap.
+    /// Formats the options as the query string of a request URI, without the
+    /// leading `?`.
+    ///
+    /// Values are percent-encoded, such as those of an [`Extend::Other`]
+    /// given by a user. The [time] is not included, as it is part of the path
+    /// of the URI.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use darksky::{Block, Options};
+    /// use darksky::{Block, Extend, Language, Options, Unit};
     ///
-    /// let options = Options::default().exclude(vec![Block::Hourly]);
-    /// let map = options.into_inner();
+    /// let options = Options::default()
+    ///     .unit(Unit::Si)
+    ///     .exclude(vec![Block::Minutely, Block::Hourly])
+    ///     .language(Language::Fr);
     ///
-    /// assert!(map.get("exclude").is_some());
+    /// assert_eq!(options.to_query(), "exclude=minutely,hourly&lang=fr&units=si");
+    /// assert_eq!(Options::default().to_query(), "");
+    ///
+    /// let options = Options::default().extend(Extend::Other("days&units=si".to_owned()));
+    /// assert_eq!(options.to_query(), "extend=days%26units%3Dsi");
     /// ```
-    pub fn into_inner(self) -> HashMap<&'static str, String> {
-        self.0
-    }
-
-    /// Set the language of the [`summary`] provided.
     ///
-    /// [`summary`]: models/struct.Datapoint.html#structfield.summary
-    pub fn language(mut self, language: Language) -> Self {
-        self.0.insert("lang", language.name().to_owned());
+    /// [`Extend::Other`]: enum.Extend.html#variant.Other
+    /// [time]: #method.time
+    pub fn to_query(&self) -> String {
+        let mut query = String::new();
+        self.push_query(&mut query);
+        query.pop();
 
-        self
+        query
     }
 
-    /// Sets the unit type returned from the API. Refer to the
-    /// [DarkSky documentation][docs] or the [`Unit`] docs for more info.
-    ///
-    /// [`Unit`]: enum.Unit.html
-    /// [docs]: https://darksky.net/dev/docs
-    pub fn unit(mut self, unit: Unit) -> Self {
-        self.0.insert("units", unit.name().to_owned());
+    /// Returns the options other than the excluded blocks, keyed by the name
+    /// of their query parameter, with `None` for those which are not set.
+    pub(crate) fn named_pairs(&self) -> [(&'static str, Option<&str>); 3] {
+        [
+            ("extend", self.extend.as_ref().map(Extend::name)),
+            ("lang", self.language.as_ref().map(Language::name)),
+            ("units", self.unit.as_ref().map(Unit::name)),
+        ]
+    }
 
-        self
+    /// Appends each option to a URI as a `key=value&` pair, percent-encoding
+    /// the keys and values.
+    pub(crate) fn push_query(&self, uri: &mut String) {
+        if !self.exclude.is_empty() {
+            uri.push_str("exclude=");
+
+            for (idx, block) in self.exclude.iter().enumerate() {
+                if idx > 0 {
+                    uri.push(',');
+                }
+
+                uri.push_str(block.name());
+            }
+
+            uri.push('&');
+        }
+
+        for (key, value) in self.named_pairs().iter() {
+            if let Some(value) = value {
+                utils::push_encoded(uri, key, b"");
+                uri.push('=');
+                utils::push_encoded(uri, value, b"");
+                uri.push('&');
+            }
+        }
     }
 }
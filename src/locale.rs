@@ -0,0 +1,274 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Localized text for the phrases derived by the crate itself, such as the
+//! labels of the [`format`] module, compass points, and UV index categories.
+//!
+//! This allows derived text to match the language of the API's localized
+//! [`summary`] fields. English, Dutch, French, German, Italian, Portuguese,
+//! and Spanish are supported, with other languages falling back to English.
+//!
+//! [`format`]: ../format/index.html
+//! [`summary`]: ../models/struct.Datapoint.html#structfield.summary
+
+use models::Severity;
+use Language;
+
+/// A table of localized phrases for a single language.
+#[derive(Debug)]
+pub struct Locale {
+    /// The label for current conditions, e.g. `"Now"`.
+    pub now: &'static str,
+    /// The heading for upcoming hours, where `{}` is replaced by the number of
+    /// hours, e.g. `"Next {} hours"`.
+    pub next_hours: &'static str,
+    /// The label for today's conditions, e.g. `"Today"`.
+    pub today: &'static str,
+    /// The phrase preceding a high temperature, e.g. `"high of"`.
+    pub high: &'static str,
+    /// The phrase preceding a low temperature, e.g. `"low of"`.
+    pub low: &'static str,
+    /// The phrase preceding an apparent temperature, e.g. `"feels like"`.
+    pub feels_like: &'static str,
+    /// The word for humidity.
+    pub humidity: &'static str,
+    /// The word for wind.
+    pub wind: &'static str,
+    /// The word for precipitation.
+    pub precipitation: &'static str,
+    /// The heading for alerts, e.g. `"Alerts"`.
+    pub alerts: &'static str,
+    /// The word preceding an expiry time, e.g. `"until"`.
+    pub until: &'static str,
+    /// The names of the [`Severity`] levels, from least to most severe.
+    ///
+    /// [`Severity`]: ../models/enum.Severity.html
+    pub severities: [&'static str; 3],
+    /// The abbreviated names of the eight compass points, clockwise from
+    /// north.
+    pub compass_points: [&'static str; 8],
+    /// The names of the UV index categories, from low to extreme.
+    pub uv_categories: [&'static str; 5],
+}
+
+impl Locale {
+    /// Returns the locale for a language, falling back to [English][`EN`] for
+    /// unsupported languages.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use darksky::locale::Locale;
+    /// use darksky::Language;
+    ///
+    /// assert_eq!(Locale::for_language(Language::Fr).now, "Maintenant");
+    /// assert_eq!(Locale::for_language(Language::Tet).now, "Now");
+    /// ```
+    ///
+    /// [`EN`]: static.EN.html
+    pub fn for_language(language: Language) -> &'static Locale {
+        match language {
+            Language::En => &EN,
+            Language::De => &DE,
+            Language::Es => &ES,
+            Language::Fr => &FR,
+            Language::It => &IT,
+            Language::Nl => &NL,
+            Language::Pt => &PT,
+            _ => &EN,
+        }
+    }
+
+    /// Returns the abbreviated name of the compass point nearest to a bearing
+    /// in degrees, such as a [`wind_bearing`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use darksky::locale::{DE, EN};
+    ///
+    /// assert_eq!(EN.compass_point(100.0), "E");
+    /// assert_eq!(DE.compass_point(300.0), "NW");
+    /// ```
+    ///
+    /// [`wind_bearing`]: ../models/struct.Datapoint.html#structfield.wind_bearing
+    pub fn compass_point(&self, bearing: f64) -> &'static str {
+        let index = (bearing.rem_euclid(360.0) / 45.0).round() as usize % 8;
+
+        self.compass_points[index]
+    }
+
+    /// Returns the name of a [`Severity`].
+    ///
+    /// [`Severity`]: ../models/enum.Severity.html
+    pub fn severity(&self, severity: Severity) -> &'static str {
+        match severity {
+            Severity::Advisory => self.severities[0],
+            Severity::Watch => self.severities[1],
+            Severity::Warning => self.severities[2],
+        }
+    }
+
+    /// Returns the name of the category of a [`uv_index`], following the World
+    /// Health Organization's scale.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use darksky::locale::{EN, ES};
+    ///
+    /// assert_eq!(EN.uv_category(7), "high");
+    /// assert_eq!(ES.uv_category(11), "extremo");
+    /// ```
+    ///
+    /// [`uv_index`]: ../models/struct.Datapoint.html#structfield.uv_index
+    pub fn uv_category(&self, index: u64) -> &'static str {
+        let category = match index {
+            0..=2 => 0,
+            3..=5 => 1,
+            6..=7 => 2,
+            8..=10 => 3,
+            _ => 4,
+        };
+
+        self.uv_categories[category]
+    }
+}
+
+/// English phrases.
+pub static EN: Locale = Locale {
+    now: "Now",
+    next_hours: "Next {} hours",
+    today: "Today",
+    high: "high of",
+    low: "low of",
+    feels_like: "feels like",
+    humidity: "humidity",
+    wind: "wind",
+    precipitation: "precipitation",
+    alerts: "Alerts",
+    until: "until",
+    severities: ["advisory", "watch", "warning"],
+    compass_points: ["N", "NE", "E", "SE", "S", "SW", "W", "NW"],
+    uv_categories: ["low", "moderate", "high", "very high", "extreme"],
+};
+
+/// German phrases.
+pub static DE: Locale = Locale {
+    now: "Jetzt",
+    next_hours: "Nächste {} Stunden",
+    today: "Heute",
+    high: "Höchstwert von",
+    low: "Tiefstwert von",
+    feels_like: "gefühlt",
+    humidity: "Luftfeuchtigkeit",
+    wind: "Wind",
+    precipitation: "Niederschlag",
+    alerts: "Warnungen",
+    until: "bis",
+    severities: ["Hinweis", "Vorwarnung", "Warnung"],
+    compass_points: ["N", "NO", "O", "SO", "S", "SW", "W", "NW"],
+    uv_categories: ["niedrig", "mäßig", "hoch", "sehr hoch", "extrem"],
+};
+
+/// Spanish phrases.
+pub static ES: Locale = Locale {
+    now: "Ahora",
+    next_hours: "Próximas {} horas",
+    today: "Hoy",
+    high: "máxima de",
+    low: "mínima de",
+    feels_like: "sensación de",
+    humidity: "humedad",
+    wind: "viento",
+    precipitation: "precipitación",
+    alerts: "Alertas",
+    until: "hasta",
+    severities: ["aviso", "vigilancia", "advertencia"],
+    compass_points: ["N", "NE", "E", "SE", "S", "SO", "O", "NO"],
+    uv_categories: ["bajo", "moderado", "alto", "muy alto", "extremo"],
+};
+
+/// French phrases.
+pub static FR: Locale = Locale {
+    now: "Maintenant",
+    next_hours: "{} prochaines heures",
+    today: "Aujourd'hui",
+    high: "maximum de",
+    low: "minimum de",
+    feels_like: "ressenti",
+    humidity: "humidité",
+    wind: "vent",
+    precipitation: "précipitations",
+    alerts: "Alertes",
+    until: "jusqu'à",
+    severities: ["avis", "veille", "avertissement"],
+    compass_points: ["N", "NE", "E", "SE", "S", "SO", "O", "NO"],
+    uv_categories: ["faible", "modéré", "élevé", "très élevé", "extrême"],
+};
+
+/// Italian phrases.
+pub static IT: Locale = Locale {
+    now: "Ora",
+    next_hours: "Prossime {} ore",
+    today: "Oggi",
+    high: "massima di",
+    low: "minima di",
+    feels_like: "percepita",
+    humidity: "umidità",
+    wind: "vento",
+    precipitation: "precipitazioni",
+    alerts: "Allerte",
+    until: "fino alle",
+    severities: ["avviso", "allerta", "allarme"],
+    compass_points: ["N", "NE", "E", "SE", "S", "SO", "O", "NO"],
+    uv_categories: ["basso", "moderato", "alto", "molto alto", "estremo"],
+};
+
+/// Dutch phrases.
+pub static NL: Locale = Locale {
+    now: "Nu",
+    next_hours: "Komende {} uur",
+    today: "Vandaag",
+    high: "maximum van",
+    low: "minimum van",
+    feels_like: "voelt als",
+    humidity: "luchtvochtigheid",
+    wind: "wind",
+    precipitation: "neerslag",
+    alerts: "Waarschuwingen",
+    until: "tot",
+    severities: ["advies", "waakzaamheid", "waarschuwing"],
+    compass_points: ["N", "NO", "O", "ZO", "Z", "ZW", "W", "NW"],
+    uv_categories: ["laag", "matig", "hoog", "zeer hoog", "extreem"],
+};
+
+/// Portuguese phrases.
+pub static PT: Locale = Locale {
+    now: "Agora",
+    next_hours: "Próximas {} horas",
+    today: "Hoje",
+    high: "máxima de",
+    low: "mínima de",
+    feels_like: "sensação de",
+    humidity: "umidade",
+    wind: "vento",
+    precipitation: "precipitação",
+    alerts: "Alertas",
+    until: "até",
+    severities: ["aviso", "vigilância", "alerta"],
+    compass_points: ["N", "NE", "L", "SE", "S", "SO", "O", "NO"],
+    uv_categories: ["baixo", "moderado", "alto", "muito alto", "extremo"],
+};
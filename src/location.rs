@@ -0,0 +1,158 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Locations to request forecasts for.
+//!
+//! Forecast methods accept any [`IntoLocation`] rather than a bare latitude
+//! and longitude, so that call sites name what they pass and a latitude and
+//! longitude cannot be swapped unnoticed. A [`Coordinate`] names its fields,
+//! while a `(f64, f64)` tuple is taken as `(latitude, longitude)`, the order
+//! DarkSky uses.
+//!
+//! With the **geo** feature, `geo_types` points and coordinates are accepted
+//! as well, and with the **geohash** feature, geohash strings are decoded to
+//! the centre of their cell.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use darksky::{Coordinate, IntoLocation};
+//!
+//! let winnipeg = Coordinate::new(49.9, -97.1);
+//!
+//! assert_eq!(winnipeg.lat, 49.9);
+//! assert_eq!((49.9, -97.1).into_location().unwrap(), winnipeg);
+//! ```
+//!
+//! [`Coordinate`]: struct.Coordinate.html
+//! [`IntoLocation`]: trait.IntoLocation.html
+
+#[cfg(feature = "geohash")]
+use alloc::string::String;
+#[cfg(feature = "geo")]
+use geo_types::{Coord, Point};
+#[cfg(feature = "geohash")]
+use geohash::{self, GeohashError};
+use Result;
+
+/// A location, as a latitude and longitude in degrees.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Coordinate {
+    /// The latitude, within `[-90, 90]`.
+    pub lat: f64,
+    /// The longitude, within `[-180, 180]`.
+    pub lon: f64,
+}
+
+impl Coordinate {
+    /// Creates a coordinate from a latitude and longitude.
+    ///
+    /// The coordinate is not validated until a request is made with it, which
+    /// fails with [`Error::InvalidCoordinate`] if either is out of range.
+    ///
+    /// [`Error::InvalidCoordinate`]: ../enum.Error.html#variant.InvalidCoordinate
+    pub fn new(lat: f64, lon: f64) -> Self {
+        Coordinate { lat, lon }
+    }
+}
+
+impl From<(f64, f64)> for Coordinate {
+    fn from((lat, lon): (f64, f64)) -> Self {
+        Coordinate { lat, lon }
+    }
+}
+
+/// A type which can be converted into a [`Coordinate`] to request a forecast
+/// for.
+///
+/// The conversion is fallible so that encoded locations can be decoded.
+///
+/// [`Coordinate`]: struct.Coordinate.html
+pub trait IntoLocation {
+    /// Converts the value into a coordinate.
+    fn into_location(self) -> Result<Coordinate>;
+}
+
+impl IntoLocation for Coordinate {
+    fn into_location(self) -> Result<Coordinate> {
+        Ok(self)
+    }
+}
+
+impl IntoLocation for &Coordinate {
+    fn into_location(self) -> Result<Coordinate> {
+        Ok(*self)
+    }
+}
+
+/// Takes the tuple as `(latitude, longitude)`.
+impl IntoLocation for (f64, f64) {
+    fn into_location(self) -> Result<Coordinate> {
+        Ok(self.into())
+    }
+}
+
+/// Takes the point's `y` as the latitude and its `x` as the longitude.
+#[cfg(feature = "geo")]
+impl IntoLocation for Point<f64> {
+    fn into_location(self) -> Result<Coordinate> {
+        Ok(Coordinate::new(self.y(), self.x()))
+    }
+}
+
+/// Takes the coordinate's `y` as the latitude and its `x` as the longitude.
+#[cfg(feature = "geo")]
+impl IntoLocation for Coord<f64> {
+    fn into_location(self) -> Result<Coordinate> {
+        Ok(Coordinate::new(self.y, self.x))
+    }
+}
+
+/// Decodes the string as a geohash, taking the centre of its cell.
+///
+/// # Errors
+///
+/// Returns [`Error::Geohash`] if the string is not a valid geohash, including
+/// if it is empty, which would otherwise decode to the whole world.
+///
+/// [`Error::Geohash`]: ../enum.Error.html#variant.Geohash
+#[cfg(feature = "geohash")]
+impl IntoLocation for &str {
+    fn into_location(self) -> Result<Coordinate> {
+        if self.is_empty() {
+            return Err(GeohashError::InvalidLength(0).into());
+        }
+
+        let (centre, _, _) = geohash::decode(self)?;
+
+        Ok(Coordinate::new(centre.y, centre.x))
+    }
+}
+
+/// Decodes the string as a geohash, taking the centre of its cell.
+#[cfg(feature = "geohash")]
+impl IntoLocation for &String {
+    fn into_location(self) -> Result<Coordinate> {
+        self.as_str().into_location()
+    }
+}
+
+/// Decodes the string as a geohash, taking the centre of its cell.
+#[cfg(feature = "geohash")]
+impl IntoLocation for String {
+    fn into_location(self) -> Result<Coordinate> {
+        self.as_str().into_location()
+    }
+}
@@ -15,6 +15,40 @@
 // CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
 //! A set of models representing data received by the API.
 
+pub mod borrowed;
+#[cfg(feature = "compact")]
+pub mod compact;
+
+mod conditions;
+mod days;
+mod finite;
+mod lenient;
+mod size;
+mod summary;
+
+pub use self::conditions::{CloudCover, PressureTendency, Visibility};
+pub use self::days::{Chunk, HighLow, LocalDate, Window};
+pub use self::finite::NonFinite;
+pub use self::lenient::{BlockError, PartialForecast};
+pub use self::summary::SummaryText;
+
+#[cfg(feature = "std")]
+use alerts::AlertQuery;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ops::Deref;
+use core::result::Result as StdResult;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "url")]
+use url::Url;
+#[cfg(feature = "std")]
+use Language;
+use {internal, Result};
+
 /// A safe representation of the indicated weather. This is useful for matching
 /// and presenting an emoji or other weather symbol or representation.
 #[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
@@ -77,7 +111,9 @@ pub enum PrecipitationType {
 }
 
 /// The severity of the weather alert.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+///
+/// Severities are ordered from least to most severe.
+#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     /// An individual should be aware of potentially severe weather.
@@ -113,6 +149,89 @@ pub struct Alert {
     pub severity: Severity,
 }
 
+#[cfg(feature = "url")]
+impl Alert {
+    /// Parses the alert's [`uri`], falling back to the unparsed string if it
+    /// is not a valid URL.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::models::{Alert, AlertUri};
+    ///
+    /// # fn main() {
+    /// let alert: Alert = serde_json::from_str(r#"{
+    ///     "title": "Winter Storm",
+    ///     "regions": ["Winnipeg"],
+    ///     "severity": "warning",
+    ///     "time": 1509993277,
+    ///     "expires": 1510036680,
+    ///     "description": "Heavy snow expected.",
+    ///     "uri": "HTTPS://Alerts.Weather.gov/cap/wwacapget.php?x=1"
+    /// }"#).unwrap();
+    ///
+    /// let uri = alert.parsed_uri();
+    /// assert_eq!(uri.as_str(), "HTTPS://Alerts.Weather.gov/cap/wwacapget.php?x=1");
+    ///
+    /// match uri {
+    ///     AlertUri::Url { url, .. } => assert_eq!(url.host_str(), Some("alerts.weather.gov")),
+    ///     AlertUri::Unparsed(uri) => panic!("invalid uri: {}", uri),
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// [`uri`]: #structfield.uri
+    pub fn parsed_uri(&self) -> AlertUri {
+        match Url::parse(&self.uri) {
+            Ok(url) => AlertUri::Url {
+                url,
+                uri: self.uri.clone(),
+            },
+            Err(_) => AlertUri::Unparsed(self.uri.clone()),
+        }
+    }
+}
+
+/// The URI of an [`Alert`], as returned by [`Alert::parsed_uri`].
+///
+/// [`Alert`]: struct.Alert.html
+/// [`Alert::parsed_uri`]: struct.Alert.html#method.parsed_uri
+#[cfg(feature = "url")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum AlertUri {
+    /// The URI, parsed as a URL.
+    Url {
+        /// The parsed URL, which may be normalized from the original, such
+        /// as by adding a trailing slash to an empty path.
+        url: Url,
+        /// The URI as given by the API.
+        uri: String,
+    },
+    /// The URI as given by the API, as it could not be parsed as a URL.
+    Unparsed(String),
+}
+
+#[cfg(feature = "url")]
+impl AlertUri {
+    /// Returns the URI as given by the API.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            AlertUri::Url { ref uri, .. } | AlertUri::Unparsed(ref uri) => uri,
+        }
+    }
+
+    /// Returns the parsed URL, if the URI could be parsed.
+    pub fn url(&self) -> Option<&Url> {
+        match *self {
+            AlertUri::Url { ref url, .. } => Some(url),
+            AlertUri::Unparsed(_) => None,
+        }
+    }
+}
+
 /// A block of data within a [`Forecast`], with potentially many [`Datapoint`]s.
 ///
 /// [`Datapoint`]: struct.Datapoint.html
@@ -124,7 +243,7 @@ pub struct Datablock {
     /// The icon representing the weather type for the datablock.
     pub icon: Option<Icon>,
     /// A written summary of the datablock's expected weather.
-    pub summary: Option<String>,
+    pub summary: Option<SummaryText>,
 }
 
 /// A datapoint within a [`Datablock`], where there is usually multiple.
@@ -140,51 +259,48 @@ pub struct Datablock {
 /// confidence levels, while larger error values represent less confidence.
 /// These fields are omitted where the confidence is not precisely known.
 ///
+/// To keep datapoints small, the error fields are grouped into a boxed
+/// [`ErrorFields`], and the fields only present on `daily` into a boxed
+/// [`DailyFields`]. Each is `None` when none of its fields are present, and
+/// every grouped field can also be read through an accessor method of the same
+/// name.
+///
+/// [`DailyFields`]: struct.DailyFields.html
 /// [`Datablock`]: struct.Datablock.html
+/// [`ErrorFields`]: struct.ErrorFields.html
+/// [`precip_accumulation`]: #structfield.precip_accumulation
+/// [`precip_accumulation_error`]: #method.precip_accumulation_error
 /// [`time`]: #structfield.time
 #[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(from = "RawDatapoint", rename_all = "camelCase")]
 pub struct Datapoint {
-    /// The unix timestamp representing when the daytime high apparent
-    /// temperature occurs.
-    ///
-    /// **Note**: This is only present on the `daily` block.
-    pub apparent_temperature_max_time: Option<u64>,
-    /// The daytime high apparent temperature.
-    ///
-    /// **Note**: This is only present on the `daily` block.
-    pub apparent_temperature_max: Option<f64>,
-    /// The unix timestamp representing when the overnight low apparent
-    /// temperature occurs.
-    ///
-    /// **Note**: This is only present on the `daily` block.
-    pub apparent_temperature_min_time: Option<u64>,
-    /// The overnight low apparent temperature.
-    ///
-    /// **Note**: This is only present on the `daily` block.
-    pub apparent_temperature_min: Option<f64>,
     /// The apparent (or "feels like") temperature in degrees Fahrenheit.
     ///
     /// **Note**: This is not present on `daily`.
     pub apparent_temperature: Option<f64>,
-    /// The amount of error possible within the [`cloud_cover`] value.
-    ///
-    /// [`cloud_cover`]: #structfield.cloud_cover
-    pub cloud_cover_error: Option<f64>,
     /// The percentage of sky occluded by clouds.
     ///
     /// This value is between `0` and `1`, inclusively.
     pub cloud_cover: Option<f64>,
-    /// The amount of error possible within the [`dew_point`] value.
+    /// Fields that are only present on the `daily` block, if any are present.
     ///
-    /// [`dew_point`]: #structfield.dew_point
-    pub dew_point_error: Option<f64>,
+    /// These are also available through accessor methods of the same name,
+    /// such as [`temperature_max`].
+    ///
+    /// [`temperature_max`]: #method.temperature_max
+    #[serde(flatten)]
+    pub daily: Option<Box<DailyFields>>,
     /// The dew point in degrees Fahrenheit.
     pub dew_point: Option<f64>,
-    /// The amount of error possible within the [`humidity`] value.
+    /// The amount of error possible within the data oriented fields, if any
+    /// are known.
     ///
-    /// [`humidity`]: #structfield.humidity
-    pub humidity_error: Option<f64>,
+    /// These are also available through accessor methods of the same name,
+    /// such as [`temperature_error`].
+    ///
+    /// [`temperature_error`]: #method.temperature_error
+    #[serde(flatten)]
+    pub errors: Option<Box<ErrorFields>>,
     /// The relative humidity.
     ///
     /// This value is between `0` and `1`, inclusively.
@@ -192,13 +308,6 @@ pub struct Datapoint {
     /// A machine-readable summary of the datapoint, suitable for selecting an
     /// icon to display.
     pub icon: Option<Icon>,
-    /// The fractional part of the [lunation number] during the given day.
-    ///
-    /// A value of `0` corresponds to a new moon, `0.25` to a first quarter
-    /// moon, `0.5` to a full moon, `0.75` to a last quarter moon.
-    ///
-    /// **Note**: This is only present on the `daily` block.
-    pub moon_phase: Option<f64>,
     /// The approximate direction of the nearest storm in degrees, with true
     /// north at 0 degrees and progressing clockwise.
     ///
@@ -213,44 +322,15 @@ pub struct Datapoint {
     ///
     /// **Note**: This is only present on the `currently` block.
     pub nearest_storm_distance: Option<f64>,
-    /// The amount of error possible within the [`ozone`] value.
-    ///
-    /// [`ozone`]: #structfield.ozone
-    pub ozone_error: Option<f64>,
     /// The columnar density of total atmospheric ozone at the given time in
     /// Dobson units.
     pub ozone: Option<f64>,
-    /// The amount of error possible within the [`precip_accumulation`] value.
-    ///
-    /// [`precip_accumulation`]: #structfield.precip_accumulation
-    pub precip_accumulation_error: Option<f64>,
     /// The amount of snowfall accumulation expected to occur, in inches.
     ///
     /// If no snowfall is expected, this will be None.
     ///
     /// **Note**: This is only present on `hourly` and `daily` blocks.
     pub precip_accumulation: Option<f64>,
-    /// The amount of error possible within the [`precip_intensity`] value.
-    ///
-    /// [`precip_intensity`]: #structfield.precip_intensity
-    pub precip_intensity_error: Option<f64>,
-    /// The amount of error possible within the [`precip_intensity_max`] value.
-    ///
-    /// [`precip_intensity_max`]: #structfield.precip_intensity_max
-    pub precip_intensity_max_error: Option<f64>,
-    /// The unix timestamp of when [`precip_intensity_max`] occurs during a
-    /// given day.
-    ///
-    /// **Note**: This is only present on the `daily` block.
-    ///
-    /// [`precip_intensity_max`]: #structfield.precip_intensity_max
-    pub precip_intensity_max_time: Option<u64>,
-    /// The maximum value of [`precip_intensity`] during a given day.
-    ///
-    /// **Note**: This is only present on the `daily` block.
-    ///
-    /// [`precip_intensity`]: #structfield.precip_intensity
-    pub precip_intensity_max: Option<f64>,
     /// The intensity (in inches of liquid water per hour) precipitation
     /// occurring at the given time.
     ///
@@ -258,10 +338,6 @@ pub struct Datapoint {
     /// precipitation occurs at all) for `minutely` datapoints, and
     /// unconditional otherwise.
     pub precip_intensity: Option<f64>,
-    /// The amount of error possible within the [`precip_probability`] value.
-    ///
-    /// [`precip_probability`]: #structfield.precip_probability
-    pub precip_probability_error: Option<f64>,
     /// The probably of precipitation occurring.
     ///
     /// This value is between `0` and `1`, inclusively.
@@ -275,19 +351,365 @@ pub struct Datapoint {
     ///
     /// [`precip_intensity`]: #structfield.precip_intensity
     pub precip_type: Option<PrecipitationType>,
-    /// The amount of error possible within the [`pressure`] value.
-    ///
-    /// [`pressure`]: #structfield.pressure
-    pub pressure_error: Option<f64>,
     /// The sea-level air pressure in millibars.
     pub pressure: Option<f64>,
+    /// Solar radiation data, as included by some compatible providers such as
+    /// Pirate Weather.
+    #[cfg(feature = "solar")]
+    pub solar: Option<Box<Solar>>,
     /// A human-readable text summary of the datapoint.
     ///
     /// **Note**: Do not use this for automated icon display purposes, use the
     /// [`icon`] field instead.
     ///
     /// [`icon`]: #structfield.icon
-    pub summary: Option<String>,
+    pub summary: Option<SummaryText>,
+    /// The air temperature in degrees Fahrenheit.
+    pub temperature: Option<f64>,
+    /// The unix timestamp at which the datapoint begins.
+    ///
+    /// `minutely` datapoints are always aligned to the top of the minute.
+    ///
+    /// `hourly` datapoints align to the top of the hour.
+    ///
+    /// `daily` datapoints align to midnight of the day.
+    ///
+    /// All are according to the local timezone.
+    pub time: u64,
+    /// The UV index.
+    pub uv_index: Option<u64>,
+    /// The average visibility in miles, capped at 10 miles.
+    pub visibility: Option<f64>,
+    /// The direction that the wind is coming from in degrees.
+    ///
+    /// True north is at 0 degrees, progressing clockwise.
+    ///
+    /// If [`wind_speed`] is `0`, then this value will not be defined.
+    ///
+    /// [`wind_speed`]: #structfield.wind_speed
+    pub wind_bearing: Option<f64>,
+    /// The wind gust speed in miles per hour.
+    pub wind_gust: Option<f64>,
+    /// The wind speed in miles per hour.
+    pub wind_speed: Option<f64>,
+}
+
+impl Datapoint {
+    /// The unix timestamp representing when the daytime high apparent
+    /// temperature occurs.
+    pub fn apparent_temperature_max_time(&self) -> Option<u64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.apparent_temperature_max_time)
+    }
+
+    /// The daytime high apparent temperature.
+    pub fn apparent_temperature_max(&self) -> Option<f64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.apparent_temperature_max)
+    }
+
+    /// The unix timestamp representing when the overnight low apparent
+    /// temperature occurs.
+    pub fn apparent_temperature_min_time(&self) -> Option<u64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.apparent_temperature_min_time)
+    }
+
+    /// The overnight low apparent temperature.
+    pub fn apparent_temperature_min(&self) -> Option<f64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.apparent_temperature_min)
+    }
+
+    /// The amount of error possible within the [`cloud_cover`] value.
+    ///
+    /// [`cloud_cover`]: #structfield.cloud_cover
+    pub fn cloud_cover_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.cloud_cover_error)
+    }
+
+    /// The amount of error possible within the [`dew_point`] value.
+    ///
+    /// [`dew_point`]: #structfield.dew_point
+    pub fn dew_point_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.dew_point_error)
+    }
+
+    /// The amount of error possible within the [`humidity`] value.
+    ///
+    /// [`humidity`]: #structfield.humidity
+    pub fn humidity_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.humidity_error)
+    }
+
+    /// The fractional part of the [lunation number] during the given day.
+    pub fn moon_phase(&self) -> Option<f64> {
+        self.daily.as_ref().and_then(|fields| fields.moon_phase)
+    }
+
+    /// The amount of error possible within the [`ozone`] value.
+    ///
+    /// [`ozone`]: #structfield.ozone
+    pub fn ozone_error(&self) -> Option<f64> {
+        self.errors.as_ref().and_then(|fields| fields.ozone_error)
+    }
+
+    /// The amount of error possible within the [`precip_accumulation`] value.
+    ///
+    /// [`precip_accumulation`]: #structfield.precip_accumulation
+    pub fn precip_accumulation_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.precip_accumulation_error)
+    }
+
+    /// The amount of error possible within the [`precip_intensity`] value.
+    ///
+    /// [`precip_intensity`]: #structfield.precip_intensity
+    pub fn precip_intensity_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.precip_intensity_error)
+    }
+
+    /// The amount of error possible within the [`precip_intensity_max`] value.
+    ///
+    /// [`precip_intensity_max`]: #method.precip_intensity_max
+    pub fn precip_intensity_max_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.precip_intensity_max_error)
+    }
+
+    /// The unix timestamp of when [`precip_intensity_max`] occurs during a
+    /// given day.
+    ///
+    /// [`precip_intensity_max`]: #method.precip_intensity_max
+    pub fn precip_intensity_max_time(&self) -> Option<u64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.precip_intensity_max_time)
+    }
+
+    /// The maximum value of [`precip_intensity`] during a given day.
+    ///
+    /// [`precip_intensity`]: #structfield.precip_intensity
+    pub fn precip_intensity_max(&self) -> Option<f64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.precip_intensity_max)
+    }
+
+    /// The amount of error possible within the [`precip_probability`] value.
+    ///
+    /// [`precip_probability`]: #structfield.precip_probability
+    pub fn precip_probability_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.precip_probability_error)
+    }
+
+    /// The amount of error possible within the [`pressure`] value.
+    ///
+    /// [`pressure`]: #structfield.pressure
+    pub fn pressure_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.pressure_error)
+    }
+
+    /// The unix timestamp of when the sun will rise during a given day.
+    pub fn sunrise_time(&self) -> Option<u64> {
+        self.daily.as_ref().and_then(|fields| fields.sunrise_time)
+    }
+
+    /// The unix timestamp of when the sun will set during a given day.
+    pub fn sunset_time(&self) -> Option<u64> {
+        self.daily.as_ref().and_then(|fields| fields.sunset_time)
+    }
+
+    /// The overnight low temperature.
+    pub fn temperature_low(&self) -> Option<f64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_low)
+    }
+
+    /// The unix timestamp representing when the overnight low temperature
+    /// occurs.
+    pub fn temperature_low_time(&self) -> Option<u64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_low_time)
+    }
+
+    /// The daytime high temperature.
+    pub fn temperature_high(&self) -> Option<f64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_high)
+    }
+
+    /// The unix timestamp representing when the daytime high temperature
+    /// occurs.
+    pub fn temperature_high_time(&self) -> Option<u64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_high_time)
+    }
+
+    /// The amount of error possible within the [`temperature_max`] value.
+    ///
+    /// [`temperature_max`]: #method.temperature_max
+    pub fn temperature_max_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.temperature_max_error)
+    }
+
+    /// The unix timestamp representing when the maximum temperature during a
+    /// given date occurs.
+    pub fn temperature_max_time(&self) -> Option<u64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_max_time)
+    }
+
+    /// The maximum temperature during a given date.
+    pub fn temperature_max(&self) -> Option<f64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_max)
+    }
+
+    /// The amount of error possible within the [`temperature_min`] value.
+    ///
+    /// [`temperature_min`]: #method.temperature_min
+    pub fn temperature_min_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.temperature_min_error)
+    }
+
+    /// The unix timestamp representing when the minimum temperature during a
+    /// given date occurs.
+    pub fn temperature_min_time(&self) -> Option<u64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_min_time)
+    }
+
+    /// The minimum temperature during a given date.
+    pub fn temperature_min(&self) -> Option<f64> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_min)
+    }
+
+    /// The amount of error possible within the [`temperature`] value.
+    ///
+    /// [`temperature`]: #structfield.temperature
+    pub fn temperature_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.temperature_error)
+    }
+
+    /// The unix timestamp of when the maximum [`uv_index`] occurs during the
+    /// given day.
+    ///
+    /// [`uv_index`]: #structfield.uv_index
+    pub fn uv_index_time(&self) -> Option<u64> {
+        self.daily.as_ref().and_then(|fields| fields.uv_index_time)
+    }
+
+    /// The amount of error possible within the [`visibility`] value.
+    ///
+    /// [`visibility`]: #structfield.visibility
+    pub fn visibility_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.visibility_error)
+    }
+
+    /// The amount of error possible within the [`wind_bearing`] value.
+    ///
+    /// [`wind_bearing`]: #structfield.wind_bearing
+    pub fn wind_bearing_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.wind_bearing_error)
+    }
+
+    /// The amount of time that the wind gust is expected to occur.
+    pub fn wind_gust_time(&self) -> Option<u64> {
+        self.daily.as_ref().and_then(|fields| fields.wind_gust_time)
+    }
+
+    /// The amount of error possible within the [`wind_speed`] value.
+    ///
+    /// [`wind_speed`]: #structfield.wind_speed
+    pub fn wind_speed_error(&self) -> Option<f64> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.wind_speed_error)
+    }
+}
+
+/// The fields of a [`Datapoint`] that are only present on the `daily` block.
+///
+/// [`Datapoint`]: struct.Datapoint.html
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyFields {
+    /// The unix timestamp representing when the daytime high apparent
+    /// temperature occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub apparent_temperature_max_time: Option<u64>,
+    /// The daytime high apparent temperature.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub apparent_temperature_max: Option<f64>,
+    /// The unix timestamp representing when the overnight low apparent
+    /// temperature occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub apparent_temperature_min_time: Option<u64>,
+    /// The overnight low apparent temperature.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub apparent_temperature_min: Option<f64>,
+    /// The fractional part of the [lunation number] during the given day.
+    ///
+    /// A value of `0` corresponds to a new moon, `0.25` to a first quarter
+    /// moon, `0.5` to a full moon, `0.75` to a last quarter moon.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub moon_phase: Option<f64>,
+    /// The unix timestamp of when [`precip_intensity_max`] occurs during a
+    /// given day.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    ///
+    /// [`precip_intensity_max`]: #structfield.precip_intensity_max
+    pub precip_intensity_max_time: Option<u64>,
+    /// The maximum value of [`precip_intensity`] during a given day.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    ///
+    /// [`precip_intensity`]: struct.Datapoint.html#structfield.precip_intensity
+    pub precip_intensity_max: Option<f64>,
     /// The unix timestamp of when the sun will rise during a given day.
     ///
     /// **Note**: This is only present on the `daily` block.
@@ -314,10 +736,6 @@ pub struct Datapoint {
     ///
     /// **Note**: This is only present on the `daily` block.
     pub temperature_high_time: Option<u64>,
-    /// The amount of error possible within the [`temperature_max`] value.
-    ///
-    /// [`temperature_max`]: #structfield.temperature_max
-    pub temperature_max_error: Option<f64>,
     /// The unix timestamp representing when the maximum temperature during a
     /// given date occurs.
     ///
@@ -327,10 +745,6 @@ pub struct Datapoint {
     ///
     /// **Note**: This is only present on the `daily` block.
     pub temperature_max: Option<f64>,
-    /// The amount of error possible within the [`temperature_min`] value.
-    ///
-    /// [`temperature_min`]: #structfield.temperature_min
-    pub temperature_min_error: Option<f64>,
     /// The unix timestamp representing when the minimum temperature during a
     /// given date occurs.
     ///
@@ -340,59 +754,270 @@ pub struct Datapoint {
     ///
     /// **Note**: This is only present on the `daily` block.
     pub temperature_min: Option<f64>,
-    /// The amount of error possible within the [`temperature`] value.
+    /// The unix timestamp of when the maximum [`uv_index`] occurs during the
+    /// given day.
     ///
-    /// [`temperature`]: #structfield.temperature
-    pub temperature_error: Option<f64>,
-    /// The air temperature in degrees Fahrenheit.
-    pub temperature: Option<f64>,
-    /// The unix timestamp at which the datapoint begins.
+    /// [`uv_index`]: struct.Datapoint.html#structfield.uv_index
+    pub uv_index_time: Option<u64>,
+    /// The amount of time that the wind gust is expected to occur.
+    pub wind_gust_time: Option<u64>,
+}
+
+/// Solar radiation data of a [`Datapoint`], as included by some compatible
+/// providers such as Pirate Weather.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate serde_json;
+///
+/// use darksky::models::Datapoint;
+///
+/// # fn main() {
+/// let datapoint: Datapoint = serde_json::from_str(r#"{
+///     "time": 1509993277,
+///     "solar": {"azimuth": 201.3, "altitude": 22.8, "dni": 612.0, "ghi": 289.5}
+/// }"#).unwrap();
+///
+/// let solar = datapoint.solar.unwrap();
+/// assert_eq!(solar.ghi, Some(289.5));
+/// assert_eq!(solar.dhi, None);
+/// # }
+/// ```
+///
+/// [`Datapoint`]: struct.Datapoint.html
+#[cfg(feature = "solar")]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Solar {
+    /// The altitude of the sun above the horizon, in degrees.
+    pub altitude: Option<f64>,
+    /// The azimuth of the sun, in degrees, with true north at 0 degrees and
+    /// progressing clockwise.
+    pub azimuth: Option<f64>,
+    /// The diffuse horizontal irradiance, in W/m².
+    pub dhi: Option<f64>,
+    /// The direct normal irradiance, in W/m².
+    pub dni: Option<f64>,
+    /// The extraterrestrial radiation at the top of the atmosphere, in W/m².
+    pub etr: Option<f64>,
+    /// The global horizontal irradiance, in W/m².
+    pub ghi: Option<f64>,
+}
+
+/// The fields of a [`Datapoint`] representing the amount of error possible
+/// within its data oriented fields.
+///
+/// [`Datapoint`]: struct.Datapoint.html
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorFields {
+    /// The amount of error possible within the [`cloud_cover`] value.
     ///
-    /// `minutely` datapoints are always aligned to the top of the minute.
+    /// [`cloud_cover`]: struct.Datapoint.html#structfield.cloud_cover
+    pub cloud_cover_error: Option<f64>,
+    /// The amount of error possible within the [`dew_point`] value.
     ///
-    /// `hourly` datapoints align to the top of the hour.
+    /// [`dew_point`]: struct.Datapoint.html#structfield.dew_point
+    pub dew_point_error: Option<f64>,
+    /// The amount of error possible within the [`humidity`] value.
     ///
-    /// `daily` datapoints align to midnight of the day.
+    /// [`humidity`]: struct.Datapoint.html#structfield.humidity
+    pub humidity_error: Option<f64>,
+    /// The amount of error possible within the [`ozone`] value.
     ///
-    /// All are according to the local timezone.
-    pub time: u64,
-    /// The UV index.
-    pub uv_index: Option<u64>,
-    /// The unix timestamp of when the maximum [`uv_index`] occurs during the
-    /// given day.
+    /// [`ozone`]: struct.Datapoint.html#structfield.ozone
+    pub ozone_error: Option<f64>,
+    /// The amount of error possible within the [`precip_accumulation`] value.
     ///
-    /// [`uv_index`]: #structfield.uv_index
-    pub uv_index_time: Option<u64>,
+    /// [`precip_accumulation`]: struct.Datapoint.html#structfield.precip_accumulation
+    pub precip_accumulation_error: Option<f64>,
+    /// The amount of error possible within the [`precip_intensity`] value.
+    ///
+    /// [`precip_intensity`]: struct.Datapoint.html#structfield.precip_intensity
+    pub precip_intensity_error: Option<f64>,
+    /// The amount of error possible within the [`precip_intensity_max`] value.
+    ///
+    /// [`precip_intensity_max`]: struct.DailyFields.html#structfield.precip_intensity_max
+    pub precip_intensity_max_error: Option<f64>,
+    /// The amount of error possible within the [`precip_probability`] value.
+    ///
+    /// [`precip_probability`]: struct.Datapoint.html#structfield.precip_probability
+    pub precip_probability_error: Option<f64>,
+    /// The amount of error possible within the [`pressure`] value.
+    ///
+    /// [`pressure`]: struct.Datapoint.html#structfield.pressure
+    pub pressure_error: Option<f64>,
+    /// The amount of error possible within the [`temperature_max`] value.
+    ///
+    /// [`temperature_max`]: struct.DailyFields.html#structfield.temperature_max
+    pub temperature_max_error: Option<f64>,
+    /// The amount of error possible within the [`temperature_min`] value.
+    ///
+    /// [`temperature_min`]: struct.DailyFields.html#structfield.temperature_min
+    pub temperature_min_error: Option<f64>,
+    /// The amount of error possible within the [`temperature`] value.
+    ///
+    /// [`temperature`]: struct.Datapoint.html#structfield.temperature
+    pub temperature_error: Option<f64>,
     /// The amount of error possible within the [`visibility`] value.
     ///
-    /// [`visibility`]: #structfield.visibility
+    /// [`visibility`]: struct.Datapoint.html#structfield.visibility
     pub visibility_error: Option<f64>,
-    /// The average visibility in miles, capped at 10 miles.
-    pub visibility: Option<f64>,
     /// The amount of error possible within the [`wind_bearing`] value.
     ///
-    /// [`wind_bearing`]: #structfield.wind_bearing
+    /// [`wind_bearing`]: struct.Datapoint.html#structfield.wind_bearing
     pub wind_bearing_error: Option<f64>,
-    /// The direction that the wind is coming from in degrees.
-    ///
-    /// True north is at 0 degrees, progressing clockwise.
-    ///
-    /// If [`wind_speed`] is `0`, then this value will not be defined.
+    /// The amount of error possible within the [`wind_speed`] value.
     ///
-    /// [`wind_speed`]: #structfield.wind_speed
+    /// [`wind_speed`]: struct.Datapoint.html#structfield.wind_speed
+    pub wind_speed_error: Option<f64>,
+}
+
+/// A flat representation of a [`Datapoint`], as it is received from the API.
+///
+/// [`Datapoint`]: struct.Datapoint.html
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RawDatapoint {
+    pub apparent_temperature_max_time: Option<u64>,
+    pub apparent_temperature_max: Option<f64>,
+    pub apparent_temperature_min_time: Option<u64>,
+    pub apparent_temperature_min: Option<f64>,
+    pub apparent_temperature: Option<f64>,
+    pub cloud_cover_error: Option<f64>,
+    pub cloud_cover: Option<f64>,
+    pub dew_point_error: Option<f64>,
+    pub dew_point: Option<f64>,
+    pub humidity_error: Option<f64>,
+    pub humidity: Option<f64>,
+    pub icon: Option<Icon>,
+    pub moon_phase: Option<f64>,
+    pub nearest_storm_bearing: Option<f64>,
+    pub nearest_storm_distance: Option<f64>,
+    pub ozone_error: Option<f64>,
+    pub ozone: Option<f64>,
+    pub precip_accumulation_error: Option<f64>,
+    pub precip_accumulation: Option<f64>,
+    pub precip_intensity_error: Option<f64>,
+    pub precip_intensity_max_error: Option<f64>,
+    pub precip_intensity_max_time: Option<u64>,
+    pub precip_intensity_max: Option<f64>,
+    pub precip_intensity: Option<f64>,
+    pub precip_probability_error: Option<f64>,
+    pub precip_probability: Option<f64>,
+    pub precip_type: Option<PrecipitationType>,
+    pub pressure_error: Option<f64>,
+    pub pressure: Option<f64>,
+    #[cfg(feature = "solar")]
+    pub solar: Option<Solar>,
+    pub summary: Option<SummaryText>,
+    pub sunrise_time: Option<u64>,
+    pub sunset_time: Option<u64>,
+    pub temperature_low: Option<f64>,
+    pub temperature_low_time: Option<u64>,
+    pub temperature_high: Option<f64>,
+    pub temperature_high_time: Option<u64>,
+    pub temperature_max_error: Option<f64>,
+    pub temperature_max_time: Option<u64>,
+    pub temperature_max: Option<f64>,
+    pub temperature_min_error: Option<f64>,
+    pub temperature_min_time: Option<u64>,
+    pub temperature_min: Option<f64>,
+    pub temperature_error: Option<f64>,
+    pub temperature: Option<f64>,
+    pub time: u64,
+    pub uv_index: Option<u64>,
+    pub uv_index_time: Option<u64>,
+    pub visibility_error: Option<f64>,
+    pub visibility: Option<f64>,
+    pub wind_bearing_error: Option<f64>,
     pub wind_bearing: Option<f64>,
-    /// The wind gust speed in miles per hour.
     pub wind_gust: Option<f64>,
-    /// The amount of time that the wind gust is expected to occur.
     pub wind_gust_time: Option<u64>,
-    /// The amount of error possible within the [`wind_speed`] value.
-    ///
-    /// [`wind_speed`]: #structfield.wind_speed
     pub wind_speed_error: Option<f64>,
-    /// The wind speed in miles per hour.
     pub wind_speed: Option<f64>,
 }
 
+impl From<RawDatapoint> for Datapoint {
+    fn from(raw: RawDatapoint) -> Self {
+        let daily = DailyFields {
+            apparent_temperature_max_time: raw.apparent_temperature_max_time,
+            apparent_temperature_max: raw.apparent_temperature_max,
+            apparent_temperature_min_time: raw.apparent_temperature_min_time,
+            apparent_temperature_min: raw.apparent_temperature_min,
+            moon_phase: raw.moon_phase,
+            precip_intensity_max_time: raw.precip_intensity_max_time,
+            precip_intensity_max: raw.precip_intensity_max,
+            sunrise_time: raw.sunrise_time,
+            sunset_time: raw.sunset_time,
+            temperature_low: raw.temperature_low,
+            temperature_low_time: raw.temperature_low_time,
+            temperature_high: raw.temperature_high,
+            temperature_high_time: raw.temperature_high_time,
+            temperature_max_time: raw.temperature_max_time,
+            temperature_max: raw.temperature_max,
+            temperature_min_time: raw.temperature_min_time,
+            temperature_min: raw.temperature_min,
+            uv_index_time: raw.uv_index_time,
+            wind_gust_time: raw.wind_gust_time,
+        };
+        let errors = ErrorFields {
+            cloud_cover_error: raw.cloud_cover_error,
+            dew_point_error: raw.dew_point_error,
+            humidity_error: raw.humidity_error,
+            ozone_error: raw.ozone_error,
+            precip_accumulation_error: raw.precip_accumulation_error,
+            precip_intensity_error: raw.precip_intensity_error,
+            precip_intensity_max_error: raw.precip_intensity_max_error,
+            precip_probability_error: raw.precip_probability_error,
+            pressure_error: raw.pressure_error,
+            temperature_max_error: raw.temperature_max_error,
+            temperature_min_error: raw.temperature_min_error,
+            temperature_error: raw.temperature_error,
+            visibility_error: raw.visibility_error,
+            wind_bearing_error: raw.wind_bearing_error,
+            wind_speed_error: raw.wind_speed_error,
+        };
+
+        Datapoint {
+            apparent_temperature: raw.apparent_temperature,
+            cloud_cover: raw.cloud_cover,
+            dew_point: raw.dew_point,
+            humidity: raw.humidity,
+            icon: raw.icon,
+            nearest_storm_bearing: raw.nearest_storm_bearing,
+            nearest_storm_distance: raw.nearest_storm_distance,
+            ozone: raw.ozone,
+            precip_accumulation: raw.precip_accumulation,
+            precip_intensity: raw.precip_intensity,
+            precip_probability: raw.precip_probability,
+            precip_type: raw.precip_type,
+            pressure: raw.pressure,
+            #[cfg(feature = "solar")]
+            solar: raw.solar.map(Box::new),
+            summary: raw.summary,
+            temperature: raw.temperature,
+            time: raw.time,
+            uv_index: raw.uv_index,
+            visibility: raw.visibility,
+            wind_bearing: raw.wind_bearing,
+            wind_gust: raw.wind_gust,
+            wind_speed: raw.wind_speed,
+            daily: if daily == DailyFields::default() {
+                None
+            } else {
+                Some(Box::new(daily))
+            },
+            errors: if errors == ErrorFields::default() {
+                None
+            } else {
+                Some(Box::new(errors))
+            },
+        }
+    }
+}
+
 /// A set of flags for a forecast, such as the [`Unit`]s specified or the vector
 /// of [DarkSky] stations reporting.
 ///
@@ -447,13 +1072,13 @@ pub struct Flags {
 ///
 /// [`Options`]: ../struct.Options.html
 /// [hyper `get_forecast`]:
-///   ../bridge/hyper/trait.DarkskyHyperRequester.html#tymethod.get_forecast
+///   ../bridge/hyper/trait.DarkskyHyperRequester.html#method.get_forecast
 /// [reqwest `get_forecast`]:
-///   ../bridge/reqwest/trait.DarkskyReqwestRequester.html#tymethod.get_forecast
+///   ../bridge/reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast
 /// [hyper `get_forecast_with_options`]:
-///   ../bridge/hyper/trait.DarkskyHyperRequester.html#tymethod.get_forecast_with_options
+///   ../bridge/hyper/trait.DarkskyHyperRequester.html#method.get_forecast_with_options
 /// [reqwest `get_forecast_with_options`]:
-///   ../bridge/reqwest/trait.DarkskyReqwestRequester.html#tymethod.get_forecast_with_options
+///   ../bridge/reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_with_options
 /// [docs]: https://darksky.net/dev/docs/forecast
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -515,3 +1140,233 @@ pub struct Forecast {
     /// The name of the timezone.
     pub timezone: String,
 }
+
+impl Forecast {
+    /// Returns the alerts matching a [query][`AlertQuery`], such as warnings
+    /// affecting a certain county, ordered as requested by the query.
+    ///
+    /// # Examples
+    ///
+    /// Retrieve the watches and warnings affecting Wake County, most severe
+    /// first:
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::alerts::AlertOrder;
+    /// use darksky::models::{Forecast, Severity};
+    ///
+    /// # fn main() {
+    /// let forecast: Forecast = serde_json::from_str(r#"{
+    ///     "latitude": 35.8,
+    ///     "longitude": -78.6,
+    ///     "timezone": "America/New_York",
+    ///     "alerts": [
+    ///         {"title": "Wind Advisory", "regions": ["Wake"], "severity": "advisory",
+    ///          "time": 1509993277, "expires": 1510036680, "description": "", "uri": ""},
+    ///         {"title": "Flood Watch", "regions": ["Durham", "Wake"], "severity": "watch",
+    ///          "time": 1509993277, "expires": 1510036680, "description": "", "uri": ""},
+    ///         {"title": "Tornado Warning", "regions": ["Wake"], "severity": "warning",
+    ///          "time": 1509993277, "expires": 1510000000, "description": "", "uri": ""}
+    ///     ]
+    /// }"#).unwrap();
+    ///
+    /// let alerts = forecast.alerts_matching(|query| {
+    ///     query.min_severity(Severity::Watch)
+    ///         .region("wake")
+    ///         .order(AlertOrder::Severity)
+    /// });
+    /// let titles = alerts.iter().map(|a| &a.title[..]).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(titles, ["Tornado Warning", "Flood Watch"]);
+    /// # }
+    /// ```
+    ///
+    /// [`AlertQuery`]: ../alerts/struct.AlertQuery.html
+    #[cfg(feature = "std")]
+    pub fn alerts_matching<F>(&self, query: F) -> Vec<&Alert>
+    where
+        F: FnOnce(AlertQuery) -> AlertQuery,
+    {
+        query(AlertQuery::default()).filter(&self.alerts)
+    }
+
+    /// Deserializes a forecast from DarkSky-shaped JSON received from
+    /// elsewhere, such as a cache or message queue.
+    ///
+    /// This is available without any of the HTTP features.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use darksky::models::Forecast;
+    ///
+    /// let body = br#"{"latitude": 37.8267, "longitude": -122.423, "timezone": "America/Los_Angeles"}"#;
+    /// let forecast = Forecast::from_slice(body).unwrap();
+    ///
+    /// assert_eq!(forecast.timezone, "America/Los_Angeles");
+    /// ```
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        internal::from_slice(bytes)
+    }
+}
+
+/// The summaries of a [`Datablock`] in a single language.
+///
+/// [`Datablock`]: struct.Datablock.html
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct BlockSummaries {
+    /// The summary of the datablock as a whole.
+    pub summary: Option<SummaryText>,
+    /// The summary of each of the datablock's [`Datapoint`]s, in the same
+    /// order as the datablock's [`data`].
+    ///
+    /// [`Datapoint`]: struct.Datapoint.html
+    /// [`data`]: struct.Datablock.html#structfield.data
+    pub data: Vec<Option<SummaryText>>,
+}
+
+impl<'a> From<&'a Datablock> for BlockSummaries {
+    fn from(block: &'a Datablock) -> Self {
+        BlockSummaries {
+            summary: block.summary.clone(),
+            data: block
+                .data
+                .as_ref()
+                .map(|data| data.iter().map(|d| d.summary.clone()).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The human-readable summaries of a [`Forecast`] in a single language.
+///
+/// [`Forecast`]: struct.Forecast.html
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Summaries {
+    /// The summary of the [`currently`] datapoint.
+    ///
+    /// [`currently`]: struct.Forecast.html#structfield.currently
+    pub currently: Option<SummaryText>,
+    /// The summaries of the [`daily`] datablock.
+    ///
+    /// [`daily`]: struct.Forecast.html#structfield.daily
+    pub daily: Option<BlockSummaries>,
+    /// The summaries of the [`hourly`] datablock.
+    ///
+    /// [`hourly`]: struct.Forecast.html#structfield.hourly
+    pub hourly: Option<BlockSummaries>,
+    /// The summaries of the [`minutely`] datablock.
+    ///
+    /// [`minutely`]: struct.Forecast.html#structfield.minutely
+    pub minutely: Option<BlockSummaries>,
+}
+
+impl<'a> From<&'a Forecast> for Summaries {
+    fn from(forecast: &'a Forecast) -> Self {
+        Summaries {
+            currently: forecast.currently.as_ref().and_then(|d| d.summary.clone()),
+            daily: forecast.daily.as_ref().map(From::from),
+            hourly: forecast.hourly.as_ref().map(From::from),
+            minutely: forecast.minutely.as_ref().map(From::from),
+        }
+    }
+}
+
+/// A [`Forecast`] along with its [`Summaries`] in multiple [`Language`]s.
+///
+/// The forecast itself is in the first language that was requested, and is
+/// only parsed once. The responses for the other languages are only used for
+/// their summaries.
+///
+/// [`Forecast`]: struct.Forecast.html
+/// [`Language`]: ../enum.Language.html
+/// [`Summaries`]: struct.Summaries.html
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct MultilingualForecast {
+    /// The forecast, in the first language that was requested.
+    pub forecast: Forecast,
+    /// The summaries of the forecast, keyed by language. This includes the
+    /// first language that was requested.
+    pub summaries: HashMap<Language, Summaries>,
+}
+
+/// A [`Forecast`] that is cheap to clone, for handing a single forecast out to
+/// many consumers.
+///
+/// Clones share the same underlying forecast, so no datapoints are copied.
+/// The forecast is read through [`Deref`].
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate serde_json;
+///
+/// use darksky::models::{Forecast, SharedForecast};
+///
+/// # fn main() {
+/// let forecast: Forecast = serde_json::from_str(r#"{
+///     "latitude": 49.9,
+///     "longitude": -97.1,
+///     "timezone": "America/Winnipeg"
+/// }"#).unwrap();
+///
+/// let shared = SharedForecast::from(forecast);
+/// let clone = shared.clone();
+///
+/// assert!(SharedForecast::ptr_eq(&shared, &clone));
+/// assert_eq!(clone.timezone, "America/Winnipeg");
+/// # }
+/// ```
+///
+/// [`Deref`]: https://doc.rust-lang.org/std/ops/trait.Deref.html
+/// [`Forecast`]: struct.Forecast.html
+#[derive(Clone, Debug)]
+pub struct SharedForecast(Arc<Forecast>);
+
+impl SharedForecast {
+    /// Returns the forecast, cloning it only if it is still shared.
+    pub fn into_inner(self) -> Forecast {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| (*shared).clone())
+    }
+
+    /// Returns whether two shared forecasts point to the same forecast.
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.0, &other.0)
+    }
+}
+
+impl Deref for SharedForecast {
+    type Target = Forecast;
+
+    fn deref(&self) -> &Forecast {
+        &self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for SharedForecast {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        Forecast::deserialize(deserializer).map(From::from)
+    }
+}
+
+impl Serialize for SharedForecast {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl From<Arc<Forecast>> for SharedForecast {
+    fn from(forecast: Arc<Forecast>) -> Self {
+        SharedForecast(forecast)
+    }
+}
+
+impl From<Forecast> for SharedForecast {
+    fn from(forecast: Forecast) -> Self {
+        SharedForecast(Arc::new(forecast))
+    }
+}
@@ -0,0 +1,658 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Borrowed variants of the [models], which borrow their strings from the
+//! buffer that they were deserialized from where possible.
+//!
+//! These are useful for high-throughput consumers that retain the response
+//! body, as they avoid allocating a `String` for every summary and station
+//! identifier. Strings containing escape sequences can not be borrowed, and
+//! are allocated instead.
+//!
+//! Use [`Forecast::from_slice`] to deserialize a response, and
+//! [`Forecast::into_owned`] to convert it into a regular
+//! [`models::Forecast`].
+//!
+//! [`Forecast::from_slice`]: struct.Forecast.html#method.from_slice
+//! [`Forecast::into_owned`]: struct.Forecast.html#method.into_owned
+//! [`models::Forecast`]: ../struct.Forecast.html
+//! [models]: ../index.html
+
+use super::{Icon, PrecipitationType, Severity, SummaryText};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Deserializer};
+use serde_json;
+use {models, Result};
+
+/// A borrowed [`Alert`][`models::Alert`].
+///
+/// [`models::Alert`]: ../struct.Alert.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Alert<'a> {
+    /// [Unix timestamp][unixtime] of when the alert expires.
+    ///
+    /// [unixtime]: https://en.wikipedia.org/wiki/Unix_time
+    pub expires: u64,
+    /// A detailed description of the alert.
+    #[serde(borrow)]
+    pub description: Cow<'a, str>,
+    /// A short text summary.
+    #[serde(borrow)]
+    pub title: Cow<'a, str>,
+    /// A URI that contains detailed information about the alert.
+    #[serde(borrow)]
+    pub uri: Cow<'a, str>,
+    /// An array of strings representing the names of the regions covered by this weather alert.
+    #[serde(borrow, deserialize_with = "vec_cow")]
+    pub regions: Vec<Cow<'a, str>>,
+    /// The UNIX time at which the alert was issued.
+    pub time: u64,
+    /// The severity of the weather alert.
+    pub severity: Severity,
+}
+
+impl<'a> Alert<'a> {
+    /// Converts the alert into an owned [`Alert`][`models::Alert`].
+    ///
+    /// [`models::Alert`]: ../struct.Alert.html
+    pub fn into_owned(self) -> models::Alert {
+        models::Alert {
+            expires: self.expires,
+            description: self.description.into_owned(),
+            title: self.title.into_owned(),
+            uri: self.uri.into_owned(),
+            regions: into_owned_vec(self.regions),
+            time: self.time,
+            severity: self.severity,
+        }
+    }
+}
+
+/// A borrowed [`Datablock`][`models::Datablock`].
+///
+/// [`models::Datablock`]: ../struct.Datablock.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Datablock<'a> {
+    /// The data for the datablock, if there is any data available.
+    #[serde(borrow)]
+    pub data: Option<Vec<Datapoint<'a>>>,
+    /// The icon representing the weather type for the datablock.
+    pub icon: Option<Icon>,
+    /// A written summary of the datablock's expected weather.
+    #[serde(borrow, default, deserialize_with = "option_cow")]
+    pub summary: Option<Cow<'a, str>>,
+}
+
+impl<'a> Datablock<'a> {
+    /// Converts the datablock into an owned
+    /// [`Datablock`][`models::Datablock`].
+    ///
+    /// [`models::Datablock`]: ../struct.Datablock.html
+    pub fn into_owned(self) -> models::Datablock {
+        models::Datablock {
+            data: self
+                .data
+                .map(|data| data.into_iter().map(Datapoint::into_owned).collect()),
+            icon: self.icon,
+            summary: self.summary.map(SummaryText::new),
+        }
+    }
+}
+
+/// A borrowed [datapoint][`models::Datapoint`] within a [`Datablock`], where
+/// there is usually multiple.
+///
+/// All fields are optional _except for [`time`]_, as some data may not be
+/// available for a location at a given point in time.
+///
+/// All of the data oriented fields may have associated `error` fields,
+/// representing the confidence in a prediction or value. An example is
+/// [`precip_accumulation`], which has an associated error field of
+/// [`precip_accumulation_error`]. Those fields represent standard deviations of
+/// the value of the associated field. Smaller error values represent greater
+/// confidence levels, while larger error values represent less confidence.
+/// These fields are omitted where the confidence is not precisely known.
+///
+/// [`Datablock`]: struct.Datablock.html
+/// [`models::Datapoint`]: ../struct.Datapoint.html
+/// [`time`]: #structfield.time
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Datapoint<'a> {
+    /// The unix timestamp representing when the daytime high apparent
+    /// temperature occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub apparent_temperature_max_time: Option<u64>,
+    /// The daytime high apparent temperature.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub apparent_temperature_max: Option<f64>,
+    /// The unix timestamp representing when the overnight low apparent
+    /// temperature occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub apparent_temperature_min_time: Option<u64>,
+    /// The overnight low apparent temperature.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub apparent_temperature_min: Option<f64>,
+    /// The apparent (or "feels like") temperature in degrees Fahrenheit.
+    ///
+    /// **Note**: This is not present on `daily`.
+    pub apparent_temperature: Option<f64>,
+    /// The amount of error possible within the [`cloud_cover`] value.
+    ///
+    /// [`cloud_cover`]: #structfield.cloud_cover
+    pub cloud_cover_error: Option<f64>,
+    /// The percentage of sky occluded by clouds.
+    ///
+    /// This value is between `0` and `1`, inclusively.
+    pub cloud_cover: Option<f64>,
+    /// The amount of error possible within the [`dew_point`] value.
+    ///
+    /// [`dew_point`]: #structfield.dew_point
+    pub dew_point_error: Option<f64>,
+    /// The dew point in degrees Fahrenheit.
+    pub dew_point: Option<f64>,
+    /// The amount of error possible within the [`humidity`] value.
+    ///
+    /// [`humidity`]: #structfield.humidity
+    pub humidity_error: Option<f64>,
+    /// The relative humidity.
+    ///
+    /// This value is between `0` and `1`, inclusively.
+    pub humidity: Option<f64>,
+    /// A machine-readable summary of the datapoint, suitable for selecting an
+    /// icon to display.
+    pub icon: Option<Icon>,
+    /// The fractional part of the [lunation number] during the given day.
+    ///
+    /// A value of `0` corresponds to a new moon, `0.25` to a first quarter
+    /// moon, `0.5` to a full moon, `0.75` to a last quarter moon.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub moon_phase: Option<f64>,
+    /// The approximate direction of the nearest storm in degrees, with true
+    /// north at 0 degrees and progressing clockwise.
+    ///
+    /// If `nearestStormDistance` is `0`, then this value will not be present.
+    ///
+    /// **Note**: This is only present on the `currently` block.
+    pub nearest_storm_bearing: Option<f64>,
+    /// The approximate distance to the nearest storm in miles.
+    ///
+    /// A storm distance of `0` doesn't necessarily refer to a storm at the
+    /// requested location, but rather a storm in the vicinity of that location.
+    ///
+    /// **Note**: This is only present on the `currently` block.
+    pub nearest_storm_distance: Option<f64>,
+    /// The amount of error possible within the [`ozone`] value.
+    ///
+    /// [`ozone`]: #structfield.ozone
+    pub ozone_error: Option<f64>,
+    /// The columnar density of total atmospheric ozone at the given time in
+    /// Dobson units.
+    pub ozone: Option<f64>,
+    /// The amount of error possible within the [`precip_accumulation`] value.
+    ///
+    /// [`precip_accumulation`]: #structfield.precip_accumulation
+    pub precip_accumulation_error: Option<f64>,
+    /// The amount of snowfall accumulation expected to occur, in inches.
+    ///
+    /// If no snowfall is expected, this will be None.
+    ///
+    /// **Note**: This is only present on `hourly` and `daily` blocks.
+    pub precip_accumulation: Option<f64>,
+    /// The amount of error possible within the [`precip_intensity`] value.
+    ///
+    /// [`precip_intensity`]: #structfield.precip_intensity
+    pub precip_intensity_error: Option<f64>,
+    /// The amount of error possible within the [`precip_intensity_max`] value.
+    ///
+    /// [`precip_intensity_max`]: #structfield.precip_intensity_max
+    pub precip_intensity_max_error: Option<f64>,
+    /// The unix timestamp of when [`precip_intensity_max`] occurs during a
+    /// given day.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    ///
+    /// [`precip_intensity_max`]: #structfield.precip_intensity_max
+    pub precip_intensity_max_time: Option<u64>,
+    /// The maximum value of [`precip_intensity`] during a given day.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    ///
+    /// [`precip_intensity`]: #structfield.precip_intensity
+    pub precip_intensity_max: Option<f64>,
+    /// The intensity (in inches of liquid water per hour) precipitation
+    /// occurring at the given time.
+    ///
+    /// This value is conditional on probability (that is, assuming any
+    /// precipitation occurs at all) for `minutely` datapoints, and
+    /// unconditional otherwise.
+    pub precip_intensity: Option<f64>,
+    /// The amount of error possible within the [`precip_probability`] value.
+    ///
+    /// [`precip_probability`]: #structfield.precip_probability
+    pub precip_probability_error: Option<f64>,
+    /// The probably of precipitation occurring.
+    ///
+    /// This value is between `0` and `1`, inclusively.
+    pub precip_probability: Option<f64>,
+    /// The type of precipitation occurring at a given time.
+    ///
+    /// If [`precip_intensity`] is `0`, then this field will be `None`.
+    ///
+    /// Additionally, due to the lack of data in DarkSky sources, historical
+    /// `precip_type` values is usually estimated, rather than observed.
+    ///
+    /// [`precip_intensity`]: #structfield.precip_intensity
+    pub precip_type: Option<PrecipitationType>,
+    /// The amount of error possible within the [`pressure`] value.
+    ///
+    /// [`pressure`]: #structfield.pressure
+    pub pressure_error: Option<f64>,
+    /// The sea-level air pressure in millibars.
+    pub pressure: Option<f64>,
+    /// Solar radiation data, as included by some compatible providers such as
+    /// Pirate Weather.
+    #[cfg(feature = "solar")]
+    pub solar: Option<models::Solar>,
+    /// A human-readable text summary of the datapoint.
+    ///
+    /// **Note**: Do not use this for automated icon display purposes, use the
+    /// [`icon`] field instead.
+    ///
+    /// [`icon`]: #structfield.icon
+    #[serde(borrow, default, deserialize_with = "option_cow")]
+    pub summary: Option<Cow<'a, str>>,
+    /// The unix timestamp of when the sun will rise during a given day.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub sunrise_time: Option<u64>,
+    /// The unix timestamp of when the sun will set during a given day.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub sunset_time: Option<u64>,
+    /// The overnight low temperature.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_low: Option<f64>,
+    /// The unix timestamp representing when the overnight low temperature
+    /// occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_low_time: Option<u64>,
+    /// The daytime high temperature.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_high: Option<f64>,
+    /// The unix timestamp representing when the daytime high temperature
+    /// occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_high_time: Option<u64>,
+    /// The amount of error possible within the [`temperature_max`] value.
+    ///
+    /// [`temperature_max`]: #structfield.temperature_max
+    pub temperature_max_error: Option<f64>,
+    /// The unix timestamp representing when the maximum temperature during a
+    /// given date occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_max_time: Option<u64>,
+    /// The maximum temperature during a given date.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_max: Option<f64>,
+    /// The amount of error possible within the [`temperature_min`] value.
+    ///
+    /// [`temperature_min`]: #structfield.temperature_min
+    pub temperature_min_error: Option<f64>,
+    /// The unix timestamp representing when the minimum temperature during a
+    /// given date occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_min_time: Option<u64>,
+    /// The minimum temperature during a given date.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_min: Option<f64>,
+    /// The amount of error possible within the [`temperature`] value.
+    ///
+    /// [`temperature`]: #structfield.temperature
+    pub temperature_error: Option<f64>,
+    /// The air temperature in degrees Fahrenheit.
+    pub temperature: Option<f64>,
+    /// The unix timestamp at which the datapoint begins.
+    ///
+    /// `minutely` datapoints are always aligned to the top of the minute.
+    ///
+    /// `hourly` datapoints align to the top of the hour.
+    ///
+    /// `daily` datapoints align to midnight of the day.
+    ///
+    /// All are according to the local timezone.
+    pub time: u64,
+    /// The UV index.
+    pub uv_index: Option<u64>,
+    /// The unix timestamp of when the maximum [`uv_index`] occurs during the
+    /// given day.
+    ///
+    /// [`uv_index`]: #structfield.uv_index
+    pub uv_index_time: Option<u64>,
+    /// The amount of error possible within the [`visibility`] value.
+    ///
+    /// [`visibility`]: #structfield.visibility
+    pub visibility_error: Option<f64>,
+    /// The average visibility in miles, capped at 10 miles.
+    pub visibility: Option<f64>,
+    /// The amount of error possible within the [`wind_bearing`] value.
+    ///
+    /// [`wind_bearing`]: #structfield.wind_bearing
+    pub wind_bearing_error: Option<f64>,
+    /// The direction that the wind is coming from in degrees.
+    ///
+    /// True north is at 0 degrees, progressing clockwise.
+    ///
+    /// If [`wind_speed`] is `0`, then this value will not be defined.
+    ///
+    /// [`wind_speed`]: #structfield.wind_speed
+    pub wind_bearing: Option<f64>,
+    /// The wind gust speed in miles per hour.
+    pub wind_gust: Option<f64>,
+    /// The amount of time that the wind gust is expected to occur.
+    pub wind_gust_time: Option<u64>,
+    /// The amount of error possible within the [`wind_speed`] value.
+    ///
+    /// [`wind_speed`]: #structfield.wind_speed
+    pub wind_speed_error: Option<f64>,
+    /// The wind speed in miles per hour.
+    pub wind_speed: Option<f64>,
+}
+
+impl<'a> Datapoint<'a> {
+    /// Converts the datapoint into an owned
+    /// [`Datapoint`][`models::Datapoint`].
+    ///
+    /// [`models::Datapoint`]: ../struct.Datapoint.html
+    pub fn into_owned(self) -> models::Datapoint {
+        models::RawDatapoint {
+            apparent_temperature_max_time: self.apparent_temperature_max_time,
+            apparent_temperature_max: self.apparent_temperature_max,
+            apparent_temperature_min_time: self.apparent_temperature_min_time,
+            apparent_temperature_min: self.apparent_temperature_min,
+            apparent_temperature: self.apparent_temperature,
+            cloud_cover_error: self.cloud_cover_error,
+            cloud_cover: self.cloud_cover,
+            dew_point_error: self.dew_point_error,
+            dew_point: self.dew_point,
+            humidity_error: self.humidity_error,
+            humidity: self.humidity,
+            icon: self.icon,
+            moon_phase: self.moon_phase,
+            nearest_storm_bearing: self.nearest_storm_bearing,
+            nearest_storm_distance: self.nearest_storm_distance,
+            ozone_error: self.ozone_error,
+            ozone: self.ozone,
+            precip_accumulation_error: self.precip_accumulation_error,
+            precip_accumulation: self.precip_accumulation,
+            precip_intensity_error: self.precip_intensity_error,
+            precip_intensity_max_error: self.precip_intensity_max_error,
+            precip_intensity_max_time: self.precip_intensity_max_time,
+            precip_intensity_max: self.precip_intensity_max,
+            precip_intensity: self.precip_intensity,
+            precip_probability_error: self.precip_probability_error,
+            precip_probability: self.precip_probability,
+            precip_type: self.precip_type,
+            pressure_error: self.pressure_error,
+            pressure: self.pressure,
+            #[cfg(feature = "solar")]
+            solar: self.solar,
+            summary: self.summary.map(SummaryText::new),
+            sunrise_time: self.sunrise_time,
+            sunset_time: self.sunset_time,
+            temperature_low: self.temperature_low,
+            temperature_low_time: self.temperature_low_time,
+            temperature_high: self.temperature_high,
+            temperature_high_time: self.temperature_high_time,
+            temperature_max_error: self.temperature_max_error,
+            temperature_max_time: self.temperature_max_time,
+            temperature_max: self.temperature_max,
+            temperature_min_error: self.temperature_min_error,
+            temperature_min_time: self.temperature_min_time,
+            temperature_min: self.temperature_min,
+            temperature_error: self.temperature_error,
+            temperature: self.temperature,
+            time: self.time,
+            uv_index: self.uv_index,
+            uv_index_time: self.uv_index_time,
+            visibility_error: self.visibility_error,
+            visibility: self.visibility,
+            wind_bearing_error: self.wind_bearing_error,
+            wind_bearing: self.wind_bearing,
+            wind_gust: self.wind_gust,
+            wind_gust_time: self.wind_gust_time,
+            wind_speed_error: self.wind_speed_error,
+            wind_speed: self.wind_speed,
+        }
+        .into()
+    }
+}
+
+/// A borrowed [`Flags`][`models::Flags`].
+///
+/// [`models::Flags`]: ../struct.Flags.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Flags<'a> {
+    /// A list of DarkSky stations used for the [`Forecast`].
+    ///
+    /// [`Forecast`]: struct.Forecast.html
+    #[serde(borrow, default, deserialize_with = "option_vec_cow")]
+    pub darksky_stations: Option<Vec<Cow<'a, str>>>,
+    /// A list of the unavailable DarkSky stations.
+    #[serde(borrow, default, deserialize_with = "option_cow")]
+    pub darksky_unavailable: Option<Cow<'a, str>>,
+    /// A list of the
+    #[serde(borrow, default, deserialize_with = "option_vec_cow")]
+    pub datapoint_stations: Option<Vec<Cow<'a, str>>>,
+    /// A list of [ISD] stations used.
+    ///
+    /// [ISD]: https://www.ncdc.noaa.gov/isd
+    #[serde(borrow, default, deserialize_with = "option_vec_cow")]
+    pub isd_stations: Option<Vec<Cow<'a, str>>>,
+    /// A list of [LAMP] stations used to obtain the information.
+    ///
+    /// [LAMP]: http://www.nws.noaa.gov/mdl/lamp/lamp_info.shtml
+    #[serde(borrow, default, deserialize_with = "option_vec_cow")]
+    pub lamp_stations: Option<Vec<Cow<'a, str>>>,
+    /// A list of [METAR] stations used to obtain the information.
+    ///
+    /// [METAR]: https://www.aviationweather.gov/metar
+    #[serde(borrow, default, deserialize_with = "option_vec_cow")]
+    pub metar_stations: Option<Vec<Cow<'a, str>>>,
+    /// The [METNO license] used.
+    ///
+    /// [METNO license]: http://www.met.no/
+    #[serde(borrow, default, deserialize_with = "option_cow")]
+    pub metno_license: Option<Cow<'a, str>>,
+    /// A list of sources used to obtain the information.
+    #[serde(borrow, default, deserialize_with = "option_vec_cow")]
+    pub sources: Option<Vec<Cow<'a, str>>>,
+    /// The [`Unit`]s used to format the data.
+    ///
+    /// [`Unit`]: ../../enum.Unit.html
+    #[serde(borrow, default, deserialize_with = "option_cow")]
+    pub units: Option<Cow<'a, str>>,
+}
+
+impl<'a> Flags<'a> {
+    /// Converts the flags into owned [`Flags`][`models::Flags`].
+    ///
+    /// [`models::Flags`]: ../struct.Flags.html
+    pub fn into_owned(self) -> models::Flags {
+        models::Flags {
+            darksky_stations: self.darksky_stations.map(into_owned_vec),
+            darksky_unavailable: self.darksky_unavailable.map(Cow::into_owned),
+            datapoint_stations: self.datapoint_stations.map(into_owned_vec),
+            isd_stations: self.isd_stations.map(into_owned_vec),
+            lamp_stations: self.lamp_stations.map(into_owned_vec),
+            metar_stations: self.metar_stations.map(into_owned_vec),
+            metno_license: self.metno_license.map(Cow::into_owned),
+            sources: self.sources.map(into_owned_vec),
+            units: self.units.map(Cow::into_owned),
+        }
+    }
+}
+
+/// A borrowed [`Forecast`][`models::Forecast`].
+///
+/// # Examples
+///
+/// Deserialize a forecast from a retained response body:
+///
+/// ```rust
+/// use darksky::models::borrowed::Forecast;
+/// use std::borrow::Cow;
+///
+/// let body = br#"{
+///     "latitude": 37.8267,
+///     "longitude": -122.423,
+///     "timezone": "America/Los_Angeles",
+///     "currently": {"time": 1509993277, "summary": "Clear"},
+///     "flags": {"isd-stations": ["724943-99999"], "units": "us"}
+/// }"#;
+///
+/// let forecast = Forecast::from_slice(body).unwrap();
+/// let summary = forecast.currently.as_ref().and_then(|c| c.summary.as_ref());
+///
+/// match summary {
+///     Some(&Cow::Borrowed(summary)) => assert_eq!(summary, "Clear"),
+///     other => panic!("summary was not borrowed: {:?}", other),
+/// }
+///
+/// let owned = forecast.into_owned();
+/// assert_eq!(owned.timezone, "America/Los_Angeles");
+/// ```
+///
+/// [`models::Forecast`]: ../struct.Forecast.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Forecast<'a> {
+    /// Contains any severe weather alerts pertinent to the location.
+    #[serde(borrow, default)]
+    pub alerts: Vec<Alert<'a>>,
+    /// The current forecast.
+    #[serde(borrow)]
+    pub currently: Option<Datapoint<'a>>,
+    /// Daily [`Datablock`]s within a forecast.
+    ///
+    /// [`Datablock`]: struct.Datablock.html
+    #[serde(borrow)]
+    pub daily: Option<Datablock<'a>>,
+    /// A set of flags returned from the API.
+    #[serde(borrow)]
+    pub flags: Option<Flags<'a>>,
+    /// Hourly [`Datablock`]s within a forecast.
+    ///
+    /// [`Datablock`]: struct.Datablock.html
+    #[serde(borrow)]
+    pub hourly: Option<Datablock<'a>>,
+    /// The latitude of the forecast's location.
+    pub latitude: f64,
+    /// The longitude of the forecast's location.
+    pub longitude: f64,
+    /// Minutely [`Datablock`]s within a forecast.
+    ///
+    /// [`Datablock`]: struct.Datablock.html
+    #[serde(borrow)]
+    pub minutely: Option<Datablock<'a>>,
+    /// The timezone offset of the forecast, relative to the UTC timezone.
+    pub offset: Option<f64>,
+    /// The name of the timezone.
+    #[serde(borrow)]
+    pub timezone: Cow<'a, str>,
+}
+
+impl<'a> Forecast<'a> {
+    /// Deserializes a forecast from a response body, borrowing strings from
+    /// it where possible.
+    pub fn from_slice(bytes: &'a [u8]) -> Result<Self> {
+        serde_json::from_slice(bytes).map_err(From::from)
+    }
+
+    /// Converts the forecast into an owned [`Forecast`][`models::Forecast`].
+    ///
+    /// [`models::Forecast`]: ../struct.Forecast.html
+    pub fn into_owned(self) -> models::Forecast {
+        models::Forecast {
+            alerts: self.alerts.into_iter().map(Alert::into_owned).collect(),
+            currently: self.currently.map(Datapoint::into_owned),
+            daily: self.daily.map(Datablock::into_owned),
+            flags: self.flags.map(Flags::into_owned),
+            hourly: self.hourly.map(Datablock::into_owned),
+            latitude: self.latitude,
+            longitude: self.longitude,
+            minutely: self.minutely.map(Datablock::into_owned),
+            offset: self.offset,
+            timezone: self.timezone.into_owned(),
+        }
+    }
+}
+
+/// A string that is borrowed from the input where possible.
+///
+/// `Cow<str>` only borrows when it is the direct type of a field, so this is
+/// used for strings nested within `Option`s and `Vec`s.
+#[derive(Deserialize)]
+struct BorrowedStr<'a>(#[serde(borrow)] Cow<'a, str>);
+
+fn option_cow<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> ::core::result::Result<Option<Cow<'a, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<BorrowedStr<'a>>::deserialize(deserializer)?;
+
+    Ok(value.map(|s| s.0))
+}
+
+fn vec_cow<'de: 'a, 'a, D>(deserializer: D) -> ::core::result::Result<Vec<Cow<'a, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Vec::<BorrowedStr<'a>>::deserialize(deserializer)?;
+
+    Ok(value.into_iter().map(|s| s.0).collect())
+}
+
+fn option_vec_cow<'de: 'a, 'a, D>(
+    deserializer: D,
+) -> ::core::result::Result<Option<Vec<Cow<'a, str>>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<Vec<BorrowedStr<'a>>>::deserialize(deserializer)?;
+
+    Ok(value.map(|v| v.into_iter().map(|s| s.0).collect()))
+}
+
+fn into_owned_vec(values: Vec<Cow<str>>) -> Vec<String> {
+    values.into_iter().map(Cow::into_owned).collect()
+}
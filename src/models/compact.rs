@@ -0,0 +1,876 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Compact variants of the [models], storing values as `f32`s and timestamps
+//! as `u32`s.
+//!
+//! These roughly halve the memory used by datapoint-heavy workloads, such as
+//! logging forecasts on constrained devices, at the cost of precision. They
+//! are deserialized directly from a response like the regular models, or can
+//! be converted from them.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate darksky;
+//! extern crate serde_json;
+//!
+//! use darksky::models::compact::Forecast;
+//!
+//! # fn main() {
+//! let forecast: Forecast = serde_json::from_str(r#"{
+//!     "latitude": 49.9,
+//!     "longitude": -97.1,
+//!     "timezone": "America/Winnipeg",
+//!     "currently": {"time": 1509993277, "temperature": -3.5}
+//! }"#).unwrap();
+//!
+//! let currently = forecast.currently.unwrap();
+//! assert_eq!(currently.time, 1509993277);
+//! assert_eq!(currently.temperature, Some(-3.5));
+//! # }
+//! ```
+//!
+//! [models]: ../index.html
+
+use super::{Alert, Flags, Icon, PrecipitationType, RawDatapoint, SummaryText};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use models;
+
+/// A compact [`Datablock`][`models::Datablock`].
+///
+/// [`models::Datablock`]: ../struct.Datablock.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Datablock {
+    /// The data for the datablock, if there is any data available.
+    pub data: Option<Vec<Datapoint>>,
+    /// The icon representing the weather type for the datablock.
+    pub icon: Option<Icon>,
+    /// A written summary of the datablock's expected weather.
+    pub summary: Option<SummaryText>,
+}
+
+impl<'a> From<&'a models::Datablock> for Datablock {
+    fn from(block: &'a models::Datablock) -> Self {
+        Datablock {
+            data: block
+                .data
+                .as_ref()
+                .map(|data| data.iter().map(From::from).collect()),
+            icon: block.icon,
+            summary: block.summary.clone(),
+        }
+    }
+}
+
+/// A compact [`Datapoint`][`models::Datapoint`].
+///
+/// Values are stored as `f32`s and timestamps as `u32`s, roughly halving the
+/// size of a datapoint. As with the regular datapoint, the fields only present
+/// on `daily` and the error fields are grouped into boxed [`DailyFields`] and
+/// [`ErrorFields`], which can also be read through accessor methods.
+///
+/// [`DailyFields`]: struct.DailyFields.html
+/// [`ErrorFields`]: struct.ErrorFields.html
+/// [`models::Datapoint`]: ../struct.Datapoint.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(from = "RawDatapoint", rename_all = "camelCase")]
+pub struct Datapoint {
+    /// The apparent (or "feels like") temperature in degrees Fahrenheit.
+    ///
+    /// **Note**: This is not present on `daily`.
+    pub apparent_temperature: Option<f32>,
+    /// The percentage of sky occluded by clouds.
+    ///
+    /// This value is between `0` and `1`, inclusively.
+    pub cloud_cover: Option<f32>,
+    /// Fields that are only present on the `daily` block, if any are present.
+    ///
+    /// These are also available through accessor methods of the same name,
+    /// such as [`temperature_max`].
+    ///
+    /// [`temperature_max`]: #method.temperature_max
+    #[serde(flatten)]
+    pub daily: Option<Box<DailyFields>>,
+    /// The dew point in degrees Fahrenheit.
+    pub dew_point: Option<f32>,
+    /// The amount of error possible within the data oriented fields, if any
+    /// are known.
+    ///
+    /// These are also available through accessor methods of the same name,
+    /// such as [`temperature_error`].
+    ///
+    /// [`temperature_error`]: #method.temperature_error
+    #[serde(flatten)]
+    pub errors: Option<Box<ErrorFields>>,
+    /// The relative humidity.
+    ///
+    /// This value is between `0` and `1`, inclusively.
+    pub humidity: Option<f32>,
+    /// A machine-readable summary of the datapoint, suitable for selecting an
+    /// icon to display.
+    pub icon: Option<Icon>,
+    /// The approximate direction of the nearest storm in degrees, with true
+    /// north at 0 degrees and progressing clockwise.
+    ///
+    /// If `nearestStormDistance` is `0`, then this value will not be present.
+    ///
+    /// **Note**: This is only present on the `currently` block.
+    pub nearest_storm_bearing: Option<f32>,
+    /// The approximate distance to the nearest storm in miles.
+    ///
+    /// A storm distance of `0` doesn't necessarily refer to a storm at the
+    /// requested location, but rather a storm in the vicinity of that location.
+    ///
+    /// **Note**: This is only present on the `currently` block.
+    pub nearest_storm_distance: Option<f32>,
+    /// The columnar density of total atmospheric ozone at the given time in
+    /// Dobson units.
+    pub ozone: Option<f32>,
+    /// The amount of snowfall accumulation expected to occur, in inches.
+    ///
+    /// If no snowfall is expected, this will be None.
+    ///
+    /// **Note**: This is only present on `hourly` and `daily` blocks.
+    pub precip_accumulation: Option<f32>,
+    /// The intensity (in inches of liquid water per hour) precipitation
+    /// occurring at the given time.
+    ///
+    /// This value is conditional on probability (that is, assuming any
+    /// precipitation occurs at all) for `minutely` datapoints, and
+    /// unconditional otherwise.
+    pub precip_intensity: Option<f32>,
+    /// The probably of precipitation occurring.
+    ///
+    /// This value is between `0` and `1`, inclusively.
+    pub precip_probability: Option<f32>,
+    /// The type of precipitation occurring at a given time.
+    ///
+    /// If [`precip_intensity`] is `0`, then this field will be `None`.
+    ///
+    /// Additionally, due to the lack of data in DarkSky sources, historical
+    /// `precip_type` values is usually estimated, rather than observed.
+    ///
+    /// [`precip_intensity`]: #structfield.precip_intensity
+    pub precip_type: Option<PrecipitationType>,
+    /// The sea-level air pressure in millibars.
+    pub pressure: Option<f32>,
+    /// A human-readable text summary of the datapoint.
+    ///
+    /// **Note**: Do not use this for automated icon display purposes, use the
+    /// [`icon`] field instead.
+    ///
+    /// [`icon`]: #structfield.icon
+    pub summary: Option<SummaryText>,
+    /// The air temperature in degrees Fahrenheit.
+    pub temperature: Option<f32>,
+    /// The unix timestamp at which the datapoint begins.
+    ///
+    /// `minutely` datapoints are always aligned to the top of the minute.
+    ///
+    /// `hourly` datapoints align to the top of the hour.
+    ///
+    /// `daily` datapoints align to midnight of the day.
+    ///
+    /// All are according to the local timezone.
+    pub time: u32,
+    /// The UV index.
+    pub uv_index: Option<u8>,
+    /// The average visibility in miles, capped at 10 miles.
+    pub visibility: Option<f32>,
+    /// The direction that the wind is coming from in degrees.
+    ///
+    /// True north is at 0 degrees, progressing clockwise.
+    ///
+    /// If [`wind_speed`] is `0`, then this value will not be defined.
+    ///
+    /// [`wind_speed`]: #structfield.wind_speed
+    pub wind_bearing: Option<f32>,
+    /// The wind gust speed in miles per hour.
+    pub wind_gust: Option<f32>,
+    /// The wind speed in miles per hour.
+    pub wind_speed: Option<f32>,
+}
+
+impl Datapoint {
+    /// The unix timestamp representing when the daytime high apparent
+    /// temperature occurs.
+    pub fn apparent_temperature_max_time(&self) -> Option<u32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.apparent_temperature_max_time)
+    }
+
+    /// The daytime high apparent temperature.
+    pub fn apparent_temperature_max(&self) -> Option<f32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.apparent_temperature_max)
+    }
+
+    /// The unix timestamp representing when the overnight low apparent
+    /// temperature occurs.
+    pub fn apparent_temperature_min_time(&self) -> Option<u32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.apparent_temperature_min_time)
+    }
+
+    /// The overnight low apparent temperature.
+    pub fn apparent_temperature_min(&self) -> Option<f32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.apparent_temperature_min)
+    }
+
+    /// The amount of error possible within the [`cloud_cover`] value.
+    ///
+    /// [`cloud_cover`]: #structfield.cloud_cover
+    pub fn cloud_cover_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.cloud_cover_error)
+    }
+
+    /// The amount of error possible within the [`dew_point`] value.
+    ///
+    /// [`dew_point`]: #structfield.dew_point
+    pub fn dew_point_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.dew_point_error)
+    }
+
+    /// The amount of error possible within the [`humidity`] value.
+    ///
+    /// [`humidity`]: #structfield.humidity
+    pub fn humidity_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.humidity_error)
+    }
+
+    /// The fractional part of the [lunation number] during the given day.
+    pub fn moon_phase(&self) -> Option<f32> {
+        self.daily.as_ref().and_then(|fields| fields.moon_phase)
+    }
+
+    /// The amount of error possible within the [`ozone`] value.
+    ///
+    /// [`ozone`]: #structfield.ozone
+    pub fn ozone_error(&self) -> Option<f32> {
+        self.errors.as_ref().and_then(|fields| fields.ozone_error)
+    }
+
+    /// The amount of error possible within the [`precip_accumulation`] value.
+    ///
+    /// [`precip_accumulation`]: #structfield.precip_accumulation
+    pub fn precip_accumulation_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.precip_accumulation_error)
+    }
+
+    /// The amount of error possible within the [`precip_intensity`] value.
+    ///
+    /// [`precip_intensity`]: #structfield.precip_intensity
+    pub fn precip_intensity_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.precip_intensity_error)
+    }
+
+    /// The amount of error possible within the [`precip_intensity_max`] value.
+    ///
+    /// [`precip_intensity_max`]: #method.precip_intensity_max
+    pub fn precip_intensity_max_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.precip_intensity_max_error)
+    }
+
+    /// The unix timestamp of when [`precip_intensity_max`] occurs during a
+    /// given day.
+    ///
+    /// [`precip_intensity_max`]: #method.precip_intensity_max
+    pub fn precip_intensity_max_time(&self) -> Option<u32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.precip_intensity_max_time)
+    }
+
+    /// The maximum value of [`precip_intensity`] during a given day.
+    ///
+    /// [`precip_intensity`]: #structfield.precip_intensity
+    pub fn precip_intensity_max(&self) -> Option<f32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.precip_intensity_max)
+    }
+
+    /// The amount of error possible within the [`precip_probability`] value.
+    ///
+    /// [`precip_probability`]: #structfield.precip_probability
+    pub fn precip_probability_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.precip_probability_error)
+    }
+
+    /// The amount of error possible within the [`pressure`] value.
+    ///
+    /// [`pressure`]: #structfield.pressure
+    pub fn pressure_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.pressure_error)
+    }
+
+    /// The unix timestamp of when the sun will rise during a given day.
+    pub fn sunrise_time(&self) -> Option<u32> {
+        self.daily.as_ref().and_then(|fields| fields.sunrise_time)
+    }
+
+    /// The unix timestamp of when the sun will set during a given day.
+    pub fn sunset_time(&self) -> Option<u32> {
+        self.daily.as_ref().and_then(|fields| fields.sunset_time)
+    }
+
+    /// The overnight low temperature.
+    pub fn temperature_low(&self) -> Option<f32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_low)
+    }
+
+    /// The unix timestamp representing when the overnight low temperature
+    /// occurs.
+    pub fn temperature_low_time(&self) -> Option<u32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_low_time)
+    }
+
+    /// The daytime high temperature.
+    pub fn temperature_high(&self) -> Option<f32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_high)
+    }
+
+    /// The unix timestamp representing when the daytime high temperature
+    /// occurs.
+    pub fn temperature_high_time(&self) -> Option<u32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_high_time)
+    }
+
+    /// The amount of error possible within the [`temperature_max`] value.
+    ///
+    /// [`temperature_max`]: #method.temperature_max
+    pub fn temperature_max_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.temperature_max_error)
+    }
+
+    /// The unix timestamp representing when the maximum temperature during a
+    /// given date occurs.
+    pub fn temperature_max_time(&self) -> Option<u32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_max_time)
+    }
+
+    /// The maximum temperature during a given date.
+    pub fn temperature_max(&self) -> Option<f32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_max)
+    }
+
+    /// The amount of error possible within the [`temperature_min`] value.
+    ///
+    /// [`temperature_min`]: #method.temperature_min
+    pub fn temperature_min_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.temperature_min_error)
+    }
+
+    /// The unix timestamp representing when the minimum temperature during a
+    /// given date occurs.
+    pub fn temperature_min_time(&self) -> Option<u32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_min_time)
+    }
+
+    /// The minimum temperature during a given date.
+    pub fn temperature_min(&self) -> Option<f32> {
+        self.daily
+            .as_ref()
+            .and_then(|fields| fields.temperature_min)
+    }
+
+    /// The amount of error possible within the [`temperature`] value.
+    ///
+    /// [`temperature`]: #structfield.temperature
+    pub fn temperature_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.temperature_error)
+    }
+
+    /// The unix timestamp of when the maximum [`uv_index`] occurs during the
+    /// given day.
+    ///
+    /// [`uv_index`]: #structfield.uv_index
+    pub fn uv_index_time(&self) -> Option<u32> {
+        self.daily.as_ref().and_then(|fields| fields.uv_index_time)
+    }
+
+    /// The amount of error possible within the [`visibility`] value.
+    ///
+    /// [`visibility`]: #structfield.visibility
+    pub fn visibility_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.visibility_error)
+    }
+
+    /// The amount of error possible within the [`wind_bearing`] value.
+    ///
+    /// [`wind_bearing`]: #structfield.wind_bearing
+    pub fn wind_bearing_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.wind_bearing_error)
+    }
+
+    /// The amount of time that the wind gust is expected to occur.
+    pub fn wind_gust_time(&self) -> Option<u32> {
+        self.daily.as_ref().and_then(|fields| fields.wind_gust_time)
+    }
+
+    /// The amount of error possible within the [`wind_speed`] value.
+    ///
+    /// [`wind_speed`]: #structfield.wind_speed
+    pub fn wind_speed_error(&self) -> Option<f32> {
+        self.errors
+            .as_ref()
+            .and_then(|fields| fields.wind_speed_error)
+    }
+}
+
+/// The fields of a compact [`Datapoint`] that are only present on the `daily`
+/// block.
+///
+/// [`Datapoint`]: struct.Datapoint.html
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyFields {
+    /// The unix timestamp representing when the daytime high apparent
+    /// temperature occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub apparent_temperature_max_time: Option<u32>,
+    /// The daytime high apparent temperature.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub apparent_temperature_max: Option<f32>,
+    /// The unix timestamp representing when the overnight low apparent
+    /// temperature occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub apparent_temperature_min_time: Option<u32>,
+    /// The overnight low apparent temperature.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub apparent_temperature_min: Option<f32>,
+    /// The fractional part of the [lunation number] during the given day.
+    ///
+    /// A value of `0` corresponds to a new moon, `0.25` to a first quarter
+    /// moon, `0.5` to a full moon, `0.75` to a last quarter moon.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub moon_phase: Option<f32>,
+    /// The unix timestamp of when [`precip_intensity_max`] occurs during a
+    /// given day.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    ///
+    /// [`precip_intensity_max`]: #structfield.precip_intensity_max
+    pub precip_intensity_max_time: Option<u32>,
+    /// The maximum value of [`precip_intensity`] during a given day.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    ///
+    /// [`precip_intensity`]: struct.Datapoint.html#structfield.precip_intensity
+    pub precip_intensity_max: Option<f32>,
+    /// The unix timestamp of when the sun will rise during a given day.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub sunrise_time: Option<u32>,
+    /// The unix timestamp of when the sun will set during a given day.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub sunset_time: Option<u32>,
+    /// The overnight low temperature.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_low: Option<f32>,
+    /// The unix timestamp representing when the overnight low temperature
+    /// occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_low_time: Option<u32>,
+    /// The daytime high temperature.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_high: Option<f32>,
+    /// The unix timestamp representing when the daytime high temperature
+    /// occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_high_time: Option<u32>,
+    /// The unix timestamp representing when the maximum temperature during a
+    /// given date occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_max_time: Option<u32>,
+    /// The maximum temperature during a given date.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_max: Option<f32>,
+    /// The unix timestamp representing when the minimum temperature during a
+    /// given date occurs.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_min_time: Option<u32>,
+    /// The minimum temperature during a given date.
+    ///
+    /// **Note**: This is only present on the `daily` block.
+    pub temperature_min: Option<f32>,
+    /// The unix timestamp of when the maximum [`uv_index`] occurs during the
+    /// given day.
+    ///
+    /// [`uv_index`]: struct.Datapoint.html#structfield.uv_index
+    pub uv_index_time: Option<u32>,
+    /// The amount of time that the wind gust is expected to occur.
+    pub wind_gust_time: Option<u32>,
+}
+
+/// The fields of a compact [`Datapoint`] representing the amount of error
+/// possible within its data oriented fields.
+///
+/// [`Datapoint`]: struct.Datapoint.html
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorFields {
+    /// The amount of error possible within the [`cloud_cover`] value.
+    ///
+    /// [`cloud_cover`]: struct.Datapoint.html#structfield.cloud_cover
+    pub cloud_cover_error: Option<f32>,
+    /// The amount of error possible within the [`dew_point`] value.
+    ///
+    /// [`dew_point`]: struct.Datapoint.html#structfield.dew_point
+    pub dew_point_error: Option<f32>,
+    /// The amount of error possible within the [`humidity`] value.
+    ///
+    /// [`humidity`]: struct.Datapoint.html#structfield.humidity
+    pub humidity_error: Option<f32>,
+    /// The amount of error possible within the [`ozone`] value.
+    ///
+    /// [`ozone`]: struct.Datapoint.html#structfield.ozone
+    pub ozone_error: Option<f32>,
+    /// The amount of error possible within the [`precip_accumulation`] value.
+    ///
+    /// [`precip_accumulation`]: struct.Datapoint.html#structfield.precip_accumulation
+    pub precip_accumulation_error: Option<f32>,
+    /// The amount of error possible within the [`precip_intensity`] value.
+    ///
+    /// [`precip_intensity`]: struct.Datapoint.html#structfield.precip_intensity
+    pub precip_intensity_error: Option<f32>,
+    /// The amount of error possible within the [`precip_intensity_max`] value.
+    ///
+    /// [`precip_intensity_max`]: struct.DailyFields.html#structfield.precip_intensity_max
+    pub precip_intensity_max_error: Option<f32>,
+    /// The amount of error possible within the [`precip_probability`] value.
+    ///
+    /// [`precip_probability`]: struct.Datapoint.html#structfield.precip_probability
+    pub precip_probability_error: Option<f32>,
+    /// The amount of error possible within the [`pressure`] value.
+    ///
+    /// [`pressure`]: struct.Datapoint.html#structfield.pressure
+    pub pressure_error: Option<f32>,
+    /// The amount of error possible within the [`temperature_max`] value.
+    ///
+    /// [`temperature_max`]: struct.DailyFields.html#structfield.temperature_max
+    pub temperature_max_error: Option<f32>,
+    /// The amount of error possible within the [`temperature_min`] value.
+    ///
+    /// [`temperature_min`]: struct.DailyFields.html#structfield.temperature_min
+    pub temperature_min_error: Option<f32>,
+    /// The amount of error possible within the [`temperature`] value.
+    ///
+    /// [`temperature`]: struct.Datapoint.html#structfield.temperature
+    pub temperature_error: Option<f32>,
+    /// The amount of error possible within the [`visibility`] value.
+    ///
+    /// [`visibility`]: struct.Datapoint.html#structfield.visibility
+    pub visibility_error: Option<f32>,
+    /// The amount of error possible within the [`wind_bearing`] value.
+    ///
+    /// [`wind_bearing`]: struct.Datapoint.html#structfield.wind_bearing
+    pub wind_bearing_error: Option<f32>,
+    /// The amount of error possible within the [`wind_speed`] value.
+    ///
+    /// [`wind_speed`]: struct.Datapoint.html#structfield.wind_speed
+    pub wind_speed_error: Option<f32>,
+}
+
+impl From<RawDatapoint> for Datapoint {
+    fn from(raw: RawDatapoint) -> Self {
+        let daily = DailyFields {
+            apparent_temperature_max_time: raw.apparent_temperature_max_time.map(timestamp),
+            apparent_temperature_max: raw.apparent_temperature_max.map(|v| v as f32),
+            apparent_temperature_min_time: raw.apparent_temperature_min_time.map(timestamp),
+            apparent_temperature_min: raw.apparent_temperature_min.map(|v| v as f32),
+            moon_phase: raw.moon_phase.map(|v| v as f32),
+            precip_intensity_max_time: raw.precip_intensity_max_time.map(timestamp),
+            precip_intensity_max: raw.precip_intensity_max.map(|v| v as f32),
+            sunrise_time: raw.sunrise_time.map(timestamp),
+            sunset_time: raw.sunset_time.map(timestamp),
+            temperature_low: raw.temperature_low.map(|v| v as f32),
+            temperature_low_time: raw.temperature_low_time.map(timestamp),
+            temperature_high: raw.temperature_high.map(|v| v as f32),
+            temperature_high_time: raw.temperature_high_time.map(timestamp),
+            temperature_max_time: raw.temperature_max_time.map(timestamp),
+            temperature_max: raw.temperature_max.map(|v| v as f32),
+            temperature_min_time: raw.temperature_min_time.map(timestamp),
+            temperature_min: raw.temperature_min.map(|v| v as f32),
+            uv_index_time: raw.uv_index_time.map(timestamp),
+            wind_gust_time: raw.wind_gust_time.map(timestamp),
+        };
+        let errors = ErrorFields {
+            cloud_cover_error: raw.cloud_cover_error.map(|v| v as f32),
+            dew_point_error: raw.dew_point_error.map(|v| v as f32),
+            humidity_error: raw.humidity_error.map(|v| v as f32),
+            ozone_error: raw.ozone_error.map(|v| v as f32),
+            precip_accumulation_error: raw.precip_accumulation_error.map(|v| v as f32),
+            precip_intensity_error: raw.precip_intensity_error.map(|v| v as f32),
+            precip_intensity_max_error: raw.precip_intensity_max_error.map(|v| v as f32),
+            precip_probability_error: raw.precip_probability_error.map(|v| v as f32),
+            pressure_error: raw.pressure_error.map(|v| v as f32),
+            temperature_max_error: raw.temperature_max_error.map(|v| v as f32),
+            temperature_min_error: raw.temperature_min_error.map(|v| v as f32),
+            temperature_error: raw.temperature_error.map(|v| v as f32),
+            visibility_error: raw.visibility_error.map(|v| v as f32),
+            wind_bearing_error: raw.wind_bearing_error.map(|v| v as f32),
+            wind_speed_error: raw.wind_speed_error.map(|v| v as f32),
+        };
+
+        Datapoint {
+            apparent_temperature: raw.apparent_temperature.map(|v| v as f32),
+            cloud_cover: raw.cloud_cover.map(|v| v as f32),
+            dew_point: raw.dew_point.map(|v| v as f32),
+            humidity: raw.humidity.map(|v| v as f32),
+            icon: raw.icon,
+            nearest_storm_bearing: raw.nearest_storm_bearing.map(|v| v as f32),
+            nearest_storm_distance: raw.nearest_storm_distance.map(|v| v as f32),
+            ozone: raw.ozone.map(|v| v as f32),
+            precip_accumulation: raw.precip_accumulation.map(|v| v as f32),
+            precip_intensity: raw.precip_intensity.map(|v| v as f32),
+            precip_probability: raw.precip_probability.map(|v| v as f32),
+            precip_type: raw.precip_type,
+            pressure: raw.pressure.map(|v| v as f32),
+            summary: raw.summary,
+            temperature: raw.temperature.map(|v| v as f32),
+            time: timestamp(raw.time),
+            uv_index: raw.uv_index.map(|v| u8::try_from(v).unwrap_or(u8::MAX)),
+            visibility: raw.visibility.map(|v| v as f32),
+            wind_bearing: raw.wind_bearing.map(|v| v as f32),
+            wind_gust: raw.wind_gust.map(|v| v as f32),
+            wind_speed: raw.wind_speed.map(|v| v as f32),
+            daily: if daily == DailyFields::default() {
+                None
+            } else {
+                Some(Box::new(daily))
+            },
+            errors: if errors == ErrorFields::default() {
+                None
+            } else {
+                Some(Box::new(errors))
+            },
+        }
+    }
+}
+
+impl<'a> From<&'a models::Datapoint> for Datapoint {
+    fn from(datapoint: &'a models::Datapoint) -> Self {
+        RawDatapoint {
+            apparent_temperature_max_time: datapoint.apparent_temperature_max_time(),
+            apparent_temperature_max: datapoint.apparent_temperature_max(),
+            apparent_temperature_min_time: datapoint.apparent_temperature_min_time(),
+            apparent_temperature_min: datapoint.apparent_temperature_min(),
+            apparent_temperature: datapoint.apparent_temperature,
+            cloud_cover_error: datapoint.cloud_cover_error(),
+            cloud_cover: datapoint.cloud_cover,
+            dew_point_error: datapoint.dew_point_error(),
+            dew_point: datapoint.dew_point,
+            humidity_error: datapoint.humidity_error(),
+            humidity: datapoint.humidity,
+            icon: datapoint.icon,
+            moon_phase: datapoint.moon_phase(),
+            nearest_storm_bearing: datapoint.nearest_storm_bearing,
+            nearest_storm_distance: datapoint.nearest_storm_distance,
+            ozone_error: datapoint.ozone_error(),
+            ozone: datapoint.ozone,
+            precip_accumulation_error: datapoint.precip_accumulation_error(),
+            precip_accumulation: datapoint.precip_accumulation,
+            precip_intensity_error: datapoint.precip_intensity_error(),
+            precip_intensity_max_error: datapoint.precip_intensity_max_error(),
+            precip_intensity_max_time: datapoint.precip_intensity_max_time(),
+            precip_intensity_max: datapoint.precip_intensity_max(),
+            precip_intensity: datapoint.precip_intensity,
+            precip_probability_error: datapoint.precip_probability_error(),
+            precip_probability: datapoint.precip_probability,
+            precip_type: datapoint.precip_type,
+            pressure_error: datapoint.pressure_error(),
+            pressure: datapoint.pressure,
+            // Compact datapoints do not keep solar data.
+            #[cfg(feature = "solar")]
+            solar: None,
+            summary: datapoint.summary.clone(),
+            sunrise_time: datapoint.sunrise_time(),
+            sunset_time: datapoint.sunset_time(),
+            temperature_low: datapoint.temperature_low(),
+            temperature_low_time: datapoint.temperature_low_time(),
+            temperature_high: datapoint.temperature_high(),
+            temperature_high_time: datapoint.temperature_high_time(),
+            temperature_max_error: datapoint.temperature_max_error(),
+            temperature_max_time: datapoint.temperature_max_time(),
+            temperature_max: datapoint.temperature_max(),
+            temperature_min_error: datapoint.temperature_min_error(),
+            temperature_min_time: datapoint.temperature_min_time(),
+            temperature_min: datapoint.temperature_min(),
+            temperature_error: datapoint.temperature_error(),
+            temperature: datapoint.temperature,
+            time: datapoint.time,
+            uv_index: datapoint.uv_index,
+            uv_index_time: datapoint.uv_index_time(),
+            visibility_error: datapoint.visibility_error(),
+            visibility: datapoint.visibility,
+            wind_bearing_error: datapoint.wind_bearing_error(),
+            wind_bearing: datapoint.wind_bearing,
+            wind_gust: datapoint.wind_gust,
+            wind_gust_time: datapoint.wind_gust_time(),
+            wind_speed_error: datapoint.wind_speed_error(),
+            wind_speed: datapoint.wind_speed,
+        }
+        .into()
+    }
+}
+
+/// A compact [`Forecast`][`models::Forecast`].
+///
+/// [`models::Forecast`]: ../struct.Forecast.html
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Forecast {
+    /// Contains any severe weather alerts pertinent to the location.
+    #[serde(default)]
+    pub alerts: Vec<Alert>,
+    /// The current forecast.
+    ///
+    /// This may be excluded by passing the [`Block::Currently`] variant to
+    /// [`Options::exclude`].
+    ///
+    /// [`Block::Currently`]: ../../enum.Block.html#variant.Currently
+    /// [`Datablock`]: struct.Datablock.html
+    /// [`Options::exclude`]: ../../struct.Options.html#method.exclude
+    pub currently: Option<Datapoint>,
+    /// Daily [`Datablock`]s within a forecast.
+    ///
+    /// This may be excluded by passing the [`Block::Daily`] variant to
+    /// [`Options::exclude`].
+    ///
+    /// [`Block::Daily`]: ../../enum.Block.html#variant.Daily
+    /// [`Datablock`]: struct.Datablock.html
+    /// [`Options::exclude`]: ../../struct.Options.html#method.exclude
+    pub daily: Option<Datablock>,
+    /// A set of flags returned from the API.
+    ///
+    /// This may be excluded by passing the [`Block::Flags`] variant to
+    /// [`Options::exclude`].
+    ///
+    /// [`Block::Flags`]: ../../enum.Block.html#variant.Flags
+    /// [`Datablock`]: struct.Datablock.html
+    /// [`Options::exclude`]: ../../struct.Options.html#method.exclude
+    pub flags: Option<Flags>,
+    /// Hourly [`Datablock`]s within a forecast.
+    ///
+    /// This may be excluded by passing the [`Block::Hourly`] variant to
+    /// [`Options::exclude`].
+    ///
+    /// [`Block::Hourly`]: ../../enum.Block.html#variant.Hourly
+    /// [`Datablock`]: struct.Datablock.html
+    /// [`Options::exclude`]: ../../struct.Options.html#method.exclude
+    pub hourly: Option<Datablock>,
+    /// The latitude of the forecast's location.
+    pub latitude: f32,
+    /// The longitude of the forecast's location.
+    pub longitude: f32,
+    /// Minutely [`Datablock`]s within a forecast.
+    ///
+    /// This may be excluded by passing the [`Block::Minutely`] variant to
+    /// [`Options::exclude`].
+    ///
+    /// [`Block::Minutely`]: ../../enum.Block.html#variant.Minutely
+    /// [`Datablock`]: struct.Datablock.html
+    /// [`Options::exclude`]: ../../struct.Options.html#method.exclude
+    pub minutely: Option<Datablock>,
+    /// The timezone offset of the forecast, relative to the UTC timezone.
+    pub offset: Option<f32>,
+    /// The name of the timezone.
+    pub timezone: String,
+}
+
+impl<'a> From<&'a models::Forecast> for Forecast {
+    fn from(forecast: &'a models::Forecast) -> Self {
+        Forecast {
+            alerts: forecast.alerts.clone(),
+            currently: forecast.currently.as_ref().map(From::from),
+            daily: forecast.daily.as_ref().map(From::from),
+            flags: forecast.flags.clone(),
+            hourly: forecast.hourly.as_ref().map(From::from),
+            latitude: forecast.latitude as f32,
+            longitude: forecast.longitude as f32,
+            minutely: forecast.minutely.as_ref().map(From::from),
+            offset: forecast.offset.map(|v| v as f32),
+            timezone: forecast.timezone.clone(),
+        }
+    }
+}
+
+/// Converts a timestamp, saturating those beyond the range of a `u32` rather
+/// than wrapping them around.
+fn timestamp(value: u64) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
@@ -0,0 +1,254 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Categorical descriptions of weather conditions, for consistent display
+//! logic.
+
+use super::{Datapoint, Forecast};
+use Unit;
+
+/// The number of kilometers in a mile, for visibilities in miles.
+const KILOMETERS_PER_MILE: f64 = 1.609_344;
+
+/// The number of seconds over which a pressure tendency is measured.
+const TENDENCY_SECONDS: u64 = 3 * 3600;
+
+/// The amount of sky covered by clouds, in the bands used by aviation
+/// weather reports.
+///
+/// Bands are measured in oktas, or eighths of the sky. Categories are ordered
+/// from least to most cloud.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum CloudCover {
+    /// No clouds (`SKC`).
+    Clear,
+    /// One to two oktas of cloud (`FEW`).
+    Few,
+    /// Three to four oktas of cloud (`SCT`).
+    Scattered,
+    /// Five to seven oktas of cloud (`BKN`).
+    Broken,
+    /// The whole sky is covered by cloud (`OVC`).
+    Overcast,
+}
+
+impl CloudCover {
+    /// Classifies a cloud cover between `0` and `1`, as given by
+    /// [`Datapoint::cloud_cover`].
+    ///
+    /// [`Datapoint::cloud_cover`]: struct.Datapoint.html#structfield.cloud_cover
+    pub fn from_fraction(cloud_cover: f64) -> Self {
+        // Rounds to the nearest okta without `f64::round`, which needs std.
+        match (cloud_cover * 8.0 + 0.5) as i64 {
+            i64::MIN..=0 => CloudCover::Clear,
+            1..=2 => CloudCover::Few,
+            3..=4 => CloudCover::Scattered,
+            5..=7 => CloudCover::Broken,
+            _ => CloudCover::Overcast,
+        }
+    }
+}
+
+/// How far one can see, in the bands used by marine and aviation forecasts.
+///
+/// Categories are ordered from worst to best visibility.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum Visibility {
+    /// Less than 1 kilometer.
+    Fog,
+    /// From 1 up to 4 kilometers.
+    Poor,
+    /// From 4 up to 10 kilometers.
+    Moderate,
+    /// 10 kilometers or more.
+    Good,
+}
+
+impl Visibility {
+    /// Classifies a visibility in kilometers.
+    pub fn from_kilometers(visibility: f64) -> Self {
+        if visibility < 1.0 {
+            Visibility::Fog
+        } else if visibility < 4.0 {
+            Visibility::Poor
+        } else if visibility < 10.0 {
+            Visibility::Moderate
+        } else {
+            Visibility::Good
+        }
+    }
+}
+
+/// The change in pressure over three hours, in the bands used by marine
+/// forecasts and shipping bulletins.
+///
+/// Categories are ordered from falling most rapidly to rising most rapidly.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum PressureTendency {
+    /// Falling by more than 3.5 hectopascals.
+    FallingRapidly,
+    /// Falling by 1.6 to 3.5 hectopascals.
+    Falling,
+    /// Falling by 0.1 to 1.5 hectopascals.
+    FallingSlowly,
+    /// Changing by less than 0.1 hectopascals.
+    Steady,
+    /// Rising by 0.1 to 1.5 hectopascals.
+    RisingSlowly,
+    /// Rising by 1.6 to 3.5 hectopascals.
+    Rising,
+    /// Rising by more than 3.5 hectopascals.
+    RisingRapidly,
+}
+
+impl PressureTendency {
+    /// Classifies a change in pressure over three hours, in hectopascals (or
+    /// millibars, as used by [`Unit::Us`]).
+    ///
+    /// [`Unit::Us`]: ../enum.Unit.html#variant.Us
+    pub fn from_change(change: f64) -> Self {
+        let magnitude = if change < 0.0 { -change } else { change };
+        let rising = change > 0.0;
+
+        if magnitude < 0.1 {
+            PressureTendency::Steady
+        } else if magnitude < 1.55 {
+            if rising {
+                PressureTendency::RisingSlowly
+            } else {
+                PressureTendency::FallingSlowly
+            }
+        } else if magnitude < 3.55 {
+            if rising {
+                PressureTendency::Rising
+            } else {
+                PressureTendency::Falling
+            }
+        } else if rising {
+            PressureTendency::RisingRapidly
+        } else {
+            PressureTendency::FallingRapidly
+        }
+    }
+}
+
+impl Forecast {
+    /// Returns the pressure tendency over the first three hours of the
+    /// [`hourly`] block.
+    ///
+    /// Returns `None` if the `hourly` block was excluded, does not cover three
+    /// hours, or is missing the pressure at either end.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::models::{Forecast, PressureTendency};
+    ///
+    /// # fn main() {
+    /// let forecast: Forecast = serde_json::from_str(r#"{
+    ///     "latitude": 49.9,
+    ///     "longitude": -97.1,
+    ///     "timezone": "America/Winnipeg",
+    ///     "hourly": {"data": [
+    ///         {"time": 1509991200, "pressure": 1012.4},
+    ///         {"time": 1509994800, "pressure": 1011.1},
+    ///         {"time": 1509998400, "pressure": 1010.0},
+    ///         {"time": 1510002000, "pressure": 1008.1}
+    ///     ]}
+    /// }"#).unwrap();
+    ///
+    /// assert_eq!(forecast.pressure_tendency(), Some(PressureTendency::FallingRapidly));
+    /// # }
+    /// ```
+    ///
+    /// [`hourly`]: #structfield.hourly
+    pub fn pressure_tendency(&self) -> Option<PressureTendency> {
+        let data = self.hourly.as_ref()?.data.as_ref()?;
+        let first = data.first()?;
+        let later = data
+            .iter()
+            .find(|hour| hour.time >= first.time + TENDENCY_SECONDS)?;
+
+        Some(PressureTendency::from_change(
+            later.pressure? - first.pressure?,
+        ))
+    }
+}
+
+impl Datapoint {
+    /// Returns the category of the datapoint's [`cloud_cover`], if it is
+    /// present.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::models::{CloudCover, Datapoint};
+    ///
+    /// # fn main() {
+    /// let datapoint: Datapoint =
+    ///     serde_json::from_str(r#"{"time": 1509993277, "cloudCover": 0.42}"#).unwrap();
+    ///
+    /// assert_eq!(datapoint.cloud_cover_category(), Some(CloudCover::Scattered));
+    /// # }
+    /// ```
+    ///
+    /// [`cloud_cover`]: #structfield.cloud_cover
+    pub fn cloud_cover_category(&self) -> Option<CloudCover> {
+        self.cloud_cover.map(CloudCover::from_fraction)
+    }
+
+    /// Returns the category of the datapoint's [`visibility`], if it is
+    /// present.
+    ///
+    /// The visibility is in kilometers for [`Unit::Ca`] and [`Unit::Si`], and
+    /// in miles otherwise, as with the units the forecast was requested in.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::models::{Datapoint, Visibility};
+    /// use darksky::Unit;
+    ///
+    /// # fn main() {
+    /// let datapoint: Datapoint =
+    ///     serde_json::from_str(r#"{"time": 1509993277, "visibility": 3}"#).unwrap();
+    ///
+    /// assert_eq!(datapoint.visibility_category(Unit::Si), Some(Visibility::Poor));
+    /// assert_eq!(datapoint.visibility_category(Unit::Us), Some(Visibility::Moderate));
+    /// # }
+    /// ```
+    ///
+    /// [`Unit::Ca`]: ../enum.Unit.html#variant.Ca
+    /// [`Unit::Si`]: ../enum.Unit.html#variant.Si
+    /// [`visibility`]: #structfield.visibility
+    pub fn visibility_category(&self, unit: Unit) -> Option<Visibility> {
+        let kilometers = match unit {
+            Unit::Ca | Unit::Si => 1.0,
+            Unit::Auto | Unit::Uk2 | Unit::Us => KILOMETERS_PER_MILE,
+        };
+
+        self.visibility
+            .map(|visibility| Visibility::from_kilometers(visibility * kilometers))
+    }
+}
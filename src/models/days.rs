@@ -0,0 +1,489 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Calendar days in a forecast's local time.
+
+use super::{Datablock, Datapoint, Forecast, Icon};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter, Result as FmtResult};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// The local hour at which the evening, and so [`Forecast::tonight`], starts.
+///
+/// [`Forecast::tonight`]: struct.Forecast.html#method.tonight
+const TONIGHT_START_HOUR: i64 = 18;
+
+/// The local hour at which the night ends the following morning.
+const TONIGHT_END_HOUR: i64 = 6;
+
+/// Returns the number of local days since the UNIX epoch of a timestamp.
+fn local_day(time: u64, offset: i64) -> i64 {
+    (time as i64 + offset).div_euclid(SECONDS_PER_DAY)
+}
+
+/// A calendar date in a forecast's local time.
+///
+/// Dates are ordered chronologically, and are displayed in ISO 8601 format,
+/// such as `2017-11-06`.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct LocalDate {
+    /// The year, such as `2017`.
+    pub year: i32,
+    /// The month of the year, from `1` to `12`.
+    pub month: u8,
+    /// The day of the month, from `1` to `31`.
+    pub day: u8,
+}
+
+impl LocalDate {
+    /// Returns the local date of a UNIX timestamp, given the local time's
+    /// offset from UTC in seconds.
+    pub fn from_timestamp(time: u64, offset: i64) -> Self {
+        Self::from_days(local_day(time, offset))
+    }
+
+    // Converts a number of days since the UNIX epoch into a proleptic
+    // Gregorian date, using the algorithm from Howard Hinnant's
+    // `civil_from_days`.
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        LocalDate {
+            year: year as i32,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+}
+
+impl Display for LocalDate {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// The high and low of a single day of a [`Forecast`], as shown in a
+/// "7-day strip".
+///
+/// [`Forecast`]: struct.Forecast.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct HighLow {
+    /// The day, in the forecast's local time.
+    pub date: LocalDate,
+    /// The daytime high temperature.
+    pub high: Option<f64>,
+    /// The overnight low temperature.
+    pub low: Option<f64>,
+    /// The icon representing the day's weather.
+    pub icon: Option<Icon>,
+    /// The probability of precipitation during the day.
+    pub precip_probability: Option<f64>,
+}
+
+/// A labeled window of local time within each day, such as `"morning"` from
+/// 6am to noon, used with [`Datablock::chunk_by`].
+///
+/// A window whose end is before its start wraps past midnight, such as a
+/// night shift from 10pm to 6am.
+///
+/// [`Datablock::chunk_by`]: struct.Datablock.html#method.chunk_by
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Window {
+    /// The label of the window.
+    pub label: String,
+    /// The start of the window, in minutes after local midnight, inclusive.
+    pub start: u32,
+    /// The end of the window, in minutes after local midnight, exclusive.
+    pub end: u32,
+}
+
+impl Window {
+    /// Creates a window from the starting hour up to the ending hour, such as
+    /// `Window::hours("afternoon", 12, 18)`.
+    pub fn hours<S: Into<String>>(label: S, start: u32, end: u32) -> Self {
+        Window {
+            label: label.into(),
+            start: start * 60,
+            end: end * 60,
+        }
+    }
+
+    /// Returns the usual parts of the day: morning from 6am, afternoon from
+    /// noon, evening from 6pm, and night from 10pm until 6am.
+    pub fn parts_of_day() -> Vec<Self> {
+        Vec::from([
+            Window::hours("morning", 6, 12),
+            Window::hours("afternoon", 12, 18),
+            Window::hours("evening", 18, 22),
+            Window::hours("night", 22, 6),
+        ])
+    }
+
+    /// Returns whether the window contains a time, in minutes after local
+    /// midnight.
+    fn contains(&self, minute: u32) -> bool {
+        if self.start <= self.end {
+            self.start <= minute && minute < self.end
+        } else {
+            self.start <= minute || minute < self.end
+        }
+    }
+}
+
+/// The datapoints of a [`Datablock`] falling within one occurrence of a
+/// [`Window`], as returned by [`Datablock::chunk_by`].
+///
+/// [`Datablock`]: struct.Datablock.html
+/// [`Datablock::chunk_by`]: struct.Datablock.html#method.chunk_by
+/// [`Window`]: struct.Window.html
+#[derive(Clone, Debug)]
+pub struct Chunk<'a> {
+    /// The label of the window.
+    pub label: &'a str,
+    /// The local date that the occurrence of the window starts on.
+    pub date: LocalDate,
+    /// The datapoints within the window.
+    pub data: &'a [Datapoint],
+}
+
+impl Datablock {
+    /// Groups the block's datapoints into occurrences of the given windows of
+    /// local time, such as mornings and afternoons or custom shift schedules.
+    ///
+    /// The `offset` is the local time's offset from UTC in seconds, as given
+    /// by [`Forecast::offset_seconds`]. Each datapoint is placed in the first
+    /// window containing it, and consecutive datapoints in the same
+    /// occurrence of a window are grouped into a single [`Chunk`]. Datapoints
+    /// outside of every window are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::models::{Forecast, Window};
+    ///
+    /// # fn main() {
+    /// // Hours from 4pm to 1am local time.
+    /// let hours = (0..10)
+    ///     .map(|hour| format!(r#"{{"time": {}, "temperature": {}}}"#, 1510005600 + hour * 3600, hour))
+    ///     .collect::<Vec<_>>()
+    ///     .join(",");
+    /// let forecast: Forecast = serde_json::from_str(&format!(r#"{{
+    ///     "latitude": 49.9,
+    ///     "longitude": -97.1,
+    ///     "timezone": "America/Winnipeg",
+    ///     "offset": -6,
+    ///     "hourly": {{"data": [{}]}}
+    /// }}"#, hours)).unwrap();
+    ///
+    /// let windows = Window::parts_of_day();
+    /// let hourly = forecast.hourly.as_ref().unwrap();
+    /// let chunks = hourly.chunk_by(&windows, forecast.offset_seconds());
+    /// let labels = chunks.iter().map(|c| (c.label, c.data.len())).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(labels, [("afternoon", 2), ("evening", 4), ("night", 4)]);
+    /// assert_eq!(chunks[2].date.to_string(), "2017-11-06");
+    /// # }
+    /// ```
+    ///
+    /// [`Chunk`]: struct.Chunk.html
+    /// [`Forecast::offset_seconds`]: struct.Forecast.html#method.offset_seconds
+    pub fn chunk_by<'a>(&'a self, windows: &'a [Window], offset: i64) -> Vec<Chunk<'a>> {
+        let data = match self.data {
+            Some(ref data) => &data[..],
+            None => return Vec::new(),
+        };
+        let mut chunks: Vec<Chunk<'a>> = Vec::new();
+        let mut current: Option<(usize, i64, usize)> = None;
+
+        for (index, datapoint) in data.iter().enumerate() {
+            let local = datapoint.time as i64 + offset;
+            let minute = (local.rem_euclid(SECONDS_PER_DAY) / 60) as u32;
+            let found = windows.iter().position(|window| window.contains(minute));
+
+            let occurrence = found.map(|window| {
+                let mut day = local.div_euclid(SECONDS_PER_DAY);
+
+                // Times after midnight in a wrapping window belong to the
+                // occurrence that started the day before.
+                if windows[window].start > windows[window].end && minute < windows[window].end {
+                    day -= 1;
+                }
+
+                (window, day)
+            });
+
+            match (current, occurrence) {
+                (Some((window, day, _)), Some(next)) if (window, day) == next => continue,
+                _ => {}
+            }
+
+            if let Some((window, day, start)) = current.take() {
+                chunks.push(Chunk {
+                    label: &windows[window].label,
+                    date: LocalDate::from_days(day),
+                    data: &data[start..index],
+                });
+            }
+
+            current = occurrence.map(|(window, day)| (window, day, index));
+        }
+
+        if let Some((window, day, start)) = current {
+            chunks.push(Chunk {
+                label: &windows[window].label,
+                date: LocalDate::from_days(day),
+                data: &data[start..],
+            });
+        }
+
+        chunks
+    }
+}
+
+impl Forecast {
+    /// Returns the local time's offset from UTC, in seconds, as given by the
+    /// [`offset`] in hours.
+    ///
+    /// [`offset`]: #structfield.offset
+    pub fn offset_seconds(&self) -> i64 {
+        (self.offset.unwrap_or(0.0) * 3600.0) as i64
+    }
+
+    /// Returns the high and low of each day of the [`daily`] block, with the
+    /// date of each day in the forecast's local time, as given by its
+    /// [`offset`].
+    ///
+    /// The high and low are the daytime high and overnight low, falling back
+    /// to the day's maximum and minimum temperatures when they are not
+    /// present.
+    ///
+    /// Returns an empty list if the `daily` block was excluded.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::models::{Forecast, Icon};
+    ///
+    /// # fn main() {
+    /// let forecast: Forecast = serde_json::from_str(r#"{
+    ///     "latitude": 49.9,
+    ///     "longitude": -97.1,
+    ///     "timezone": "America/Winnipeg",
+    ///     "offset": -6,
+    ///     "daily": {"data": [
+    ///         {"time": 1509948000, "icon": "snow", "precipProbability": 0.8,
+    ///          "temperatureHigh": 2.5, "temperatureLow": -8},
+    ///         {"time": 1510034400, "temperatureMax": 4, "temperatureMin": -6}
+    ///     ]}
+    /// }"#).unwrap();
+    ///
+    /// let days = forecast.daily_highs_lows();
+    ///
+    /// assert_eq!(days[0].date.to_string(), "2017-11-06");
+    /// assert_eq!((days[0].high, days[0].low), (Some(2.5), Some(-8.0)));
+    /// assert_eq!(days[0].icon, Some(Icon::Snow));
+    /// assert_eq!(days[1].date.to_string(), "2017-11-07");
+    /// assert_eq!((days[1].high, days[1].low), (Some(4.0), Some(-6.0)));
+    /// # }
+    /// ```
+    ///
+    /// [`daily`]: #structfield.daily
+    /// [`offset`]: #structfield.offset
+    pub fn daily_highs_lows(&self) -> Vec<HighLow> {
+        let offset = self.offset_seconds();
+        let data = match self.daily.as_ref().and_then(|daily| daily.data.as_ref()) {
+            Some(data) => data,
+            None => return Vec::new(),
+        };
+
+        data.iter()
+            .map(|day| HighLow {
+                date: LocalDate::from_timestamp(day.time, offset),
+                high: day.temperature_high().or_else(|| day.temperature_max()),
+                low: day.temperature_low().or_else(|| day.temperature_min()),
+                icon: day.icon,
+                precip_probability: day.precip_probability,
+            })
+            .collect()
+    }
+
+    /// Returns the `daily` datapoint of today in the forecast's local time.
+    ///
+    /// "Today" is the local day of the [`currently`] datapoint, falling back
+    /// to the first `hourly` or `daily` datapoint if it was excluded. Days are
+    /// resolved using the forecast's [`offset`] rather than UTC, so a forecast
+    /// requested shortly before local midnight still refers to the right day.
+    ///
+    /// Returns `None` if the `daily` block was excluded or does not cover
+    /// today.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::models::Forecast;
+    ///
+    /// # fn main() {
+    /// // 23:30 local time, which is already the next day in UTC.
+    /// let forecast: Forecast = serde_json::from_str(r#"{
+    ///     "latitude": 49.9,
+    ///     "longitude": -97.1,
+    ///     "timezone": "America/Winnipeg",
+    ///     "offset": -6,
+    ///     "currently": {"time": 1510032600},
+    ///     "daily": {"data": [
+    ///         {"time": 1509948000, "temperatureHigh": 2.5},
+    ///         {"time": 1510034400, "temperatureHigh": 4}
+    ///     ]}
+    /// }"#).unwrap();
+    ///
+    /// assert_eq!(forecast.today().unwrap().temperature_high(), Some(2.5));
+    /// assert_eq!(forecast.tomorrow().unwrap().temperature_high(), Some(4.0));
+    /// # }
+    /// ```
+    ///
+    /// [`currently`]: #structfield.currently
+    /// [`offset`]: #structfield.offset
+    pub fn today(&self) -> Option<&Datapoint> {
+        self.relative_day(0)
+    }
+
+    /// Returns the `daily` datapoint of tomorrow in the forecast's local time.
+    ///
+    /// See [`today`] for how the current day is resolved.
+    ///
+    /// [`today`]: #method.today
+    pub fn tomorrow(&self) -> Option<&Datapoint> {
+        self.relative_day(1)
+    }
+
+    /// Returns the `hourly` datapoints of tonight in the forecast's local
+    /// time, from 6pm until 6am the following morning.
+    ///
+    /// Before 6am, tonight is the night that is already under way. See
+    /// [`today`] for how the current time is resolved.
+    ///
+    /// Returns an empty slice if the `hourly` block was excluded or does not
+    /// cover tonight.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::models::Forecast;
+    ///
+    /// # fn main() {
+    /// // Hours from 4pm to 8am local time, on a forecast fetched at 4pm.
+    /// let hours = (0..17)
+    ///     .map(|hour| format!(r#"{{"time": {}}}"#, 1510005600 + hour * 3600))
+    ///     .collect::<Vec<_>>()
+    ///     .join(",");
+    /// let forecast: Forecast = serde_json::from_str(&format!(r#"{{
+    ///     "latitude": 49.9,
+    ///     "longitude": -97.1,
+    ///     "timezone": "America/Winnipeg",
+    ///     "offset": -6,
+    ///     "hourly": {{"data": [{}]}}
+    /// }}"#, hours)).unwrap();
+    ///
+    /// let tonight = forecast.tonight();
+    ///
+    /// assert_eq!(tonight.len(), 12);
+    /// assert_eq!(tonight[0].time, 1510012800);
+    /// # }
+    /// ```
+    ///
+    /// [`today`]: #method.today
+    pub fn tonight(&self) -> &[Datapoint] {
+        let data = match self.hourly.as_ref().and_then(|hourly| hourly.data.as_ref()) {
+            Some(data) => data,
+            None => return &[],
+        };
+        let now = match self.now() {
+            Some(now) => now,
+            None => return &[],
+        };
+
+        let offset = self.offset_seconds();
+        let mut day = local_day(now, offset);
+
+        if (now as i64 + offset).rem_euclid(SECONDS_PER_DAY) < TONIGHT_END_HOUR * 3600 {
+            day -= 1;
+        }
+
+        let start = day * SECONDS_PER_DAY + TONIGHT_START_HOUR * 3600 - offset;
+        let end = (day + 1) * SECONDS_PER_DAY + TONIGHT_END_HOUR * 3600 - offset;
+        let position = |bound: i64| {
+            data.iter()
+                .position(|hour| hour.time as i64 >= bound)
+                .unwrap_or(data.len())
+        };
+
+        &data[position(start)..position(end)]
+    }
+
+    /// Returns the `daily` datapoint the given number of local days from
+    /// today.
+    fn relative_day(&self, days: i64) -> Option<&Datapoint> {
+        let offset = self.offset_seconds();
+        let day = local_day(self.now()?, offset) + days;
+
+        self.daily
+            .as_ref()?
+            .data
+            .as_ref()?
+            .iter()
+            .find(|datapoint| local_day(datapoint.time, offset) == day)
+    }
+
+    /// Returns the time the forecast is for, being the time of the
+    /// `currently` datapoint, or of the first `hourly` or `daily` datapoint if
+    /// it was excluded.
+    fn now(&self) -> Option<u64> {
+        let first = |block: &Option<Datablock>| {
+            block
+                .as_ref()
+                .and_then(|block| block.data.as_ref())
+                .and_then(|data| data.first())
+                .map(|datapoint| datapoint.time)
+        };
+
+        self.currently
+            .as_ref()
+            .map(|currently| currently.time)
+            .or_else(|| first(&self.hourly))
+            .or_else(|| first(&self.daily))
+    }
+}
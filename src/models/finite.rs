@@ -0,0 +1,282 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Opt-in checks for non-finite values, such as `NaN` or infinity, in the
+//! numeric fields of the models.
+
+#[cfg(feature = "solar")]
+use super::Solar;
+use super::{DailyFields, Datablock, Datapoint, ErrorFields, Forecast};
+use {Error, Result};
+
+/// What to do with a non-finite value found in a numeric field.
+///
+/// JSON has no representation of `NaN` or infinity, so these can't come from
+/// the API directly, but may come from forecasts relayed through other serde
+/// formats or built in code. Left alone, a single `NaN` poisons any statistics
+/// computed over a block, and is rejected by many databases.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum NonFinite {
+    /// Fail with [`Error::NonFinite`], naming the offending field.
+    ///
+    /// [`Error::NonFinite`]: ../enum.Error.html#variant.NonFinite
+    Reject,
+    /// Replace the value with `None`, as if the field was not present.
+    ///
+    /// Fields that are not optional, such as a forecast's latitude and
+    /// longitude, are still rejected.
+    Sanitize,
+}
+
+fn check(value: &mut Option<f64>, name: &'static str, policy: NonFinite) -> Result<()> {
+    match *value {
+        Some(v) if !v.is_finite() => match policy {
+            NonFinite::Reject => Err(Error::NonFinite(name)),
+            NonFinite::Sanitize => {
+                *value = None;
+
+                Ok(())
+            }
+        },
+        _ => Ok(()),
+    }
+}
+
+fn check_required(value: f64, name: &'static str) -> Result<()> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(Error::NonFinite(name))
+    }
+}
+
+impl DailyFields {
+    fn check_finite(&mut self, policy: NonFinite) -> Result<()> {
+        check(
+            &mut self.apparent_temperature_max,
+            "apparent_temperature_max",
+            policy,
+        )?;
+        check(
+            &mut self.apparent_temperature_min,
+            "apparent_temperature_min",
+            policy,
+        )?;
+        check(&mut self.moon_phase, "moon_phase", policy)?;
+        check(
+            &mut self.precip_intensity_max,
+            "precip_intensity_max",
+            policy,
+        )?;
+        check(&mut self.temperature_low, "temperature_low", policy)?;
+        check(&mut self.temperature_high, "temperature_high", policy)?;
+        check(&mut self.temperature_max, "temperature_max", policy)?;
+        check(&mut self.temperature_min, "temperature_min", policy)?;
+
+        Ok(())
+    }
+}
+
+impl ErrorFields {
+    fn check_finite(&mut self, policy: NonFinite) -> Result<()> {
+        check(&mut self.cloud_cover_error, "cloud_cover_error", policy)?;
+        check(&mut self.dew_point_error, "dew_point_error", policy)?;
+        check(&mut self.humidity_error, "humidity_error", policy)?;
+        check(&mut self.ozone_error, "ozone_error", policy)?;
+        check(
+            &mut self.precip_accumulation_error,
+            "precip_accumulation_error",
+            policy,
+        )?;
+        check(
+            &mut self.precip_intensity_error,
+            "precip_intensity_error",
+            policy,
+        )?;
+        check(
+            &mut self.precip_intensity_max_error,
+            "precip_intensity_max_error",
+            policy,
+        )?;
+        check(
+            &mut self.precip_probability_error,
+            "precip_probability_error",
+            policy,
+        )?;
+        check(&mut self.pressure_error, "pressure_error", policy)?;
+        check(
+            &mut self.temperature_max_error,
+            "temperature_max_error",
+            policy,
+        )?;
+        check(
+            &mut self.temperature_min_error,
+            "temperature_min_error",
+            policy,
+        )?;
+        check(&mut self.temperature_error, "temperature_error", policy)?;
+        check(&mut self.visibility_error, "visibility_error", policy)?;
+        check(&mut self.wind_bearing_error, "wind_bearing_error", policy)?;
+        check(&mut self.wind_speed_error, "wind_speed_error", policy)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "solar")]
+impl Solar {
+    fn check_finite(&mut self, policy: NonFinite) -> Result<()> {
+        check(&mut self.altitude, "altitude", policy)?;
+        check(&mut self.azimuth, "azimuth", policy)?;
+        check(&mut self.dhi, "dhi", policy)?;
+        check(&mut self.dni, "dni", policy)?;
+        check(&mut self.etr, "etr", policy)?;
+        check(&mut self.ghi, "ghi", policy)?;
+
+        Ok(())
+    }
+}
+
+impl Datablock {
+    /// Checks each of the block's datapoints for non-finite values, handling
+    /// them according to the given policy.
+    ///
+    /// See [`Forecast::check_finite`] for more information.
+    ///
+    /// [`Forecast::check_finite`]: struct.Forecast.html#method.check_finite
+    pub fn check_finite(&mut self, policy: NonFinite) -> Result<()> {
+        for datapoint in self.data.iter_mut().flat_map(|data| data.iter_mut()) {
+            datapoint.check_finite(policy)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Datapoint {
+    /// Checks the datapoint's numeric fields for non-finite values, handling
+    /// them according to the given policy.
+    ///
+    /// See [`Forecast::check_finite`] for more information.
+    ///
+    /// [`Forecast::check_finite`]: struct.Forecast.html#method.check_finite
+    pub fn check_finite(&mut self, policy: NonFinite) -> Result<()> {
+        check(
+            &mut self.apparent_temperature,
+            "apparent_temperature",
+            policy,
+        )?;
+        check(&mut self.cloud_cover, "cloud_cover", policy)?;
+        check(&mut self.dew_point, "dew_point", policy)?;
+        check(&mut self.humidity, "humidity", policy)?;
+        check(
+            &mut self.nearest_storm_bearing,
+            "nearest_storm_bearing",
+            policy,
+        )?;
+        check(
+            &mut self.nearest_storm_distance,
+            "nearest_storm_distance",
+            policy,
+        )?;
+        check(&mut self.ozone, "ozone", policy)?;
+        check(&mut self.precip_accumulation, "precip_accumulation", policy)?;
+        check(&mut self.precip_intensity, "precip_intensity", policy)?;
+        check(&mut self.precip_probability, "precip_probability", policy)?;
+        check(&mut self.pressure, "pressure", policy)?;
+        check(&mut self.temperature, "temperature", policy)?;
+        check(&mut self.visibility, "visibility", policy)?;
+        check(&mut self.wind_bearing, "wind_bearing", policy)?;
+        check(&mut self.wind_gust, "wind_gust", policy)?;
+        check(&mut self.wind_speed, "wind_speed", policy)?;
+
+        if let Some(ref mut daily) = self.daily {
+            daily.check_finite(policy)?;
+        }
+
+        if let Some(ref mut errors) = self.errors {
+            errors.check_finite(policy)?;
+        }
+
+        #[cfg(feature = "solar")]
+        {
+            if let Some(ref mut solar) = self.solar {
+                solar.check_finite(policy)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Forecast {
+    /// Checks every numeric field of the forecast for non-finite values, such
+    /// as `NaN` or infinity, handling them according to the given policy.
+    ///
+    /// This is opt-in: deserialization itself accepts whatever the format
+    /// produces. With [`NonFinite::Reject`], the first offending field is
+    /// returned in the error, and the forecast may have been partially
+    /// checked.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::models::{Forecast, NonFinite};
+    /// use darksky::Error;
+    /// use std::f64;
+    ///
+    /// # fn main() {
+    /// let mut forecast: Forecast = serde_json::from_str(r#"{
+    ///     "latitude": 49.9,
+    ///     "longitude": -97.1,
+    ///     "timezone": "America/Winnipeg",
+    ///     "hourly": {"data": [{"time": 1509993277, "temperature": -3.5}]}
+    /// }"#).unwrap();
+    /// forecast.hourly.as_mut().unwrap().data.as_mut().unwrap()[0].humidity = Some(f64::NAN);
+    ///
+    /// match forecast.clone().check_finite(NonFinite::Reject) {
+    ///     Err(Error::NonFinite(field)) => assert_eq!(field, "humidity"),
+    ///     other => panic!("expected a non-finite error: {:?}", other),
+    /// }
+    ///
+    /// forecast.check_finite(NonFinite::Sanitize).unwrap();
+    /// let hour = &forecast.hourly.unwrap().data.unwrap()[0];
+    /// assert_eq!(hour.humidity, None);
+    /// assert_eq!(hour.temperature, Some(-3.5));
+    /// # }
+    /// ```
+    ///
+    /// [`NonFinite::Reject`]: enum.NonFinite.html#variant.Reject
+    pub fn check_finite(&mut self, policy: NonFinite) -> Result<()> {
+        check_required(self.latitude, "latitude")?;
+        check_required(self.longitude, "longitude")?;
+        check(&mut self.offset, "offset", policy)?;
+
+        if let Some(ref mut currently) = self.currently {
+            currently.check_finite(policy)?;
+        }
+
+        for block in [&mut self.daily, &mut self.hourly, &mut self.minutely].iter_mut() {
+            if let Some(ref mut block) = **block {
+                block.check_finite(policy)?;
+            }
+        }
+
+        Ok(())
+    }
+}
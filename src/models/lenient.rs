@@ -0,0 +1,133 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Best-effort parsing of forecasts, where malformed blocks are dropped
+//! rather than failing the whole forecast.
+
+use super::{Alert, Datablock, Datapoint, Flags, Forecast};
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::de::DeserializeOwned;
+use serde_json::{self, Map, Value};
+use {Error, Result};
+
+/// An error parsing a single block of a forecast, which was dropped by
+/// [`Forecast::from_slice_lenient`].
+///
+/// [`Forecast::from_slice_lenient`]: struct.Forecast.html#method.from_slice_lenient
+#[derive(Debug)]
+pub struct BlockError {
+    /// The name of the block, such as `minutely` or `alerts`.
+    pub block: &'static str,
+    /// Why the block could not be parsed.
+    pub error: Error,
+}
+
+/// A forecast parsed by [`Forecast::from_slice_lenient`], along with the
+/// errors of any blocks that were dropped.
+///
+/// [`Forecast::from_slice_lenient`]: struct.Forecast.html#method.from_slice_lenient
+#[derive(Debug)]
+pub struct PartialForecast {
+    /// The forecast, without any malformed blocks.
+    pub forecast: Forecast,
+    /// The errors of the blocks that were dropped, in the order of
+    /// [`BLOCK_NAMES`].
+    ///
+    /// [`BLOCK_NAMES`]: ../constants/constant.BLOCK_NAMES.html
+    pub errors: Vec<BlockError>,
+}
+
+impl PartialForecast {
+    /// Returns whether every block was parsed.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Removes a block from the response if it fails to parse as `T`, recording
+/// the error.
+fn check<T>(map: &mut Map<String, Value>, block: &'static str, errors: &mut Vec<BlockError>)
+where
+    T: DeserializeOwned,
+{
+    let result = match map.get(block) {
+        Some(value) => T::deserialize(value).map(|_| ()),
+        None => return,
+    };
+
+    if let Err(why) = result {
+        map.remove(block);
+        errors.push(BlockError {
+            block,
+            error: Error::Json(why),
+        });
+    }
+}
+
+impl Forecast {
+    /// Deserializes a forecast in "best effort" mode, where a malformed block,
+    /// such as one with a bad datapoint in `minutely`, is dropped instead of
+    /// failing the whole forecast.
+    ///
+    /// Dropped blocks are `None`, or empty in the case of `alerts`, and their
+    /// errors are returned alongside the forecast.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Json`] if the body is not a JSON object, or if the
+    /// fields outside of the blocks, such as the latitude, are malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use darksky::models::Forecast;
+    ///
+    /// let body = br#"{
+    ///     "latitude": 49.9,
+    ///     "longitude": -97.1,
+    ///     "timezone": "America/Winnipeg",
+    ///     "currently": {"time": 1509993277, "temperature": -3.5},
+    ///     "minutely": {"data": [{"time": "soon"}]}
+    /// }"#;
+    ///
+    /// let partial = Forecast::from_slice_lenient(body).unwrap();
+    ///
+    /// assert!(partial.forecast.currently.is_some());
+    /// assert!(partial.forecast.minutely.is_none());
+    /// assert_eq!(partial.errors.len(), 1);
+    /// assert_eq!(partial.errors[0].block, "minutely");
+    /// ```
+    ///
+    /// [`Error::Json`]: ../enum.Error.html#variant.Json
+    pub fn from_slice_lenient(bytes: &[u8]) -> Result<PartialForecast> {
+        let mut map = match serde_json::from_slice(bytes)? {
+            Value::Object(map) => map,
+            other => return Err(Error::Decode("expected a forecast object", other)),
+        };
+        let mut errors = Vec::new();
+
+        check::<Vec<Alert>>(&mut map, "alerts", &mut errors);
+        check::<Datapoint>(&mut map, "currently", &mut errors);
+        check::<Datablock>(&mut map, "daily", &mut errors);
+        check::<Flags>(&mut map, "flags", &mut errors);
+        check::<Datablock>(&mut map, "hourly", &mut errors);
+        check::<Datablock>(&mut map, "minutely", &mut errors);
+
+        let forecast = serde_json::from_value(Value::Object(map))?;
+
+        Ok(PartialForecast { forecast, errors })
+    }
+}
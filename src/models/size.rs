@@ -0,0 +1,200 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Approximation of the memory used by the models, for size-based cache
+//! eviction.
+
+#[cfg(feature = "solar")]
+use super::Solar;
+use super::{Alert, DailyFields, Datablock, Datapoint, ErrorFields, Flags, Forecast, SummaryText};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem;
+
+/// The number of bytes a value owns on the heap, excluding its inline size.
+trait HeapSize {
+    fn heap_size(&self) -> usize;
+}
+
+impl HeapSize for String {
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl HeapSize for SummaryText {
+    fn heap_size(&self) -> usize {
+        SummaryText::heap_size(self)
+    }
+}
+
+impl<T: HeapSize> HeapSize for Box<T> {
+    fn heap_size(&self) -> usize {
+        mem::size_of::<T>() + (**self).heap_size()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Option<T> {
+    fn heap_size(&self) -> usize {
+        self.as_ref().map_or(0, HeapSize::heap_size)
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    fn heap_size(&self) -> usize {
+        self.capacity() * mem::size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+impl HeapSize for Alert {
+    fn heap_size(&self) -> usize {
+        self.description.heap_size()
+            + self.regions.heap_size()
+            + self.title.heap_size()
+            + self.uri.heap_size()
+    }
+}
+
+impl HeapSize for DailyFields {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl HeapSize for Datablock {
+    fn heap_size(&self) -> usize {
+        self.data.heap_size() + self.summary.heap_size()
+    }
+}
+
+impl HeapSize for Datapoint {
+    fn heap_size(&self) -> usize {
+        let size = self.daily.heap_size() + self.errors.heap_size() + self.summary.heap_size();
+
+        #[cfg(feature = "solar")]
+        let size = size + self.solar.heap_size();
+
+        size
+    }
+}
+
+#[cfg(feature = "solar")]
+impl HeapSize for Solar {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl HeapSize for ErrorFields {
+    fn heap_size(&self) -> usize {
+        0
+    }
+}
+
+impl HeapSize for Flags {
+    fn heap_size(&self) -> usize {
+        self.darksky_stations.heap_size()
+            + self.darksky_unavailable.heap_size()
+            + self.datapoint_stations.heap_size()
+            + self.isd_stations.heap_size()
+            + self.lamp_stations.heap_size()
+            + self.metar_stations.heap_size()
+            + self.metno_license.heap_size()
+            + self.sources.heap_size()
+            + self.units.heap_size()
+    }
+}
+
+impl HeapSize for Forecast {
+    fn heap_size(&self) -> usize {
+        self.alerts.heap_size()
+            + self.currently.heap_size()
+            + self.daily.heap_size()
+            + self.flags.heap_size()
+            + self.hourly.heap_size()
+            + self.minutely.heap_size()
+            + self.timezone.heap_size()
+    }
+}
+
+impl Alert {
+    /// Returns the approximate number of bytes used by the alert, including
+    /// the strings and vectors that it owns.
+    pub fn approx_size_bytes(&self) -> usize {
+        mem::size_of::<Self>() + self.heap_size()
+    }
+}
+
+impl Datablock {
+    /// Returns the approximate number of bytes used by the datablock, including
+    /// the strings and vectors that it owns.
+    pub fn approx_size_bytes(&self) -> usize {
+        mem::size_of::<Self>() + self.heap_size()
+    }
+}
+
+impl Datapoint {
+    /// Returns the approximate number of bytes used by the datapoint, including
+    /// the strings and vectors that it owns.
+    pub fn approx_size_bytes(&self) -> usize {
+        mem::size_of::<Self>() + self.heap_size()
+    }
+}
+
+impl Flags {
+    /// Returns the approximate number of bytes used by the flags, including
+    /// the strings and vectors that it owns.
+    pub fn approx_size_bytes(&self) -> usize {
+        mem::size_of::<Self>() + self.heap_size()
+    }
+}
+
+impl Forecast {
+    /// Returns the approximate number of bytes used by the forecast, including
+    /// the strings and vectors that it owns.
+    ///
+    /// This accounts for the capacity of each allocation rather than its
+    /// length, but not for allocator overhead, so it is suited to size-based
+    /// cache eviction rather than precise accounting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// extern crate darksky;
+    /// extern crate serde_json;
+    ///
+    /// use darksky::models::Forecast;
+    ///
+    /// # fn main() {
+    /// let empty: Forecast = serde_json::from_str(r#"{
+    ///     "latitude": 49.9,
+    ///     "longitude": -97.1,
+    ///     "timezone": "America/Winnipeg"
+    /// }"#).unwrap();
+    /// let hourly: Forecast = serde_json::from_str(r#"{
+    ///     "latitude": 49.9,
+    ///     "longitude": -97.1,
+    ///     "timezone": "America/Winnipeg",
+    ///     "hourly": {"data": [{"time": 1509993277}, {"time": 1509996877}]}
+    /// }"#).unwrap();
+    ///
+    /// assert!(hourly.approx_size_bytes() > empty.approx_size_bytes());
+    /// # }
+    /// ```
+    pub fn approx_size_bytes(&self) -> usize {
+        mem::size_of::<Self>() + self.heap_size()
+    }
+}
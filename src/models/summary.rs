@@ -0,0 +1,230 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! The text of `summary` fields, which shares static storage for commonly
+//! repeated values when the `cow-summaries` feature is enabled.
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::borrow::Borrow;
+use core::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use core::ops::Deref;
+use core::result::Result as StdResult;
+use serde::de::{Deserialize, Deserializer, Error as DeError, Visitor};
+use serde::{Serialize, Serializer};
+
+/// The text of a `summary` field, such as `"Partly Cloudy"`.
+///
+/// This dereferences to `str`. When the `cow-summaries` feature is enabled,
+/// commonly repeated summaries are borrowed from static storage rather than
+/// allocated for every datapoint; the type is the same either way.
+#[derive(Clone, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SummaryText(Storage);
+
+#[cfg(not(feature = "cow-summaries"))]
+type Storage = String;
+
+#[cfg(feature = "cow-summaries")]
+type Storage = Cow<'static, str>;
+
+/// Summaries that are commonly repeated across datapoints, sorted so that
+/// they can be binary searched.
+#[cfg(feature = "cow-summaries")]
+const COMMON: &[&str] = &[
+    "Breezy",
+    "Breezy and Mostly Cloudy",
+    "Breezy and Overcast",
+    "Breezy and Partly Cloudy",
+    "Clear",
+    "Clear throughout the day.",
+    "Dangerously Windy",
+    "Drizzle",
+    "Dry",
+    "Flurries",
+    "Foggy",
+    "Foggy in the morning.",
+    "Heavy Rain",
+    "Heavy Sleet",
+    "Heavy Snow",
+    "Humid",
+    "Humid and Mostly Cloudy",
+    "Humid and Overcast",
+    "Humid and Partly Cloudy",
+    "Light Rain",
+    "Light Rain and Breezy",
+    "Light Sleet",
+    "Light Snow",
+    "Light rain throughout the day.",
+    "Mostly Cloudy",
+    "Mostly cloudy throughout the day.",
+    "Overcast",
+    "Overcast throughout the day.",
+    "Partly Cloudy",
+    "Partly cloudy throughout the day.",
+    "Possible Drizzle",
+    "Possible Flurries",
+    "Possible Light Rain",
+    "Possible Light Sleet",
+    "Possible Light Snow",
+    "Possible Thunderstorm",
+    "Rain",
+    "Rain and Windy",
+    "Rain throughout the day.",
+    "Sleet",
+    "Snow",
+    "Thunderstorm",
+    "Windy",
+    "Windy and Mostly Cloudy",
+    "Windy and Overcast",
+    "Windy and Partly Cloudy",
+];
+
+impl SummaryText {
+    /// Converts a summary into its stored representation.
+    #[cfg(not(feature = "cow-summaries"))]
+    pub(crate) fn new(value: Cow<str>) -> Self {
+        SummaryText(value.into_owned())
+    }
+
+    /// Converts a summary into its stored representation, borrowing common
+    /// summaries from static storage rather than allocating them.
+    #[cfg(feature = "cow-summaries")]
+    pub(crate) fn new(value: Cow<str>) -> Self {
+        SummaryText(match COMMON.binary_search(&&*value) {
+            Ok(index) => Cow::Borrowed(COMMON[index]),
+            Err(_) => Cow::Owned(value.into_owned()),
+        })
+    }
+
+    /// Returns the summary as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The number of bytes the summary owns on the heap.
+    #[cfg(not(feature = "cow-summaries"))]
+    pub(crate) fn heap_size(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// The number of bytes the summary owns on the heap, which is nothing for
+    /// summaries borrowed from static storage.
+    #[cfg(feature = "cow-summaries")]
+    pub(crate) fn heap_size(&self) -> usize {
+        match self.0 {
+            Cow::Borrowed(_) => 0,
+            Cow::Owned(ref value) => value.capacity(),
+        }
+    }
+}
+
+impl AsRef<str> for SummaryText {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl Borrow<str> for SummaryText {
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl Debug for SummaryText {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Deref for SummaryText {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for SummaryText {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(self)
+    }
+}
+
+impl<'a> From<&'a str> for SummaryText {
+    fn from(value: &'a str) -> Self {
+        SummaryText::new(Cow::Borrowed(value))
+    }
+}
+
+impl From<String> for SummaryText {
+    fn from(value: String) -> Self {
+        SummaryText::new(Cow::Owned(value))
+    }
+}
+
+impl From<SummaryText> for String {
+    #[cfg_attr(not(feature = "cow-summaries"), allow(clippy::useless_conversion))]
+    fn from(value: SummaryText) -> Self {
+        value.0.into()
+    }
+}
+
+impl PartialEq<str> for SummaryText {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for SummaryText {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for SummaryText {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl Serialize for SummaryText {
+    fn serialize<S: Serializer>(&self, serializer: S) -> StdResult<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SummaryText {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> StdResult<Self, D::Error> {
+        struct SummaryVisitor;
+
+        impl<'de> Visitor<'de> for SummaryVisitor {
+            type Value = SummaryText;
+
+            fn expecting(&self, f: &mut Formatter) -> FmtResult {
+                f.write_str("a summary string")
+            }
+
+            fn visit_str<E: DeError>(self, value: &str) -> StdResult<Self::Value, E> {
+                Ok(SummaryText::from(value))
+            }
+
+            fn visit_string<E: DeError>(self, value: String) -> StdResult<Self::Value, E> {
+                Ok(SummaryText::from(value))
+            }
+        }
+
+        deserializer.deserialize_str(SummaryVisitor)
+    }
+}
@@ -0,0 +1,506 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Support for publishing forecast data to MQTT topics via the `rumqttc`
+//! crate.
+//!
+//! Each selected field of the [`currently`] datapoint is published to its own
+//! topic as a small JSON payload of the form `{"time":...,"value":...}`, and
+//! any [`Alert`]s are published as a JSON array to the `alerts` topic.
+//!
+//! Topics are built from a template, where `{location}` is replaced with the
+//! name given for the location and `{field}` is replaced with the name of the
+//! field being published. The default template is
+//! `darksky/{location}/{field}`. As the location name is a single level of
+//! the topic, any `/`, `+`, or `#` in it is replaced with `_`, rather than
+//! adding a level or a wildcard.
+//!
+//! Forecasts can be published as they are retrieved, or polled for and
+//! published on an interval with [`publish_polled`].
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`DarkskyMqttPublisher`].
+//!
+//! [`Alert`]: ../models/struct.Alert.html
+//! [`DarkskyMqttPublisher`]: trait.DarkskyMqttPublisher.html
+//! [`currently`]: ../models/struct.Forecast.html#structfield.currently
+//! [`publish_polled`]: trait.DarkskyMqttPublisher.html#method.publish_polled
+
+use models::{Datapoint, Forecast};
+use rumqttc::{Client, QoS};
+use serde_json::{self, Value};
+use std::thread;
+use std::time::Duration;
+use Result;
+
+/// A field of a [`Datapoint`] that can be published.
+///
+/// [`Datapoint`]: ../models/struct.Datapoint.html
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub enum Field {
+    /// The [`apparent_temperature`] field.
+    ///
+    /// [`apparent_temperature`]: ../models/struct.Datapoint.html#structfield.apparent_temperature
+    ApparentTemperature,
+    /// The [`cloud_cover`] field.
+    ///
+    /// [`cloud_cover`]: ../models/struct.Datapoint.html#structfield.cloud_cover
+    CloudCover,
+    /// The [`dew_point`] field.
+    ///
+    /// [`dew_point`]: ../models/struct.Datapoint.html#structfield.dew_point
+    DewPoint,
+    /// The [`humidity`] field.
+    ///
+    /// [`humidity`]: ../models/struct.Datapoint.html#structfield.humidity
+    Humidity,
+    /// The [`icon`] field.
+    ///
+    /// [`icon`]: ../models/struct.Datapoint.html#structfield.icon
+    Icon,
+    /// The [`nearest_storm_distance`] field.
+    ///
+    /// [`nearest_storm_distance`]: ../models/struct.Datapoint.html#structfield.nearest_storm_distance
+    NearestStormDistance,
+    /// The [`ozone`] field.
+    ///
+    /// [`ozone`]: ../models/struct.Datapoint.html#structfield.ozone
+    Ozone,
+    /// The [`precip_intensity`] field.
+    ///
+    /// [`precip_intensity`]: ../models/struct.Datapoint.html#structfield.precip_intensity
+    PrecipIntensity,
+    /// The [`precip_probability`] field.
+    ///
+    /// [`precip_probability`]: ../models/struct.Datapoint.html#structfield.precip_probability
+    PrecipProbability,
+    /// The [`pressure`] field.
+    ///
+    /// [`pressure`]: ../models/struct.Datapoint.html#structfield.pressure
+    Pressure,
+    /// The [`summary`] field.
+    ///
+    /// [`summary`]: ../models/struct.Datapoint.html#structfield.summary
+    Summary,
+    /// The [`temperature`] field.
+    ///
+    /// [`temperature`]: ../models/struct.Datapoint.html#structfield.temperature
+    Temperature,
+    /// The [`uv_index`] field.
+    ///
+    /// [`uv_index`]: ../models/struct.Datapoint.html#structfield.uv_index
+    UvIndex,
+    /// The [`visibility`] field.
+    ///
+    /// [`visibility`]: ../models/struct.Datapoint.html#structfield.visibility
+    Visibility,
+    /// The [`wind_bearing`] field.
+    ///
+    /// [`wind_bearing`]: ../models/struct.Datapoint.html#structfield.wind_bearing
+    WindBearing,
+    /// The [`wind_gust`] field.
+    ///
+    /// [`wind_gust`]: ../models/struct.Datapoint.html#structfield.wind_gust
+    WindGust,
+    /// The [`wind_speed`] field.
+    ///
+    /// [`wind_speed`]: ../models/struct.Datapoint.html#structfield.wind_speed
+    WindSpeed,
+}
+
+impl Field {
+    fn name(&self) -> &str {
+        use self::Field::*;
+
+        match *self {
+            ApparentTemperature => "apparent_temperature",
+            CloudCover => "cloud_cover",
+            DewPoint => "dew_point",
+            Humidity => "humidity",
+            Icon => "icon",
+            NearestStormDistance => "nearest_storm_distance",
+            Ozone => "ozone",
+            PrecipIntensity => "precip_intensity",
+            PrecipProbability => "precip_probability",
+            Pressure => "pressure",
+            Summary => "summary",
+            Temperature => "temperature",
+            UvIndex => "uv_index",
+            Visibility => "visibility",
+            WindBearing => "wind_bearing",
+            WindGust => "wind_gust",
+            WindSpeed => "wind_speed",
+        }
+    }
+
+    fn value(&self, datapoint: &Datapoint) -> Result<Value> {
+        use self::Field::*;
+
+        let value = match *self {
+            ApparentTemperature => serde_json::to_value(datapoint.apparent_temperature)?,
+            CloudCover => serde_json::to_value(datapoint.cloud_cover)?,
+            DewPoint => serde_json::to_value(datapoint.dew_point)?,
+            Humidity => serde_json::to_value(datapoint.humidity)?,
+            Icon => serde_json::to_value(datapoint.icon)?,
+            NearestStormDistance => serde_json::to_value(datapoint.nearest_storm_distance)?,
+            Ozone => serde_json::to_value(datapoint.ozone)?,
+            PrecipIntensity => serde_json::to_value(datapoint.precip_intensity)?,
+            PrecipProbability => serde_json::to_value(datapoint.precip_probability)?,
+            Pressure => serde_json::to_value(datapoint.pressure)?,
+            Summary => serde_json::to_value(&datapoint.summary)?,
+            Temperature => serde_json::to_value(datapoint.temperature)?,
+            UvIndex => serde_json::to_value(datapoint.uv_index)?,
+            Visibility => serde_json::to_value(datapoint.visibility)?,
+            WindBearing => serde_json::to_value(datapoint.wind_bearing)?,
+            WindGust => serde_json::to_value(datapoint.wind_gust)?,
+            WindSpeed => serde_json::to_value(datapoint.wind_speed)?,
+        };
+
+        Ok(value)
+    }
+}
+
+/// Build the options for publishing a forecast, including the topic template,
+/// the [`Field`]s to publish, whether to publish alerts, and the QoS and
+/// retain flag of the published messages.
+///
+/// By default, the [temperature][`Field::Temperature`],
+/// [humidity][`Field::Humidity`], [pressure][`Field::Pressure`],
+/// [precipitation probability][`Field::PrecipProbability`], and
+/// [summary][`Field::Summary`] are published along with alerts, using a QoS of
+/// `AtLeastOnce` with the retain flag set.
+///
+/// [`Field`]: enum.Field.html
+/// [`Field::Humidity`]: enum.Field.html#variant.Humidity
+/// [`Field::PrecipProbability`]: enum.Field.html#variant.PrecipProbability
+/// [`Field::Pressure`]: enum.Field.html#variant.Pressure
+/// [`Field::Summary`]: enum.Field.html#variant.Summary
+/// [`Field::Temperature`]: enum.Field.html#variant.Temperature
+#[derive(Clone, Debug)]
+pub struct PublishOptions {
+    alerts: bool,
+    fields: Vec<Field>,
+    qos: QoS,
+    retain: bool,
+    topic: String,
+}
+
+impl Default for PublishOptions {
+    fn default() -> Self {
+        PublishOptions {
+            alerts: true,
+            fields: vec![
+                Field::Temperature,
+                Field::Humidity,
+                Field::Pressure,
+                Field::PrecipProbability,
+                Field::Summary,
+            ],
+            qos: QoS::AtLeastOnce,
+            retain: true,
+            topic: "darksky/{location}/{field}".to_owned(),
+        }
+    }
+}
+
+impl PublishOptions {
+    /// Sets whether the forecast's alerts are published to the `alerts` topic.
+    pub fn alerts(mut self, alerts: bool) -> Self {
+        self.alerts = alerts;
+
+        self
+    }
+
+    /// Sets the list of [`Field`]s to publish, replacing the defaults.
+    ///
+    /// [`Field`]: enum.Field.html
+    pub fn fields(mut self, fields: Vec<Field>) -> Self {
+        self.fields = fields;
+
+        self
+    }
+
+    /// Sets the QoS of published messages.
+    pub fn qos(mut self, qos: QoS) -> Self {
+        self.qos = qos;
+
+        self
+    }
+
+    /// Sets whether the broker should retain published messages.
+    pub fn retain(mut self, retain: bool) -> Self {
+        self.retain = retain;
+
+        self
+    }
+
+    /// Sets the topic template. `{location}` and `{field}` are replaced with
+    /// the location name and field name, respectively, with any `/`, `+`, or
+    /// `#` in the location name replaced with `_`.
+    pub fn topic<S: Into<String>>(mut self, topic: S) -> Self {
+        self.topic = topic.into();
+
+        self
+    }
+}
+
+/// A topic and JSON payload ready to be published.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Message {
+    /// The topic that the message is published to.
+    pub topic: String,
+    /// The JSON-encoded payload of the message.
+    pub payload: Vec<u8>,
+}
+
+#[derive(Serialize)]
+struct Payload {
+    time: u64,
+    value: Value,
+}
+
+/// Builds the list of messages that would be published for a forecast.
+///
+/// Fields that are not present on the forecast's [`currently`] datapoint are
+/// skipped, as are alerts if there are none.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate serde_json;
+///
+/// use darksky::models::Forecast;
+/// use darksky::mqtt::{self, Field, PublishOptions};
+///
+/// # fn main() {
+/// let forecast: Forecast = serde_json::from_str(r#"{
+///     "latitude": 49.9,
+///     "longitude": -97.1,
+///     "timezone": "America/Winnipeg",
+///     "currently": {"time": 1509993277, "temperature": -3.5}
+/// }"#).unwrap();
+///
+/// let options = PublishOptions::default()
+///     .topic("home/weather/{location}/{field}")
+///     .fields(vec![Field::Temperature, Field::Humidity]);
+/// let messages = mqtt::messages("winnipeg", &forecast, &options).unwrap();
+///
+/// assert_eq!(messages.len(), 1);
+/// assert_eq!(messages[0].topic, "home/weather/winnipeg/temperature");
+/// assert_eq!(messages[0].payload, br#"{"time":1509993277,"value":-3.5}"#.to_vec());
+/// # }
+/// ```
+///
+/// [`currently`]: ../models/struct.Forecast.html#structfield.currently
+pub fn messages(
+    location: &str,
+    forecast: &Forecast,
+    options: &PublishOptions,
+) -> Result<Vec<Message>> {
+    let mut messages = Vec::new();
+
+    if let Some(ref currently) = forecast.currently {
+        for field in &options.fields {
+            let value = field.value(currently)?;
+
+            if value.is_null() {
+                continue;
+            }
+
+            let payload = Payload {
+                time: currently.time,
+                value,
+            };
+
+            messages.push(Message {
+                topic: topic(&options.topic, location, field.name()),
+                payload: serde_json::to_vec(&payload)?,
+            });
+        }
+    }
+
+    if options.alerts && !forecast.alerts.is_empty() {
+        messages.push(Message {
+            topic: topic(&options.topic, location, "alerts"),
+            payload: serde_json::to_vec(&forecast.alerts)?,
+        });
+    }
+
+    Ok(messages)
+}
+
+/// Builds a topic from its template, replacing the placeholders in a single
+/// pass, so that a location name containing one isn't replaced in turn.
+fn topic(template: &str, location: &str, field: &str) -> String {
+    let mut topic = String::with_capacity(template.len() + location.len() + field.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        topic.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("{location}") {
+            topic.extend(location.chars().map(|c| match c {
+                '/' | '+' | '#' => '_',
+                c => c,
+            }));
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("{field}") {
+            topic.push_str(field);
+            rest = after;
+        } else {
+            topic.push('{');
+            rest = &rest[1..];
+        }
+    }
+
+    topic.push_str(rest);
+
+    topic
+}
+
+/// The trait for `rumqttc` implementations publishing forecasts to a broker.
+pub trait DarkskyMqttPublisher {
+    /// Publishes the selected fields and alerts of a [forecast][`Forecast`]
+    /// for the named location. For a full list of options, refer to the
+    /// documentation for the [`PublishOptions`] builder.
+    ///
+    /// This is meant to be called each time a forecast is retrieved, or
+    /// refer to [`publish_polled`] to poll for forecasts and publish them.
+    ///
+    /// # Examples
+    ///
+    /// Publish the temperature and any alerts of a forecast to a local
+    /// broker:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate rumqttc;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// use darksky::models::Forecast;
+    /// use darksky::mqtt::{DarkskyMqttPublisher, Field};
+    /// use rumqttc::{Client, MqttOptions};
+    ///
+    /// # fn get_forecast() -> Forecast { unimplemented!() }
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// let options = MqttOptions::new("darksky", "localhost", 1883);
+    /// let (client, mut connection) = Client::new(options, 10);
+    ///
+    /// let forecast = get_forecast();
+    /// client.publish_forecast("alcatraz", &forecast, |o| o
+    ///     .fields(vec![Field::Temperature]))?;
+    ///
+    /// // Drive the connection so that the messages are actually sent.
+    /// for notification in connection.iter().take(3) {
+    ///     println!("{:?}", notification);
+    /// }
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`Forecast`]: ../models/struct.Forecast.html
+    /// [`PublishOptions`]: struct.PublishOptions.html
+    /// [`publish_polled`]: #method.publish_polled
+    fn publish_forecast<F>(&self, location: &str, forecast: &Forecast, options: F) -> Result<()>
+    where
+        F: FnOnce(PublishOptions) -> PublishOptions;
+
+    /// Polls for a forecast every `interval` with `fetch`, publishing each one
+    /// for the named location as with [`publish_forecast`].
+    ///
+    /// This blocks the current thread, and only returns once fetching or
+    /// publishing a forecast fails, with the error. The client's connection
+    /// must be driven on another thread for the messages to be sent.
+    ///
+    /// # Examples
+    ///
+    /// Publish the forecast for a location every 10 minutes:
+    ///
+    /// ```rust,no_run
+    /// extern crate darksky;
+    /// extern crate rumqttc;
+    ///
+    /// # use std::error::Error;
+    /// #
+    /// use darksky::models::Forecast;
+    /// use darksky::mqtt::DarkskyMqttPublisher;
+    /// use rumqttc::{Client, MqttOptions};
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// # fn get_forecast() -> darksky::Result<Forecast> { unimplemented!() }
+    /// #
+    /// # fn try_main() -> Result<(), Box<dyn Error>> {
+    /// let options = MqttOptions::new("darksky", "localhost", 1883);
+    /// let (client, mut connection) = Client::new(options, 10);
+    ///
+    /// thread::spawn(move || for _ in connection.iter() {});
+    ///
+    /// let interval = Duration::from_secs(10 * 60);
+    /// client.publish_polled("alcatraz", interval, get_forecast, |o| o)?;
+    /// #     Ok(())
+    /// # }
+    /// #
+    /// # fn main() {
+    /// #     try_main().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// [`publish_forecast`]: #tymethod.publish_forecast
+    fn publish_polled<F, O>(
+        &self,
+        location: &str,
+        interval: Duration,
+        mut fetch: F,
+        options: O,
+    ) -> Result<()>
+    where
+        F: FnMut() -> Result<Forecast>,
+        O: FnOnce(PublishOptions) -> PublishOptions,
+    {
+        let options = options(PublishOptions::default());
+
+        loop {
+            let forecast = fetch()?;
+            self.publish_forecast(location, &forecast, |_| options.clone())?;
+
+            thread::sleep(interval);
+        }
+    }
+}
+
+impl DarkskyMqttPublisher for Client {
+    fn publish_forecast<F>(&self, location: &str, forecast: &Forecast, options: F) -> Result<()>
+    where
+        F: FnOnce(PublishOptions) -> PublishOptions,
+    {
+        let options = options(PublishOptions::default());
+
+        for message in messages(location, forecast, &options)? {
+            self.publish(message.topic, options.qos, options.retain, message.payload)?;
+        }
+
+        Ok(())
+    }
+}
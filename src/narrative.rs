@@ -0,0 +1,197 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Generation of short, human-readable narratives from a [`Forecast`].
+//!
+//! Narratives are generated locally from the forecast's datapoints, rather
+//! than relying on the API's [`summary`] fields, and are always in English.
+//!
+//! [`Forecast`]: ../models/struct.Forecast.html
+//! [`summary`]: ../models/struct.Datapoint.html#structfield.summary
+
+use models::{Datapoint, Forecast, PrecipitationType};
+
+/// The minimum probability of precipitation for an hour to be considered wet.
+const PRECIP_PROBABILITY: f64 = 0.5;
+
+/// The number of hourly datapoints that are considered.
+const HOURS: usize = 24;
+
+/// Condenses a forecast into a short paragraph, such as
+/// `"Rain starting around 3pm, high of 18°C, windy overnight."`.
+///
+/// The narrative covers precipitation and wind over the next 24 hours of the
+/// [`hourly`] block, and the high and low temperatures of the first day of the
+/// [`daily`] block. Times are in the forecast's local time, as given by its
+/// [`offset`].
+///
+/// Temperatures and wind speeds are interpreted according to the
+/// [`units`][`Flags::units`] flag, defaulting to [US units][`Unit::Us`].
+///
+/// An empty string is returned if there is nothing to narrate.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate serde_json;
+///
+/// use darksky::models::Forecast;
+/// use darksky::narrative;
+///
+/// # fn main() {
+/// let forecast: Forecast = serde_json::from_str(r#"{
+///     "latitude": 51.5,
+///     "longitude": -0.12,
+///     "timezone": "Europe/London",
+///     "offset": 0,
+///     "flags": {"units": "si"},
+///     "hourly": {"data": [
+///         {"time": 1509998400, "precipProbability": 0.1, "windSpeed": 3.0},
+///         {"time": 1510002000, "precipProbability": 0.7, "precipType": "rain"},
+///         {"time": 1510005600, "precipProbability": 0.8, "precipType": "rain"}
+///     ]},
+///     "daily": {"data": [
+///         {"time": 1509926400, "temperatureHigh": 18.2, "temperatureLow": 9.4}
+///     ]}
+/// }"#).unwrap();
+///
+/// assert_eq!(
+///     narrative::summarize(&forecast),
+///     "Rain starting around 9pm, high of 18°C, low of 9°C.",
+/// );
+/// # }
+/// ```
+///
+/// [`Flags::units`]: ../models/struct.Flags.html#structfield.units
+/// [`Unit::Us`]: ../enum.Unit.html#variant.Us
+/// [`daily`]: ../models/struct.Forecast.html#structfield.daily
+/// [`hourly`]: ../models/struct.Forecast.html#structfield.hourly
+/// [`offset`]: ../models/struct.Forecast.html#structfield.offset
+pub fn summarize(forecast: &Forecast) -> String {
+    let units = forecast
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.units.as_ref())
+        .map(|units| &units[..])
+        .unwrap_or("us");
+    let offset = forecast.offset_seconds();
+    let hourly = forecast
+        .hourly
+        .as_ref()
+        .and_then(|block| block.data.as_ref())
+        .map(|data| &data[..data.len().min(HOURS)])
+        .unwrap_or(&[]);
+
+    let mut parts = Vec::new();
+
+    if let Some(part) = precipitation(hourly, offset) {
+        parts.push(part);
+    }
+
+    let today = forecast
+        .daily
+        .as_ref()
+        .and_then(|block| block.data.as_ref())
+        .and_then(|data| data.first());
+
+    if let Some(today) = today {
+        let symbol = if units == "us" { "°F" } else { "°C" };
+        let high = today.temperature_high().or(today.temperature_max());
+        let low = today.temperature_low().or(today.temperature_min());
+
+        if let Some(high) = high {
+            parts.push(format!("high of {:.0}{}", high, symbol));
+        }
+
+        if let Some(low) = low {
+            parts.push(format!("low of {:.0}{}", low, symbol));
+        }
+    }
+
+    if let Some(part) = wind(hourly, offset, units) {
+        parts.push(part);
+    }
+
+    let mut narrative = parts.join(", ");
+
+    if let Some(first) = narrative.chars().next() {
+        let upper = first.to_uppercase().collect::<String>();
+        narrative.replace_range(..first.len_utf8(), &upper);
+        narrative.push('.');
+    }
+
+    narrative
+}
+
+fn precipitation(hourly: &[Datapoint], offset: i64) -> Option<String> {
+    let is_wet = |d: &Datapoint| d.precip_probability.unwrap_or(0.0) >= PRECIP_PROBABILITY;
+    let start = hourly.iter().position(is_wet)?;
+    let kind = match hourly[start].precip_type {
+        Some(PrecipitationType::Rain) => "Rain",
+        Some(PrecipitationType::Sleet) => "Sleet",
+        Some(PrecipitationType::Snow) => "Snow",
+        None => "Precipitation",
+    };
+
+    if start > 0 {
+        let time = hour(hourly[start].time, offset);
+
+        return Some(format!("{} starting around {}", kind, time));
+    }
+
+    match hourly.iter().position(|d| !is_wet(d)) {
+        Some(end) => Some(format!(
+            "{} until around {}",
+            kind,
+            hour(hourly[end].time, offset),
+        )),
+        None => Some(format!("{} throughout the day", kind)),
+    }
+}
+
+fn wind(hourly: &[Datapoint], offset: i64, units: &str) -> Option<String> {
+    // Roughly 20mph, in each unit's wind speed.
+    let threshold = match units {
+        "si" => 9.0,
+        "ca" => 32.0,
+        _ => 20.0,
+    };
+    let windy = hourly
+        .iter()
+        .find(|d| d.wind_speed.unwrap_or(0.0) >= threshold)?;
+
+    let part = match local_hour(windy.time, offset) {
+        5..=11 => "in the morning",
+        12..=16 => "in the afternoon",
+        17..=21 => "in the evening",
+        _ => "overnight",
+    };
+
+    Some(format!("windy {}", part))
+}
+
+pub(crate) fn local_hour(time: u64, offset: i64) -> i64 {
+    (time as i64 + offset).rem_euclid(86_400) / 3600
+}
+
+pub(crate) fn hour(time: u64, offset: i64) -> String {
+    match local_hour(time, offset) {
+        0 => "midnight".to_owned(),
+        12 => "noon".to_owned(),
+        h if h < 12 => format!("{}am", h),
+        h => format!("{}pm", h - 12),
+    }
+}
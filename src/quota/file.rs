@@ -0,0 +1,91 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! A quota store keeping the day's API call count in a JSON file.
+
+use super::{DailyCalls, QuotaStore};
+use serde_json;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process;
+use Result;
+
+/// A [`QuotaStore`] keeping the count in a JSON file, such as
+/// `{"day": 17476, "calls": 42}`.
+///
+/// The file is written whole before replacing any existing file, so that
+/// processes sharing it never read a partial count.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::quota::{DailyCalls, FileQuotaStore, QuotaStore};
+/// use std::env;
+///
+/// let path = env::temp_dir().join("darksky-quota-doc.json");
+/// let store = FileQuotaStore::new(&path);
+///
+/// store.save(DailyCalls::today(42)).unwrap();
+///
+/// // Another process using the same file sees the count.
+/// let other = FileQuotaStore::new(&path);
+/// assert_eq!(other.load().unwrap(), Some(DailyCalls::today(42)));
+/// #
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+///
+/// [`QuotaStore`]: trait.QuotaStore.html
+#[derive(Clone, Debug)]
+pub struct FileQuotaStore {
+    path: PathBuf,
+}
+
+impl FileQuotaStore {
+    /// Creates a store keeping the count in the given file, which is created
+    /// when the count is first saved.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        FileQuotaStore { path: path.into() }
+    }
+
+    /// Returns the file the count is kept in.
+    pub fn get_path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl QuotaStore for FileQuotaStore {
+    fn load(&self) -> Result<Option<DailyCalls>> {
+        match fs::read(&self.path) {
+            Ok(contents) => Ok(Some(serde_json::from_slice(&contents)?)),
+            Err(ref why) if why.kind() == ErrorKind::NotFound => Ok(None),
+            Err(why) => Err(why.into()),
+        }
+    }
+
+    fn save(&self, calls: DailyCalls) -> Result<()> {
+        let contents = serde_json::to_vec(&calls)?;
+        let temp = self.path.with_extension(format!("{}.tmp", process::id()));
+        fs::write(&temp, contents)?;
+
+        if let Err(why) = fs::rename(&temp, &self.path) {
+            let _ = fs::remove_file(&temp);
+
+            return Err(why.into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,207 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Persistence of the number of API calls made each day, so that it
+//! outlives the process.
+//!
+//! A [`DarkskyClient`] tracks the day's API calls from the
+//! `X-Forecast-API-Calls` header of its responses, as given by
+//! [`DarkskyClient::calls_today`]. With a [`QuotaStore`], the count is saved
+//! after each response and loaded when it is next read, so that short-lived
+//! processes, such as a script run by cron, know the day's count before their
+//! first response, and processes sharing a store see each other's calls.
+//!
+//! The [`FileQuotaStore`] keeps the count in a file. With the `quota-sqlite`
+//! feature, the [`SqliteQuotaStore`] keeps counts in a SQLite database,
+//! under a key for each token or application sharing it.
+//!
+//! [`DarkskyClient`]: ../facade/struct.DarkskyClient.html
+//! [`DarkskyClient::calls_today`]: ../facade/struct.DarkskyClient.html#method.calls_today
+//! [`FileQuotaStore`]: struct.FileQuotaStore.html
+//! [`QuotaStore`]: trait.QuotaStore.html
+//! [`SqliteQuotaStore`]: struct.SqliteQuotaStore.html
+
+mod file;
+#[cfg(feature = "quota-sqlite")]
+mod sqlite;
+
+pub use self::file::FileQuotaStore;
+#[cfg(feature = "quota-sqlite")]
+pub use self::sqlite::SqliteQuotaStore;
+
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+use Result;
+
+/// The number of API calls made on a UTC day.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct DailyCalls {
+    /// The UTC day, as a number of days since the Unix epoch.
+    pub day: u64,
+    /// The number of API calls made on the day.
+    pub calls: u64,
+}
+
+impl DailyCalls {
+    /// Creates a count of the calls made on the current UTC day.
+    pub fn today(calls: u64) -> Self {
+        DailyCalls {
+            day: utc_day(),
+            calls,
+        }
+    }
+
+    /// Returns whether the count is of the current UTC day.
+    pub fn is_today(&self) -> bool {
+        self.day == utc_day()
+    }
+}
+
+/// A store persisting the number of API calls made on the latest day.
+///
+/// Errors of a store are not fatal to a request: a failed load is treated
+/// as if nothing were stored, and a failed save is ignored.
+pub trait QuotaStore: Send + Sync {
+    /// Returns the count last saved, if there is one.
+    fn load(&self) -> Result<Option<DailyCalls>>;
+
+    /// Saves a count, replacing the one saved so far.
+    fn save(&self, calls: DailyCalls) -> Result<()>;
+}
+
+impl<T: QuotaStore + ?Sized> QuotaStore for &T {
+    fn load(&self) -> Result<Option<DailyCalls>> {
+        (**self).load()
+    }
+
+    fn save(&self, calls: DailyCalls) -> Result<()> {
+        (**self).save(calls)
+    }
+}
+
+impl<T: QuotaStore + ?Sized> QuotaStore for Box<T> {
+    fn load(&self) -> Result<Option<DailyCalls>> {
+        (**self).load()
+    }
+
+    fn save(&self, calls: DailyCalls) -> Result<()> {
+        (**self).save(calls)
+    }
+}
+
+impl<T: QuotaStore + ?Sized> QuotaStore for Arc<T> {
+    fn load(&self) -> Result<Option<DailyCalls>> {
+        (**self).load()
+    }
+
+    fn save(&self, calls: DailyCalls) -> Result<()> {
+        (**self).save(calls)
+    }
+}
+
+/// The number of API calls made on the current UTC day, as last reported by
+/// the API, shared between the requests of a client and optionally
+/// persisted to a [`QuotaStore`].
+///
+/// [`QuotaStore`]: trait.QuotaStore.html
+#[derive(Default)]
+pub(crate) struct CallCount {
+    latest: Mutex<Option<DailyCalls>>,
+    store: Option<Arc<dyn QuotaStore>>,
+}
+
+impl CallCount {
+    /// Creates a count persisted to the given store.
+    pub(crate) fn persisted(store: Arc<dyn QuotaStore>) -> Self {
+        CallCount {
+            latest: Mutex::new(None),
+            store: Some(store),
+        }
+    }
+
+    /// Records the number of calls reported by a response received now.
+    ///
+    /// Responses may arrive out of order, and other processes may share the
+    /// store, so a lower count for the same day is ignored.
+    pub(crate) fn record(&self, calls: u64) {
+        let mut latest = self.lock();
+        let stored = self.load();
+        let merged = newest(newest(*latest, stored), Some(DailyCalls::today(calls)));
+
+        if let (Some(ref store), Some(merged)) = (&self.store, merged) {
+            if stored != Some(merged) {
+                let _ = store.save(merged);
+            }
+        }
+
+        *latest = merged;
+    }
+
+    /// Returns the number of calls reported today, if any were.
+    pub(crate) fn today(&self) -> Option<u64> {
+        let mut latest = self.lock();
+        *latest = newest(*latest, self.load());
+
+        latest
+            .filter(DailyCalls::is_today)
+            .map(|latest| latest.calls)
+    }
+
+    /// Loads the count saved in the store, if there is one.
+    fn load(&self) -> Option<DailyCalls> {
+        self.store
+            .as_ref()
+            .and_then(|store| store.load().ok())
+            .and_then(|calls| calls)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<DailyCalls>> {
+        self.latest
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Debug for CallCount {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("CallCount")
+            .field("latest", &*self.lock())
+            .field("persisted", &self.store.is_some())
+            .finish()
+    }
+}
+
+/// Returns the later of two counts, or the higher if they are of the same
+/// day.
+fn newest(a: Option<DailyCalls>, b: Option<DailyCalls>) -> Option<DailyCalls> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if (b.day, b.calls) > (a.day, a.calls) {
+            b
+        } else {
+            a
+        }),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+/// Returns the current UTC day, as a number of days since the Unix epoch.
+fn utc_day() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs() / 86_400)
+        .unwrap_or(0)
+}
@@ -0,0 +1,151 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! A quota store keeping the day's API call count in a SQLite database.
+
+use super::{DailyCalls, QuotaStore};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::borrow::Cow;
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use Result;
+
+/// A [`QuotaStore`] keeping counts in a SQLite database, in a
+/// `darksky_quota` table created if it does not exist.
+///
+/// Each count is kept under a key, which defaults to `default`, so that the
+/// counts of several tokens or applications can share a database.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::quota::{DailyCalls, QuotaStore, SqliteQuotaStore};
+///
+/// let store = SqliteQuotaStore::open_in_memory().unwrap().key("backfill");
+///
+/// store.save(DailyCalls::today(42)).unwrap();
+/// assert_eq!(store.load().unwrap(), Some(DailyCalls::today(42)));
+/// ```
+///
+/// [`QuotaStore`]: trait.QuotaStore.html
+pub struct SqliteQuotaStore {
+    connection: Mutex<Connection>,
+    key: Cow<'static, str>,
+}
+
+impl SqliteQuotaStore {
+    /// Creates a store using the given connection, creating its table if it
+    /// does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Sqlite`] if the table could not be created.
+    ///
+    /// [`Error::Sqlite`]: ../enum.Error.html#variant.Sqlite
+    pub fn new(connection: Connection) -> Result<Self> {
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS darksky_quota (
+                key TEXT PRIMARY KEY NOT NULL,
+                day INTEGER NOT NULL,
+                calls INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(SqliteQuotaStore {
+            connection: Mutex::new(connection),
+            key: Cow::Borrowed("default"),
+        })
+    }
+
+    /// Creates a store in the database at the given path, creating the
+    /// database if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Sqlite`] if the database could not be opened.
+    ///
+    /// [`Error::Sqlite`]: ../enum.Error.html#variant.Sqlite
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::new(Connection::open(path)?)
+    }
+
+    /// Creates a store in a database held in memory, such as for tests.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Sqlite`] if the database could not be opened.
+    ///
+    /// [`Error::Sqlite`]: ../enum.Error.html#variant.Sqlite
+    pub fn open_in_memory() -> Result<Self> {
+        Self::new(Connection::open_in_memory()?)
+    }
+
+    /// Sets the key the count is kept under.
+    pub fn key<T: Into<Cow<'static, str>>>(mut self, key: T) -> Self {
+        self.key = key.into();
+
+        self
+    }
+
+    /// Returns the key the count is kept under.
+    pub fn get_key(&self) -> &str {
+        &self.key
+    }
+
+    /// Locks the connection, recovering it if another thread panicked while
+    /// holding the lock.
+    fn lock(&self) -> MutexGuard<'_, Connection> {
+        self.connection
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Debug for SqliteQuotaStore {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("SqliteQuotaStore")
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl QuotaStore for SqliteQuotaStore {
+    fn load(&self) -> Result<Option<DailyCalls>> {
+        self.lock()
+            .query_row(
+                "SELECT day, calls FROM darksky_quota WHERE key = ?1",
+                params![self.key],
+                |row| {
+                    Ok(DailyCalls {
+                        day: row.get::<_, i64>(0)? as u64,
+                        calls: row.get::<_, i64>(1)? as u64,
+                    })
+                },
+            )
+            .optional()
+            .map_err(From::from)
+    }
+
+    fn save(&self, calls: DailyCalls) -> Result<()> {
+        self.lock().execute(
+            "INSERT OR REPLACE INTO darksky_quota (key, day, calls) VALUES (?1, ?2, ?3)",
+            params![self.key, calls.day as i64, calls.calls as i64],
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Transport-agnostic construction of requests and parsing of responses.
+//!
+//! These build [`http::Request`]s for the DarkSky API and parse response
+//! bodies into [`Forecast`]s without performing any I/O, so that requests can
+//! be driven by HTTP stacks that no bridge supports, such as those with custom
+//! TLS, HTTP caches, or test harnesses.
+//!
+//! # Examples
+//!
+//! Build a request, send it with any client, and parse the response body:
+//!
+//! ```rust
+//! extern crate darksky;
+//!
+//! # use std::error::Error;
+//! #
+//! # fn try_main() -> Result<(), Box<dyn Error>> {
+//! use darksky::{request, Block};
+//!
+//! let req = request::forecast("token", (37.8267, -122.423), |o| o
+//!     .exclude(vec![Block::Minutely]))?;
+//! assert_eq!(
+//!     req.uri(),
+//!     "https://api.darksky.net/forecast/token/37.8267,-122.423?exclude=minutely&",
+//! );
+//!
+//! // Send `req` with the HTTP stack of your choice, then parse the body.
+//! let body = br#"{"latitude": 37.8267, "longitude": -122.423, "timezone": "America/Los_Angeles"}"#;
+//! let forecast = request::parse_response(body)?;
+//!
+//! assert_eq!(forecast.timezone, "America/Los_Angeles");
+//! #     Ok(())
+//! # }
+//! #
+//! # fn main() {
+//! #     try_main().unwrap();
+//! # }
+//! ```
+//!
+//! [`Forecast`]: ../models/struct.Forecast.html
+//! [`http::Request`]: https://docs.rs/http/0.2/http/request/struct.Request.html
+
+use cache::{Freshness, CACHE_CONTROL_HEADER};
+use constants::API_CALLS_HEADER;
+use http::{Request, Response, Uri};
+use models::Forecast;
+use std::fmt::Display;
+use std::str::FromStr;
+use {internal, utils, Error, IntoLocation, Options, Result};
+
+/// Builds a `GET` request for a [`Forecast`] for the given location, setting
+/// options where needed. For a full list of options, refer to the documentation
+/// for the [`Options`] builder.
+///
+/// [`Forecast`]: ../models/struct.Forecast.html
+/// [`Options`]: ../struct.Options.html
+pub fn forecast<F, L>(token: &str, location: L, options: F) -> Result<Request<()>>
+where
+    F: FnOnce(Options) -> Options,
+    L: IntoLocation,
+{
+    let options = options(Options::default());
+
+    build(utils::located_uri(token, location, options))
+}
+
+/// Builds a `GET` request for a [`Forecast`] at the given time by using
+/// DarkSky's Time Machine API.
+///
+/// Refer to [`DarkskyReqwestRequester::get_forecast_time_machine`] for the
+/// accepted time formats. The time is not validated.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::request;
+///
+/// let req = request::forecast_time_machine("token", (37.8267, -122.423), 1_450_000_000, |o| o)
+///     .unwrap();
+///
+/// assert_eq!(
+///     req.uri(),
+///     "https://api.darksky.net/forecast/token/37.8267,-122.423,1450000000?",
+/// );
+/// ```
+///
+/// [`DarkskyReqwestRequester::get_forecast_time_machine`]:
+///   ../bridge/reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_time_machine
+/// [`Forecast`]: ../models/struct.Forecast.html
+pub fn forecast_time_machine<D, F, L>(
+    token: &str,
+    location: L,
+    time: D,
+    options: F,
+) -> Result<Request<()>>
+where
+    D: Display,
+    F: FnOnce(Options) -> Options,
+    L: IntoLocation,
+{
+    forecast(token, location, |opt| options(opt).time(time))
+}
+
+/// Parses the body of a response to a forecast request into a [`Forecast`].
+///
+/// The response's status is not checked, so a non-success response results
+/// in an error for its body not being a forecast.
+///
+/// [`Forecast`]: ../models/struct.Forecast.html
+pub fn parse_response(body: &[u8]) -> Result<Forecast> {
+    internal::from_slice(body)
+}
+
+/// Returns the number of API calls made with the token on the current UTC
+/// day, from the `X-Forecast-API-Calls` header of a response, if it gives
+/// one.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate http;
+///
+/// use darksky::constants::DEFAULT_DAILY_LIMIT;
+/// use darksky::request;
+/// use http::Response;
+///
+/// # fn main() {
+/// let response = Response::builder()
+///     .header("X-Forecast-API-Calls", "990")
+///     .body(())
+///     .unwrap();
+/// let calls = request::api_calls(&response).unwrap();
+///
+/// assert_eq!(DEFAULT_DAILY_LIMIT - calls, 10);
+/// # }
+/// ```
+pub fn api_calls<T>(response: &Response<T>) -> Option<u64> {
+    response
+        .headers()
+        .get(API_CALLS_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|calls| calls.trim().parse().ok())
+}
+
+/// Returns how long a response received now is fresh for, from its
+/// `Cache-Control` header, if it gives one.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate http;
+///
+/// use darksky::request;
+/// use http::Response;
+/// use std::time::Duration;
+///
+/// # fn main() {
+/// let response = Response::builder()
+///     .header("Cache-Control", "max-age=3600")
+///     .body(())
+///     .unwrap();
+/// let freshness = request::freshness(&response).unwrap();
+///
+/// assert_eq!(freshness.max_age, Duration::from_secs(3600));
+/// assert!(freshness.is_fresh());
+/// # }
+/// ```
+pub fn freshness<T>(response: &Response<T>) -> Option<Freshness> {
+    response
+        .headers()
+        .get(CACHE_CONTROL_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(Freshness::from_cache_control)
+}
+
+fn build(uri: Result<String>) -> Result<Request<()>> {
+    let uri = Uri::from_str(&uri?).map_err(Error::Uri)?;
+    let mut req = Request::new(());
+    *req.uri_mut() = uri;
+
+    Ok(req)
+}
@@ -0,0 +1,225 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! A `tower::Service` for requesting forecasts, for composing requests with
+//! tower middleware such as timeouts, retries, load shedding, and tracing.
+//!
+//! The service sends requests over any [`HttpSend`] backend.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`ForecastService`].
+//!
+//! [`ForecastService`]: struct.ForecastService.html
+//! [`HttpSend`]: ../bridge/send/trait.HttpSend.html
+
+use bridge::send::{ForecastFuture, HttpSend};
+use constants::Config;
+use models::Forecast;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_service::Service;
+use {utils, Coordinate, Error, Options, Result, TokenProvider};
+
+/// A request for a forecast made through a [`ForecastService`].
+///
+/// # Examples
+///
+/// Request an extended forecast for a past time:
+///
+/// ```rust
+/// use darksky::service::ForecastRequest;
+///
+/// let request = ForecastRequest::new((37.8267, -122.423))
+///     .time(1_450_000_000)
+///     .options(|o| o.extend_hourly());
+///
+/// assert_eq!(request.get_location().lat, 37.8267);
+/// assert_eq!(request.get_time(), Some("1450000000"));
+/// ```
+///
+/// [`ForecastService`]: struct.ForecastService.html
+#[derive(Clone, Debug)]
+pub struct ForecastRequest {
+    location: Coordinate,
+    options: Options,
+    time: Option<String>,
+}
+
+impl ForecastRequest {
+    /// Creates a request for a current forecast at the given location, with
+    /// no options.
+    ///
+    /// The location is a [`Coordinate`], or a `(latitude, longitude)` tuple.
+    /// It is not validated until the request is made.
+    ///
+    /// [`Coordinate`]: ../location/struct.Coordinate.html
+    pub fn new<L: Into<Coordinate>>(location: L) -> Self {
+        ForecastRequest {
+            location: location.into(),
+            options: Options::default(),
+            time: None,
+        }
+    }
+
+    /// Sets options for the request. For a full list of options, refer to
+    /// the documentation for the [`Options`] builder.
+    ///
+    /// [`Options`]: ../struct.Options.html
+    pub fn options<F: FnOnce(Options) -> Options>(mut self, options: F) -> Self {
+        self.options = options(self.options);
+
+        self
+    }
+
+    /// Sets the time to request a forecast for by using DarkSky's Time
+    /// Machine API.
+    ///
+    /// Refer to [`DarkskyReqwestRequester::get_forecast_time_machine`] for
+    /// the accepted time formats. The time is not validated.
+    ///
+    /// [`DarkskyReqwestRequester::get_forecast_time_machine`]:
+    ///   ../bridge/reqwest/blocking/trait.DarkskyReqwestRequester.html#method.get_forecast_time_machine
+    pub fn time<D: Display>(mut self, time: D) -> Self {
+        self.time = Some(time.to_string());
+
+        self
+    }
+
+    /// Returns the location of the request.
+    pub fn get_location(&self) -> Coordinate {
+        self.location
+    }
+
+    /// Returns the options of the request.
+    pub fn get_options(&self) -> &Options {
+        &self.options
+    }
+
+    /// Returns the time of the request, if it is for a Time Machine forecast.
+    pub fn get_time(&self) -> Option<&str> {
+        self.time.as_ref().map(|time| &time[..])
+    }
+}
+
+/// A `tower::Service` requesting forecasts with a token over an
+/// [`HttpSend`] backend.
+///
+/// The service is always ready, leaving rate limiting and concurrency limits
+/// to middleware.
+///
+/// Requests are made to the API at [`API_URL`] unless the service is created
+/// with a [`Config`] pointing elsewhere, such as a mock server in tests.
+///
+/// # Examples
+///
+/// Create a service over a hyper client:
+///
+#[cfg_attr(feature = "hyper", doc = "```rust,no_run")]
+#[cfg_attr(not(feature = "hyper"), doc = "```rust,ignore")]
+/// extern crate darksky;
+/// extern crate hyper;
+///
+/// use darksky::service::{ForecastRequest, ForecastService};
+/// use hyper::Client;
+///
+/// # fn main() {
+/// let client = Client::new();
+/// let service = ForecastService::new(client, "token");
+///
+/// // Wrap `service` in tower middleware, then call it with requests:
+/// let request = ForecastRequest::new((37.8267, -122.423));
+/// #     drop((service, request));
+/// # }
+/// ```
+///
+/// [`API_URL`]: ../constants/constant.API_URL.html
+/// [`Config`]: ../constants/struct.Config.html
+/// [`HttpSend`]: ../bridge/send/trait.HttpSend.html
+#[derive(Clone)]
+pub struct ForecastService<S> {
+    config: Config,
+    sender: S,
+    token: Arc<dyn TokenProvider>,
+}
+
+impl<S: HttpSend> ForecastService<S> {
+    /// Creates a service sending requests over the given backend, using the
+    /// token of the given [`TokenProvider`].
+    ///
+    /// [`TokenProvider`]: ../trait.TokenProvider.html
+    pub fn new<T: TokenProvider + 'static>(sender: S, token: T) -> Self {
+        Self::with_config(sender, token, Config::default())
+    }
+
+    /// Creates a service sending requests over the given backend, using the
+    /// given token and configuration, such as the base URI of the API.
+    ///
+    /// The configuration's timeout and user agent are applied by the
+    /// backends listed in [`HttpSend::send_with`].
+    ///
+    /// [`HttpSend::send_with`]: ../bridge/send/trait.HttpSend.html#method.send_with
+    pub fn with_config<T: TokenProvider + 'static>(sender: S, token: T, config: Config) -> Self {
+        ForecastService {
+            config,
+            sender,
+            token: Arc::new(token),
+        }
+    }
+
+    /// Returns the configuration of the service.
+    pub fn get_config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns a reference to the backend.
+    pub fn get_ref(&self) -> &S {
+        &self.sender
+    }
+}
+
+impl<S: HttpSend> Service<ForecastRequest> for ForecastService<S> {
+    type Response = Forecast;
+    type Error = Error;
+    type Future = ForecastFuture<S::Future>;
+
+    fn poll_ready(&mut self, _: &mut Context) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: ForecastRequest) -> Self::Future {
+        let token = match self.token.token() {
+            Ok(token) => token,
+            Err(why) => return ForecastFuture::configured(&self.sender, &self.config, Err(why)),
+        };
+        let uri = utils::snapped(self.sender.uri_config(), req.location.lat, req.location.lon)
+            .and_then(|(lat, lon)| {
+                utils::uri_configured(&self.config, &token, lat, lon, req.time, req.options)
+            });
+
+        ForecastFuture::configured(&self.sender, &self.config, uri)
+            .reporting_to(&self.token, &token)
+    }
+}
+
+impl<S: Debug> Debug for ForecastService<S> {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("ForecastService")
+            .field("config", &self.config)
+            .field("sender", &self.sender)
+            .finish()
+    }
+}
@@ -0,0 +1,255 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Rendering of forecasts and datapoints from user-provided templates, such as
+//! `"{temperature:.0}°, {summary}, rain {precip_probability:%}"`, for tools
+//! like status bars whose output is configured without writing Rust.
+//!
+//! # Syntax
+//!
+//! A placeholder is a field name within braces, such as `{temperature}`.
+//! Field names are those of the [`Datapoint`] model, including those only
+//! present on `daily`, such as `{temperature_high}`.
+//!
+//! A placeholder may be followed by a format after a colon:
+//!
+//! - `{temperature:.1}` renders a number with one decimal place;
+//! - `{humidity:%}` renders a fraction as a whole percentage, e.g. `71%`;
+//! - `{humidity:.1%}` renders a fraction as a percentage with one decimal
+//!   place.
+//!
+//! Missing values render as nothing, and unknown fields are left as they are,
+//! so that mistakes are visible in the output. Literal braces are written as
+//! `{{` and `}}`.
+//!
+//! When rendering a [`Forecast`], field names are paths into the forecast,
+//! such as `{currently.temperature}` or `{timezone}`. Blocks' datapoints are
+//! indexed by number, such as `{hourly.data.1.summary}`, and `today` is a
+//! shorthand for the first daily datapoint, as in `{today.temperature_high}`.
+//!
+//! [`Datapoint`]: ../models/struct.Datapoint.html
+//! [`Forecast`]: ../models/struct.Forecast.html
+
+use models::{DailyFields, Datapoint, ErrorFields, Forecast};
+use serde_json::{self, Value};
+use std::fmt::Write;
+
+/// Renders a template against a datapoint.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate serde_json;
+///
+/// use darksky::models::Datapoint;
+/// use darksky::template;
+///
+/// # fn main() {
+/// let datapoint: Datapoint = serde_json::from_str(r#"{
+///     "time": 1509993277,
+///     "summary": "Light Rain",
+///     "temperature": 11.6,
+///     "precipProbability": 0.65
+/// }"#).unwrap();
+///
+/// let rendered = template::render(
+///     "{temperature:.0}°, {summary}, rain {precip_probability:%}",
+///     &datapoint,
+/// );
+///
+/// assert_eq!(rendered, "12°, Light Rain, rain 65%");
+/// # }
+/// ```
+pub fn render(template: &str, datapoint: &Datapoint) -> String {
+    let value = serde_json::to_value(datapoint).unwrap_or(Value::Null);
+
+    render_value(template, &value)
+}
+
+/// Renders a template against a forecast, where field names are paths into
+/// the forecast.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate serde_json;
+///
+/// use darksky::models::Forecast;
+/// use darksky::template;
+///
+/// # fn main() {
+/// let forecast: Forecast = serde_json::from_str(r#"{
+///     "latitude": 49.9,
+///     "longitude": -97.1,
+///     "timezone": "America/Winnipeg",
+///     "currently": {"time": 1509993277, "temperature": -3.5},
+///     "daily": {"data": [
+///         {"time": 1509948000, "temperatureHigh": -1.2, "temperatureLow": -8.9}
+///     ]}
+/// }"#).unwrap();
+///
+/// let rendered = template::render_forecast(
+///     "{currently.temperature:.0}° ({today.temperature_high:.0}°/{today.temperature_low:.0}°)",
+///     &forecast,
+/// );
+///
+/// assert_eq!(rendered, "-4° (-1°/-9°)");
+/// # }
+/// ```
+pub fn render_forecast(template: &str, forecast: &Forecast) -> String {
+    let value = serde_json::to_value(forecast).unwrap_or(Value::Null);
+
+    render_value(template, &value)
+}
+
+fn render_value(template: &str, value: &Value) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find(&['{', '}'][..]) {
+        rendered.push_str(&rest[..start]);
+        let tail = &rest[start..];
+
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            rendered.push_str(&tail[..1]);
+            rest = &tail[2..];
+
+            continue;
+        }
+
+        let end = match tail.find('}') {
+            Some(end) if tail.starts_with('{') => end,
+            _ => {
+                rendered.push_str(&tail[..1]);
+                rest = &tail[1..];
+
+                continue;
+            }
+        };
+        let placeholder = &tail[1..end];
+        let (path, spec) = match placeholder.find(':') {
+            Some(colon) => (&placeholder[..colon], &placeholder[colon + 1..]),
+            None => (placeholder, ""),
+        };
+
+        match lookup(value, path) {
+            Some(field) => write_field(&mut rendered, field, spec),
+            None => rendered.push_str(&tail[..=end]),
+        }
+
+        rest = &tail[end + 1..];
+    }
+
+    rendered.push_str(rest);
+
+    rendered
+}
+
+/// Looks up a dotted path of snake cased field names, returning `None` if the
+/// field is unknown, and `Some(&Value::Null)` if it is known but missing.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = value;
+
+    for segment in path.split('.') {
+        if segment == "today" {
+            current = current.get("daily")?.get("data")?.get(0)?;
+
+            continue;
+        }
+
+        current = match *current {
+            Value::Array(ref values) => values.get(segment.parse::<usize>().ok()?)?,
+            Value::Object(ref fields) => {
+                let name = camel_case(segment);
+
+                match fields.get(&name) {
+                    Some(field) => field,
+                    None if is_flattened_field(&name) => &Value::Null,
+                    None => return None,
+                }
+            }
+            _ => return None,
+        };
+    }
+
+    Some(current)
+}
+
+/// Returns whether a camel cased field name is one of the [`DailyFields`] or
+/// [`ErrorFields`] of a [`Datapoint`], which are left out of its JSON when
+/// none of them are present, unlike its other fields.
+///
+/// [`DailyFields`]: ../models/struct.DailyFields.html
+/// [`Datapoint`]: ../models/struct.Datapoint.html
+/// [`ErrorFields`]: ../models/struct.ErrorFields.html
+fn is_flattened_field(name: &str) -> bool {
+    let daily = serde_json::to_value(DailyFields::default()).unwrap_or(Value::Null);
+    let errors = serde_json::to_value(ErrorFields::default()).unwrap_or(Value::Null);
+
+    daily.get(name).is_some() || errors.get(name).is_some()
+}
+
+fn write_field(rendered: &mut String, field: &Value, spec: &str) {
+    let (spec, percent) = match spec.strip_suffix('%') {
+        Some(spec) => (spec, true),
+        None => (spec, false),
+    };
+    let precision = spec
+        .strip_prefix('.')
+        .and_then(|precision| precision.parse::<usize>().ok());
+
+    match *field {
+        Value::Null => {}
+        Value::Number(ref number) => {
+            let mut number = number.as_f64().unwrap_or(0.0);
+
+            if percent {
+                number *= 100.0;
+            }
+
+            let _ = match precision {
+                Some(precision) => write!(rendered, "{:.*}", precision, number),
+                None if percent => write!(rendered, "{:.0}", number),
+                None => write!(rendered, "{}", number),
+            };
+
+            if percent {
+                rendered.push('%');
+            }
+        }
+        Value::String(ref string) => rendered.push_str(string),
+        ref other => rendered.push_str(&other.to_string()),
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    let mut upper = false;
+
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            camel.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            camel.push(c);
+        }
+    }
+
+    camel
+}
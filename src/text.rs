@@ -0,0 +1,168 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Normalization of the API's [`summary`] text, for composing summaries into
+//! user interfaces.
+//!
+//! Summaries are given as sentences, such as `"Light rain starting in 20
+//! min."`, which need their period removed and their case adjusted when they
+//! are placed inside other text.
+//!
+//! [`summary`]: ../models/struct.Datapoint.html#structfield.summary
+
+use models::Forecast;
+
+/// Removes any trailing periods and whitespace from a summary.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::text;
+///
+/// assert_eq!(text::strip_period("Rain throughout the week. "), "Rain throughout the week");
+/// ```
+pub fn strip_period(summary: &str) -> &str {
+    summary.trim_end().trim_end_matches('.').trim_end()
+}
+
+/// Capitalizes the first letter of a summary.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::text;
+///
+/// assert_eq!(text::sentence_case("light rain"), "Light rain");
+/// ```
+pub fn sentence_case(summary: &str) -> String {
+    let mut chars = summary.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Lowercases the first letter of a summary, for use in the middle of a
+/// sentence.
+///
+/// A first word written entirely in capitals, such as `"UV"`, is left as it
+/// is.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::text;
+///
+/// assert_eq!(text::mid_sentence("Light rain"), "light rain");
+/// assert_eq!(text::mid_sentence("UV index high"), "UV index high");
+/// ```
+pub fn mid_sentence(summary: &str) -> String {
+    let first_word = summary.split_whitespace().next().unwrap_or("");
+    let acronym = first_word.chars().filter(|c| c.is_alphabetic()).count() > 1
+        && !first_word.chars().any(char::is_lowercase);
+
+    if acronym {
+        return summary.to_owned();
+    }
+
+    let mut chars = summary.chars();
+
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Joins the summary of the current conditions with the summary of the next
+/// hour into a single phrase, without a trailing period.
+///
+/// The next hour's summary is omitted if it only repeats the current one.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::text;
+///
+/// assert_eq!(
+///     text::join("Clear", "Light rain starting in 20 min."),
+///     "Clear, light rain starting in 20 min",
+/// );
+/// assert_eq!(text::join("Clear.", "clear"), "Clear");
+/// ```
+pub fn join(current: &str, next_hour: &str) -> String {
+    let current = sentence_case(strip_period(current));
+    let next_hour = strip_period(next_hour);
+
+    if next_hour.is_empty() || next_hour.eq_ignore_ascii_case(&current) {
+        current
+    } else if current.is_empty() {
+        sentence_case(next_hour)
+    } else {
+        format!("{}, {}", current, mid_sentence(next_hour))
+    }
+}
+
+/// Joins the summaries of a forecast's [`currently`] datapoint and its
+/// [`minutely`] block, which describes the next hour, using [`join`].
+///
+/// Returns `None` if neither summary is present.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate darksky;
+/// extern crate serde_json;
+///
+/// use darksky::models::Forecast;
+/// use darksky::text;
+///
+/// # fn main() {
+/// let forecast: Forecast = serde_json::from_str(r#"{
+///     "latitude": 49.9,
+///     "longitude": -97.1,
+///     "timezone": "America/Winnipeg",
+///     "currently": {"time": 1509993277, "summary": "Overcast"},
+///     "minutely": {"summary": "Snow starting in 15 min.", "data": []}
+/// }"#).unwrap();
+///
+/// assert_eq!(
+///     text::current_and_next_hour(&forecast).unwrap(),
+///     "Overcast, snow starting in 15 min",
+/// );
+/// # }
+/// ```
+///
+/// [`currently`]: ../models/struct.Forecast.html#structfield.currently
+/// [`join`]: fn.join.html
+/// [`minutely`]: ../models/struct.Forecast.html#structfield.minutely
+pub fn current_and_next_hour(forecast: &Forecast) -> Option<String> {
+    let current = forecast
+        .currently
+        .as_ref()
+        .and_then(|currently| currently.summary.as_ref());
+    let next_hour = forecast
+        .minutely
+        .as_ref()
+        .and_then(|minutely| minutely.summary.as_ref());
+
+    match (current, next_hour) {
+        (None, None) => None,
+        (current, next_hour) => Some(join(
+            current.map_or("", |summary| &summary[..]),
+            next_hour.map_or("", |summary| &summary[..]),
+        )),
+    }
+}
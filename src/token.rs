@@ -0,0 +1,558 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use core::fmt::{Debug, Formatter, Result as FmtResult};
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::borrow::ToOwned;
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+#[cfg(feature = "std")]
+use std::sync::{Mutex, MutexGuard};
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(feature = "std")]
+use std::{env, fs};
+use {Error, Result};
+
+/// The length of a DarkSky API key.
+const TOKEN_LEN: usize = 32;
+
+/// A validated API token.
+///
+/// Parsing a token up front catches mistakes such as a trailing newline read
+/// from a file or environment variable, which would otherwise surface as a
+/// baffling `403` or URI error at request time.
+///
+/// The token is redacted from its `Debug` output, so that it does not leak
+/// into logs.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::ApiToken;
+///
+/// let token = ApiToken::parse("0123456789abcdef0123456789ABCDEF").unwrap();
+/// assert_eq!(token.as_str(), "0123456789abcdef0123456789ABCDEF");
+/// assert_eq!(format!("{:?}", token), "ApiToken(\"0123…\")");
+///
+/// assert!(ApiToken::parse("0123456789abcdef0123456789abcdef\n").is_err());
+/// ```
+#[derive(Clone, Eq, Hash, PartialEq)]
+pub struct ApiToken(String);
+
+impl ApiToken {
+    /// Parses a DarkSky API key, which is 32 hexadecimal characters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidToken`] describing the problem if the token is
+    /// not in that format.
+    ///
+    /// [`Error::InvalidToken`]: enum.Error.html#variant.InvalidToken
+    pub fn parse(token: &str) -> Result<Self> {
+        Self::check_common(token)?;
+
+        if token.len() != TOKEN_LEN {
+            return Err(Error::InvalidToken("expected 32 characters"));
+        }
+
+        if !token.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(Error::InvalidToken("expected only hexadecimal characters"));
+        }
+
+        Ok(ApiToken(String::from(token)))
+    }
+
+    /// Parses a token for a provider with a DarkSky-compatible API, whose
+    /// tokens may not be in DarkSky's format.
+    ///
+    /// This only checks that the token is non-empty and can be placed in a URI
+    /// path as-is, being made of letters, digits, `-`, `_`, `.`, and `~`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use darksky::ApiToken;
+    ///
+    /// assert!(ApiToken::parse_compatible("pw_Xy7-key").is_ok());
+    /// assert!(ApiToken::parse_compatible("pw key").is_err());
+    /// ```
+    pub fn parse_compatible(token: &str) -> Result<Self> {
+        Self::check_common(token)?;
+
+        let safe = |b: u8| b.is_ascii_alphanumeric() || b"-_.~".contains(&b);
+
+        if !token.bytes().all(safe) {
+            return Err(Error::InvalidToken(
+                "expected only letters, digits, '-', '_', '.', and '~'",
+            ));
+        }
+
+        Ok(ApiToken(String::from(token)))
+    }
+
+    /// Returns the token as a string, for passing to a requester.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn check_common(token: &str) -> Result<()> {
+        if token.is_empty() {
+            Err(Error::InvalidToken("the token is empty"))
+        } else if token.trim() != token {
+            Err(Error::InvalidToken(
+                "the token has leading or trailing whitespace, such as a newline",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl AsRef<str> for ApiToken {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Debug for ApiToken {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        let shown = self.0.get(..4).unwrap_or("");
+
+        f.debug_tuple("ApiToken")
+            .field(&format_args!("\"{}…\"", shown))
+            .finish()
+    }
+}
+
+impl FromStr for ApiToken {
+    type Err = Error;
+
+    fn from_str(token: &str) -> Result<Self> {
+        Self::parse(token)
+    }
+}
+
+/// A source of the API token, asked for the token on each request.
+///
+/// Clients such as [`DarkskyClient`] take a provider rather than a string, so
+/// that a token kept elsewhere, such as in a secrets manager, can be rotated
+/// without rebuilding them. Providers are implemented for strings and
+/// [`ApiToken`]s, and tokens can be read from an environment variable with
+/// [`EnvToken`] or from a file with [`FileToken`].
+///
+/// # Examples
+///
+/// A provider fetching its token from elsewhere:
+///
+/// ```rust
+/// use darksky::{Result, TokenProvider};
+/// use std::borrow::Cow;
+/// use std::sync::RwLock;
+///
+/// struct Rotating {
+///     current: RwLock<String>,
+/// }
+///
+/// impl TokenProvider for Rotating {
+///     fn token(&self) -> Result<Cow<str>> {
+///         Ok(Cow::Owned(self.current.read().unwrap().clone()))
+///     }
+/// }
+///
+/// let provider = Rotating {
+///     current: RwLock::new("old".to_owned()),
+/// };
+/// *provider.current.write().unwrap() = "new".to_owned();
+///
+/// assert_eq!(provider.token().unwrap(), "new");
+/// ```
+///
+/// [`ApiToken`]: struct.ApiToken.html
+/// [`DarkskyClient`]: struct.DarkskyClient.html
+/// [`EnvToken`]: struct.EnvToken.html
+/// [`FileToken`]: struct.FileToken.html
+pub trait TokenProvider: Send + Sync {
+    /// Returns the current token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token could not be retrieved, which fails the
+    /// request it was retrieved for.
+    fn token(&self) -> Result<Cow<'_, str>>;
+
+    /// Reports that a request made with a token returned by [`token`]
+    /// failed, such as with an [`Error::Api`] when the token has reached its
+    /// daily limit.
+    ///
+    /// This is called by clients taking a provider, so that a provider with
+    /// several tokens can stop giving out one that is failing. The default
+    /// does nothing.
+    ///
+    /// [`Error::Api`]: enum.Error.html#variant.Api
+    /// [`token`]: #tymethod.token
+    fn report_error(&self, _token: &str, _error: &Error) {}
+}
+
+impl TokenProvider for str {
+    fn token(&self) -> Result<Cow<'_, str>> {
+        Ok(Cow::Borrowed(self))
+    }
+}
+
+impl TokenProvider for String {
+    fn token(&self) -> Result<Cow<'_, str>> {
+        Ok(Cow::Borrowed(self))
+    }
+}
+
+impl TokenProvider for ApiToken {
+    fn token(&self) -> Result<Cow<'_, str>> {
+        Ok(Cow::Borrowed(&self.0))
+    }
+}
+
+impl<T: TokenProvider + ?Sized> TokenProvider for &T {
+    fn token(&self) -> Result<Cow<'_, str>> {
+        (**self).token()
+    }
+
+    fn report_error(&self, token: &str, error: &Error) {
+        (**self).report_error(token, error)
+    }
+}
+
+impl<T: TokenProvider + ?Sized> TokenProvider for Box<T> {
+    fn token(&self) -> Result<Cow<'_, str>> {
+        (**self).token()
+    }
+
+    fn report_error(&self, token: &str, error: &Error) {
+        (**self).report_error(token, error)
+    }
+}
+
+impl<T: TokenProvider + ?Sized> TokenProvider for Arc<T> {
+    fn token(&self) -> Result<Cow<'_, str>> {
+        (**self).token()
+    }
+
+    fn report_error(&self, token: &str, error: &Error) {
+        (**self).report_error(token, error)
+    }
+}
+
+/// A [`TokenProvider`] reading the token from an environment variable on
+/// each request.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::{EnvToken, TokenProvider};
+/// use std::env;
+///
+/// env::set_var("DOCTEST_FORECAST_TOKEN", "token");
+///
+/// let provider = EnvToken::new("DOCTEST_FORECAST_TOKEN");
+/// assert_eq!(provider.token().unwrap(), "token");
+/// ```
+///
+/// [`TokenProvider`]: trait.TokenProvider.html
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EnvToken {
+    name: Cow<'static, str>,
+}
+
+#[cfg(feature = "std")]
+impl EnvToken {
+    /// Creates a provider reading the environment variable with the given
+    /// name.
+    pub fn new<T: Into<Cow<'static, str>>>(name: T) -> Self {
+        EnvToken { name: name.into() }
+    }
+
+    /// Returns the name of the environment variable.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[cfg(feature = "std")]
+impl TokenProvider for EnvToken {
+    /// Reads the environment variable.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Env`] if the variable is not set or is not unicode.
+    ///
+    /// [`Error::Env`]: enum.Error.html#variant.Env
+    fn token(&self) -> Result<Cow<'_, str>> {
+        Ok(Cow::Owned(env::var(&*self.name)?))
+    }
+}
+
+/// A [`TokenProvider`] reading the token from a file on each request, such as
+/// a mounted secret.
+///
+/// Leading and trailing whitespace, such as a trailing newline, is trimmed
+/// from the file's contents.
+///
+/// [`TokenProvider`]: trait.TokenProvider.html
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct FileToken {
+    path: PathBuf,
+}
+
+#[cfg(feature = "std")]
+impl FileToken {
+    /// Creates a provider reading the file at the given path.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        FileToken { path: path.into() }
+    }
+
+    /// Returns the path of the file.
+    pub fn get_path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+#[cfg(feature = "std")]
+impl TokenProvider for FileToken {
+    /// Reads the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file could not be read.
+    ///
+    /// [`Error::Io`]: enum.Error.html#variant.Io
+    fn token(&self) -> Result<Cow<'_, str>> {
+        let contents = fs::read_to_string(&self.path)?;
+
+        Ok(Cow::Owned(contents.trim().to_owned()))
+    }
+}
+
+/// A [`TokenProvider`] spreading requests over several tokens, such as to
+/// make use of the daily limit of each.
+///
+/// Tokens are given out round-robin. When a request fails with an
+/// [`Error::Api`] of `401`, `403`, or `429`, meaning that its token was
+/// rejected or has reached its daily limit, the token is skipped for the rest
+/// of the UTC day and later requests fail over to the next one. Once every
+/// token has been skipped, retrieving a token fails with
+/// [`Error::TokensExhausted`].
+///
+/// The number of calls and errors of each token are counted, and can be read
+/// with [`usage`].
+///
+/// # Examples
+///
+#[cfg_attr(feature = "hyper", doc = "```rust,no_run")]
+#[cfg_attr(not(feature = "hyper"), doc = "```rust,ignore")]
+/// extern crate darksky;
+/// extern crate hyper;
+///
+/// use darksky::{DarkskyClient, TokenPool};
+/// use hyper::Client;
+/// use std::sync::Arc;
+///
+/// # fn main() {
+/// let pool = Arc::new(TokenPool::new(vec!["first-token", "second-token"]));
+/// let client = DarkskyClient::new(Client::new(), pool.clone());
+///
+/// // After making requests with the client:
+/// for (index, usage) in pool.usage().iter().enumerate() {
+///     println!("token {}: {} calls, {} errors", index, usage.calls, usage.errors);
+/// }
+/// #     drop(client);
+/// # }
+/// ```
+///
+/// [`Error::Api`]: enum.Error.html#variant.Api
+/// [`Error::TokensExhausted`]: enum.Error.html#variant.TokensExhausted
+/// [`TokenProvider`]: trait.TokenProvider.html
+/// [`usage`]: #method.usage
+#[cfg(feature = "std")]
+pub struct TokenPool {
+    state: Mutex<PoolState>,
+    tokens: Vec<String>,
+}
+
+#[cfg(feature = "std")]
+struct PoolState {
+    next: usize,
+    usage: Vec<TokenUsage>,
+    /// The UTC day since the UNIX epoch on which each token was exhausted.
+    exhausted_on: Vec<Option<u64>>,
+}
+
+/// The usage of a token of a [`TokenPool`], as returned by
+/// [`TokenPool::usage`].
+///
+/// [`TokenPool`]: struct.TokenPool.html
+/// [`TokenPool::usage`]: struct.TokenPool.html#method.usage
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TokenUsage {
+    /// The number of times the token was given out for a request.
+    pub calls: u64,
+    /// The number of requests made with the token that failed.
+    pub errors: u64,
+    /// Whether the token is being skipped for the rest of the UTC day.
+    pub exhausted: bool,
+}
+
+#[cfg(feature = "std")]
+impl TokenPool {
+    /// Creates a pool of the given tokens, starting with the first.
+    pub fn new<I, T>(tokens: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let tokens = tokens.into_iter().map(Into::into).collect::<Vec<String>>();
+        let state = PoolState {
+            next: 0,
+            usage: vec![TokenUsage::default(); tokens.len()],
+            exhausted_on: vec![None; tokens.len()],
+        };
+
+        TokenPool {
+            state: Mutex::new(state),
+            tokens,
+        }
+    }
+
+    /// Returns the usage of each token, in the order they were given.
+    pub fn usage(&self) -> Vec<TokenUsage> {
+        let mut state = self.lock();
+        state.expire(today());
+
+        state.usage.clone()
+    }
+
+    /// Stops skipping exhausted tokens, such as after a plan upgrade.
+    pub fn reset(&self) {
+        let mut state = self.lock();
+        let state = &mut *state;
+
+        for (usage, exhausted_on) in state.usage.iter_mut().zip(&mut state.exhausted_on) {
+            usage.exhausted = false;
+            *exhausted_on = None;
+        }
+    }
+
+    /// Returns the number of tokens in the pool.
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Returns whether there are no tokens in the pool.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PoolState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(feature = "std")]
+impl PoolState {
+    /// Stops skipping tokens exhausted before the given day.
+    fn expire(&mut self, today: u64) {
+        for (usage, exhausted_on) in self.usage.iter_mut().zip(&mut self.exhausted_on) {
+            if exhausted_on.is_some_and(|day| day < today) {
+                usage.exhausted = false;
+                *exhausted_on = None;
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl TokenProvider for TokenPool {
+    /// Returns the next token which is not exhausted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TokensExhausted`] if every token is exhausted, or the
+    /// pool is empty.
+    ///
+    /// [`Error::TokensExhausted`]: enum.Error.html#variant.TokensExhausted
+    fn token(&self) -> Result<Cow<'_, str>> {
+        let mut state = self.lock();
+        state.expire(today());
+
+        let len = self.tokens.len();
+
+        for offset in 0..len {
+            let index = (state.next + offset) % len;
+
+            if !state.usage[index].exhausted {
+                state.next = (index + 1) % len;
+                state.usage[index].calls += 1;
+
+                return Ok(Cow::Borrowed(&self.tokens[index]));
+            }
+        }
+
+        Err(Error::TokensExhausted)
+    }
+
+    fn report_error(&self, token: &str, error: &Error) {
+        let index = match self.tokens.iter().position(|other| other == token) {
+            Some(index) => index,
+            None => return,
+        };
+        let mut state = self.lock();
+        state.usage[index].errors += 1;
+
+        if let Error::Api(401, _) | Error::Api(403, _) | Error::Api(429, _) = *error {
+            state.usage[index].exhausted = true;
+            state.exhausted_on[index] = Some(today());
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Debug for TokenPool {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.debug_struct("TokenPool")
+            .field("usage", &self.lock().usage)
+            .finish()
+    }
+}
+
+/// Returns the current UTC day since the UNIX epoch.
+#[cfg(feature = "std")]
+fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or(0)
+}
@@ -1,15 +1,22 @@
 //! Utilities that provide some basic functionality that may be useful, but are
 //! generally non-essential for usage of the library.
 
-use constants::API_URL;
-use std::collections::HashMap;
+use constants::{Config, API_URL};
+use itoa;
+use ryu;
+use std::borrow::Cow;
 use std::fmt::Write;
-use Result;
+#[cfg(feature = "url")]
+use url::Url;
+#[cfg(feature = "url")]
+use Block;
+use {Coordinate, Error, IntoLocation, Language, Options, Result};
 
 /// Formats a URI for retrieving a forecast without options.
 ///
 /// Accepts the token to use, as well as the latitude and longitude of the
-/// location being requested.
+/// location being requested. The coordinates are not checked, which can be
+/// done with [`validate_coordinates`].
 ///
 /// # Examples
 ///
@@ -24,15 +31,31 @@ use Result;
 ///
 /// assert_eq!(uri, exp);
 /// ```
+///
+/// [`validate_coordinates`]: fn.validate_coordinates.html
 #[inline]
 pub fn uri(token: &str, lat: f64, long: f64) -> String {
-    format!("{}/forecast/{}/{},{}?units=auto", API_URL, token, lat, long)
+    based_uri(API_URL, token, lat, long)
+}
+
+/// Formats a URI for retrieving a forecast without options from the given
+/// base URI of the API.
+fn based_uri(base: &str, token: &str, lat: f64, long: f64) -> String {
+    let mut uri = String::with_capacity(estimate_len(base, token, None));
+    push_location(&mut uri, base, token, lat, long, None);
+    uri.push_str("?units=auto");
+
+    uri
 }
 
 /// Formats a URI for retrieving a forecast with options.
 ///
 /// Accepts the token to use, the latitude and longitude of the location being
-/// requested, and additional options for the request.
+/// requested, and additional options for the request. The time is placed in
+/// the path for a Time Machine request, falling back to the [`Options::time`]
+/// of the options if it is `None`.
+///
+/// Returns [`Error::InvalidCoordinate`] if the coordinates are out of range.
 ///
 /// # Examples
 ///
@@ -42,9 +65,7 @@ pub fn uri(token: &str, lat: f64, long: f64) -> String {
 /// ```rust
 /// use darksky::{Block, Options, utils};
 ///
-/// let options = Options::default()
-///     .exclude(vec![Block::Hourly])
-///     .into_inner();
+/// let options = Options::default().exclude(vec![Block::Hourly]);
 /// let uri = utils::uri_optioned(
 ///     "def",
 ///     -4.13,
@@ -58,45 +79,566 @@ pub fn uri(token: &str, lat: f64, long: f64) -> String {
 /// ```
 ///
 /// [`Block::Hourly`]: ../enum.Block.html#variant.Hourly
+/// [`Error::InvalidCoordinate`]: ../enum.Error.html#variant.InvalidCoordinate
+/// [`Options::time`]: ../struct.Options.html#method.time
 #[inline]
 pub fn uri_optioned(
     token: &str,
     lat: f64,
     long: f64,
     time: Option<String>,
-    options: HashMap<&'static str, String>,
+    options: Options,
+) -> Result<String> {
+    validate_coordinates(lat, long)?;
+
+    let time = time.as_ref().map(|time| &time[..]).or(options.get_time());
+    let mut uri = String::with_capacity(estimate_len(API_URL, token, Some(&options)));
+    push_location(&mut uri, API_URL, token, lat, long, time);
+    push_options(&mut uri, &options);
+
+    Ok(uri)
+}
+
+/// Formats a URI for retrieving a forecast with options, using the base URI
+/// of the API from a [`Config`].
+///
+/// The coordinates are first snapped to the config's [grid], if it has one.
+/// This is otherwise equivalent to [`uri_optioned`].
+///
+/// # Examples
+///
+/// Format a request URI for a local mirror of the API:
+///
+/// ```rust
+/// use darksky::constants::Config;
+/// use darksky::{Options, utils};
+///
+/// let config = Config::default().api_url("http://localhost:8080");
+/// let uri = utils::uri_configured(&config, "def", -4.13, 14.32, None, Options::default()).unwrap();
+///
+/// assert_eq!(uri, "http://localhost:8080/forecast/def/-4.13,14.32?");
+///
+/// let config = config.grid(0.1);
+/// let uri = utils::uri_configured(&config, "def", -4.13, 14.32, None, Options::default()).unwrap();
+///
+/// assert_eq!(uri, "http://localhost:8080/forecast/def/-4.1,14.3?");
+/// ```
+///
+/// [`Config`]: ../constants/struct.Config.html
+/// [grid]: ../constants/struct.Config.html#method.grid
+/// [`uri_optioned`]: fn.uri_optioned.html
+pub fn uri_configured(
+    config: &Config,
+    token: &str,
+    lat: f64,
+    long: f64,
+    time: Option<String>,
+    options: Options,
+) -> Result<String> {
+    validate_coordinates(lat, long)?;
+
+    let base = config.get_api_url();
+    let (lat, long) = config.snap(lat, long);
+    let time = time.as_ref().map(|time| &time[..]).or(options.get_time());
+    let mut uri = String::with_capacity(estimate_len(base, token, Some(&options)));
+    push_location(&mut uri, base, token, lat, long, time);
+    push_options(&mut uri, &options);
+
+    Ok(uri)
+}
+
+/// Writes a URI for retrieving a forecast into an existing buffer, replacing
+/// its contents.
+///
+/// This is equivalent to [`uri_optioned`], but reuses the buffer's allocation
+/// and does not consume the options, so it is suited to hot loops making many
+/// requests. The buffer's capacity is only grown if the URI does not fit.
+///
+/// # Examples
+///
+/// Format request URIs for two locations using the same buffer:
+///
+/// ```rust
+/// use darksky::{Block, Options, utils};
+///
+/// let options = Options::default().exclude(vec![Block::Hourly]);
+/// let mut uri = String::new();
+///
+/// utils::build_uri_into(&mut uri, "def", -4.13, 14.32, None, &options);
+/// assert_eq!(uri, "https://api.darksky.net/forecast/def/-4.13,14.32?exclude=hourly&");
+///
+/// utils::build_uri_into(&mut uri, "def", 49.9, -97.1, Some(1_450_000_000), &options);
+/// assert_eq!(
+///     uri,
+///     "https://api.darksky.net/forecast/def/49.9,-97.1,1450000000?exclude=hourly&",
+/// );
+/// ```
+///
+/// [`uri_optioned`]: fn.uri_optioned.html
+pub fn build_uri_into(
+    uri: &mut String,
+    token: &str,
+    lat: f64,
+    long: f64,
+    time: Option<u64>,
+    options: &Options,
+) {
+    let mut buffer = itoa::Buffer::new();
+    let time = match time {
+        Some(time) => Some(buffer.format(time)),
+        None => options.get_time(),
+    };
+
+    uri.clear();
+    uri.reserve(estimate_len(API_URL, token, Some(options)));
+    push_location(uri, API_URL, token, lat, long, time);
+    push_options(uri, options);
+}
+
+/// Checks that a latitude is within `[-90, 90]` and a longitude within
+/// `[-180, 180]`, so that invalid requests fail before being sent rather than
+/// with an error from the API.
+///
+/// This is done by every requester, and by the URI builders other than
+/// [`uri`] and [`build_uri_into`].
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::{utils, Error};
+///
+/// assert!(utils::validate_coordinates(49.9, -97.1).is_ok());
+/// assert!(utils::validate_coordinates(-90.0, 180.0).is_ok());
+///
+/// match utils::validate_coordinates(91.0, -97.1) {
+///     Err(Error::InvalidCoordinate(lat, long)) => assert_eq!((lat, long), (91.0, -97.1)),
+///     other => panic!("expected an invalid coordinate, got {:?}", other),
+/// }
+///
+/// assert!(utils::validate_coordinates(49.9, f64::NAN).is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidCoordinate`] if either is out of range, or is not
+/// finite.
+///
+/// [`Error::InvalidCoordinate`]: ../enum.Error.html#variant.InvalidCoordinate
+/// [`build_uri_into`]: fn.build_uri_into.html
+/// [`uri`]: fn.uri.html
+pub fn validate_coordinates(lat: f64, long: f64) -> Result<()> {
+    // Ranges don't contain NaN, and infinities are outside of them.
+    if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&long) {
+        Ok(())
+    } else {
+        Err(Error::InvalidCoordinate(lat, long))
+    }
+}
+
+/// Checks a latitude and longitude with [`validate_coordinates`], then snaps
+/// them to the [grid] of a configuration, such as that of a backend, if one
+/// is given.
+///
+/// [`validate_coordinates`]: fn.validate_coordinates.html
+/// [grid]: ../constants/struct.Config.html#method.grid
+pub(crate) fn snapped(config: Option<&Config>, lat: f64, long: f64) -> Result<(f64, f64)> {
+    validate_coordinates(lat, long)?;
+
+    Ok(config.map_or((lat, long), |config| config.snap(lat, long)))
+}
+
+/// Converts a location into a coordinate, checking it with
+/// [`validate_coordinates`].
+///
+/// [`validate_coordinates`]: fn.validate_coordinates.html
+pub(crate) fn checked_location<L: IntoLocation>(location: L) -> Result<Coordinate> {
+    let location = location.into_location()?;
+
+    validate_coordinates(location.lat, location.lon).map(|()| location)
+}
+
+/// Formats a URI for retrieving a forecast for a location without options,
+/// after checking its coordinates with [`validate_coordinates`].
+///
+/// [`validate_coordinates`]: fn.validate_coordinates.html
+pub(crate) fn checked_uri<L: IntoLocation>(token: &str, location: L) -> Result<String> {
+    let Coordinate { lat, lon } = location.into_location()?;
+
+    validate_coordinates(lat, lon).map(|()| uri(token, lat, lon))
+}
+
+/// Formats a URI for retrieving a forecast for a location, as with
+/// [`checked_uri`] without options and [`located_uri`] with them, using the
+/// base URI of the API from a [`Config`] and snapping the coordinates to its
+/// [grid].
+///
+/// [`Config`]: ../constants/struct.Config.html
+/// [`checked_uri`]: fn.checked_uri.html
+/// [`located_uri`]: fn.located_uri.html
+/// [grid]: ../constants/struct.Config.html#method.grid
+pub(crate) fn configured_uri<L: IntoLocation>(
+    config: &Config,
+    token: &str,
+    location: L,
+    options: Option<Options>,
 ) -> Result<String> {
-    let mut uri = String::new();
-    uri.push_str(API_URL);
+    let Coordinate { lat, lon } = location.into_location()?;
+
+    match options {
+        Some(options) => uri_configured(config, token, lat, lon, None, options),
+        None => {
+            validate_coordinates(lat, lon)?;
+            let (lat, lon) = config.snap(lat, lon);
+
+            Ok(based_uri(config.get_api_url(), token, lat, lon))
+        }
+    }
+}
+
+/// Formats a URI for retrieving a forecast for a location with options, as
+/// with [`uri_optioned`].
+///
+/// [`uri_optioned`]: fn.uri_optioned.html
+pub(crate) fn located_uri<L: IntoLocation>(
+    token: &str,
+    location: L,
+    options: Options,
+) -> Result<String> {
+    let Coordinate { lat, lon } = location.into_location()?;
+
+    uri_optioned(token, lat, lon, None, options)
+}
+
+/// Builds a typed URL for retrieving a forecast without options.
+///
+/// This is equivalent to [`uri`], but builds a typed URL, so that its
+/// components can be inspected or modified, such as to point it at another
+/// host.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::utils;
+///
+/// let mut url = utils::url("abc", -7.3, 8.17).unwrap();
+/// assert_eq!(url.host_str(), Some("api.darksky.net"));
+/// assert_eq!(url.path(), "/forecast/abc/-7.3,8.17");
+/// assert_eq!(url.query(), Some("units=auto"));
+///
+/// url.set_host(Some("localhost")).unwrap();
+/// assert_eq!(url.as_str(), "https://localhost/forecast/abc/-7.3,8.17?units=auto");
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidCoordinate`] if the coordinates are out of range,
+/// or [`Error::Url`] if the URI is not a valid URL.
+///
+/// [`Error::InvalidCoordinate`]: ../enum.Error.html#variant.InvalidCoordinate
+/// [`Error::Url`]: ../enum.Error.html#variant.Url
+/// [`uri`]: fn.uri.html
+#[cfg(feature = "url")]
+pub fn url(token: &str, lat: f64, long: f64) -> Result<Url> {
+    validate_coordinates(lat, long)?;
+
+    build_url(API_URL, token, lat, long, None, None)
+}
+
+/// Builds a typed URL for retrieving a forecast with options.
+///
+/// This is equivalent to [`uri_optioned`], but builds a typed URL, so that
+/// its components can be inspected or modified.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::{utils, Block, Options};
+///
+/// let options = Options::default().exclude(vec![Block::Hourly]).time(1_450_000_000);
+/// let url = utils::url_optioned("def", -4.13, 14.32, None, options).unwrap();
+///
+/// assert_eq!(url.path(), "/forecast/def/-4.13,14.32,1450000000");
+/// assert_eq!(
+///     url.query_pairs().collect::<Vec<_>>(),
+///     [("exclude".into(), "hourly".into())],
+/// );
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidCoordinate`] if the coordinates are out of range,
+/// or [`Error::Url`] if the URI is not a valid URL.
+///
+/// [`Error::InvalidCoordinate`]: ../enum.Error.html#variant.InvalidCoordinate
+/// [`Error::Url`]: ../enum.Error.html#variant.Url
+/// [`uri_optioned`]: fn.uri_optioned.html
+#[cfg(feature = "url")]
+pub fn url_optioned(
+    token: &str,
+    lat: f64,
+    long: f64,
+    time: Option<String>,
+    options: Options,
+) -> Result<Url> {
+    validate_coordinates(lat, long)?;
+
+    let time = time.as_ref().map(|time| &time[..]).or(options.get_time());
+
+    build_url(API_URL, token, lat, long, time, Some(&options))
+}
+
+/// Builds a typed URL for retrieving a forecast with options, using the base
+/// URI of the API from a [`Config`].
+///
+/// This is equivalent to [`uri_configured`], but builds a typed URL on top of
+/// the config's base URI, so that its components can be inspected or
+/// modified.
+///
+/// # Examples
+///
+/// Keep the path of a base URI pointing at a proxy:
+///
+/// ```rust
+/// use darksky::constants::Config;
+/// use darksky::{utils, Options};
+///
+/// let config = Config::default().api_url("http://localhost:8080/darksky");
+/// let options = Options::default().time("2015-12-13T09:46:40+01:00");
+/// let url = utils::url_configured(&config, "def", -4.13, 14.32, None, options).unwrap();
+///
+/// assert_eq!(url.path(), "/darksky/forecast/def/-4.13,14.32,2015-12-13T09:46:40+01:00");
+/// assert_eq!(url.query(), Some(""));
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidCoordinate`] if the coordinates are out of range,
+/// or [`Error::Url`] if the config's base URI is not a valid URL.
+///
+/// [`Config`]: ../constants/struct.Config.html
+/// [`Error::InvalidCoordinate`]: ../enum.Error.html#variant.InvalidCoordinate
+/// [`Error::Url`]: ../enum.Error.html#variant.Url
+/// [`uri_configured`]: fn.uri_configured.html
+#[cfg(feature = "url")]
+pub fn url_configured(
+    config: &Config,
+    token: &str,
+    lat: f64,
+    long: f64,
+    time: Option<String>,
+    options: Options,
+) -> Result<Url> {
+    validate_coordinates(lat, long)?;
+
+    let (lat, long) = config.snap(lat, long);
+    let time = time.as_ref().map(|time| &time[..]).or(options.get_time());
+
+    build_url(config.get_api_url(), token, lat, long, time, Some(&options))
+}
+
+/// Builds a typed URL for retrieving a forecast from the base URI of the API,
+/// appending the location to its path, and the options to its query, or
+/// `units=auto` without any.
+///
+/// The path is formatted as with the string builders, so the two agree on
+/// the coordinates and time, while the query is encoded by the URL itself.
+#[cfg(feature = "url")]
+fn build_url(
+    base: &str,
+    token: &str,
+    lat: f64,
+    long: f64,
+    time: Option<&str>,
+    options: Option<&Options>,
+) -> Result<Url> {
+    let mut url = Url::parse(base)?;
+    let mut path = url.path().trim_end_matches('/').to_owned();
+    push_path(&mut path, token, lat, long, time);
+    url.set_path(&path);
+
+    {
+        let mut query = url.query_pairs_mut();
+
+        match options {
+            Some(options) => {
+                if !options.exclude.is_empty() {
+                    let names: Vec<&str> = options.exclude.iter().map(Block::name).collect();
+                    query.append_pair("exclude", &names.join(","));
+                }
+
+                for (key, value) in options.named_pairs().iter() {
+                    if let Some(value) = value {
+                        query.append_pair(key, value);
+                    }
+                }
+            }
+            None => {
+                query.append_pair("units", "auto");
+            }
+        }
+    }
+
+    Ok(url)
+}
+
+/// The text replacing tokens masked by [`redact`].
+///
+/// [`redact`]: fn.redact.html
+pub const REDACTED: &str = "****";
+
+/// Masks the token in the path of every forecast URI in the text, such as a
+/// URI about to be logged or the message of an error.
+///
+/// The path segment following each `/forecast/` is replaced with
+/// [`REDACTED`]. The [`Error`]s of this library are masked this way when
+/// displayed or debugged.
+///
+/// # Examples
+///
+/// ```rust
+/// use darksky::utils;
+///
+/// let uri = utils::uri("0123456789abcdef", 49.9, -97.1);
+///
+/// assert_eq!(
+///     utils::redact(&uri),
+///     "https://api.darksky.net/forecast/****/49.9,-97.1?units=auto",
+/// );
+/// ```
+///
+/// [`Error`]: ../enum.Error.html
+/// [`REDACTED`]: constant.REDACTED.html
+pub fn redact(text: &str) -> Cow<'_, str> {
+    const PREFIX: &str = "/forecast/";
+
+    if !text.contains(PREFIX) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(idx) = rest.find(PREFIX) {
+        let (before, after) = rest.split_at(idx + PREFIX.len());
+        redacted.push_str(before);
+
+        let end = after
+            .find(|c: char| c == '/' || c == '?' || c == '#' || c == '"' || c.is_whitespace())
+            .unwrap_or(after.len());
+
+        if end > 0 {
+            redacted.push_str(REDACTED);
+        }
+
+        rest = &after[end..];
+    }
+
+    redacted.push_str(rest);
+
+    Cow::Owned(redacted)
+}
+
+/// Formats a URI for retrieving a forecast with options in the given
+/// language, if any.
+pub(crate) fn language_uri(
+    config: &Config,
+    token: &str,
+    location: Coordinate,
+    options: &Options,
+    language: Option<Language>,
+) -> Result<String> {
+    let options = match language {
+        Some(language) => options.clone().language(language),
+        None => options.clone(),
+    };
+
+    uri_configured(config, token, location.lat, location.lon, None, options)
+}
+
+/// Estimates the length of a URI, erring on the side of over-allocating.
+fn estimate_len(base: &str, token: &str, options: Option<&Options>) -> usize {
+    // "/forecast/", "/", "," and "?", two coordinates of up to 24 characters
+    // each, and a "," followed by a timestamp of up to 20 digits.
+    let fixed = base.len() + 13 + 48 + 21;
+    // Every option but the excluded blocks and extension fits in 64
+    // characters, and each block name in 10 with its ",".
+    let options = options.map_or("units=auto".len(), |options| {
+        let extend = options
+            .extend
+            .as_ref()
+            .map_or(0, |extend| extend.name().len());
+
+        64 + 10 * options.exclude.len() + extend
+    });
+
+    fixed + token.len() + options
+}
+
+fn push_location(
+    uri: &mut String,
+    base: &str,
+    token: &str,
+    lat: f64,
+    long: f64,
+    time: Option<&str>,
+) {
+    uri.push_str(base);
+    push_path(uri, token, lat, long, time);
+}
+
+/// Appends the path of a forecast request for a location to a URI, as
+/// shared by the string and [`Url`] builders.
+///
+/// [`Url`]: https://docs.rs/url/*/url/struct.Url.html
+fn push_path(uri: &mut String, token: &str, lat: f64, long: f64, time: Option<&str>) {
+    let mut buffer = ryu::Buffer::new();
+
     uri.push_str("/forecast/");
     uri.push_str(token);
     uri.push('/');
-    write!(uri, "{}", lat)?;
+    push_float(uri, &mut buffer, lat);
     uri.push(',');
-    write!(uri, "{}", long)?;
+    push_float(uri, &mut buffer, long);
 
     if let Some(time) = time {
         uri.push(',');
-        write!(uri, "{}", time)?;
+        push_encoded(uri, time, b":+");
     }
+}
 
+fn push_options(uri: &mut String, options: &Options) {
     uri.push('?');
+    options.push_query(uri);
+}
 
-    for (k, v) in options {
-        uri.push_str(k);
-        uri.push('=');
-
-        {
-            let v_bytes = v.into_bytes();
+/// Appends a value to a URI, percent-encoding every byte other than the
+/// unreserved characters of RFC 3986 and those given.
+pub(crate) fn push_encoded(uri: &mut String, value: &str, keep: &[u8]) {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
 
-            unsafe {
-                let bytes = uri.as_mut_vec();
-                bytes.extend(v_bytes);
-            }
+    for &byte in value.as_bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) || keep.contains(&byte) {
+            uri.push(char::from(byte));
+        } else {
+            uri.push('%');
+            uri.push(char::from(HEX[usize::from(byte >> 4)]));
+            uri.push(char::from(HEX[usize::from(byte & 0xf)]));
         }
-
-        uri.push('&');
     }
+}
 
-    Ok(uri)
+/// Appends a float to a URI as its `Display` implementation formats it.
+///
+/// ryu is used where its output matches, omitting the fractional part of
+/// whole numbers. It switches to scientific notation for small and large
+/// magnitudes, such as `1e-7`, which the API rejects, so those fall back to
+/// `Display`.
+pub(crate) fn push_float(uri: &mut String, buffer: &mut ryu::Buffer, value: f64) {
+    let formatted = buffer.format(value);
+
+    if formatted.contains('e') {
+        let _ = write!(uri, "{}", value);
+    } else {
+        uri.push_str(formatted.strip_suffix(".0").unwrap_or(formatted));
+    }
 }
@@ -0,0 +1,323 @@
+// ISC License (ISC)
+//
+// Copyright (c) 2016, Zeyla Hellyer <zey@zey.moe>
+//
+// Permission to use, copy, modify, and/or distribute this software for any
+// purpose with or without fee is hereby granted, provided that the above
+// copyright notice and this permission notice appear in all copies.
+//
+// THE SOFTWARE IS PROVIDED "AS IS" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+// WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+// MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY
+// SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES WHATSOEVER
+// RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN ACTION OF
+// CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF OR IN
+// CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+//! Webhook notifications for new and escalated alerts, sent with the `reqwest`
+//! crate's blocking client.
+//!
+//! # Examples
+//!
+//! Refer to the documentation for [`WebhookNotifier`].
+//!
+//! [`WebhookNotifier`]: struct.WebhookNotifier.html
+
+use alerts::{AlertEvent, AlertQuery, AlertWatcher};
+use locale;
+use models::{Alert, Forecast, Severity};
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+use serde_json;
+use std::thread;
+use std::time::Duration;
+use Result;
+
+/// Renders the payload for an alert event from a template.
+///
+/// The following placeholders are replaced, with their values escaped so that
+/// they can be placed inside of a JSON string:
+///
+/// - `{event}`: either `issued` or `escalated`;
+/// - `{title}`, `{description}`, `{uri}`: the respective alert fields;
+/// - `{severity}`: the alert's severity, e.g. `warning`;
+/// - `{previous_severity}`: the severity before escalation, or the current
+///   severity for newly issued alerts;
+/// - `{regions}`: the alert's regions, separated by commas;
+/// - `{time}`, `{expires}`: the respective Unix timestamps.
+///
+/// Any other braces, such as those of the JSON payload itself, are left as is.
+///
+/// # Examples
+///
+/// Render a Discord-compatible payload:
+///
+/// ```rust
+/// extern crate darksky;
+///
+/// use darksky::alerts::AlertEvent;
+/// use darksky::models::{Alert, Severity};
+/// use darksky::webhook;
+///
+/// # fn main() {
+/// let event = AlertEvent::Issued(Alert {
+///     expires: 1510036680,
+///     description: "Heavy snow expected.".to_owned(),
+///     title: "Winter \"Storm\" Warning".to_owned(),
+///     uri: "https://alerts.weather.gov/1".to_owned(),
+///     regions: vec!["Winnipeg".to_owned()],
+///     time: 1509993277,
+///     severity: Severity::Warning,
+/// });
+///
+/// let payload = webhook::render(r#"{"content": "{severity}: {title}"}"#, &event);
+///
+/// assert_eq!(payload, r#"{"content": "warning: Winter \"Storm\" Warning"}"#);
+/// # }
+/// ```
+pub fn render(template: &str, event: &AlertEvent) -> String {
+    let alert = event.alert();
+    let (name, previous) = match *event {
+        AlertEvent::Issued(ref alert) => ("issued", alert.severity),
+        AlertEvent::Escalated { previous, .. } => ("escalated", previous),
+    };
+
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    // Each placeholder is replaced in a single pass, so that braces within
+    // the substituted values are never treated as placeholders themselves.
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let tail = &rest[start..];
+
+        let value = tail.find('}').and_then(|end| {
+            let value = match &tail[1..end] {
+                "event" => name.to_owned(),
+                "title" => escape(&alert.title),
+                "description" => escape(&alert.description),
+                "uri" => escape(&alert.uri),
+                "severity" => locale::EN.severity(alert.severity).to_owned(),
+                "previous_severity" => locale::EN.severity(previous).to_owned(),
+                "regions" => escape(&alert.regions.join(", ")),
+                "time" => alert.time.to_string(),
+                "expires" => alert.expires.to_string(),
+                _ => return None,
+            };
+
+            Some((value, end))
+        });
+
+        match value {
+            Some((value, end)) => {
+                rendered.push_str(&value);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+
+    rendered.push_str(rest);
+
+    rendered
+}
+
+/// Posts a JSON payload to a webhook URL whenever a new or escalated alert is
+/// detected for a location.
+///
+/// By default the payload is an object containing the `event` (`issued` or
+/// `escalated`) and the full `alert`. A custom [template][`template`] can be
+/// used to match the payload format expected by services such as Slack or
+/// Discord.
+///
+/// Failed deliveries are retried [`retries`] times, doubling the
+/// [delay][`retry_delay`] between each attempt.
+///
+/// # Examples
+///
+/// Post to a Slack webhook on each new or escalated alert:
+///
+/// ```rust,no_run
+/// extern crate darksky;
+/// extern crate reqwest;
+///
+/// # use std::error::Error;
+/// #
+/// # fn try_main() -> Result<(), Box<dyn Error>> {
+/// use darksky::webhook::WebhookNotifier;
+/// use darksky::DarkskyReqwestRequester;
+/// use reqwest::blocking::Client;
+/// use std::env;
+///
+/// let token = env::var("FORECAST_TOKEN")?;
+/// let client = Client::new();
+///
+/// let mut notifier = WebhookNotifier::new("https://hooks.slack.com/services/T0/B0/X")
+///     .template(r#"{"text": "{severity}: {title} ({regions})"}"#)
+///     .retries(5);
+///
+/// let forecast = client.get_forecast(&token, (37.8267, -122.423))?;
+/// let events = notifier.notify(&forecast)?;
+///
+/// println!("Sent {} notifications", events.len());
+/// #     Ok(())
+/// # }
+/// #
+/// # fn main() {
+/// #     try_main().unwrap();
+/// # }
+/// ```
+///
+/// [`retries`]: #method.retries
+/// [`retry_delay`]: #method.retry_delay
+/// [`template`]: #method.template
+#[derive(Debug)]
+pub struct WebhookNotifier {
+    client: Client,
+    retries: u32,
+    retry_delay: Duration,
+    template: Option<String>,
+    url: String,
+    watcher: AlertWatcher,
+}
+
+impl WebhookNotifier {
+    /// Creates a notifier posting to the given URL, retrying failed deliveries
+    /// 3 times with an initial delay of 1 second.
+    pub fn new<S: Into<String>>(url: S) -> Self {
+        WebhookNotifier {
+            client: Client::new(),
+            retries: 3,
+            retry_delay: Duration::from_secs(1),
+            template: None,
+            url: url.into(),
+            watcher: AlertWatcher::new(),
+        }
+    }
+
+    /// Sets the client used to post payloads.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+
+        self
+    }
+
+    /// Sets a filter for the alerts that are notified about, such as a minimum
+    /// severity or a set of regions. Refer to [`AlertWatcher::filter`] for
+    /// details.
+    ///
+    /// [`AlertWatcher::filter`]: ../alerts/struct.AlertWatcher.html#method.filter
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: FnOnce(AlertQuery) -> AlertQuery,
+    {
+        self.watcher = self.watcher.filter(filter);
+
+        self
+    }
+
+    /// Sets the number of times a failed delivery is retried.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+
+        self
+    }
+
+    /// Sets the delay before the first retry. The delay is doubled after each
+    /// subsequent attempt.
+    pub fn retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+
+        self
+    }
+
+    /// Sets the template used to render payloads. Refer to [`render`] for the
+    /// available placeholders.
+    ///
+    /// [`render`]: fn.render.html
+    pub fn template<S: Into<String>>(mut self, template: S) -> Self {
+        self.template = Some(template.into());
+
+        self
+    }
+
+    /// Checks a forecast for new or escalated alerts, posting a payload for
+    /// each one.
+    ///
+    /// Returns the events that were sent.
+    pub fn notify(&mut self, forecast: &Forecast) -> Result<Vec<AlertEvent>> {
+        let events = self.watcher.check(forecast);
+
+        for event in &events {
+            self.send(event)?;
+        }
+
+        Ok(events)
+    }
+
+    /// Posts the payload for a single event, retrying on failure.
+    pub fn send(&self, event: &AlertEvent) -> Result<()> {
+        let payload = match self.template {
+            Some(ref template) => render(template, event),
+            None => default_payload(event)?,
+        };
+
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+
+        loop {
+            let res = self
+                .client
+                .post(&self.url)
+                .header(CONTENT_TYPE, "application/json")
+                .body(payload.clone())
+                .send()
+                .and_then(|res| res.error_for_status());
+
+            match res {
+                Ok(_) => return Ok(()),
+                Err(_) if attempt < self.retries => {
+                    attempt += 1;
+                    thread::sleep(delay);
+                    delay *= 2;
+                }
+                Err(why) => return Err(From::from(why)),
+            }
+        }
+    }
+}
+
+fn default_payload(event: &AlertEvent) -> Result<String> {
+    #[derive(Serialize)]
+    struct Payload<'a> {
+        event: &'a str,
+        alert: &'a Alert,
+        previous_severity: Option<Severity>,
+    }
+
+    let payload = match *event {
+        AlertEvent::Issued(ref alert) => Payload {
+            event: "issued",
+            alert,
+            previous_severity: None,
+        },
+        AlertEvent::Escalated {
+            ref alert,
+            previous,
+        } => Payload {
+            event: "escalated",
+            alert,
+            previous_severity: Some(previous),
+        },
+    };
+
+    serde_json::to_string(&payload).map_err(From::from)
+}
+
+fn escape(value: &str) -> String {
+    serde_json::to_string(value)
+        .map(|quoted| quoted[1..quoted.len() - 1].to_owned())
+        .unwrap_or_default()
+}
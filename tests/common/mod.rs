@@ -0,0 +1,118 @@
+//! Fixtures shared by the integration tests.
+
+#![allow(dead_code)]
+
+use darksky::bridge::send::{MetaSlot, ResponseMeta};
+use darksky::bridge::HttpSend;
+use darksky::constants::Config;
+use darksky::Result;
+use std::future::{self, Future, Ready};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+pub const BODY: &str = r#"{"latitude": 49.9, "longitude": -97.1, "timezone": "America/Winnipeg"}"#;
+
+/// A backend responding to every request with the same body, recording the
+/// URIs requested and the configurations they were sent with.
+#[derive(Clone)]
+pub struct Stub {
+    body: &'static str,
+    pub configs: Arc<Mutex<Vec<Config>>>,
+    pub uris: Arc<Mutex<Vec<String>>>,
+}
+
+impl Stub {
+    pub fn new(body: &'static str) -> Self {
+        Stub {
+            body,
+            configs: Arc::new(Mutex::new(Vec::new())),
+            uris: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// The number of requests sent.
+    pub fn calls(&self) -> usize {
+        self.uris.lock().unwrap().len()
+    }
+}
+
+impl Default for Stub {
+    fn default() -> Self {
+        Stub::new(BODY)
+    }
+}
+
+impl HttpSend for Stub {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        self.uris.lock().unwrap().push(uri);
+
+        future::ready(Ok(self.body.as_bytes().to_vec()))
+    }
+
+    fn send_with(&self, uri: String, config: &Config) -> Self::Future {
+        self.configs.lock().unwrap().push(config.clone());
+
+        self.send(uri)
+    }
+}
+
+/// A backend reading its responses into the buffers it is given, recording
+/// their capacities, and recording a status of 200 into the slots it is
+/// given.
+#[derive(Default)]
+pub struct Buffered {
+    pub capacities: Mutex<Vec<usize>>,
+}
+
+impl HttpSend for Buffered {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, _: String) -> Self::Future {
+        future::ready(Ok(BODY.as_bytes().to_vec()))
+    }
+
+    fn send_recording(&self, uri: String, _: Option<&Config>, slot: &MetaSlot) -> Self::Future {
+        record_ok(slot);
+
+        self.send(uri)
+    }
+
+    fn send_into(
+        &self,
+        _: String,
+        _: Option<&Config>,
+        slot: Option<&MetaSlot>,
+        mut buffer: Vec<u8>,
+    ) -> Self::Future {
+        self.capacities.lock().unwrap().push(buffer.capacity());
+
+        if let Some(slot) = slot {
+            record_ok(slot);
+        }
+
+        buffer.extend_from_slice(BODY.as_bytes());
+
+        future::ready(Ok(buffer))
+    }
+}
+
+/// Records a successful response into a slot.
+fn record_ok(slot: &MetaSlot) {
+    slot.record(ResponseMeta {
+        status: Some(200),
+        ..ResponseMeta::default()
+    });
+}
+
+/// Polls a future once, as stub backends respond immediately.
+pub fn resolve<F: Future + Unpin>(mut future: F) -> F::Output {
+    let mut cx = Context::from_waker(Waker::noop());
+
+    match Pin::new(&mut future).poll(&mut cx) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("stub future was not ready"),
+    }
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "air-quality")]
+
+extern crate darksky;
+extern crate serde_json;
+
+use darksky::air_quality::{self, AirQualityProvider, OpenAq};
+use darksky::models::Forecast;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+
+const LOCATIONS: &str = r#"{"results": [{"id": 2178, "name": "Winnipeg Ellen St", "sensors": [
+    {"id": 3917, "parameter": {"name": "pm25", "units": "µg/m³"}},
+    {"id": 3918, "parameter": {"name": "o3", "units": "ppm"}}
+]}]}"#;
+const LATEST: &str = r#"{"results": [
+    {"datetime": {"utc": "2017-11-06T18:00:00Z"}, "value": 12.0, "sensorsId": 3917},
+    {"datetime": {"utc": "2017-11-06T18:00:00Z"}, "value": 0.031, "sensorsId": 3918}
+]}"#;
+
+/// Serves a canned body for each request in turn, returning the request lines
+/// that were received.
+fn serve(bodies: &'static [&'static str]) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        bodies
+            .iter()
+            .map(|body| {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body,
+                )
+                .unwrap();
+
+                request_line
+            })
+            .collect()
+    });
+
+    (url, handle)
+}
+
+#[test]
+fn test_openaq_latest_readings() {
+    let (url, handle) = serve(&[LOCATIONS, LATEST]);
+    let provider = OpenAq::new("key").api_url(url).radius(5_000);
+
+    let air_quality = provider.air_quality(49.9, -97.1).unwrap().unwrap();
+    assert_eq!(air_quality.location.as_ref().unwrap(), "Winnipeg Ellen St");
+    assert_eq!(air_quality.reading("o3").unwrap().value, 0.031);
+    assert_eq!(air_quality.us_aqi(), Some(56));
+
+    let request_lines = handle.join().unwrap();
+    assert!(request_lines[0]
+        .starts_with("GET /v3/locations?coordinates=49.9,-97.1&radius=5000&limit=1 "));
+    assert!(request_lines[1].starts_with("GET /v3/locations/2178/latest "));
+}
+
+#[test]
+fn test_openaq_no_nearby_location() {
+    let (url, handle) = serve(&[r#"{"results": []}"#]);
+    let provider = OpenAq::new("key").api_url(url);
+
+    assert!(provider.air_quality(0.0, 0.0).unwrap().is_none());
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_enriched_forecast_envelope() {
+    let (url, handle) = serve(&[LOCATIONS, LATEST]);
+    let provider = OpenAq::new("key").api_url(url);
+    let forecast = Forecast::from_slice(
+        br#"{"latitude": 49.9, "longitude": -97.1, "timezone": "America/Winnipeg"}"#,
+    )
+    .unwrap();
+
+    let enriched = air_quality::enrich(forecast, &provider).unwrap();
+    let value = serde_json::to_value(&enriched).unwrap();
+    assert_eq!(value["timezone"], "America/Winnipeg");
+    assert_eq!(value["airQuality"]["readings"][0]["parameter"], "pm25");
+
+    handle.join().unwrap();
+}
@@ -0,0 +1,177 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+use darksky::backfill::{Backfill, BackfillStatus, Job, Scheduler};
+use darksky::bridge::limit::RateLimiter;
+use darksky::models::Forecast;
+use darksky::Result;
+use std::env;
+use std::fs;
+use std::time::{Duration, Instant, SystemTime};
+
+const DAY: u64 = 86_400;
+
+fn fetch(time: u64) -> Result<Forecast> {
+    Forecast::from_slice(
+        format!(
+            r#"{{"latitude": 49.9, "longitude": -97.1, "timezone": "America/Winnipeg",
+                 "currently": {{"time": {}}}}}"#,
+            time,
+        )
+        .as_bytes(),
+    )
+}
+
+#[test]
+fn test_backfill_resumes_under_quota() {
+    let path = env::temp_dir().join(format!("darksky-backfill-{}.json", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let mut stored = Vec::new();
+
+    let mut backfill = Backfill::new(0, 5 * DAY)
+        .daily_limit(2)
+        .progress_file(&path)
+        .unwrap();
+    let status = backfill
+        .run(fetch, |time, _| {
+            stored.push(time);
+
+            Ok(())
+        })
+        .unwrap();
+
+    assert!(matches!(status, BackfillStatus::QuotaExhausted { .. }));
+    assert_eq!(stored, [0, DAY]);
+    assert_eq!(backfill.remaining(), 3);
+
+    // A new run on the same day resumes from the file, with no quota left.
+    let mut backfill = Backfill::new(0, 5 * DAY)
+        .daily_limit(4)
+        .progress_file(&path)
+        .unwrap();
+    let status = backfill
+        .run(fetch, |time, forecast| {
+            assert_eq!(forecast.currently.unwrap().time, time);
+            stored.push(time);
+
+            Ok(())
+        })
+        .unwrap();
+
+    match status {
+        BackfillStatus::QuotaExhausted { resume_at } => assert!(resume_at > SystemTime::now()),
+        BackfillStatus::Complete => panic!("expected the quota to be exhausted"),
+    }
+    assert_eq!(stored, [0, DAY, 2 * DAY, 3 * DAY]);
+    assert_eq!(backfill.progress().next, 4 * DAY);
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_backfill_retries_failed_day() {
+    let mut backfill = Backfill::new(0, 2 * DAY);
+    let mut fail = true;
+
+    let result = backfill.run(fetch, |_, _| {
+        if fail {
+            fail = false;
+
+            Err(darksky::Error::InvalidToken("test"))
+        } else {
+            Ok(())
+        }
+    });
+    assert!(result.is_err());
+    assert_eq!(backfill.progress().next, 0);
+
+    let status = backfill.run(fetch, |_, _| Ok(())).unwrap();
+    assert_eq!(status, BackfillStatus::Complete);
+    assert_eq!(backfill.remaining(), 0);
+}
+
+fn jobs() -> Vec<Job> {
+    (0..3)
+        .flat_map(|day| {
+            vec![
+                Job::new((49.9, -97.1), day * DAY),
+                Job::new((45.4, -75.7), day * DAY),
+            ]
+        })
+        .collect()
+}
+
+#[test]
+fn test_scheduler_resumes_under_quota() {
+    let path = env::temp_dir().join(format!("darksky-scheduler-{}.json", std::process::id()));
+    let _ = fs::remove_file(&path);
+    let mut stored = Vec::new();
+
+    let mut scheduler = Scheduler::new(jobs())
+        .daily_limit(4)
+        .progress_file(&path)
+        .unwrap();
+    let status = scheduler
+        .run(
+            |job| fetch(job.time),
+            |job, forecast| {
+                assert_eq!(forecast.currently.unwrap().time, job.time);
+                stored.push(*job);
+
+                Ok(())
+            },
+        )
+        .unwrap();
+
+    assert!(matches!(status, BackfillStatus::QuotaExhausted { .. }));
+    assert_eq!(stored, &jobs()[..4]);
+    assert_eq!(scheduler.remaining(), 2);
+
+    // A new run resumes from the file, ignoring the jobs it was created with.
+    let mut scheduler = Scheduler::new(Vec::new())
+        .daily_limit(6)
+        .progress_file(&path)
+        .unwrap();
+    assert_eq!(scheduler.progress().pending, &jobs()[4..]);
+    assert_eq!(scheduler.progress().calls, 4);
+
+    let status = scheduler
+        .run(
+            |job| fetch(job.time),
+            |job, _| {
+                stored.push(*job);
+
+                Ok(())
+            },
+        )
+        .unwrap();
+
+    assert_eq!(status, BackfillStatus::Complete);
+    assert_eq!(stored, jobs());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_scheduler_paced() {
+    let mut scheduler = Scheduler::new(jobs()).rate_limiter(RateLimiter::new().per_second(1));
+    let mut sent = Vec::new();
+    let result = scheduler.run(
+        |job| {
+            sent.push(Instant::now());
+
+            if sent.len() == 2 {
+                Err(darksky::Error::Timeout)
+            } else {
+                fetch(job.time)
+            }
+        },
+        |_, _| Ok(()),
+    );
+
+    // The second job waited for its turn, failed, and is left in the queue.
+    assert!(result.is_err());
+    assert!(sent[1] - sent[0] >= Duration::from_millis(900));
+    assert_eq!(scheduler.remaining(), 5);
+}
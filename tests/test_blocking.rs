@@ -0,0 +1,38 @@
+#![cfg(feature = "blocking")]
+
+extern crate darksky;
+
+use darksky::blocking::{self, Client};
+use darksky::*;
+use std::env;
+
+#[test]
+fn test_new() {
+    Client::new().unwrap();
+}
+
+#[ignore]
+#[test]
+fn test_get_forecast() {
+    let token = env::var("FORECAST_TOKEN").expect("forecast token");
+
+    blocking::get_forecast(&token, (37.8267, -122.423)).unwrap_or_else(|why| panic!("{:?}", why));
+}
+
+#[ignore]
+#[test]
+fn test_reused_client() {
+    let token = env::var("FORECAST_TOKEN").expect("forecast token");
+    let client = Client::new().unwrap();
+
+    client
+        .get_forecast_with_options(&token, (19.2465, -99.1013), |opt| {
+            opt.exclude(vec![Block::Currently, Block::Daily])
+                .language(Language::Es)
+                .unit(Unit::Si)
+        })
+        .unwrap_or_else(|why| panic!("{:?}", why));
+    client
+        .get_forecast_time_machine(&token, (19.2465, -99.1013), 1_450_000_000, |opt| opt)
+        .unwrap_or_else(|why| panic!("{:?}", why));
+}
@@ -0,0 +1,236 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+mod common;
+
+use common::{resolve, Stub, BODY};
+use darksky::bridge::middleware::Layered;
+use darksky::bridge::send::{MetaSlot, ResponseMeta};
+use darksky::bridge::{DarkskyRequester, HttpSend};
+use darksky::cache::{CacheStore, Cached, FileStore, Freshness, MemoryStore};
+use darksky::constants::Config;
+use darksky::models::SharedForecast;
+use darksky::{Block, Error, Result};
+use std::future::{self, Ready};
+use std::sync::{Arc, Mutex};
+use std::{env, fs, process, thread};
+use std::time::Duration;
+
+#[test]
+fn test_cached_per_location_and_options() {
+    let client = Layered::new(Stub::new(BODY)).layer(Cached::new(MemoryStore::new(10)));
+
+    resolve(client.get_forecast("a", (49.9, -97.1))).unwrap();
+    resolve(client.get_forecast("b", (49.9, -97.1))).unwrap();
+    assert_eq!(client.get_ref().calls(), 1);
+
+    resolve(client.get_forecast("a", (49.8, -97.1))).unwrap();
+    assert_eq!(client.get_ref().calls(), 2);
+
+    let options = |o: darksky::Options| o.exclude(vec![Block::Minutely]);
+    resolve(client.get_forecast_with_options("a", (49.9, -97.1), options)).unwrap();
+    resolve(client.get_forecast_with_options("a", (49.9, -97.1), options)).unwrap();
+    assert_eq!(client.get_ref().calls(), 3);
+}
+
+#[test]
+fn test_cached_expiry() {
+    let cached = Cached::new(MemoryStore::new(10)).ttl(Duration::from_millis(10));
+    let client = Layered::new(Stub::new(BODY)).layer(cached);
+
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+    thread::sleep(Duration::from_millis(20));
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+
+    assert_eq!(client.get_ref().calls(), 2);
+}
+
+#[test]
+fn test_shared_forecast() {
+    let cached = Arc::new(Cached::new(MemoryStore::new(10)));
+    let client = Layered::new(Stub::new(BODY)).layer(Arc::clone(&cached));
+    let uri = "https://api.darksky.net/forecast/token/49.9,-97.1?units=auto";
+
+    assert!(cached.get_forecast(uri).unwrap().is_none());
+
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+
+    let shared = cached.get_forecast(uri).unwrap().unwrap();
+    let clone = shared.clone();
+
+    assert_eq!(clone.timezone, "America/Winnipeg");
+    assert!(SharedForecast::ptr_eq(&shared, &clone));
+}
+
+#[test]
+fn test_api_errors_not_cached() {
+    let stub = Stub::new(r#"{"code": 403, "error": "daily usage limit exceeded"}"#);
+    let client = Layered::new(stub).layer(Cached::new(MemoryStore::new(10)));
+
+    for _ in 0..2 {
+        match resolve(client.get_forecast("token", (49.9, -97.1))) {
+            Err(Error::Api(403, _)) => {}
+            other => panic!("expected an API error, got {:?}", other),
+        }
+    }
+
+    assert_eq!(client.get_ref().calls(), 2);
+}
+
+/// A backend recording a `Cache-Control` header with each response.
+struct Fresh(Stub, &'static str);
+
+impl HttpSend for Fresh {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        self.0.send(uri)
+    }
+
+    fn send_recording(&self, uri: String, _: Option<&Config>, slot: &MetaSlot) -> Self::Future {
+        slot.record(ResponseMeta {
+            freshness: Freshness::from_cache_control(self.1),
+            ..ResponseMeta::default()
+        });
+
+        self.send(uri)
+    }
+}
+
+#[test]
+fn test_cache_control() {
+    let store = Arc::new(MemoryStore::new(10));
+    let cached = Cached::new(Arc::clone(&store));
+    let client = Layered::new(Fresh(Stub::new(BODY), "max-age=3600")).layer(cached);
+
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+    assert_eq!(client.get_ref().0.calls(), 1);
+
+    let cached = Cached::new(Arc::clone(&store));
+    let client = Layered::new(Fresh(Stub::new(BODY), "no-store")).layer(cached);
+    resolve(client.get_forecast("token", (49.8, -97.1))).unwrap();
+    resolve(client.get_forecast("token", (49.8, -97.1))).unwrap();
+    assert_eq!(client.get_ref().0.calls(), 2);
+
+    let cached = Cached::new(store).cache_control(false);
+    let client = Layered::new(Fresh(Stub::new(BODY), "no-store")).layer(cached);
+    resolve(client.get_forecast("token", (49.7, -97.1))).unwrap();
+    resolve(client.get_forecast("token", (49.7, -97.1))).unwrap();
+    assert_eq!(client.get_ref().0.calls(), 1);
+}
+
+/// A backend failing every request after the first.
+#[derive(Default)]
+struct Flaky(Mutex<usize>);
+
+impl HttpSend for Flaky {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, _: String) -> Self::Future {
+        let mut calls = self.0.lock().unwrap();
+        *calls += 1;
+
+        future::ready(match *calls {
+            1 => Ok(BODY.as_bytes().to_vec()),
+            _ => Err(Error::Timeout),
+        })
+    }
+}
+
+#[test]
+fn test_stale_on_error() {
+    let stale = Arc::new(Mutex::new(None));
+    let recorded = Arc::clone(&stale);
+    let cached = Cached::new(MemoryStore::new(10))
+        .ttl(Duration::from_millis(1))
+        .stale_on_error(Duration::from_secs(60));
+    let client = Layered::new(Flaky::default())
+        .after(move |request, _| *recorded.lock().unwrap() = request.get_meta().stale)
+        .layer(cached);
+
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+    assert!(stale.lock().unwrap().is_none());
+    thread::sleep(Duration::from_millis(5));
+
+    let forecast = resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+    assert_eq!(forecast.timezone, "America/Winnipeg");
+    assert!(stale.lock().unwrap().is_some());
+
+    match resolve(client.get_forecast("token", (49.8, -97.1))) {
+        Err(Error::Timeout) => {}
+        other => panic!("expected a timeout, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_no_stale_by_default() {
+    let cached = Cached::new(MemoryStore::new(10)).ttl(Duration::from_millis(1));
+    let client = Layered::new(Flaky::default()).layer(cached);
+
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+    thread::sleep(Duration::from_millis(5));
+
+    assert!(resolve(client.get_forecast("token", (49.9, -97.1))).is_err());
+}
+
+#[test]
+fn test_memory_store_eviction() {
+    let store = MemoryStore::new(2);
+    let ttl = Duration::from_secs(60);
+
+    store.put("a", b"1".to_vec(), ttl).unwrap();
+    store.put("b", b"2".to_vec(), ttl).unwrap();
+    store.put("a", b"3".to_vec(), ttl).unwrap();
+    store.put("c", b"4".to_vec(), ttl).unwrap();
+
+    assert_eq!(store.get("a").unwrap(), Some(b"3".to_vec()));
+    assert_eq!(store.get("b").unwrap(), None);
+    assert_eq!(store.get("c").unwrap(), Some(b"4".to_vec()));
+
+    store.remove("a").unwrap();
+    assert_eq!(store.len(), 1);
+
+    store.clear();
+    assert!(store.is_empty());
+}
+
+#[test]
+fn test_file_store() {
+    let dir = env::temp_dir().join(format!("darksky-test-file-store-{}", process::id()));
+    let store = FileStore::new(&dir).unwrap();
+
+    store.put("fresh", b"1".to_vec(), Duration::from_secs(60)).unwrap();
+    store.put("stale", b"2".to_vec(), Duration::from_millis(0)).unwrap();
+    fs::write(dir.join("garbage.cache"), "not a response").unwrap();
+
+    assert_eq!(store.get("fresh").unwrap(), Some(b"1".to_vec()));
+    assert_eq!(store.get("missing").unwrap(), None);
+    assert_eq!(store.purge().unwrap(), 2);
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+    store.remove("fresh").unwrap();
+    assert_eq!(store.get("fresh").unwrap(), None);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_file_store_across_clients() {
+    let dir = env::temp_dir().join(format!("darksky-test-file-layer-{}", process::id()));
+
+    for _ in 0..2 {
+        let cached = Cached::new(FileStore::new(&dir).unwrap());
+        let client = Layered::new(Stub::new(BODY)).layer(cached);
+        resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+
+        assert!(client.get_ref().calls() <= 1);
+    }
+
+    let store = FileStore::new(&dir).unwrap();
+    let key = darksky::cache::cache_key("https://api.darksky.net/forecast/x/49.9,-97.1?units=auto");
+    assert!(store.get(&key).unwrap().unwrap().ends_with(BODY.as_bytes()));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+use darksky::bridge::cancel::CancelHandle;
+use darksky::{Error, Result};
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+/// A waker counting the number of times it has been woken.
+#[derive(Default)]
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn poll<F: Future + Unpin>(future: &mut F, waker: &Waker) -> Poll<F::Output> {
+    Pin::new(future).poll(&mut Context::from_waker(waker))
+}
+
+#[test]
+fn test_cancel_wakes_pending_requests() {
+    let counter = Arc::new(CountingWaker::default());
+    let waker = Waker::from(Arc::clone(&counter));
+    let handle = CancelHandle::new();
+    let mut first = handle.cancellable(future::pending::<Result<()>>());
+    let mut second = handle.cancellable(future::pending::<Result<()>>());
+
+    assert!(poll(&mut first, &waker).is_pending());
+    assert!(poll(&mut second, &waker).is_pending());
+    assert_eq!(counter.0.load(Ordering::SeqCst), 0);
+
+    handle.clone().cancel();
+    assert!(handle.is_cancelled());
+    assert_eq!(counter.0.load(Ordering::SeqCst), 2);
+
+    match poll(&mut first, &waker) {
+        Poll::Ready(Err(Error::Cancelled)) => {}
+        other => panic!("expected a cancellation, got {:?}", other),
+    }
+    match poll(&mut second, &waker) {
+        Poll::Ready(Err(Error::Cancelled)) => {}
+        other => panic!("expected a cancellation, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_cancel_before_polling() {
+    let handle = CancelHandle::new();
+    handle.cancel();
+
+    let mut request = handle.cancellable(future::ready(Ok(42)));
+
+    match poll(&mut request, Waker::noop()) {
+        Poll::Ready(Err(Error::Cancelled)) => {}
+        other => panic!("expected a cancellation, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_uncancelled_request_completes() {
+    let handle = CancelHandle::new();
+    let mut request = handle.cancellable(future::ready(Ok(42)));
+
+    match poll(&mut request, Waker::noop()) {
+        Poll::Ready(Ok(42)) => {}
+        other => panic!("expected the request to complete, got {:?}", other),
+    }
+    assert!(!handle.is_cancelled());
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+mod common;
+
+use common::{resolve, BODY};
+use darksky::bridge::circuit::CircuitBreaker;
+use darksky::bridge::middleware::Layered;
+use darksky::bridge::{DarkskyRequester, HttpSend};
+use darksky::{Error, Result};
+use std::future::{self, Ready};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// A backend timing out while it is down, counting the requests sent.
+#[derive(Default)]
+struct Upstream {
+    down: Mutex<bool>,
+    sent: Mutex<u32>,
+}
+
+impl Upstream {
+    fn set_down(&self, down: bool) {
+        *self.down.lock().unwrap() = down;
+    }
+
+    fn sent(&self) -> u32 {
+        *self.sent.lock().unwrap()
+    }
+}
+
+impl HttpSend for Upstream {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, _: String) -> Self::Future {
+        *self.sent.lock().unwrap() += 1;
+
+        if *self.down.lock().unwrap() {
+            future::ready(Err(Error::Timeout))
+        } else {
+            future::ready(Ok(BODY.as_bytes().to_vec()))
+        }
+    }
+}
+
+fn setup(cool_down: Duration) -> (Arc<CircuitBreaker>, Layered<Upstream>) {
+    let breaker = Arc::new(CircuitBreaker::new().threshold(3).cool_down(cool_down));
+    let client = Layered::new(Upstream::default()).layer(Arc::clone(&breaker));
+
+    (breaker, client)
+}
+
+#[test]
+fn test_opens_after_threshold() {
+    let (breaker, client) = setup(Duration::from_secs(60));
+    client.get_ref().set_down(true);
+
+    for _ in 0..3 {
+        match resolve(client.get_forecast("token", (49.9, -97.1))) {
+            Err(Error::Timeout) => {}
+            other => panic!("expected a timeout, got {:?}", other),
+        }
+    }
+
+    assert!(breaker.is_open());
+    assert_eq!(breaker.failures(), 3);
+
+    match resolve(client.get_forecast("token", (49.9, -97.1))) {
+        Err(Error::CircuitOpen(retry_in)) => assert!(retry_in <= Duration::from_secs(60)),
+        other => panic!("expected an open circuit, got {:?}", other),
+    }
+
+    assert_eq!(client.get_ref().sent(), 3);
+}
+
+#[test]
+fn test_success_resets_failures() {
+    let (breaker, client) = setup(Duration::from_secs(60));
+
+    client.get_ref().set_down(true);
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap_err();
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap_err();
+    assert_eq!(breaker.failures(), 2);
+
+    client.get_ref().set_down(false);
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+    assert_eq!(breaker.failures(), 0);
+    assert!(!breaker.is_open());
+}
+
+#[test]
+fn test_trial_after_cool_down() {
+    let (breaker, client) = setup(Duration::from_millis(20));
+    client.get_ref().set_down(true);
+
+    for _ in 0..3 {
+        resolve(client.get_forecast("token", (49.9, -97.1))).unwrap_err();
+    }
+
+    // A failed trial keeps the circuit open for another cool-down.
+    thread::sleep(Duration::from_millis(30));
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap_err();
+    assert_eq!(client.get_ref().sent(), 4);
+
+    match resolve(client.get_forecast("token", (49.9, -97.1))) {
+        Err(Error::CircuitOpen(_)) => {}
+        other => panic!("expected an open circuit, got {:?}", other),
+    }
+
+    // A successful trial closes it.
+    thread::sleep(Duration::from_millis(30));
+    client.get_ref().set_down(false);
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+    assert!(!breaker.is_open());
+    assert_eq!(client.get_ref().sent(), 5);
+}
+
+#[test]
+fn test_api_errors() {
+    for &(code, opens) in &[(403, false), (503, true)] {
+        let breaker = Arc::new(CircuitBreaker::new().threshold(1));
+        let client = Layered::new(Upstream::default())
+            .layer(Arc::clone(&breaker))
+            .after(move |_, response| {
+                *response = Ok(format!(r#"{{"code": {}, "error": "failed"}}"#, code).into_bytes());
+            });
+
+        match resolve(client.get_forecast("token", (49.9, -97.1))) {
+            Err(Error::Api(c, _)) => assert_eq!(c, code),
+            other => panic!("expected an API error, got {:?}", other),
+        }
+
+        assert_eq!(breaker.is_open(), opens);
+    }
+}
@@ -0,0 +1,75 @@
+#![cfg(any(feature = "hyper-native-tls", feature = "hyper-rustls"))]
+
+extern crate darksky;
+extern crate hyper;
+extern crate tokio;
+
+use darksky::client::{self, ConnectionConfig};
+use hyper::client::connect::Connect;
+use hyper::{Body, Client, Uri};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+fn connection_config() -> ConnectionConfig {
+    ConnectionConfig::new()
+        .max_idle_per_host(4)
+        .idle_timeout(Duration::from_secs(30))
+        .tcp_keepalive(Duration::from_secs(60))
+}
+
+/// Asserts that the client refuses to connect over plain HTTP, rather than
+/// failing to connect to the closed port.
+fn assert_https_only<C>(client: Client<C, Body>)
+where
+    C: Connect + Clone + Send + Sync + 'static,
+{
+    let runtime = Runtime::new().unwrap();
+    let uri = Uri::from_static("http://127.0.0.1:9/");
+
+    let why = runtime.block_on(client.get(uri)).unwrap_err();
+
+    let message = why.to_string();
+
+    assert!(why.is_connect());
+    assert!(
+        message.contains("https") || message.contains("scheme"),
+        "{}",
+        message,
+    );
+}
+
+#[cfg(feature = "hyper-native-tls")]
+#[test]
+fn test_hyper_native_tls_https_only() {
+    assert_https_only(client::hyper_native_tls());
+}
+
+#[cfg(feature = "hyper-rustls")]
+#[test]
+fn test_hyper_rustls_https_only() {
+    assert_https_only(client::hyper_rustls());
+}
+
+#[cfg(feature = "hyper-native-tls")]
+#[test]
+fn test_hyper_native_tls_with_config_https_only() {
+    assert_https_only(client::hyper_native_tls_with_config(&connection_config()));
+}
+
+#[cfg(feature = "hyper-rustls")]
+#[test]
+fn test_hyper_rustls_with_config_https_only() {
+    assert_https_only(client::hyper_rustls_with_config(&connection_config()));
+}
+
+#[cfg(feature = "hyper-rustls")]
+#[test]
+fn test_hyper_rustls_http2_https_only() {
+    use darksky::client::Http2;
+
+    for &http2 in &[Http2::Alpn, Http2::PriorKnowledge] {
+        let config = connection_config().http2(http2);
+
+        assert_https_only(client::hyper_rustls_with_config(&config));
+    }
+}
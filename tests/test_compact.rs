@@ -0,0 +1,17 @@
+#![cfg(feature = "compact")]
+
+extern crate darksky;
+extern crate serde_json;
+
+use darksky::models::compact::Datapoint;
+
+#[test]
+fn test_out_of_range_values_saturate() {
+    let datapoint: Datapoint =
+        serde_json::from_str(r#"{"time": 4294967296, "sunriseTime": 8589934593, "uvIndex": 300}"#)
+            .unwrap();
+
+    assert_eq!(datapoint.time, u32::MAX);
+    assert_eq!(datapoint.sunrise_time(), Some(u32::MAX));
+    assert_eq!(datapoint.uv_index, Some(u8::MAX));
+}
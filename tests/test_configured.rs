@@ -0,0 +1,162 @@
+#![cfg(feature = "reqwest-blocking")]
+
+extern crate darksky;
+extern crate reqwest;
+
+use darksky::bridge::configured::ConfiguredClient;
+use darksky::bridge::middleware::Layered;
+use darksky::constants::Config;
+use darksky::{Block, DarkskyReqwestRequester, Error, Language, Unit};
+use reqwest::blocking::Client;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const BODY: &str = r#"{"latitude": 49.9, "longitude": -97.1, "timezone": "America/Winnipeg"}"#;
+
+/// Serves a single request with a canned forecast, returning the request
+/// line and headers that were received.
+fn serve(status: &'static str) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 2 {
+            request_line.push_str(&line);
+            line.clear();
+        }
+
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nX-Forecast-API-Calls: 42\r\n\
+             Connection: close\r\n\r\n{}",
+            status,
+            BODY.len(),
+            BODY,
+        )
+        .unwrap();
+
+        request_line
+    });
+
+    (url, handle)
+}
+
+#[test]
+fn test_get_forecast_from_mirror() {
+    let (url, handle) = serve("200 OK");
+    let client = ConfiguredClient::new(Client::new(), Config::default().api_url(url));
+
+    let forecast = client.get_forecast("token", (49.9, -97.1)).unwrap();
+    assert_eq!(forecast.timezone, "America/Winnipeg");
+
+    let request_line = handle.join().unwrap();
+    assert!(request_line.starts_with("GET /forecast/token/49.9,-97.1?units=auto& "));
+}
+
+#[test]
+fn test_check_status_of_mirror() {
+    let (url, handle) = serve("403 Forbidden");
+    let config = Config::default().api_url(url).daily_limit(100);
+    let client = ConfiguredClient::new(Client::new(), config);
+
+    let health = client.check_status("token").unwrap();
+    assert!(health.reachable);
+    assert!(!health.authorized);
+    assert_eq!(
+        health.quota_remaining(client.config().get_daily_limit()),
+        Some(58)
+    );
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_get_forecast_timeout() {
+    // The connection is accepted by the OS, but never responded to.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let config = Config::default()
+        .api_url(format!("http://{}", listener.local_addr().unwrap()))
+        .timeout(Duration::from_millis(100));
+    let client = ConfiguredClient::new(Client::new(), config);
+
+    match client.get_forecast("token", (49.9, -97.1)) {
+        Err(Error::Timeout) => {}
+        other => panic!("expected a timeout, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_user_agent() {
+    let (url, handle) = serve("200 OK");
+    let config = Config::default().api_url(url).user_agent("test/1.0");
+    let client = ConfiguredClient::new(Client::new(), config);
+
+    client.get_forecast("token", (49.9, -97.1)).unwrap();
+
+    let request = handle.join().unwrap().to_lowercase();
+    assert!(request.contains("user-agent: test/1.0\r\n"), "{}", request);
+}
+
+#[test]
+fn test_default_options() {
+    let (url, handle) = serve("200 OK");
+    let client = ConfiguredClient::new(Client::new(), Config::default().api_url(url))
+        .default_options(|o| o.unit(Unit::Si).language(Language::De));
+
+    client.get_forecast("token", (49.9, -97.1)).unwrap();
+
+    let request_line = handle.join().unwrap();
+    let query = request_line.split(' ').nth(1).unwrap();
+    assert!(query.contains("units=si"), "{}", query);
+    assert!(query.contains("lang=de"), "{}", query);
+    assert!(!query.contains("units=auto"), "{}", query);
+}
+
+#[test]
+fn test_default_options_overridden() {
+    let (url, handle) = serve("200 OK");
+    let client = ConfiguredClient::new(Client::new(), Config::default().api_url(url))
+        .default_options(|o| o.unit(Unit::Si).language(Language::De));
+
+    client
+        .get_forecast_with_options("token", (49.9, -97.1), |o| {
+            o.language(Language::Fr).exclude(vec![Block::Minutely])
+        })
+        .unwrap();
+
+    let request_line = handle.join().unwrap();
+    let query = request_line.split(' ').nth(1).unwrap();
+    assert!(query.contains("units=si"), "{}", query);
+    assert!(query.contains("lang=fr"), "{}", query);
+    assert!(query.contains("exclude=minutely"), "{}", query);
+}
+
+#[test]
+fn test_layered_bridge() {
+    let (url, handle) = serve("200 OK");
+    let sent = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&sent);
+    let client = Layered::new(ConfiguredClient::new(
+        Client::new(),
+        Config::default().api_url(url),
+    ))
+    .before(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let forecast = client.get_forecast("token", (49.9, -97.1)).unwrap();
+    assert_eq!(forecast.timezone, "America/Winnipeg");
+    assert_eq!(sent.load(Ordering::SeqCst), 1);
+
+    handle.join().unwrap();
+}
@@ -0,0 +1,151 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+extern crate serde_json;
+
+use darksky::adapters::ensemble::{Ensemble, Outcome, Strategy};
+use darksky::models::Forecast;
+use darksky::{Coordinate, Error, Result};
+use std::future::{self, Future, Ready};
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+fn forecast(source: &str, temperature: f64, uv_index: u64) -> Forecast {
+    serde_json::from_str(&format!(
+        r#"{{
+            "latitude": 49.9,
+            "longitude": -97.1,
+            "timezone": "America/Winnipeg",
+            "currently": {{"time": 1509993277, "temperature": {t}, "uvIndex": {uv}, "icon": "snow"}},
+            "hourly": {{"data": [
+                {{"time": 1509991200, "temperature": {t}}},
+                {{"time": 1509994800, "temperature": {t}}}
+            ]}},
+            "flags": {{"sources": ["{source}"], "units": "si"}}
+        }}"#,
+        t = temperature,
+        uv = uv_index,
+        source = source,
+    ))
+    .unwrap()
+}
+
+/// A provider which is always up, with the given forecast.
+fn up(forecast: Forecast) -> impl Fn(Coordinate) -> Ready<Result<Forecast>> {
+    move |_| future::ready(Ok(forecast.clone()))
+}
+
+/// A provider which is always down.
+fn down(_: Coordinate) -> Ready<Result<Forecast>> {
+    future::ready(Err(Error::Timeout))
+}
+
+fn resolve<F: Future + Unpin>(mut future: F) -> F::Output {
+    let mut cx = Context::from_waker(Waker::noop());
+
+    match Pin::new(&mut future).poll(&mut cx) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("ensemble future was not ready"),
+    }
+}
+
+#[test]
+fn test_first_success() {
+    let ensemble = Ensemble::new(Strategy::FirstSuccess)
+        .provider("slow", |_| future::pending())
+        .provider("down", down)
+        .provider("up", up(forecast("up", 10.0, 1)));
+
+    match resolve(ensemble.get_forecast((49.9, -97.1))).unwrap() {
+        Outcome::First { provider, forecast } => {
+            assert_eq!(provider, "up");
+            assert_eq!(forecast.timezone, "America/Winnipeg");
+        }
+        other => panic!("expected the first forecast, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_first_success_all_failed() {
+    let ensemble = Ensemble::new(Strategy::FirstSuccess)
+        .provider("down", down)
+        .provider("also down", |_| future::ready(Err(Error::Cancelled)));
+
+    match resolve(ensemble.get_forecast((49.9, -97.1))) {
+        Err(Error::Timeout) => {}
+        other => panic!("expected the first provider's error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_all() {
+    let ensemble = Ensemble::new(Strategy::All)
+        .provider("down", down)
+        .provider("up", up(forecast("up", 10.0, 1)));
+
+    assert_eq!(ensemble.get_providers(), ["down", "up"]);
+
+    let outcome = resolve(ensemble.get_forecast((49.9, -97.1))).unwrap();
+    assert!(outcome.forecast().is_some());
+
+    match outcome {
+        Outcome::All(results) => {
+            assert_eq!(results.len(), 2);
+            assert_eq!(results[0].0, "down");
+            assert!(results[0].1.is_err());
+            assert_eq!(results[1].0, "up");
+            assert!(results[1].1.is_ok());
+        }
+        other => panic!("expected all results, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_blend() {
+    let ensemble = Ensemble::new(Strategy::Blend)
+        .provider("a", up(forecast("a", 10.0, 1)))
+        .provider("down", down)
+        .provider("b", up(forecast("b", 20.0, 2)));
+
+    let (providers, forecast) = match resolve(ensemble.get_forecast((49.9, -97.1))).unwrap() {
+        Outcome::Blended {
+            providers,
+            forecast,
+        } => (providers, forecast),
+        other => panic!("expected a blended forecast, got {:?}", other),
+    };
+
+    assert_eq!(providers, ["a", "b"]);
+
+    let currently = forecast.currently.unwrap();
+    assert_eq!(currently.time, 1_509_993_277);
+    assert_eq!(currently.temperature, Some(15.0));
+    assert_eq!(currently.uv_index, Some(2));
+
+    let hourly = forecast.hourly.unwrap().data.unwrap();
+    assert_eq!(hourly[0].temperature, Some(15.0));
+    assert_eq!(hourly[1].time, 1_509_994_800);
+
+    assert_eq!(forecast.flags.unwrap().sources.unwrap(), ["a", "b"]);
+}
+
+#[test]
+fn test_no_providers() {
+    let ensemble = Ensemble::new(Strategy::Blend);
+
+    match resolve(ensemble.get_forecast((49.9, -97.1))) {
+        Err(Error::NoProviders) => {}
+        other => panic!("expected no providers, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_coordinate() {
+    let ensemble = Ensemble::new(Strategy::FirstSuccess)
+        .provider("up", |_| -> Ready<Result<Forecast>> { panic!("requested") });
+
+    match resolve(ensemble.get_forecast((91.0, -97.1))) {
+        Err(Error::InvalidCoordinate(..)) => {}
+        other => panic!("expected an invalid coordinate, got {:?}", other),
+    }
+}
@@ -0,0 +1,225 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+mod common;
+
+use common::{resolve, Stub, BODY};
+use darksky::bridge::send::{MetaSlot, ResponseMeta};
+use darksky::bridge::HttpSend;
+use darksky::constants::Config;
+use darksky::{
+    Block, DarkskyClient, DarkskyClientBuilder, EnvToken, Error, Language, Result, TokenPool,
+    TokenProvider, Unit,
+};
+use std::borrow::Cow;
+use std::future::{self, Ready};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A backend reporting each call as the next of the day.
+#[derive(Clone, Default)]
+struct Counting {
+    calls: Arc<Mutex<u64>>,
+}
+
+impl HttpSend for Counting {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, _: String) -> Self::Future {
+        future::ready(Ok(BODY.as_bytes().to_vec()))
+    }
+
+    fn send_recording(&self, uri: String, _: Option<&Config>, slot: &MetaSlot) -> Self::Future {
+        let mut calls = self.calls.lock().unwrap();
+        *calls += 1;
+
+        slot.record(ResponseMeta {
+            api_calls: Some(*calls),
+            ..ResponseMeta::default()
+        });
+
+        self.send(uri)
+    }
+}
+
+#[test]
+fn test_forecast() {
+    let client = DarkskyClient::new(Stub::default(), "token");
+    let forecast = resolve(client.forecast((49.9, -97.1))).unwrap();
+
+    assert_eq!(forecast.timezone, "America/Winnipeg");
+    assert_eq!(
+        *client.get_ref().uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/token/49.9,-97.1?units=auto&"],
+    );
+}
+
+#[test]
+fn test_forecast_at_with_default_options() {
+    let client = DarkskyClient::new(Stub::default(), "token")
+        .default_options(|o| o.language(Language::Fr).unit(Unit::Si));
+
+    resolve(client.forecast_at((49.9, -97.1), 1_450_000_000)).unwrap();
+    resolve(client.forecast_with_options((49.9, -97.1), |o| o.unit(Unit::Us))).unwrap();
+
+    let uris = client.get_ref().uris.lock().unwrap();
+    assert!(uris[0].starts_with("https://api.darksky.net/forecast/token/49.9,-97.1,1450000000?"));
+    assert!(uris[0].contains("lang=fr&"));
+    assert!(uris[0].contains("units=si&"));
+    assert!(uris[1].contains("lang=fr&"));
+    assert!(uris[1].contains("units=us&"));
+}
+
+#[test]
+fn test_builder() {
+    let client = DarkskyClientBuilder::new("token")
+        .language(Language::Es)
+        .unit(Unit::Ca)
+        .exclude(vec![Block::Minutely])
+        .api_url("http://localhost:8080")
+        .timeout(Duration::from_secs(5))
+        .user_agent("test/1.0")
+        .build(Stub::default());
+
+    resolve(client.forecast((49.9, -97.1))).unwrap();
+
+    let uris = client.get_ref().uris.lock().unwrap();
+    assert!(uris[0].starts_with("http://localhost:8080/forecast/token/49.9,-97.1?"));
+    assert!(uris[0].contains("exclude=minutely&"));
+    assert!(uris[0].contains("lang=es&"));
+    assert!(uris[0].contains("units=ca&"));
+
+    let configs = client.get_ref().configs.lock().unwrap();
+    assert_eq!(configs[0].get_timeout(), Some(Duration::from_secs(5)));
+    assert_eq!(configs[0].get_user_agent(), Some("test/1.0"));
+}
+
+#[test]
+fn test_exclusions_merged() {
+    let client = DarkskyClientBuilder::new("token")
+        .exclude(vec![Block::Minutely, Block::Flags])
+        .build(Stub::default());
+
+    resolve(client.forecast_with_options((49.9, -97.1), |o| {
+        o.exclude(vec![Block::Hourly, Block::Minutely])
+    }))
+    .unwrap();
+
+    assert_eq!(
+        *client.get_ref().uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/token/49.9,-97.1?exclude=minutely,flags,hourly&units=auto&"],
+    );
+}
+
+#[test]
+fn test_with_api_url() {
+    let client = DarkskyClient::new(Stub::default(), "token")
+        .default_options(|o| o.language(Language::Fr));
+    let staging = client.with_api_url("http://staging:8080");
+
+    resolve(staging.forecast((49.9, -97.1))).unwrap();
+    resolve(client.forecast((49.9, -97.1))).unwrap();
+
+    let uris = client.get_ref().uris.lock().unwrap();
+    assert!(uris[0].starts_with("http://staging:8080/forecast/token/49.9,-97.1?"));
+    assert!(uris[0].contains("lang=fr&"));
+    assert!(uris[1].starts_with("https://api.darksky.net/forecast/token/49.9,-97.1?"));
+}
+
+/// A token provider whose token can be replaced while it is in use.
+#[derive(Default)]
+struct Rotating(Mutex<String>);
+
+impl TokenProvider for Rotating {
+    fn token(&self) -> Result<Cow<'_, str>> {
+        Ok(Cow::Owned(self.0.lock().unwrap().clone()))
+    }
+}
+
+#[test]
+fn test_token_provider_rotation() {
+    let provider = Arc::new(Rotating::default());
+    *provider.0.lock().unwrap() = "old".to_owned();
+
+    let client = DarkskyClient::new(Stub::default(), provider.clone());
+    resolve(client.forecast((49.9, -97.1))).unwrap();
+
+    *provider.0.lock().unwrap() = "new".to_owned();
+    resolve(client.forecast((49.9, -97.1))).unwrap();
+
+    assert_eq!(
+        *client.get_ref().uris.lock().unwrap(),
+        [
+            "https://api.darksky.net/forecast/old/49.9,-97.1?units=auto&",
+            "https://api.darksky.net/forecast/new/49.9,-97.1?units=auto&",
+        ],
+    );
+}
+
+#[test]
+fn test_token_provider_error() {
+    let client = DarkskyClient::new(
+        Stub::default(),
+        EnvToken::new("DARKSKY_TEST_FACADE_UNSET_TOKEN"),
+    );
+
+    match resolve(client.forecast((49.9, -97.1))) {
+        Err(Error::Env(_)) => {}
+        other => panic!("expected an environment variable error, got {:?}", other),
+    }
+
+    assert!(client.get_ref().uris.lock().unwrap().is_empty());
+}
+
+/// A backend rejecting requests made with the token `exhausted`.
+struct Quota;
+
+impl HttpSend for Quota {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        let body = if uri.contains("/exhausted/") {
+            r#"{"code": 403, "error": "daily usage limit exceeded"}"#
+        } else {
+            BODY
+        };
+
+        future::ready(Ok(body.as_bytes().to_vec()))
+    }
+}
+
+#[test]
+fn test_token_pool_failover() {
+    let pool = Arc::new(TokenPool::new(vec!["exhausted", "fresh"]));
+    let client = DarkskyClient::new(Quota, pool.clone());
+
+    match resolve(client.forecast((49.9, -97.1))) {
+        Err(Error::Api(403, _)) => {}
+        other => panic!("expected a quota error, got {:?}", other),
+    }
+
+    for _ in 0..2 {
+        resolve(client.forecast((49.9, -97.1))).unwrap();
+    }
+
+    let usage = pool.usage();
+    assert!(usage[0].exhausted);
+    assert_eq!((usage[0].calls, usage[0].errors), (1, 1));
+    assert_eq!((usage[1].calls, usage[1].errors), (2, 0));
+}
+
+#[test]
+fn test_calls_today() {
+    let client = DarkskyClient::new(Counting::default(), "token");
+    assert_eq!(client.calls_today(), None);
+
+    resolve(client.forecast((49.9, -97.1))).unwrap();
+    resolve(client.clone().forecast((49.9, -97.1))).unwrap();
+    assert_eq!(client.calls_today(), Some(2));
+
+    // Backends which don't record response metadata leave the count unknown.
+    let client = DarkskyClient::new(Stub::default(), "token");
+    resolve(client.forecast((49.9, -97.1))).unwrap();
+    assert_eq!(client.calls_today(), None);
+}
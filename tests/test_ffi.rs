@@ -0,0 +1,70 @@
+#![cfg(feature = "ffi")]
+
+extern crate darksky;
+
+use darksky::ffi::*;
+use std::os::raw::c_char;
+use std::ptr;
+
+const FORECAST: &str = r#"{
+    "latitude": 49.9,
+    "longitude": -97.1,
+    "timezone": "America/Winnipeg",
+    "currently": {"time": 1509993277, "summary": "Clear", "temperature": -3.5},
+    "daily": {"data": [{"time": 1509948000, "temperatureMax": 2.5, "temperatureMin": -8}]},
+    "alerts": [{"title": "Wind Advisory", "regions": [], "severity": "watch",
+                "time": 1509993277, "expires": 1510036680, "description": "", "uri": ""}]
+}"#;
+
+#[test]
+fn test_forecast_from_json() {
+    unsafe {
+        let forecast = darksky_forecast_from_json(FORECAST.as_ptr() as *const _, FORECAST.len());
+        assert!(!forecast.is_null());
+        assert_eq!(darksky_forecast_latitude(forecast), 49.9);
+
+        let currently = darksky_forecast_currently(forecast);
+        assert_eq!(darksky_datapoint_time(currently), 1509993277);
+        assert_eq!(darksky_datapoint_temperature(currently), -3.5);
+        assert!(darksky_datapoint_humidity(currently).is_nan());
+
+        assert_eq!(darksky_forecast_hourly_len(forecast), 0);
+        assert!(darksky_forecast_hourly(forecast, 0).is_null());
+        let today = darksky_forecast_daily(forecast, 0);
+        assert_eq!(darksky_datapoint_temperature_max(today), 2.5);
+
+        let alert = darksky_forecast_alert(forecast, 0);
+        assert_eq!(darksky_alert_severity(alert), DarkskySeverity::Watch);
+        assert_eq!(darksky_alert_expires(alert), 1510036680);
+
+        darksky_forecast_free(forecast);
+    }
+}
+
+#[test]
+fn test_forecast_from_invalid_json() {
+    let json = "{\"latitude\": 1}";
+
+    unsafe {
+        assert!(darksky_forecast_from_json(json.as_ptr() as *const _, json.len()).is_null());
+        assert!(darksky_forecast_from_json(ptr::null(), 0).is_null());
+    }
+}
+
+#[test]
+fn test_strings_are_truncated() {
+    let mut buffer = [0x7f as c_char; 8];
+
+    unsafe {
+        let forecast = darksky_forecast_from_json(FORECAST.as_ptr() as *const _, FORECAST.len());
+        let len = darksky_forecast_timezone(forecast, buffer.as_mut_ptr(), buffer.len());
+        assert_eq!(len, "America/Winnipeg".len());
+        let bytes = buffer.iter().map(|&c| c as u8).collect::<Vec<_>>();
+        assert_eq!(bytes, b"America\0");
+
+        let summary = darksky_forecast_currently(forecast);
+        assert_eq!(darksky_datapoint_summary(summary, ptr::null_mut(), 0), 5);
+
+        darksky_forecast_free(forecast);
+    }
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+extern crate serde_json;
+
+use darksky::format::{self, FormatOptions};
+use darksky::models::Forecast;
+
+#[test]
+fn test_only_active_alerts() {
+    let forecast: Forecast = serde_json::from_str(
+        r#"{
+        "latitude": 49.9,
+        "longitude": -97.1,
+        "timezone": "America/Winnipeg",
+        "offset": 0,
+        "currently": {"time": 1509993277},
+        "alerts": [
+            {"title": "Expired", "time": 1509900000, "expires": 1509990000, "severity": "advisory",
+             "description": "", "uri": "", "regions": []},
+            {"title": "Active", "time": 1509990000, "expires": 1510000000, "severity": "warning",
+             "description": "", "uri": "", "regions": []},
+            {"title": "Upcoming", "time": 1509995000, "expires": 1510010000, "severity": "watch",
+             "description": "", "uri": "", "regions": []}
+        ]
+    }"#,
+    )
+    .unwrap();
+
+    let rendered = format::forecast(&forecast, &FormatOptions::default());
+
+    assert!(rendered.contains("[warning] Active"), "{}", rendered);
+    assert!(!rendered.contains("Expired"), "{}", rendered);
+    assert!(!rendered.contains("Upcoming"), "{}", rendered);
+}
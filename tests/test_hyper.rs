@@ -5,19 +5,87 @@ extern crate darksky;
 extern crate futures;
 extern crate hyper;
 extern crate hyper_tls;
-extern crate tokio_core;
+extern crate tokio;
 
+use darksky::bridge::configured::ConfiguredClient;
+use darksky::constants::Config;
 use darksky::*;
-use futures::{future, Future};
+use futures::future;
 use hyper::client::HttpConnector;
 use hyper::{Body, Client};
 use hyper_tls::HttpsConnector;
 use std::env;
-use tokio_core::reactor::Core;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+use tokio::runtime::Runtime;
 
 #[inline]
 fn client() -> Client<HttpsConnector<HttpConnector>, Body> {
-    Client::builder().build(HttpsConnector::new(4).unwrap())
+    Client::builder().build(HttpsConnector::new())
+}
+
+#[test]
+fn test_get_forecast_from_mirror() {
+    let body = r#"{"latitude": 49.9, "longitude": -97.1, "timezone": "America/Winnipeg"}"#;
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 2 {
+            line.clear();
+        }
+
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body,
+        )
+        .unwrap();
+    });
+
+    let runtime = Runtime::new().unwrap();
+    let client = ConfiguredClient::new(Client::new(), Config::default().api_url(url));
+
+    let forecast = runtime
+        .block_on(client.get_forecast("token", (49.9, -97.1)))
+        .unwrap();
+    assert_eq!(forecast.timezone, "America/Winnipeg");
+
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_get_forecast_timeout() {
+    // The connection is accepted by the OS, but never responded to.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let runtime = Runtime::new().unwrap();
+
+    let client = ConfiguredClient::new(
+        Client::new(),
+        Config::default()
+            .api_url(url.clone())
+            .timeout(Duration::from_millis(100)),
+    );
+    match runtime.block_on(client.get_forecast("token", (49.9, -97.1))) {
+        Err(Error::Timeout) => {}
+        other => panic!("expected a timeout, got {:?}", other),
+    }
+
+    let client = ConfiguredClient::new(Client::new(), Config::default().api_url(url));
+    let future = client
+        .get_forecast("token", (49.9, -97.1))
+        .timeout(Duration::from_millis(100));
+    match runtime.block_on(future) {
+        Err(Error::Timeout) => {}
+        other => panic!("expected a timeout, got {:?}", other),
+    }
 }
 
 #[ignore]
@@ -25,79 +93,92 @@ fn client() -> Client<HttpsConnector<HttpConnector>, Body> {
 fn test_get_forecast() {
     let token = env::var("FORECAST_TOKEN").expect("forecast token");
 
-    let mut core = Core::new().unwrap();
+    let runtime = Runtime::new().unwrap();
     let client = client();
 
     let futures = vec![
-        client.get_forecast(&token[..], 37.8267, -122.423),
-        client.get_forecast(&token[..], 39.9042, 166.4074),
-        client.get_forecast(&token[..], 19.2465, -99.1013),
+        client.get_forecast(&token[..], (37.8267, -122.423)),
+        client.get_forecast(&token[..], (39.9042, 166.4074)),
+        client.get_forecast(&token[..], (19.2465, -99.1013)),
     ];
 
-    let done = future::join_all(futures)
-        .and_then(|_| {
-            assert!(true);
+    runtime
+        .block_on(future::try_join_all(futures))
+        .unwrap_or_else(|why| panic!("{:?}", why));
+}
 
-            Ok(())
-        }).or_else(|_| {
-            assert!(false);
+#[ignore]
+#[test]
+fn test_get_forecast_with_options() {
+    let token = env::var("FORECAST_TOKEN").expect("forecast token");
 
-            Err(())
-        });
+    let runtime = Runtime::new().unwrap();
+    let client = client();
 
-    core.run(done).expect("core err");
+    let req = client.get_forecast_with_options(&token[..], (19.2465, -99.1013), |opt| {
+        opt.exclude(vec![Block::Currently, Block::Daily])
+            .extend_hourly()
+            .language(Language::Es)
+            .unit(Unit::Si)
+    });
+
+    runtime
+        .block_on(req)
+        .unwrap_or_else(|why| panic!("{:?}", why));
 }
 
 #[ignore]
 #[test]
-fn test_get_forecast_with_options() {
+fn test_time_machine() {
     let token = env::var("FORECAST_TOKEN").expect("forecast token");
 
-    let mut core = Core::new().unwrap();
+    let runtime = Runtime::new().unwrap();
     let client = client();
 
-    let done = client
-        .get_forecast_with_options(&token[..], 19.2465, -99.1013, |opt| {
+    let req =
+        client.get_forecast_time_machine(&token[..], (19.2465, -99.1013), 1_450_000_000, |opt| {
             opt.exclude(vec![Block::Currently, Block::Daily])
                 .extend_hourly()
                 .language(Language::Es)
                 .unit(Unit::Si)
-        }).and_then(|_| {
-            assert!(true);
-
-            Ok(())
-        }).or_else(|_| {
-            assert!(false);
-
-            Err(())
         });
 
-    core.run(done).expect("core err");
+    runtime
+        .block_on(req)
+        .unwrap_or_else(|why| panic!("{:?}", why));
 }
 
 #[ignore]
 #[test]
-fn test_time_machine() {
+fn test_get_forecast_pooled() {
     let token = env::var("FORECAST_TOKEN").expect("forecast token");
 
-    let mut core = Core::new().unwrap();
-    let client = client();
+    let runtime = Runtime::new().unwrap();
+    let client = bridge::pool::PooledClient::new(client());
 
-    let done = client
-        .get_forecast_time_machine(&token[..], 19.2465, -99.1013, 1_450_000_000, |opt| {
-            opt.exclude(vec![Block::Currently, Block::Daily])
-                .extend_hourly()
-                .language(Language::Es)
-                .unit(Unit::Si)
-        }).map(|_forecast| {
-            assert!(true);
+    let futures = vec![
+        client.get_forecast(&token[..], (37.8267, -122.423)),
+        client.get_forecast(&token[..], (39.9042, 166.4074)),
+    ];
 
-            ()
-        }).map_err(|why| {
-            panic!("{:?}", why);
+    runtime
+        .block_on(future::try_join_all(futures))
+        .unwrap_or_else(|why| panic!("{:?}", why));
+    assert_eq!(client.pool().len(), 2);
+}
 
-            ()
-        });
+#[ignore]
+#[test]
+fn test_check_status() {
+    let token = env::var("FORECAST_TOKEN").expect("forecast token");
+
+    let runtime = Runtime::new().unwrap();
+    let client = client();
+
+    let health = runtime
+        .block_on(client.check_status(&token[..]))
+        .expect("status err");
 
-    core.run(done).expect("core err");
+    assert!(health.is_healthy());
+    assert!(health.api_calls.is_some());
 }
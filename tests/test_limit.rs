@@ -0,0 +1,107 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+mod common;
+
+use common::{resolve, Buffered, Stub, BODY};
+use darksky::bridge::limit::{RateLimited, RateLimiter};
+use darksky::bridge::retry::Delay;
+use darksky::bridge::send::MetaSlot;
+use darksky::bridge::{DarkskyRequester, HttpSend};
+use std::future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Wraps a backend, recording the delays waited out instead of sleeping.
+fn limited(limiter: RateLimiter, delays: &Arc<Mutex<Vec<Duration>>>) -> RateLimited<Stub> {
+    let delays = Arc::clone(delays);
+
+    RateLimited::new(Stub::default(), limiter).sleep(move |delay| -> Delay {
+        delays.lock().unwrap().push(delay);
+
+        Box::pin(future::ready(()))
+    })
+}
+
+#[test]
+fn test_unlimited() {
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let client = limited(RateLimiter::new(), &delays);
+
+    for _ in 0..10 {
+        resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+    }
+
+    assert_eq!(client.get_ref().calls(), 10);
+    assert!(delays.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_per_second() {
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let client = limited(RateLimiter::new().per_second(4), &delays);
+
+    for _ in 0..8 {
+        resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+    }
+
+    // The first 4 are a burst, and the rest are spaced 250ms apart.
+    let delays = delays.lock().unwrap();
+    assert_eq!(delays.len(), 4);
+
+    for (idx, delay) in delays.iter().enumerate() {
+        let expected = Duration::from_millis(250 * (idx as u64 + 1));
+
+        assert!(*delay <= expected, "{:?} for {}", delay, idx);
+        assert!(
+            *delay + Duration::from_millis(100) > expected,
+            "{:?} for {}",
+            delay,
+            idx
+        );
+    }
+}
+
+#[test]
+fn test_per_day() {
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let client = limited(RateLimiter::new().per_day(3), &delays);
+
+    for _ in 0..4 {
+        resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+    }
+
+    // The fourth waits until midnight UTC.
+    let delays = delays.lock().unwrap();
+    assert_eq!(delays.len(), 1);
+    assert!(delays[0] > Duration::from_secs(0));
+    assert!(delays[0] <= Duration::from_secs(86_400));
+}
+
+#[test]
+fn test_shared_limiter() {
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let limiter = RateLimiter::new().per_day(2);
+    let first = limited(limiter.clone(), &delays);
+    let second = limited(limiter, &delays);
+
+    resolve(first.get_forecast("token", (49.9, -97.1))).unwrap();
+    resolve(second.get_forecast("token", (49.9, -97.1))).unwrap();
+    assert!(delays.lock().unwrap().is_empty());
+
+    resolve(first.get_forecast("token", (49.9, -97.1))).unwrap();
+    assert_eq!(delays.lock().unwrap().len(), 1);
+}
+
+#[test]
+fn test_forwards_buffer_and_slot() {
+    let client = RateLimited::new(Buffered::default(), RateLimiter::new().per_second(1));
+    let slot = MetaSlot::new();
+
+    let body = client.send_into("uri".to_owned(), None, Some(&slot), Vec::with_capacity(64));
+
+    assert_eq!(resolve(body).unwrap(), BODY.as_bytes());
+    assert_eq!(*client.get_ref().capacities.lock().unwrap(), [64]);
+    assert_eq!(slot.get().status, Some(200));
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+mod common;
+
+use common::{resolve, Stub};
+use darksky::bridge::middleware::{Layered, Middleware, RequestContext};
+use darksky::bridge::DarkskyRequester;
+use darksky::{Error, Result};
+use std::sync::{Arc, Mutex};
+
+/// A layer recording when it runs, under a name.
+struct Record(&'static str, Arc<Mutex<Vec<String>>>);
+
+impl Middleware for Record {
+    fn before(&self, _: &mut RequestContext) -> Option<Result<Vec<u8>>> {
+        self.1.lock().unwrap().push(format!("before {}", self.0));
+
+        None
+    }
+
+    fn after(&self, _: &RequestContext, _: &mut Result<Vec<u8>>) {
+        self.1.lock().unwrap().push(format!("after {}", self.0));
+    }
+}
+
+/// A layer answering every request with an API error.
+struct Reject;
+
+impl Middleware for Reject {
+    fn before(&self, _: &mut RequestContext) -> Option<Result<Vec<u8>>> {
+        Some(Err(Error::Api(403, "daily usage limit exceeded".to_owned())))
+    }
+}
+
+#[test]
+fn test_layer_order() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let client = Layered::new(Stub::default())
+        .layer(Record("outer", Arc::clone(&log)))
+        .layer(Record("inner", Arc::clone(&log)));
+
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["before outer", "before inner", "after inner", "after outer"],
+    );
+}
+
+#[test]
+fn test_before_rewrites_uri() {
+    let client = Layered::new(Stub::default()).before(|request| request.set_token("secret"));
+
+    resolve(client.get_forecast("-", (49.9, -97.1))).unwrap();
+
+    assert_eq!(
+        *client.get_ref().uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/secret/49.9,-97.1?units=auto"],
+    );
+}
+
+#[test]
+fn test_short_circuit() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let client = Layered::new(Stub::default())
+        .layer(Record("outer", Arc::clone(&log)))
+        .layer(Reject)
+        .layer(Record("skipped", Arc::clone(&log)));
+
+    match resolve(client.get_forecast("token", (49.9, -97.1))) {
+        Err(Error::Api(403, _)) => {}
+        other => panic!("expected an API error, got {:?}", other),
+    }
+
+    assert!(client.get_ref().uris.lock().unwrap().is_empty());
+    assert_eq!(*log.lock().unwrap(), ["before outer", "after outer"]);
+}
+
+#[test]
+fn test_after_replaces_response() {
+    let client = Layered::new(Stub::default()).after(|_, response| {
+        *response = Err(Error::Api(500, "replaced".to_owned()));
+    });
+
+    match resolve(client.get_forecast("token", (49.9, -97.1))) {
+        Err(Error::Api(500, message)) => assert_eq!(message, "replaced"),
+        other => panic!("expected an API error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_debug_redacts_token() {
+    let request = RequestContext::new("https://api.darksky.net/forecast/secret/49.9,-97.1");
+
+    assert!(!format!("{:?}", request).contains("secret"));
+}
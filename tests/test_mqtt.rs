@@ -0,0 +1,51 @@
+#![cfg(feature = "mqtt")]
+
+extern crate darksky;
+extern crate serde_json;
+
+use darksky::models::Forecast;
+use darksky::mqtt::{self, Field, PublishOptions};
+
+fn forecast() -> Forecast {
+    serde_json::from_str(
+        r#"{
+            "latitude": 49.9,
+            "longitude": -97.1,
+            "timezone": "America/Winnipeg",
+            "currently": {"time": 1509993277, "temperature": -3.5}
+        }"#,
+    )
+    .unwrap()
+}
+
+fn topics(location: &str, template: &str) -> Vec<String> {
+    let options = PublishOptions::default()
+        .topic(template)
+        .fields(vec![Field::Temperature]);
+
+    mqtt::messages(location, &forecast(), &options)
+        .unwrap()
+        .into_iter()
+        .map(|message| message.topic)
+        .collect()
+}
+
+#[test]
+fn test_location_placeholders_not_expanded() {
+    assert_eq!(
+        topics("{field}", "darksky/{location}/{field}"),
+        ["darksky/{field}/temperature"],
+    );
+    assert_eq!(
+        topics("home", "{field}/{location}/{other}"),
+        ["temperature/home/{other}"],
+    );
+}
+
+#[test]
+fn test_location_reserved_characters_escaped() {
+    assert_eq!(
+        topics("north/#1+2", "darksky/{location}/{field}"),
+        ["darksky/north__1_2/temperature"],
+    );
+}
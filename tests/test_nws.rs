@@ -0,0 +1,242 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+use darksky::adapters::nws;
+use darksky::bridge::HttpSend;
+use darksky::models::{Icon, PrecipitationType, Severity};
+use darksky::{Error, Result};
+use std::future::{self, Future, Ready};
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll, Waker};
+
+const POINTS: &str = r#"{
+    "geometry": {"type": "Point", "coordinates": [-77.0352, 38.8894]},
+    "properties": {
+        "forecast": "https://api.weather.gov/gridpoints/LWX/96,70/forecast",
+        "forecastHourly": "https://api.weather.gov/gridpoints/LWX/96,70/forecast/hourly",
+        "timeZone": "America/New_York"
+    }
+}"#;
+
+const HOURLY: &str = r#"{"properties": {"periods": [
+    {
+        "startTime": "2023-11-14T18:00:00-05:00",
+        "isDaytime": false,
+        "temperature": 5,
+        "temperatureUnit": "C",
+        "probabilityOfPrecipitation": {"unitCode": "wmoUnit:percent", "value": 40},
+        "dewpoint": {"unitCode": "wmoUnit:degC", "value": 1.5},
+        "relativeHumidity": {"unitCode": "wmoUnit:percent", "value": 75},
+        "windSpeed": "18 km/h",
+        "windDirection": "NW",
+        "icon": "https://api.weather.gov/icons/land/night/rain_showers,40?size=small",
+        "shortForecast": "Chance Rain Showers"
+    },
+    {
+        "startTime": "2023-11-14T19:00:00-05:00",
+        "isDaytime": false,
+        "temperature": 41,
+        "temperatureUnit": "F",
+        "probabilityOfPrecipitation": {"unitCode": "wmoUnit:percent", "value": null},
+        "windSpeed": "5 to 15 mph",
+        "windDirection": "S",
+        "icon": "https://api.weather.gov/icons/land/night/few?size=small",
+        "shortForecast": "Mostly Clear"
+    }
+]}}"#;
+
+const DAILY: &str = r#"{"properties": {"periods": [
+    {
+        "startTime": "2023-11-14T18:00:00-05:00",
+        "isDaytime": false,
+        "temperature": 3,
+        "temperatureUnit": "C",
+        "icon": "https://api.weather.gov/icons/land/night/snow,60?size=medium",
+        "shortForecast": "Snow Likely"
+    },
+    {
+        "startTime": "2023-11-15T06:00:00-05:00",
+        "isDaytime": true,
+        "temperature": 12,
+        "temperatureUnit": "C",
+        "icon": "https://api.weather.gov/icons/land/day/tsra,60/rain,30?size=medium",
+        "shortForecast": "Thunderstorms"
+    },
+    {
+        "startTime": "2023-11-15T18:00:00-05:00",
+        "isDaytime": false,
+        "temperature": 4,
+        "temperatureUnit": "C",
+        "icon": "https://api.weather.gov/icons/land/night/ovc?size=medium",
+        "shortForecast": "Cloudy"
+    }
+]}}"#;
+
+const ALERTS: &str = r#"{"features": [
+    {"properties": {
+        "@id": "https://api.weather.gov/alerts/urn:oid:1",
+        "areaDesc": "District of Columbia",
+        "sent": "2023-11-14T12:00:00-05:00",
+        "expires": "2023-11-15T06:00:00-05:00",
+        "ends": "2023-11-15T12:00:00-05:00",
+        "event": "Flood Watch",
+        "headline": "Flood Watch issued November 14",
+        "description": "Flooding is possible."
+    }},
+    {"properties": {
+        "@id": "https://api.weather.gov/alerts/urn:oid:2",
+        "areaDesc": "Fairfax; Arlington",
+        "sent": "2023-11-14T13:00:00Z",
+        "expires": "2023-11-14T18:00:00Z",
+        "event": "Wind Advisory",
+        "description": "Gusty winds."
+    }}
+]}"#;
+
+/// A backend responding to each of the NWS's endpoints with a canned body,
+/// recording the URIs requested.
+struct Stub {
+    alerts: Option<&'static str>,
+    uris: Mutex<Vec<String>>,
+}
+
+impl Stub {
+    fn new() -> Self {
+        Stub {
+            alerts: Some(ALERTS),
+            uris: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl HttpSend for Stub {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        let body = if uri.contains("/points/") {
+            Some(POINTS)
+        } else if uri.contains("/forecast/hourly") {
+            Some(HOURLY)
+        } else if uri.contains("/forecast") {
+            Some(DAILY)
+        } else {
+            self.alerts
+        };
+
+        self.uris.lock().unwrap().push(uri);
+
+        future::ready(
+            body.map(|body| body.as_bytes().to_vec())
+                .ok_or(Error::Timeout),
+        )
+    }
+}
+
+fn resolve<F: Future + Unpin>(mut future: F) -> F::Output {
+    let mut cx = Context::from_waker(Waker::noop());
+
+    match Pin::new(&mut future).poll(&mut cx) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("stub future was not ready"),
+    }
+}
+
+#[test]
+fn test_get_forecast() {
+    let stub = Stub::new();
+    let forecast = resolve(nws::get_forecast(&stub, (38.8894, -77.0352))).unwrap();
+
+    assert_eq!(forecast.timezone, "America/New_York");
+    assert_eq!(forecast.offset, Some(-5.0));
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        [
+            "https://api.weather.gov/points/38.8894,-77.0352",
+            "https://api.weather.gov/alerts/active?point=38.8894,-77.0352",
+            "https://api.weather.gov/gridpoints/LWX/96,70/forecast/hourly?units=si",
+            "https://api.weather.gov/gridpoints/LWX/96,70/forecast?units=si",
+        ],
+    );
+}
+
+#[test]
+fn test_get_forecast_alerts_error() {
+    let stub = Stub {
+        alerts: None,
+        uris: Mutex::new(Vec::new()),
+    };
+
+    match resolve(nws::get_forecast(&stub, (38.8894, -77.0352))) {
+        Err(Error::Timeout) => {}
+        other => panic!("expected the alerts' error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_hourly() {
+    let forecast = nws::to_forecast(
+        POINTS.as_bytes(),
+        HOURLY.as_bytes(),
+        DAILY.as_bytes(),
+        ALERTS.as_bytes(),
+    )
+    .unwrap();
+    let currently = forecast.currently.unwrap();
+    let hourly = forecast.hourly.unwrap().data.unwrap();
+
+    assert_eq!(currently.time, 1_700_002_800);
+    assert_eq!(currently.temperature, Some(5.0));
+    assert_eq!(currently.humidity, Some(0.75));
+    assert_eq!(currently.precip_probability, Some(0.4));
+    assert_eq!(currently.icon, Some(Icon::Rain));
+    assert_eq!(currently.precip_type, Some(PrecipitationType::Rain));
+    assert_eq!(currently.wind_bearing, Some(315.0));
+    assert_eq!(currently.wind_speed, Some(5.0));
+
+    assert_eq!(hourly.len(), 2);
+    assert_eq!(hourly[1].temperature, Some(5.0));
+    assert_eq!(hourly[1].icon, Some(Icon::ClearNight));
+    assert_eq!(hourly[1].precip_probability, None);
+    assert_eq!(hourly[1].wind_bearing, Some(180.0));
+}
+
+#[test]
+fn test_daily() {
+    let forecast = nws::to_forecast(
+        POINTS.as_bytes(),
+        HOURLY.as_bytes(),
+        DAILY.as_bytes(),
+        ALERTS.as_bytes(),
+    )
+    .unwrap();
+    let daily = forecast.daily.unwrap().data.unwrap();
+
+    assert_eq!(daily.len(), 2);
+    // The evening of the 14th, starting at local midnight.
+    assert_eq!(daily[0].time, 1_699_938_000);
+    assert_eq!(daily[0].icon, Some(Icon::Snow));
+    assert_eq!(daily[1].time, 1_700_024_400);
+    assert_eq!(daily[1].icon, Some(Icon::Thunderstorm));
+}
+
+#[test]
+fn test_alerts() {
+    let alerts = nws::to_alerts(ALERTS.as_bytes()).unwrap();
+
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts[0].title, "Flood Watch issued November 14");
+    assert_eq!(alerts[0].severity, Severity::Watch);
+    assert_eq!(alerts[0].expires, 1_700_067_600);
+    assert_eq!(alerts[0].uri, "https://api.weather.gov/alerts/urn:oid:1");
+    assert_eq!(alerts[1].title, "Wind Advisory");
+    assert_eq!(alerts[1].severity, Severity::Advisory);
+    assert_eq!(alerts[1].regions, ["Fairfax", "Arlington"]);
+    assert_eq!(alerts[1].time, 1_699_966_800);
+}
+
+#[test]
+fn test_invalid_body() {
+    assert!(nws::to_alerts(b"{}").is_err());
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+use darksky::adapters::open_meteo;
+use darksky::bridge::HttpSend;
+use darksky::models::{Icon, PrecipitationType};
+use darksky::{Coordinate, Result};
+use std::future::{self, Future, Ready};
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll, Waker};
+
+const BODY: &str = r#"{
+    "latitude": 49.9,
+    "longitude": -97.1,
+    "timezone": "America/Winnipeg",
+    "utc_offset_seconds": -18000,
+    "current": {
+        "time": 1700000000,
+        "temperature_2m": -3.5,
+        "relative_humidity_2m": 75,
+        "apparent_temperature": -8.0,
+        "is_day": 0,
+        "precipitation": 0.0,
+        "weather_code": 0,
+        "cloud_cover": 25,
+        "pressure_msl": 1012.5,
+        "wind_speed_10m": 4.5,
+        "wind_direction_10m": 270,
+        "wind_gusts_10m": 9.0
+    },
+    "hourly": {
+        "time": [1700000000, 1700003600],
+        "temperature_2m": [-3.5, null],
+        "relative_humidity_2m": [75, 80],
+        "precipitation_probability": [50, 100],
+        "precipitation": [0.5, 1.0],
+        "weather_code": [71, 61],
+        "visibility": [2500, 10000],
+        "uv_index": [0.4, 1.6],
+        "is_day": [1, 1]
+    },
+    "daily": {
+        "time": [1699941600],
+        "weather_code": [95],
+        "temperature_2m_max": [2.0],
+        "temperature_2m_min": [-6.0],
+        "sunrise": [1699968000],
+        "sunset": [1700001000],
+        "precipitation_sum": [12.0],
+        "precipitation_probability_max": [100]
+    }
+}"#;
+
+/// A backend responding to every request with the same body, recording the
+/// URIs requested.
+struct Stub {
+    uris: Mutex<Vec<String>>,
+}
+
+impl HttpSend for Stub {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        self.uris.lock().unwrap().push(uri);
+
+        future::ready(Ok(BODY.as_bytes().to_vec()))
+    }
+}
+
+fn resolve<F: Future + Unpin>(mut future: F) -> F::Output {
+    let mut cx = Context::from_waker(Waker::noop());
+
+    match Pin::new(&mut future).poll(&mut cx) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("stub future was not ready"),
+    }
+}
+
+#[test]
+fn test_get_forecast() {
+    let stub = Stub {
+        uris: Mutex::new(Vec::new()),
+    };
+    let forecast = resolve(open_meteo::get_forecast(&stub, (49.9, -97.1))).unwrap();
+
+    assert_eq!(forecast.timezone, "America/Winnipeg");
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        [open_meteo::uri(Coordinate::new(49.9, -97.1))]
+    );
+}
+
+#[test]
+fn test_flags() {
+    let forecast = open_meteo::to_forecast(BODY.as_bytes()).unwrap();
+    let flags = forecast.flags.unwrap();
+
+    assert_eq!(flags.units.unwrap(), "si");
+    assert_eq!(flags.sources.unwrap(), ["open-meteo"]);
+}
+
+#[test]
+fn test_currently() {
+    let forecast = open_meteo::to_forecast(BODY.as_bytes()).unwrap();
+    let currently = forecast.currently.unwrap();
+
+    assert_eq!(forecast.offset, Some(-5.0));
+    assert_eq!(currently.time, 1_700_000_000);
+    assert_eq!(currently.temperature, Some(-3.5));
+    assert_eq!(currently.humidity, Some(0.75));
+    assert_eq!(currently.cloud_cover, Some(0.25));
+    assert_eq!(currently.icon, Some(Icon::ClearNight));
+    assert_eq!(currently.wind_bearing, Some(270.0));
+}
+
+#[test]
+fn test_hourly() {
+    let forecast = open_meteo::to_forecast(BODY.as_bytes()).unwrap();
+    let hourly = forecast.hourly.unwrap().data.unwrap();
+
+    assert_eq!(hourly.len(), 2);
+    assert_eq!(hourly[0].icon, Some(Icon::Snow));
+    assert_eq!(hourly[0].precip_type, Some(PrecipitationType::Snow));
+    assert_eq!(hourly[0].precip_probability, Some(0.5));
+    assert_eq!(hourly[0].visibility, Some(2.5));
+    assert_eq!(hourly[0].uv_index, Some(0));
+    assert_eq!(hourly[1].temperature, None);
+    assert_eq!(hourly[1].icon, Some(Icon::Rain));
+    assert_eq!(hourly[1].uv_index, Some(2));
+    assert_eq!(hourly[1].dew_point, None);
+}
+
+#[test]
+fn test_daily() {
+    let forecast = open_meteo::to_forecast(BODY.as_bytes()).unwrap();
+    let daily = forecast.daily.unwrap().data.unwrap();
+
+    assert_eq!(daily.len(), 1);
+    assert_eq!(daily[0].icon, Some(Icon::Thunderstorm));
+    assert_eq!(daily[0].precip_intensity, Some(0.5));
+    assert_eq!(daily[0].precip_probability, Some(1.0));
+}
+
+#[test]
+fn test_invalid_body() {
+    assert!(open_meteo::to_forecast(b"{}").is_err());
+}
@@ -0,0 +1,72 @@
+#![cfg(any(feature = "reqwest-blocking", feature = "ureq"))]
+
+extern crate darksky;
+
+use darksky::client::{self, Proxy};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread::{self, JoinHandle};
+
+/// Accepts a single connection as an HTTP proxy, refusing it after reading
+/// the request head, and returns the head that was received.
+fn proxy() -> (Proxy, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut head = Vec::new();
+
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+
+            if line.trim().is_empty() {
+                break;
+            }
+
+            head.push(line.trim().to_owned());
+        }
+
+        stream
+            .write_all(b"HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n")
+            .unwrap();
+
+        head
+    });
+
+    (Proxy::new(url).basic_auth("user", "hunter2"), handle)
+}
+
+fn assert_tunnelled(head: &[String]) {
+    assert_eq!(head[0], "CONNECT api.darksky.net:443 HTTP/1.1");
+    // "user:hunter2", base64-encoded.
+    assert!(head
+        .iter()
+        .any(|line| line.eq_ignore_ascii_case("proxy-authorization: Basic dXNlcjpodW50ZXIy")));
+}
+
+#[cfg(feature = "reqwest-blocking")]
+#[test]
+fn test_reqwest_blocking_proxy() {
+    use darksky::DarkskyReqwestRequester;
+
+    let (proxy, handle) = proxy();
+    let client = client::reqwest_blocking_with_proxy(&proxy).unwrap();
+
+    assert!(client.get_forecast("token", (49.9, -97.1)).is_err());
+    assert_tunnelled(&handle.join().unwrap());
+}
+
+#[cfg(feature = "ureq")]
+#[test]
+fn test_ureq_proxy() {
+    use darksky::DarkskyUreqRequester;
+
+    let (proxy, handle) = proxy();
+    let agent = client::ureq_with_proxy(&proxy).unwrap();
+
+    assert!(agent.get_forecast("token", (49.9, -97.1)).is_err());
+    assert_tunnelled(&handle.join().unwrap());
+}
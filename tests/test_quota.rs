@@ -0,0 +1,142 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+mod common;
+
+use common::{resolve, BODY};
+use darksky::bridge::send::{MetaSlot, ResponseMeta};
+use darksky::bridge::HttpSend;
+use darksky::constants::Config;
+use darksky::quota::{DailyCalls, FileQuotaStore, QuotaStore};
+use darksky::{DarkskyClient, DarkskyClientBuilder, Result};
+use std::env;
+use std::fs;
+use std::future::{self, Ready};
+use std::path::PathBuf;
+
+/// A backend reporting the same number of calls made today on every
+/// response.
+struct Reporting(u64);
+
+impl HttpSend for Reporting {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, _: String) -> Self::Future {
+        future::ready(Ok(BODY.as_bytes().to_vec()))
+    }
+
+    fn send_recording(&self, uri: String, _: Option<&Config>, slot: &MetaSlot) -> Self::Future {
+        slot.record(ResponseMeta {
+            api_calls: Some(self.0),
+            ..ResponseMeta::default()
+        });
+
+        self.send(uri)
+    }
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("darksky-{}-{}.json", name, std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    path
+}
+
+#[test]
+fn test_file_store() {
+    let path = temp_path("quota-file");
+    let store = FileQuotaStore::new(&path);
+
+    assert_eq!(store.load().unwrap(), None);
+
+    store
+        .save(DailyCalls {
+            day: 17_476,
+            calls: 42,
+        })
+        .unwrap();
+    assert_eq!(
+        store.load().unwrap(),
+        Some(DailyCalls {
+            day: 17_476,
+            calls: 42
+        })
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_count_outlives_client() {
+    let path = temp_path("quota-client");
+
+    let client = DarkskyClient::new(Reporting(42), "token").quota_store(FileQuotaStore::new(&path));
+    resolve(client.forecast((49.9, -97.1))).unwrap();
+    assert_eq!(client.calls_today(), Some(42));
+    drop(client);
+
+    // A later run knows the count before its first response.
+    let client = DarkskyClientBuilder::new("token")
+        .quota_store(FileQuotaStore::new(&path))
+        .build(Reporting(43));
+    assert_eq!(client.calls_today(), Some(42));
+
+    resolve(client.forecast((49.9, -97.1))).unwrap();
+    assert_eq!(
+        FileQuotaStore::new(&path).load().unwrap(),
+        Some(DailyCalls::today(43))
+    );
+
+    // A lower count from a response arriving late is ignored.
+    let late = DarkskyClient::new(Reporting(40), "token").quota_store(FileQuotaStore::new(&path));
+    resolve(late.forecast((49.9, -97.1))).unwrap();
+    assert_eq!(late.calls_today(), Some(43));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_yesterday_ignored() {
+    let path = temp_path("quota-yesterday");
+    let store = FileQuotaStore::new(&path);
+    let today = DailyCalls::today(0).day;
+    store
+        .save(DailyCalls {
+            day: today - 1,
+            calls: 999,
+        })
+        .unwrap();
+
+    let client = DarkskyClient::new(Reporting(1), "token").quota_store(store);
+    assert_eq!(client.calls_today(), None);
+
+    resolve(client.forecast((49.9, -97.1))).unwrap();
+    assert_eq!(client.calls_today(), Some(1));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "quota-sqlite")]
+#[test]
+fn test_sqlite_store() {
+    use darksky::quota::SqliteQuotaStore;
+
+    let path = temp_path("quota-sqlite");
+    let first = SqliteQuotaStore::open(&path).unwrap();
+    let second = SqliteQuotaStore::open(&path).unwrap().key("other");
+
+    first.save(DailyCalls::today(7)).unwrap();
+    assert_eq!(first.load().unwrap(), Some(DailyCalls::today(7)));
+    assert_eq!(second.load().unwrap(), None);
+
+    let client = DarkskyClient::new(Reporting(8), "token").quota_store(first);
+    assert_eq!(client.calls_today(), Some(7));
+    resolve(client.forecast((49.9, -97.1))).unwrap();
+    assert_eq!(
+        SqliteQuotaStore::open(&path).unwrap().load().unwrap(),
+        Some(DailyCalls::today(8))
+    );
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+mod common;
+
+use common::Stub;
+use darksky::bridge::limit::{RateLimited, RateLimiter};
+use darksky::bridge::retry::Retrying;
+use darksky::bridge::HttpSend;
+use darksky::utils::{self, REDACTED};
+use darksky::Error;
+use std::io::Error as IoError;
+
+#[test]
+fn test_redact_without_token() {
+    let text = "connection refused (os error 111)";
+
+    assert_eq!(utils::redact(text), text);
+}
+
+#[test]
+fn test_redact_mirror() {
+    assert_eq!(
+        utils::redact("http://127.0.0.1:8080/forecast/SECRET/49.9,-97.1?units=si"),
+        format!(
+            "http://127.0.0.1:8080/forecast/{}/49.9,-97.1?units=si",
+            REDACTED
+        ),
+    );
+}
+
+#[test]
+fn test_redact_multiple() {
+    let text = "error for url (https://api.darksky.net/forecast/ONE/1,2): \
+                redirected to \"https://api.darksky.net/forecast/TWO\"";
+
+    let redacted = utils::redact(text);
+
+    assert!(!redacted.contains("ONE"), "{}", redacted);
+    assert!(!redacted.contains("TWO"), "{}", redacted);
+    assert!(redacted.ends_with("/forecast/****\""), "{}", redacted);
+}
+
+#[test]
+fn test_error_redacted() {
+    let io = IoError::other("failed to fetch https://api.darksky.net/forecast/SECRET/1,2");
+    let why = Error::from(io);
+
+    let display = why.to_string();
+    let debug = format!("{:?}", why);
+    let pretty = format!("{:#?}", why);
+
+    for text in &[&display, &debug, &pretty] {
+        assert!(!text.contains("SECRET"), "{}", text);
+        assert!(text.contains(REDACTED), "{}", text);
+    }
+
+    assert!(debug.starts_with("Io("), "{}", debug);
+}
+
+#[test]
+fn test_retry_future_redacted() {
+    let client = Retrying::new(Stub::default());
+    let future = client.send("https://api.darksky.net/forecast/SECRET/1,2".to_owned());
+
+    let debug = format!("{:?}", future);
+    assert!(!debug.contains("SECRET"), "{}", debug);
+    assert!(debug.contains(REDACTED), "{}", debug);
+}
+
+#[test]
+fn test_limited_future_redacted() {
+    let client = RateLimited::new(Stub::default(), RateLimiter::new().per_second(1));
+    let future = client.send("https://api.darksky.net/forecast/SECRET/1,2".to_owned());
+
+    let debug = format!("{:?}", future);
+    assert!(!debug.contains("SECRET"), "{}", debug);
+    assert!(debug.contains(REDACTED), "{}", debug);
+}
+
+#[cfg(feature = "reqwest-blocking")]
+#[test]
+fn test_reqwest_error_redacted() {
+    extern crate reqwest;
+
+    let why: Error = reqwest::blocking::get("http://127.0.0.1:9/forecast/SECRET/1,2")
+        .unwrap_err()
+        .into();
+
+    assert!(!why.to_string().contains("SECRET"), "{}", why);
+    assert!(!format!("{:?}", why).contains("SECRET"), "{:?}", why);
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "http")]
+
+extern crate darksky;
+extern crate http;
+
+use darksky::{request, Block, Error, Unit};
+
+#[test]
+fn test_forecast_request() {
+    let req = request::forecast("token", (49.9, -97.1), |o| {
+        o.exclude(vec![Block::Minutely]).unit(Unit::Si)
+    })
+    .unwrap();
+
+    assert_eq!(req.method(), "GET");
+    assert_eq!(req.uri().host(), Some("api.darksky.net"));
+    assert_eq!(req.uri().path(), "/forecast/token/49.9,-97.1");
+
+    let query = req.uri().query().unwrap();
+    assert!(query.contains("exclude=minutely"));
+    assert!(query.contains("units=si"));
+}
+
+#[test]
+fn test_invalid_token() {
+    match request::forecast("not a token", (49.9, -97.1), |o| o) {
+        Err(Error::Uri(_)) => {}
+        other => panic!("expected a URI error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_error_response() {
+    let body = br#"{"code": 403, "error": "permission denied"}"#;
+
+    assert!(request::parse_response(body).is_err());
+}
+
+#[test]
+fn test_api_calls() {
+    let response = http::Response::builder()
+        .header("X-Forecast-API-Calls", " 42")
+        .body(())
+        .unwrap();
+
+    assert_eq!(request::api_calls(&response), Some(42));
+    assert!(request::api_calls(&http::Response::new(())).is_none());
+}
+
+#[test]
+fn test_freshness() {
+    let response = http::Response::builder()
+        .header("Cache-Control", "public, no-cache")
+        .body(())
+        .unwrap();
+    let freshness = request::freshness(&response).unwrap();
+
+    assert!(!freshness.is_fresh());
+    assert!(request::freshness(&http::Response::new(())).is_none());
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "reqwest-async")]
+
+extern crate darksky;
+extern crate reqwest;
+extern crate tokio;
+
+use darksky::bridge::configured::ConfiguredClient;
+use darksky::constants::Config;
+use darksky::DarkskyReqwestAsyncRequester;
+use reqwest::Client;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
+
+const BODY: &str = r#"{"latitude": 49.9, "longitude": -97.1, "timezone": "America/Winnipeg"}"#;
+
+#[tokio::test]
+async fn test_get_forecast_from_mirror() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+
+        let mut line = String::new();
+        while reader.read_line(&mut line).unwrap() > 2 {
+            line.clear();
+        }
+
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            BODY.len(),
+            BODY,
+        )
+        .unwrap();
+
+        request_line
+    });
+
+    let client = ConfiguredClient::new(Client::new(), Config::default().api_url(url));
+
+    let forecast = client.get_forecast("token", (49.9, -97.1)).await.unwrap();
+    assert_eq!(forecast.timezone, "America/Winnipeg");
+
+    let request_line = handle.join().unwrap();
+    assert!(request_line.starts_with("GET /forecast/token/49.9,-97.1?units=auto& "));
+}
@@ -0,0 +1,215 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+extern crate tokio;
+
+mod common;
+
+use common::{resolve, Buffered, BODY};
+use darksky::bridge::retry::{Delay, RetryPolicy, Retrying};
+use darksky::bridge::send::{MetaSlot, ResponseMeta};
+use darksky::bridge::{DarkskyRequester, HttpSend};
+use darksky::constants::Config;
+use darksky::{Error, Result};
+use std::future::{self, Ready};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::runtime::Runtime;
+
+/// A backend failing with the given responses before responding with a
+/// forecast, counting the requests sent.
+struct Flaky {
+    failures: Mutex<Vec<Result<Vec<u8>>>>,
+    sent: Mutex<u32>,
+}
+
+impl Flaky {
+    fn new(failures: Vec<Result<Vec<u8>>>) -> Self {
+        Flaky {
+            failures: Mutex::new(failures),
+            sent: Mutex::new(0),
+        }
+    }
+
+    fn sent(&self) -> u32 {
+        *self.sent.lock().unwrap()
+    }
+}
+
+impl HttpSend for Flaky {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, _: String) -> Self::Future {
+        *self.sent.lock().unwrap() += 1;
+
+        let mut failures = self.failures.lock().unwrap();
+
+        if failures.is_empty() {
+            future::ready(Ok(BODY.as_bytes().to_vec()))
+        } else {
+            future::ready(failures.remove(0))
+        }
+    }
+}
+
+/// A backend behind a proxy, answering with a page that is not JSON and the
+/// given statuses before responding with a forecast.
+struct Proxied {
+    statuses: Mutex<Vec<u16>>,
+    sent: Mutex<u32>,
+}
+
+impl HttpSend for Proxied {
+    type Future = Ready<Result<Vec<u8>>>;
+
+    fn send(&self, uri: String) -> Self::Future {
+        self.send_recording(uri, None, &MetaSlot::new())
+    }
+
+    fn send_recording(&self, _: String, _: Option<&Config>, slot: &MetaSlot) -> Self::Future {
+        *self.sent.lock().unwrap() += 1;
+
+        let mut statuses = self.statuses.lock().unwrap();
+        let (status, body) = if statuses.is_empty() {
+            (200, BODY)
+        } else {
+            (statuses.remove(0), "<html>Unavailable</html>")
+        };
+
+        slot.record(ResponseMeta {
+            status: Some(status),
+            ..ResponseMeta::default()
+        });
+
+        future::ready(Ok(body.as_bytes().to_vec()))
+    }
+}
+
+fn api_error(code: u16) -> Result<Vec<u8>> {
+    Ok(format!(r#"{{"code": {}, "error": "failed"}}"#, code).into_bytes())
+}
+
+/// Wraps a backend, recording the delays waited out instead of sleeping.
+fn retrying(backend: Flaky, delays: &Arc<Mutex<Vec<Duration>>>) -> Retrying<Flaky> {
+    let delays = Arc::clone(delays);
+
+    Retrying::new(backend)
+        .policy(RetryPolicy::new().jitter(false).max_attempts(4))
+        .sleep(move |delay| -> Delay {
+            delays.lock().unwrap().push(delay);
+
+            Box::pin(future::ready(()))
+        })
+}
+
+#[test]
+fn test_retries_transient_failures() {
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let client = retrying(
+        Flaky::new(vec![Err(Error::Timeout), api_error(503)]),
+        &delays,
+    );
+
+    let forecast = resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+
+    assert_eq!(forecast.timezone, "America/Winnipeg");
+    assert_eq!(client.get_ref().sent(), 3);
+    assert_eq!(
+        *delays.lock().unwrap(),
+        [Duration::from_millis(200), Duration::from_millis(400)],
+    );
+}
+
+#[test]
+fn test_retries_failed_statuses() {
+    let client = Retrying::new(Proxied {
+        statuses: Mutex::new(vec![502, 429]),
+        sent: Mutex::new(0),
+    })
+    .policy(RetryPolicy::new().max_attempts(4))
+    .sleep(|_| -> Delay { Box::pin(future::ready(())) });
+
+    let forecast = resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+
+    assert_eq!(forecast.timezone, "America/Winnipeg");
+    assert_eq!(*client.get_ref().sent.lock().unwrap(), 3);
+}
+
+#[test]
+fn test_max_attempts() {
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let failures = (0..10).map(|_| Err(Error::Timeout)).collect();
+    let client = retrying(Flaky::new(failures), &delays);
+
+    match resolve(client.get_forecast("token", (49.9, -97.1))) {
+        Err(Error::Timeout) => {}
+        other => panic!("expected a timeout, got {:?}", other),
+    }
+
+    assert_eq!(client.get_ref().sent(), 4);
+    assert_eq!(delays.lock().unwrap().len(), 3);
+}
+
+#[test]
+fn test_permanent_failures_not_retried() {
+    let delays = Arc::new(Mutex::new(Vec::new()));
+    let client = retrying(Flaky::new(vec![api_error(403)]), &delays);
+
+    match resolve(client.get_forecast("token", (49.9, -97.1))) {
+        Err(Error::Api(403, _)) => {}
+        other => panic!("expected an API error, got {:?}", other),
+    }
+
+    assert_eq!(client.get_ref().sent(), 1);
+
+    let client = retrying(Flaky::new(vec![Err(Error::Cancelled)]), &delays);
+    assert!(resolve(client.get_forecast("token", (49.9, -97.1))).is_err());
+    assert_eq!(client.get_ref().sent(), 1);
+    assert!(delays.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_jittered_delay() {
+    let policy = RetryPolicy::new()
+        .base_delay(Duration::from_secs(1))
+        .max_delay(Duration::from_secs(3));
+
+    for retry in 1..10 {
+        let cap = Duration::from_secs(1 << (retry - 1).min(2)).min(Duration::from_secs(3));
+        let delay = policy.delay(retry);
+
+        assert!(
+            delay >= cap / 2 && delay <= cap,
+            "{:?} for {}",
+            delay,
+            retry
+        );
+    }
+}
+
+#[test]
+fn test_default_sleep() {
+    let backend = Flaky::new(vec![Err(Error::Timeout)]);
+    let client =
+        Retrying::new(backend).policy(RetryPolicy::new().base_delay(Duration::from_millis(1)));
+    let runtime = Runtime::new().unwrap();
+
+    let forecast = runtime
+        .block_on(client.get_forecast("token", (49.9, -97.1)))
+        .unwrap();
+
+    assert_eq!(forecast.timezone, "America/Winnipeg");
+    assert_eq!(client.get_ref().sent(), 2);
+}
+
+#[test]
+fn test_forwards_buffer_and_slot() {
+    let client = Retrying::new(Buffered::default());
+    let slot = MetaSlot::new();
+
+    let body = client.send_into("uri".to_owned(), None, Some(&slot), Vec::with_capacity(64));
+
+    assert_eq!(resolve(body).unwrap(), BODY.as_bytes());
+    assert_eq!(*client.get_ref().capacities.lock().unwrap(), [64]);
+    assert_eq!(slot.get().status, Some(200));
+}
@@ -0,0 +1,247 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+#[cfg(feature = "geo")]
+extern crate geo_types;
+extern crate serde_json;
+
+mod common;
+
+use common::{resolve, Stub, BODY};
+use darksky::bridge::DarkskyRequester;
+use darksky::{Block, Coordinate, Error, Extend, Language, Options};
+
+#[test]
+fn test_get_forecast() {
+    let stub = Stub::new(BODY);
+    let forecast = resolve(stub.get_forecast("token", (49.9, -97.1))).unwrap();
+
+    assert_eq!(forecast.timezone, "America/Winnipeg");
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/token/49.9,-97.1?units=auto"],
+    );
+}
+
+#[test]
+fn test_locations() {
+    let stub = Stub::new(BODY);
+    let location = Coordinate {
+        lat: -7.3,
+        lon: 8.17,
+    };
+
+    resolve(stub.get_forecast("token", Coordinate::new(49.9, -97.1))).unwrap();
+    resolve(stub.get_forecast("token", location)).unwrap();
+    resolve(stub.get_forecast("token", (49.9, -97.1))).unwrap();
+
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        [
+            "https://api.darksky.net/forecast/token/49.9,-97.1?units=auto",
+            "https://api.darksky.net/forecast/token/-7.3,8.17?units=auto",
+            "https://api.darksky.net/forecast/token/49.9,-97.1?units=auto",
+        ],
+    );
+}
+
+#[test]
+fn test_tiny_coordinates() {
+    let stub = Stub::new(BODY);
+
+    resolve(stub.get_forecast("token", (1e-7, -2.5e-6))).unwrap();
+
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/token/0.0000001,-0.0000025?units=auto"],
+    );
+}
+
+#[cfg(feature = "geo")]
+#[test]
+fn test_geo_locations() {
+    use geo_types::{coord, point};
+
+    let stub = Stub::new(BODY);
+
+    resolve(stub.get_forecast("token", point! { x: -97.1, y: 49.9 })).unwrap();
+    resolve(stub.get_forecast("token", coord! { x: -97.1, y: 49.9 })).unwrap();
+
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        [
+            "https://api.darksky.net/forecast/token/49.9,-97.1?units=auto",
+            "https://api.darksky.net/forecast/token/49.9,-97.1?units=auto",
+        ],
+    );
+}
+
+#[cfg(feature = "geohash")]
+#[test]
+fn test_geohash_locations() {
+    let stub = Stub::new(BODY);
+    let hash = String::from("s0");
+
+    resolve(stub.get_forecast("token", "s0")).unwrap();
+    resolve(stub.get_forecast("token", &hash)).unwrap();
+    resolve(stub.get_forecast("token", hash)).unwrap();
+
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        [
+            "https://api.darksky.net/forecast/token/2.8125,5.625?units=auto",
+            "https://api.darksky.net/forecast/token/2.8125,5.625?units=auto",
+            "https://api.darksky.net/forecast/token/2.8125,5.625?units=auto",
+        ],
+    );
+
+    for &hash in &["s0a", ""] {
+        match resolve(stub.get_forecast("token", hash)) {
+            Err(Error::Geohash(_)) => {}
+            other => panic!("expected an invalid geohash, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_get_forecast_time_machine() {
+    let stub = Stub::new(BODY);
+    let req = stub.get_forecast_time_machine("token", (49.9, -97.1), 1_450_000_000, |o| {
+        o.exclude(vec![Block::Hourly])
+    });
+    resolve(req).unwrap();
+
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/token/49.9,-97.1,1450000000?exclude=hourly&"],
+    );
+}
+
+#[test]
+fn test_options_time() {
+    let stub = Stub::new(BODY);
+    let req = stub.get_forecast_with_options("token", (49.9, -97.1), |o| {
+        o.time("2015-12-13T09:46:40Z").exclude(vec![Block::Hourly])
+    });
+    resolve(req).unwrap();
+
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/token/49.9,-97.1,2015-12-13T09:46:40Z?exclude=hourly&"],
+    );
+}
+
+#[test]
+fn test_options_encoded() {
+    let stub = Stub::new(BODY);
+    let req = stub.get_forecast_with_options("token", (49.9, -97.1), |o| {
+        o.time("2015-12-13T09:46:40+01:00?#")
+            .extend(Extend::Other("hourly daily".to_owned()))
+    });
+    resolve(req).unwrap();
+
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/token/49.9,-97.1,2015-12-13T09:46:40+01:00%3F%23?extend=hourly%20daily&"],
+    );
+}
+
+#[test]
+fn test_options_language() {
+    let stub = Stub::new(BODY);
+
+    for language in [
+        Language::Ja,
+        Language::ZhTw,
+        Language::Other("xx".to_owned()),
+    ] {
+        resolve(stub.get_forecast_with_options("token", (49.9, -97.1), |o| o.language(language)))
+            .unwrap();
+    }
+
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        [
+            "https://api.darksky.net/forecast/token/49.9,-97.1?lang=ja&",
+            "https://api.darksky.net/forecast/token/49.9,-97.1?lang=zh-tw&",
+            "https://api.darksky.net/forecast/token/49.9,-97.1?lang=xx&",
+        ],
+    );
+}
+
+#[test]
+fn test_language_serde() {
+    let languages: Vec<Language> = serde_json::from_str(r#"["ko", "x-pig-latin", "xx"]"#).unwrap();
+
+    assert_eq!(
+        languages,
+        [
+            Language::Ko,
+            Language::XPigLatin,
+            Language::Other("xx".to_owned())
+        ],
+    );
+    assert_eq!(
+        serde_json::to_string(&languages).unwrap(),
+        r#"["ko","x-pig-latin","xx"]"#,
+    );
+}
+
+#[test]
+fn test_error_body() {
+    let stub = Stub::new(r#"{"code": 400, "error": "The given location is invalid."}"#);
+
+    match resolve(stub.get_forecast("token", (49.9, -97.1))) {
+        Err(Error::Api(400, message)) => assert_eq!(message, "The given location is invalid."),
+        other => panic!("expected an API error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_coordinate() {
+    let stub = Stub::new(BODY);
+
+    for &(lat, long) in &[
+        (91.0, -97.1),
+        (49.9, -180.5),
+        (f64::NAN, -97.1),
+        (49.9, f64::INFINITY),
+    ] {
+        match resolve(stub.get_forecast_with_options("token", (lat, long), |o| o)) {
+            Err(Error::InvalidCoordinate(..)) => {}
+            other => panic!("expected an invalid coordinate, got {:?}", other),
+        }
+    }
+
+    match resolve(stub.get_forecast("token", (-90.5, 0.0))) {
+        Err(Error::InvalidCoordinate(lat, long)) => assert_eq!((lat, long), (-90.5, 0.0)),
+        other => panic!("expected an invalid coordinate, got {:?}", other),
+    }
+
+    assert!(stub.uris.lock().unwrap().is_empty());
+}
+
+#[test]
+fn test_invalid_body() {
+    let stub = Stub::new(r#"{"latitude": "north"}"#);
+
+    match resolve(stub.get_forecast("token", (49.9, -97.1))) {
+        Err(Error::Json(_)) => {}
+        other => panic!("expected a JSON error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_boxed_requester() {
+    use darksky::bridge::DynDarkskyRequester;
+
+    let stub = Stub::new(BODY);
+    let requester: Box<dyn DynDarkskyRequester> = Box::new(stub);
+
+    let location = Coordinate::new(49.9, -97.1);
+    let options = Options::default().exclude(vec![Block::Hourly]);
+    let req = requester.get_forecast_time_machine("token", location, "1450000000", options);
+    let forecast = resolve(req).unwrap();
+
+    assert_eq!(forecast.timezone, "America/Winnipeg");
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "tower")]
+
+extern crate darksky;
+extern crate tower_service;
+
+mod common;
+
+use common::Stub;
+use darksky::constants::Config;
+use darksky::service::{ForecastRequest, ForecastService};
+use darksky::Block;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+use tower_service::Service;
+
+#[test]
+fn test_call() {
+    let stub = Stub::default();
+    let mut service = ForecastService::new(stub.clone(), "token");
+    let mut cx = Context::from_waker(Waker::noop());
+
+    match service.poll_ready(&mut cx) {
+        Poll::Ready(Ok(())) => {}
+        _ => panic!("service was not ready"),
+    }
+
+    let request = ForecastRequest::new((49.9, -97.1))
+        .time(1_450_000_000)
+        .options(|o| o.exclude(vec![Block::Flags]));
+    let mut future = service.call(request);
+
+    match Pin::new(&mut future).poll(&mut cx) {
+        Poll::Ready(forecast) => assert_eq!(forecast.unwrap().timezone, "America/Winnipeg"),
+        Poll::Pending => panic!("stub future was not ready"),
+    }
+
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/token/49.9,-97.1,1450000000?exclude=flags&"],
+    );
+}
+
+#[test]
+fn test_call_with_config() {
+    let stub = Stub::default();
+    let config = Config::default().api_url("http://localhost:8080");
+    let mut service = ForecastService::with_config(stub.clone(), "token", config);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    let mut future = service.call(ForecastRequest::new((49.9, -97.1)));
+
+    match Pin::new(&mut future).poll(&mut cx) {
+        Poll::Ready(forecast) => assert_eq!(forecast.unwrap().timezone, "America/Winnipeg"),
+        Poll::Pending => panic!("stub future was not ready"),
+    }
+
+    assert_eq!(
+        *stub.uris.lock().unwrap(),
+        ["http://localhost:8080/forecast/token/49.9,-97.1?"],
+    );
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+mod common;
+
+use common::{resolve, Stub};
+use darksky::bridge::middleware::Layered;
+use darksky::bridge::snap::Snap;
+use darksky::bridge::DarkskyRequester;
+use darksky::cache::{Cached, MemoryStore};
+use darksky::constants::Config;
+
+#[test]
+fn test_snap_shares_cache_entries() {
+    let client = Layered::new(Stub::default())
+        .layer(Snap::decimals(3))
+        .layer(Cached::new(MemoryStore::new(10)));
+
+    for &(lat, long) in &[(49.895121, -97.138437), (49.895372, -97.138219)] {
+        resolve(client.get_forecast("token", (lat, long))).unwrap();
+    }
+
+    assert_eq!(
+        *client.get_ref().uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/token/49.895,-97.138?units=auto"],
+    );
+}
+
+#[test]
+fn test_snap_after_cache() {
+    let client = Layered::new(Stub::default())
+        .layer(Cached::new(MemoryStore::new(10)))
+        .layer(Snap::decimals(3));
+
+    for &(lat, long) in &[(49.895121, -97.138437), (49.895372, -97.138219)] {
+        resolve(client.get_forecast_with_options("token", (lat, long), |o| o)).unwrap();
+    }
+
+    assert_eq!(
+        *client.get_ref().uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/token/49.895,-97.138?"],
+    );
+}
+
+#[test]
+fn test_snap_grid() {
+    let client = Layered::new(Stub::default()).layer(Snap::grid(0.25));
+
+    resolve(client.get_forecast("token", (49.9, -97.1))).unwrap();
+
+    assert_eq!(
+        *client.get_ref().uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/token/50,-97?units=auto"],
+    );
+}
+
+#[test]
+fn test_snap_config() {
+    assert_eq!(Snap::new(&Config::default()).get_grid(), None);
+    assert_eq!(
+        Snap::new(&Config::default().grid(0.1)).get_grid(),
+        Some(0.1)
+    );
+    assert_eq!(Snap::decimals(2).get_grid(), Some(0.01));
+
+    let client = Layered::new(Stub::default()).layer(Snap::new(&Config::default()));
+
+    resolve(client.get_forecast("token", (49.895121, -97.138437))).unwrap();
+
+    assert_eq!(
+        *client.get_ref().uris.lock().unwrap(),
+        ["https://api.darksky.net/forecast/token/49.895121,-97.138437?units=auto"],
+    );
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+extern crate serde_json;
+
+use darksky::models::{Datapoint, SummaryText};
+
+#[test]
+fn test_summary_text() {
+    let datapoint: Datapoint =
+        serde_json::from_str(r#"{"time": 1509993277, "summary": "Clear"}"#).unwrap();
+    let summary: &SummaryText = datapoint.summary.as_ref().unwrap();
+
+    assert_eq!(summary, "Clear");
+    assert_eq!(summary.to_lowercase(), "clear");
+    assert_eq!(SummaryText::from("Light Snow".to_owned()), "Light Snow");
+    assert_eq!(
+        serde_json::to_string(&datapoint.summary).unwrap(),
+        r#""Clear""#,
+    );
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+extern crate serde_json;
+
+use darksky::models::{Datapoint, Forecast};
+use darksky::template;
+
+#[test]
+fn test_unknown_field_left_as_is() {
+    let datapoint: Datapoint =
+        serde_json::from_str(r#"{"time": 1509993277, "temperature": 11.6}"#).unwrap();
+
+    assert_eq!(
+        template::render("{temprature:.0}°, {temperature:.0}°", &datapoint),
+        "{temprature:.0}°, 12°",
+    );
+}
+
+#[test]
+fn test_missing_field_renders_nothing() {
+    let datapoint: Datapoint = serde_json::from_str(r#"{"time": 1509993277}"#).unwrap();
+
+    assert_eq!(
+        template::render("[{summary}][{temperature_high}][{ozone_error}]", &datapoint),
+        "[][][]",
+    );
+}
+
+#[test]
+fn test_unknown_forecast_path_left_as_is() {
+    let forecast: Forecast = serde_json::from_str(
+        r#"{
+            "latitude": 49.9,
+            "longitude": -97.1,
+            "timezone": "America/Winnipeg",
+            "currently": {"time": 1509993277, "temperature": -3.5}
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        template::render_forecast("{timezone} {currently.temprature} {tz}", &forecast),
+        "America/Winnipeg {currently.temprature} {tz}",
+    );
+}
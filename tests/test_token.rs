@@ -0,0 +1,113 @@
+#![cfg(feature = "std")]
+
+extern crate darksky;
+
+use darksky::{ApiToken, EnvToken, Error, FileToken, TokenPool, TokenProvider, TokenUsage};
+use std::env;
+use std::fs;
+
+#[test]
+fn test_static_tokens() {
+    let token = ApiToken::parse("0123456789abcdef0123456789abcdef").unwrap();
+
+    assert_eq!("token".token().unwrap(), "token");
+    assert_eq!("token".to_owned().token().unwrap(), "token");
+    assert_eq!(token.token().unwrap(), "0123456789abcdef0123456789abcdef");
+}
+
+#[test]
+fn test_env_token() {
+    let provider = EnvToken::new("DARKSKY_TEST_ENV_TOKEN");
+
+    match provider.token() {
+        Err(Error::Env(_)) => {}
+        other => panic!("expected an environment variable error, got {:?}", other),
+    }
+
+    env::set_var("DARKSKY_TEST_ENV_TOKEN", "first");
+    assert_eq!(provider.token().unwrap(), "first");
+
+    env::set_var("DARKSKY_TEST_ENV_TOKEN", "second");
+    assert_eq!(provider.token().unwrap(), "second");
+}
+
+#[test]
+fn test_file_token() {
+    let path = env::temp_dir().join(format!("darksky-test-token-{}", std::process::id()));
+    let provider = FileToken::new(&path);
+
+    match provider.token() {
+        Err(Error::Io(_)) => {}
+        other => panic!("expected an io error, got {:?}", other),
+    }
+
+    fs::write(&path, "first\n").unwrap();
+    assert_eq!(provider.token().unwrap(), "first");
+
+    fs::write(&path, "second\n").unwrap();
+    assert_eq!(provider.token().unwrap(), "second");
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_pool_round_robin() {
+    let pool = TokenPool::new(vec!["a", "b", "c"]);
+
+    let tokens = (0..4)
+        .map(|_| pool.token().unwrap().into_owned())
+        .collect::<Vec<_>>();
+
+    assert_eq!(tokens, ["a", "b", "c", "a"]);
+    assert_eq!(pool.usage()[0].calls, 2);
+    assert_eq!(pool.usage()[2].calls, 1);
+}
+
+#[test]
+fn test_pool_failover() {
+    let pool = TokenPool::new(vec!["a", "b"]);
+
+    assert_eq!(pool.token().unwrap(), "a");
+    pool.report_error(
+        "a",
+        &Error::Api(403, "daily usage limit exceeded".to_owned()),
+    );
+    // Other errors are counted, without skipping the token.
+    pool.report_error("b", &Error::Timeout);
+
+    assert_eq!(pool.token().unwrap(), "b");
+    assert_eq!(pool.token().unwrap(), "b");
+    assert_eq!(
+        pool.usage(),
+        [
+            TokenUsage {
+                calls: 1,
+                errors: 1,
+                exhausted: true,
+            },
+            TokenUsage {
+                calls: 2,
+                errors: 1,
+                exhausted: false,
+            },
+        ],
+    );
+
+    pool.report_error("b", &Error::Api(401, "permission denied".to_owned()));
+
+    match pool.token() {
+        Err(Error::TokensExhausted) => {}
+        other => panic!("expected the pool to be exhausted, got {:?}", other),
+    }
+
+    pool.reset();
+    assert_eq!(pool.token().unwrap(), "a");
+}
+
+#[test]
+fn test_empty_pool() {
+    let pool = TokenPool::new(Vec::<String>::new());
+
+    assert!(pool.is_empty());
+    assert!(pool.token().is_err());
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "webhook")]
+
+extern crate darksky;
+
+use darksky::alerts::AlertEvent;
+use darksky::models::{Alert, Severity};
+use darksky::webhook;
+
+fn alert(title: &str, severity: Severity) -> Alert {
+    Alert {
+        expires: 1510036680,
+        description: "Heavy snow expected.".to_owned(),
+        title: title.to_owned(),
+        uri: "https://alerts.weather.gov/1".to_owned(),
+        regions: vec!["Winnipeg".to_owned(), "Brandon".to_owned()],
+        time: 1509993277,
+        severity,
+    }
+}
+
+#[test]
+fn test_render_placeholders() {
+    let event = AlertEvent::Escalated {
+        alert: alert("Winter Storm Warning", Severity::Warning),
+        previous: Severity::Watch,
+    };
+    let template =
+        r#"{"text": "{event} {previous_severity}->{severity} {regions} {time}-{expires}"}"#;
+
+    assert_eq!(
+        webhook::render(template, &event),
+        r#"{"text": "escalated watch->warning Winnipeg, Brandon 1509993277-1510036680"}"#,
+    );
+}
+
+#[test]
+fn test_render_values_not_substituted() {
+    let event = AlertEvent::Issued(alert("{description} {uri}", Severity::Advisory));
+
+    assert_eq!(
+        webhook::render(r#"{"title": "{title}", "{unknown}": "{severity}"}"#, &event),
+        r#"{"title": "{description} {uri}", "{unknown}": "advisory"}"#,
+    );
+}